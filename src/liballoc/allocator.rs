@@ -382,6 +382,45 @@ impl fmt::Display for AllocErr {
     }
 }
 
+/// The error type for `try_reserve` methods of the collections.
+///
+/// It distinguishes a capacity that cannot even be represented from an
+/// allocation that the allocator failed to satisfy.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+pub enum CollectionAllocErr {
+    /// Error due to the computed capacity exceeding the collection's maximum
+    /// (usually `isize::MAX` bytes).
+    CapacityOverflow,
+    /// Error due to the allocator (see the `AllocErr` type's docs).
+    AllocErr(AllocErr),
+}
+
+#[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+impl From<AllocErr> for CollectionAllocErr {
+    fn from(err: AllocErr) -> Self {
+        CollectionAllocErr::AllocErr(err)
+    }
+}
+
+#[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+impl CollectionAllocErr {
+    pub fn description(&self) -> &str {
+        match *self {
+            CollectionAllocErr::CapacityOverflow => "capacity overflow",
+            CollectionAllocErr::AllocErr(ref err) => err.description(),
+        }
+    }
+}
+
+// (we need this for downstream impl of trait Error)
+#[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+impl fmt::Display for CollectionAllocErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
 /// The `CannotReallocInPlace` error is used when `grow_in_place` or
 /// `shrink_in_place` were unable to reuse the given memory block for
 /// a requested layout.
@@ -95,6 +95,7 @@
 #![feature(fmt_internals)]
 #![feature(fundamental)]
 #![feature(fused)]
+#![feature(futures_api)]
 #![feature(generic_param_attrs)]
 #![feature(i128_type)]
 #![feature(inclusive_range)]
@@ -164,6 +165,7 @@ mod boxed_test;
 pub mod arc;
 pub mod rc;
 pub mod raw_vec;
+pub mod task;
 
 // collections modules
 pub mod binary_heap;
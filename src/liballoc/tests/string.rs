@@ -504,3 +504,20 @@ fn test_into_boxed_str() {
     let ys = xs.into_boxed_str();
     assert_eq!(&*ys, "hello my name is bob");
 }
+
+#[test]
+fn test_try_reserve() {
+    use std::collections::CollectionAllocErr::*;
+    use std::usize;
+
+    let mut s = String::new();
+    assert!(s.try_reserve(10).is_ok());
+    assert!(s.capacity() >= 10);
+
+    s.push_str("hello");
+    if let Err(CapacityOverflow) = s.try_reserve(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+    if let Err(CapacityOverflow) = s.try_reserve_exact(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+    assert_eq!(s, "hello");
+}
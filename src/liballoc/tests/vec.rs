@@ -968,3 +968,105 @@ fn drain_filter_complex() {
 }
 
 
+
+#[test]
+fn test_try_reserve() {
+    use std::collections::CollectionAllocErr::*;
+    use std::usize;
+
+    let mut empty_bytes: Vec<u8> = Vec::new();
+    assert!(empty_bytes.try_reserve(16).is_ok());
+    assert!(empty_bytes.capacity() >= 16);
+
+    let mut ten_u32s: Vec<u32> = vec![0; 10];
+    if let Err(CapacityOverflow) = ten_u32s.try_reserve(usize::MAX) {
+    } else { panic!("usize::MAX should trigger an overflow!") }
+    if let Err(CapacityOverflow) = ten_u32s.try_reserve(usize::MAX / 2) {
+    } else { panic!("usize::MAX / 2 u32s should trigger an overflow!") }
+    assert_eq!(ten_u32s.len(), 10);
+
+    let mut one_byte = vec![0u8];
+    if let Err(CapacityOverflow) = one_byte.try_reserve(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+}
+
+#[test]
+fn test_try_reserve_exact() {
+    use std::collections::CollectionAllocErr::*;
+    use std::usize;
+
+    let mut empty_bytes: Vec<u8> = Vec::new();
+    assert!(empty_bytes.try_reserve_exact(7).is_ok());
+    assert!(empty_bytes.capacity() >= 7);
+
+    let mut ten_u32s: Vec<u32> = vec![0; 10];
+    if let Err(CapacityOverflow) = ten_u32s.try_reserve_exact(usize::MAX - 10) {
+    } else { panic!("usize::MAX - 10 u32s should trigger an overflow!") }
+    if let Err(CapacityOverflow) = ten_u32s.try_reserve_exact(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+    assert_eq!(ten_u32s.len(), 10);
+}
+
+#[test]
+fn test_new_in_custom_allocator() {
+    use std::cell::Cell;
+    use std::heap::{Alloc, AllocErr, Heap, Layout};
+    use std::rc::Rc;
+
+    // Forwards to the global heap while counting live allocations.
+    #[derive(Clone)]
+    struct Counting(Rc<Cell<usize>>);
+
+    unsafe impl Alloc for Counting {
+        unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+            self.0.set(self.0.get() + 1);
+            Heap.alloc(layout)
+        }
+
+        unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            Heap.dealloc(ptr, layout)
+        }
+    }
+
+    let live = Rc::new(Cell::new(0));
+    {
+        let mut v = Vec::new_in(Counting(live.clone()));
+        assert_eq!(live.get(), 0);
+        v.extend(0..100);
+        v.push(100);
+        assert_eq!(live.get(), 1);
+        assert_eq!(v.len(), 101);
+        assert_eq!(v[100], 100);
+
+        let tail = v.split_off(50);
+        assert_eq!(live.get(), 2);
+        assert_eq!(tail, (50..101).collect::<Vec<_>>());
+
+        let copy = tail.clone();
+        assert_eq!(live.get(), 3);
+        assert_eq!(copy, tail);
+
+        let drained: Vec<_> = v.drain(..10).collect();
+        assert_eq!(drained, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(live.get(), 3);
+    }
+    assert_eq!(live.get(), 0);
+
+    let v: Vec<u8, Counting> = Vec::with_capacity_in(16, Counting(live.clone()));
+    assert_eq!(v.capacity(), 16);
+    assert_eq!(live.get(), 1);
+    drop(v);
+    assert_eq!(live.get(), 0);
+
+    let mut v = Vec::with_capacity_in(4, Counting(live.clone()));
+    v.push(1);
+    v.push(2);
+    let (ptr, len, cap, alloc) = v.into_raw_parts_with_alloc();
+    assert_eq!((len, cap), (2, 4));
+    assert_eq!(live.get(), 1);
+    let v = unsafe { Vec::from_raw_parts_in(ptr, len, cap, alloc) };
+    assert_eq!(v, [1, 2]);
+    drop(v);
+    assert_eq!(live.get(), 0);
+}
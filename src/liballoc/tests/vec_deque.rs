@@ -1022,3 +1022,61 @@ fn test_placement_in() {
     }
     assert_eq!(buf, [5,4,3,1,2,6]);
 }
+
+#[test]
+fn test_try_reserve() {
+    use std::collections::CollectionAllocErr::*;
+    use std::usize;
+
+    let mut empty: VecDeque<u8> = VecDeque::new();
+    assert!(empty.try_reserve(10).is_ok());
+    assert!(empty.capacity() >= 10);
+
+    let mut ten: VecDeque<u32> = (0..10).collect();
+    if let Err(CapacityOverflow) = ten.try_reserve(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+    if let Err(CapacityOverflow) = ten.try_reserve_exact(usize::MAX - 5) {
+    } else { panic!("usize::MAX - 5 should trigger an overflow!") }
+    assert_eq!(ten.len(), 10);
+}
+
+#[test]
+fn test_new_in_custom_allocator() {
+    use std::cell::Cell;
+    use std::heap::{Alloc, AllocErr, Heap, Layout};
+    use std::rc::Rc;
+
+    // Forwards to the global heap while counting live allocations.
+    #[derive(Clone)]
+    struct Counting(Rc<Cell<usize>>);
+
+    unsafe impl Alloc for Counting {
+        unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+            self.0.set(self.0.get() + 1);
+            Heap.alloc(layout)
+        }
+
+        unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            Heap.dealloc(ptr, layout)
+        }
+    }
+
+    let live = Rc::new(Cell::new(0));
+    {
+        let mut d = VecDeque::new_in(Counting(live.clone()));
+        assert_eq!(live.get(), 1);
+        for i in 0..100 {
+            d.push_front(i);
+        }
+        assert_eq!(live.get(), 1);
+        assert_eq!(d.len(), 100);
+        assert_eq!(d[0], 99);
+
+        let back = d.split_off(50);
+        assert_eq!(live.get(), 2);
+        assert_eq!(back.front(), Some(&49));
+        assert_eq!(d.clone(), d);
+    }
+    assert_eq!(live.get(), 0);
+}
@@ -82,7 +82,9 @@ use core::slice;
 
 use borrow::ToOwned;
 use borrow::Cow;
+use allocator::{Alloc, CollectionAllocErr};
 use boxed::Box;
+use heap::Heap;
 use raw_vec::RawVec;
 use super::range::RangeArgument;
 use Bound::{Excluded, Included, Unbounded};
@@ -295,9 +297,20 @@ use Bound::{Excluded, Included, Unbounded};
 /// [`insert`]: ../../std/vec/struct.Vec.html#method.insert
 /// [`reserve`]: ../../std/vec/struct.Vec.html#method.reserve
 /// [owned slice]: ../../std/boxed/struct.Box.html
+///
+/// # Allocators
+///
+/// `Vec` is parameterized over the allocator its buffer is obtained from,
+/// which defaults to the global [`Heap`]. A vector backed by another
+/// allocator is created with [`Vec::new_in`] or [`Vec::with_capacity_in`],
+/// and all growth and deallocation then goes through that allocator.
+///
+/// [`Heap`]: ../../std/heap/struct.Heap.html
+/// [`Vec::new_in`]: #method.new_in
+/// [`Vec::with_capacity_in`]: #method.with_capacity_in
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct Vec<T> {
-    buf: RawVec<T>,
+pub struct Vec<T, A: Alloc = Heap> {
+    buf: RawVec<T, A>,
     len: usize,
 }
 
@@ -424,6 +437,173 @@ impl<T> Vec<T> {
         }
     }
 
+    /// Converts the vector into [`Box<[T]>`][owned slice].
+    ///
+    /// Note that this will drop any excess capacity. Calling this and
+    /// converting back to a vector with [`into_vec`] is equivalent to calling
+    /// [`shrink_to_fit`].
+    ///
+    /// [owned slice]: ../../std/boxed/struct.Box.html
+    /// [`into_vec`]: ../../std/primitive.slice.html#method.into_vec
+    /// [`shrink_to_fit`]: #method.shrink_to_fit
+    ///
+    /// This is only available for vectors backed by the global heap, since
+    /// `Box` has no allocator parameter and always frees its contents there.
+    /// A vector using another allocator can be taken apart with
+    /// [`into_raw_parts_with_alloc`] instead.
+    ///
+    /// [`into_raw_parts_with_alloc`]: #method.into_raw_parts_with_alloc
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let v = vec![1, 2, 3];
+    ///
+    /// let slice = v.into_boxed_slice();
+    /// ```
+    ///
+    /// Any excess capacity is removed:
+    ///
+    /// ```
+    /// let mut vec = Vec::with_capacity(10);
+    /// vec.extend([1, 2, 3].iter().cloned());
+    ///
+    /// assert_eq!(vec.capacity(), 10);
+    /// let slice = vec.into_boxed_slice();
+    /// assert_eq!(slice.into_vec().capacity(), 3);
+    /// ```
+    #[stable(feature = "rust1", since = "1.0.0")]
+    pub fn into_boxed_slice(mut self) -> Box<[T]> {
+        unsafe {
+            self.shrink_to_fit();
+            let buf = ptr::read(&self.buf);
+            mem::forget(self);
+            buf.into_box()
+        }
+    }
+
+}
+
+impl<T, A: Alloc> Vec<T, A> {
+    /// Constructs a new, empty `Vec<T, A>` whose buffer will be allocated
+    /// from `alloc`.
+    ///
+    /// The vector will not allocate until elements are pushed onto it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::heap::Heap;
+    ///
+    /// let mut vec: Vec<i32, Heap> = Vec::new_in(Heap);
+    /// vec.push(1);
+    /// assert_eq!(vec, [1]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn new_in(alloc: A) -> Self {
+        Vec {
+            buf: RawVec::new_in(alloc),
+            len: 0,
+        }
+    }
+
+    /// Constructs a new, empty `Vec<T, A>` with the specified capacity,
+    /// allocated from `alloc`.
+    ///
+    /// The vector will be able to hold exactly `capacity` elements without
+    /// reallocating. If `capacity` is 0, the vector will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::heap::Heap;
+    ///
+    /// let mut vec: Vec<i32, Heap> = Vec::with_capacity_in(10, Heap);
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 10);
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Vec {
+            buf: RawVec::with_capacity_in(capacity, alloc),
+            len: 0,
+        }
+    }
+
+    /// Creates a `Vec<T, A>` directly from the raw components of another
+    /// vector that was allocated from `alloc`.
+    ///
+    /// # Safety
+    ///
+    /// The same invariants as for [`from_raw_parts`] apply, except that
+    /// `ptr` must have been allocated by `alloc` rather than by the global
+    /// heap.
+    ///
+    /// [`from_raw_parts`]: #method.from_raw_parts
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub unsafe fn from_raw_parts_in(ptr: *mut T, length: usize, capacity: usize,
+                                    alloc: A) -> Self {
+        Vec {
+            buf: RawVec::from_raw_parts_in(ptr, capacity, alloc),
+            len: length,
+        }
+    }
+
+    /// Returns a reference to the allocator backing this vector.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn allocator(&self) -> &A {
+        self.buf.alloc()
+    }
+
+    /// Decomposes a `Vec<T, A>` into its raw components.
+    ///
+    /// Returns the raw pointer to the underlying data, the length of the
+    /// vector (in elements), the allocated capacity of the data (in elements)
+    /// and the allocator the data was allocated from. These are the same
+    /// arguments in the same order as the arguments to [`from_raw_parts_in`].
+    ///
+    /// After calling this function, the caller is responsible for the memory
+    /// previously managed by the `Vec`. The only way to release it is to
+    /// convert the parts back into a `Vec` with [`from_raw_parts_in`].
+    ///
+    /// [`from_raw_parts_in`]: #method.from_raw_parts_in
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::heap::Heap;
+    ///
+    /// let mut v: Vec<i32, Heap> = Vec::with_capacity_in(4, Heap);
+    /// v.extend([1, 2, 3].iter().cloned());
+    ///
+    /// let (ptr, len, cap, alloc) = v.into_raw_parts_with_alloc();
+    /// assert_eq!(len, 3);
+    /// assert_eq!(cap, 4);
+    ///
+    /// let rebuilt = unsafe { Vec::from_raw_parts_in(ptr, len, cap, alloc) };
+    /// assert_eq!(rebuilt, [1, 2, 3]);
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn into_raw_parts_with_alloc(self) -> (*mut T, usize, usize, A) {
+        unsafe {
+            let ptr = self.buf.ptr();
+            let len = self.len;
+            let cap = self.buf.cap();
+            let alloc = ptr::read(self.buf.alloc());
+            mem::forget(self);
+            (ptr, len, cap, alloc)
+        }
+    }
+
     /// Returns the number of elements the vector can hold without
     /// reallocating.
     ///
@@ -486,61 +666,100 @@ impl<T> Vec<T> {
         self.buf.reserve_exact(self.len, additional);
     }
 
-    /// Shrinks the capacity of the vector as much as possible.
+    /// Tries to reserve capacity for at least `additional` more elements to be inserted
+    /// in the given `Vec<T>`. The collection may reserve more space to avoid
+    /// frequent reallocations. After calling `try_reserve`, capacity will be
+    /// greater than or equal to `self.len() + additional`. Does nothing if
+    /// capacity is already sufficient.
     ///
-    /// It will drop down as close as possible to the length but the allocator
-    /// may still inform the vector that there is space for a few more elements.
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
     ///
     /// # Examples
     ///
     /// ```
-    /// let mut vec = Vec::with_capacity(10);
-    /// vec.extend([1, 2, 3].iter().cloned());
-    /// assert_eq!(vec.capacity(), 10);
-    /// vec.shrink_to_fit();
-    /// assert!(vec.capacity() >= 3);
+    /// #![feature(try_reserve)]
+    /// use std::collections::CollectionAllocErr;
+    ///
+    /// fn process_data(data: &[u32]) -> Result<Vec<u32>, CollectionAllocErr> {
+    ///     let mut output = Vec::new();
+    ///
+    ///     // Pre-reserve the memory, exiting if we can't
+    ///     output.try_reserve(data.len())?;
+    ///
+    ///     // Now we know this can't OOM in the middle of our complex work
+    ///     output.extend(data.iter().map(|&val| {
+    ///         val * 2 + 5 // very complicated
+    ///     }));
+    ///
+    ///     Ok(output)
+    /// }
+    /// # process_data(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
     /// ```
-    #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn shrink_to_fit(&mut self) {
-        self.buf.shrink_to_fit(self.len);
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), CollectionAllocErr> {
+        self.buf.try_reserve(self.len, additional)
     }
 
-    /// Converts the vector into [`Box<[T]>`][owned slice].
+    /// Tries to reserve the minimum capacity for exactly `additional` more elements to
+    /// be inserted in the given `Vec<T>`. After calling `try_reserve_exact`,
+    /// capacity will be greater than or equal to `self.len() + additional`.
+    /// Does nothing if the capacity is already sufficient.
     ///
-    /// Note that this will drop any excess capacity. Calling this and
-    /// converting back to a vector with [`into_vec`] is equivalent to calling
-    /// [`shrink_to_fit`].
+    /// Note that the allocator may give the collection more space than it
+    /// requests. Therefore capacity can not be relied upon to be precisely
+    /// minimal. Prefer `try_reserve` if future insertions are expected.
     ///
-    /// [owned slice]: ../../std/boxed/struct.Box.html
-    /// [`into_vec`]: ../../std/primitive.slice.html#method.into_vec
-    /// [`shrink_to_fit`]: #method.shrink_to_fit
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
     ///
     /// # Examples
     ///
     /// ```
-    /// let v = vec![1, 2, 3];
+    /// #![feature(try_reserve)]
+    /// use std::collections::CollectionAllocErr;
     ///
-    /// let slice = v.into_boxed_slice();
+    /// fn process_data(data: &[u32]) -> Result<Vec<u32>, CollectionAllocErr> {
+    ///     let mut output = Vec::new();
+    ///
+    ///     // Pre-reserve the memory, exiting if we can't
+    ///     output.try_reserve_exact(data.len())?;
+    ///
+    ///     // Now we know this can't OOM in the middle of our complex work
+    ///     output.extend(data.iter().map(|&val| {
+    ///         val * 2 + 5 // very complicated
+    ///     }));
+    ///
+    ///     Ok(output)
+    /// }
+    /// # process_data(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
     /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), CollectionAllocErr> {
+        self.buf.try_reserve_exact(self.len, additional)
+    }
+
+    /// Shrinks the capacity of the vector as much as possible.
     ///
-    /// Any excess capacity is removed:
+    /// It will drop down as close as possible to the length but the allocator
+    /// may still inform the vector that there is space for a few more elements.
+    ///
+    /// # Examples
     ///
     /// ```
     /// let mut vec = Vec::with_capacity(10);
     /// vec.extend([1, 2, 3].iter().cloned());
-    ///
     /// assert_eq!(vec.capacity(), 10);
-    /// let slice = vec.into_boxed_slice();
-    /// assert_eq!(slice.into_vec().capacity(), 3);
+    /// vec.shrink_to_fit();
+    /// assert!(vec.capacity() >= 3);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn into_boxed_slice(mut self) -> Box<[T]> {
-        unsafe {
-            self.shrink_to_fit();
-            let buf = ptr::read(&self.buf);
-            mem::forget(self);
-            buf.into_box()
-        }
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.shrink_to_fit(self.len);
     }
 
     /// Shortens the vector, keeping the first `len` elements and dropping
@@ -999,7 +1218,7 @@ impl<T> Vec<T> {
     #[unstable(feature = "collection_placement",
                reason = "placement protocol is subject to change",
                issue = "30172")]
-    pub fn place_back(&mut self) -> PlaceBack<T> {
+    pub fn place_back(&mut self) -> PlaceBack<T, A> {
         PlaceBack { vec: self }
     }
 
@@ -1089,7 +1308,7 @@ impl<T> Vec<T> {
     /// assert_eq!(v, &[]);
     /// ```
     #[stable(feature = "drain", since = "1.6.0")]
-    pub fn drain<R>(&mut self, range: R) -> Drain<T>
+    pub fn drain<R>(&mut self, range: R) -> Drain<T, A>
         where R: RangeArgument<usize>
     {
         // Memory safety
@@ -1204,11 +1423,11 @@ impl<T> Vec<T> {
     /// ```
     #[inline]
     #[stable(feature = "split_off", since = "1.4.0")]
-    pub fn split_off(&mut self, at: usize) -> Self {
+    pub fn split_off(&mut self, at: usize) -> Self where A: Clone {
         assert!(at <= self.len(), "`at` out of bounds");
 
         let other_len = self.len - at;
-        let mut other = Vec::with_capacity(other_len);
+        let mut other = Vec::with_capacity_in(other_len, self.buf.alloc().clone());
 
         // Unsafely `set_len` and copy items to `other`.
         unsafe {
@@ -1223,7 +1442,7 @@ impl<T> Vec<T> {
     }
 }
 
-impl<T: Clone> Vec<T> {
+impl<T: Clone, A: Alloc> Vec<T, A> {
     /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
     ///
     /// If `new_len` is greater than `len`, the `Vec` is extended by the
@@ -1280,7 +1499,7 @@ impl<T: Clone> Vec<T> {
     }
 }
 
-impl<T: Default> Vec<T> {
+impl<T: Default, A: Alloc> Vec<T, A> {
     /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
     ///
     /// If `new_len` is greater than `len`, the `Vec` is extended by the
@@ -1335,7 +1554,7 @@ impl<T: Default> ExtendWith<T> for ExtendDefault {
     fn next(&self) -> T { Default::default() }
     fn last(self) -> T { Default::default() }
 }
-impl<T> Vec<T> {
+impl<T, A: Alloc> Vec<T, A> {
     /// Extend the vector by `n` values, using the given generator.
     fn extend_with<E: ExtendWith<T>>(&mut self, n: usize, value: E) {
         self.reserve(n);
@@ -1395,7 +1614,7 @@ impl<'a> Drop for SetLenOnDrop<'a> {
     }
 }
 
-impl<T: PartialEq> Vec<T> {
+impl<T: PartialEq, A: Alloc> Vec<T, A> {
     /// Removes consecutive repeated elements in the vector.
     ///
     /// If the vector is sorted, this removes all duplicates.
@@ -1518,28 +1737,29 @@ impl_spec_from_elem!(f64, |x: f64| x == 0. && x.is_sign_positive());
 ////////////////////////////////////////////////////////////////////////////////
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Clone> Clone for Vec<T> {
-    #[cfg(not(test))]
-    fn clone(&self) -> Vec<T> {
-        <[T]>::to_vec(&**self)
+impl<T: Clone, A: Alloc + Clone> Clone for Vec<T, A> {
+    fn clone(&self) -> Vec<T, A> {
+        let mut vector = Vec::with_capacity_in(self.len(), self.buf.alloc().clone());
+        vector.extend_from_slice(self);
+        vector
     }
 
-    // HACK(japaric): with cfg(test) the inherent `[T]::to_vec` method, which is
-    // required for this method definition, is not available. Instead use the
-    // `slice::to_vec`  function which is only available with cfg(test)
-    // NB see the slice::hack module in slice.rs for more information
-    #[cfg(test)]
-    fn clone(&self) -> Vec<T> {
-        ::slice::to_vec(&**self)
-    }
+    fn clone_from(&mut self, other: &Vec<T, A>) {
+        // drop anything in self that will not be overwritten
+        self.truncate(other.len());
+        let len = self.len();
 
-    fn clone_from(&mut self, other: &Vec<T>) {
-        other.as_slice().clone_into(self);
+        // reuse the contained values' allocations/resources.
+        self.clone_from_slice(&other[..len]);
+
+        // self.len <= other.len due to the truncate above, so the
+        // slice here is always in-bounds.
+        self.extend_from_slice(&other[len..]);
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Hash> Hash for Vec<T> {
+impl<T: Hash, A: Alloc> Hash for Vec<T, A> {
     #[inline]
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
         Hash::hash(&**self, state)
@@ -1547,7 +1767,7 @@ impl<T: Hash> Hash for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> Index<usize> for Vec<T> {
+impl<T, A: Alloc> Index<usize> for Vec<T, A> {
     type Output = T;
 
     #[inline]
@@ -1558,7 +1778,7 @@ impl<T> Index<usize> for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> IndexMut<usize> for Vec<T> {
+impl<T, A: Alloc> IndexMut<usize> for Vec<T, A> {
     #[inline]
     fn index_mut(&mut self, index: usize) -> &mut T {
         // NB built-in indexing via `&mut [T]`
@@ -1568,7 +1788,7 @@ impl<T> IndexMut<usize> for Vec<T> {
 
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::Index<ops::Range<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::Index<ops::Range<usize>> for Vec<T, A> {
     type Output = [T];
 
     #[inline]
@@ -1577,7 +1797,7 @@ impl<T> ops::Index<ops::Range<usize>> for Vec<T> {
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::Index<ops::RangeTo<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::Index<ops::RangeTo<usize>> for Vec<T, A> {
     type Output = [T];
 
     #[inline]
@@ -1586,7 +1806,7 @@ impl<T> ops::Index<ops::RangeTo<usize>> for Vec<T> {
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::Index<ops::RangeFrom<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::Index<ops::RangeFrom<usize>> for Vec<T, A> {
     type Output = [T];
 
     #[inline]
@@ -1595,7 +1815,7 @@ impl<T> ops::Index<ops::RangeFrom<usize>> for Vec<T> {
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::Index<ops::RangeFull> for Vec<T> {
+impl<T, A: Alloc> ops::Index<ops::RangeFull> for Vec<T, A> {
     type Output = [T];
 
     #[inline]
@@ -1604,7 +1824,7 @@ impl<T> ops::Index<ops::RangeFull> for Vec<T> {
     }
 }
 #[unstable(feature = "inclusive_range", reason = "recently added, follows RFC", issue = "28237")]
-impl<T> ops::Index<ops::RangeInclusive<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::Index<ops::RangeInclusive<usize>> for Vec<T, A> {
     type Output = [T];
 
     #[inline]
@@ -1613,7 +1833,7 @@ impl<T> ops::Index<ops::RangeInclusive<usize>> for Vec<T> {
     }
 }
 #[unstable(feature = "inclusive_range", reason = "recently added, follows RFC", issue = "28237")]
-impl<T> ops::Index<ops::RangeToInclusive<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::Index<ops::RangeToInclusive<usize>> for Vec<T, A> {
     type Output = [T];
 
     #[inline]
@@ -1623,42 +1843,42 @@ impl<T> ops::Index<ops::RangeToInclusive<usize>> for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::IndexMut<ops::Range<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::IndexMut<ops::Range<usize>> for Vec<T, A> {
     #[inline]
     fn index_mut(&mut self, index: ops::Range<usize>) -> &mut [T] {
         IndexMut::index_mut(&mut **self, index)
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::IndexMut<ops::RangeTo<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::IndexMut<ops::RangeTo<usize>> for Vec<T, A> {
     #[inline]
     fn index_mut(&mut self, index: ops::RangeTo<usize>) -> &mut [T] {
         IndexMut::index_mut(&mut **self, index)
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::IndexMut<ops::RangeFrom<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::IndexMut<ops::RangeFrom<usize>> for Vec<T, A> {
     #[inline]
     fn index_mut(&mut self, index: ops::RangeFrom<usize>) -> &mut [T] {
         IndexMut::index_mut(&mut **self, index)
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::IndexMut<ops::RangeFull> for Vec<T> {
+impl<T, A: Alloc> ops::IndexMut<ops::RangeFull> for Vec<T, A> {
     #[inline]
     fn index_mut(&mut self, _index: ops::RangeFull) -> &mut [T] {
         self
     }
 }
 #[unstable(feature = "inclusive_range", reason = "recently added, follows RFC", issue = "28237")]
-impl<T> ops::IndexMut<ops::RangeInclusive<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::IndexMut<ops::RangeInclusive<usize>> for Vec<T, A> {
     #[inline]
     fn index_mut(&mut self, index: ops::RangeInclusive<usize>) -> &mut [T] {
         IndexMut::index_mut(&mut **self, index)
     }
 }
 #[unstable(feature = "inclusive_range", reason = "recently added, follows RFC", issue = "28237")]
-impl<T> ops::IndexMut<ops::RangeToInclusive<usize>> for Vec<T> {
+impl<T, A: Alloc> ops::IndexMut<ops::RangeToInclusive<usize>> for Vec<T, A> {
     #[inline]
     fn index_mut(&mut self, index: ops::RangeToInclusive<usize>) -> &mut [T] {
         IndexMut::index_mut(&mut **self, index)
@@ -1666,7 +1886,7 @@ impl<T> ops::IndexMut<ops::RangeToInclusive<usize>> for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::Deref for Vec<T> {
+impl<T, A: Alloc> ops::Deref for Vec<T, A> {
     type Target = [T];
 
     fn deref(&self) -> &[T] {
@@ -1679,7 +1899,7 @@ impl<T> ops::Deref for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::DerefMut for Vec<T> {
+impl<T, A: Alloc> ops::DerefMut for Vec<T, A> {
     fn deref_mut(&mut self) -> &mut [T] {
         unsafe {
             let ptr = self.buf.ptr();
@@ -1693,7 +1913,7 @@ impl<T> ops::DerefMut for Vec<T> {
 impl<T> FromIterator<T> for Vec<T> {
     #[inline]
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Vec<T> {
-        <Self as SpecExtend<T, I::IntoIter>>::from_iter(iter.into_iter())
+        <Self as SpecFromIter<T, I::IntoIter>>::from_iter(iter.into_iter())
     }
 }
 
@@ -1738,7 +1958,7 @@ impl<T> IntoIterator for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, T> IntoIterator for &'a Vec<T> {
+impl<'a, T, A: Alloc> IntoIterator for &'a Vec<T, A> {
     type Item = &'a T;
     type IntoIter = slice::Iter<'a, T>;
 
@@ -1748,7 +1968,7 @@ impl<'a, T> IntoIterator for &'a Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, T> IntoIterator for &'a mut Vec<T> {
+impl<'a, T, A: Alloc> IntoIterator for &'a mut Vec<T, A> {
     type Item = &'a mut T;
     type IntoIter = slice::IterMut<'a, T>;
 
@@ -1758,20 +1978,24 @@ impl<'a, T> IntoIterator for &'a mut Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> Extend<T> for Vec<T> {
+impl<T, A: Alloc> Extend<T> for Vec<T, A> {
     #[inline]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         <Self as SpecExtend<T, I::IntoIter>>::spec_extend(self, iter.into_iter())
     }
 }
 
-// Specialization trait used for Vec::from_iter and Vec::extend
-trait SpecExtend<T, I> {
+// Specialization trait used for Vec::from_iter
+trait SpecFromIter<T, I> {
     fn from_iter(iter: I) -> Self;
+}
+
+// Specialization trait used for Vec::extend
+trait SpecExtend<T, I> {
     fn spec_extend(&mut self, iter: I);
 }
 
-impl<T, I> SpecExtend<T, I> for Vec<T>
+impl<T, I> SpecFromIter<T, I> for Vec<T>
     where I: Iterator<Item=T>,
 {
     default fn from_iter(mut iterator: I) -> Self {
@@ -1795,13 +2019,9 @@ impl<T, I> SpecExtend<T, I> for Vec<T>
         <Vec<T> as SpecExtend<T, I>>::spec_extend(&mut vector, iterator);
         vector
     }
-
-    default fn spec_extend(&mut self, iter: I) {
-        self.extend_desugared(iter)
-    }
 }
 
-impl<T, I> SpecExtend<T, I> for Vec<T>
+impl<T, I> SpecFromIter<T, I> for Vec<T>
     where I: TrustedLen<Item=T>,
 {
     default fn from_iter(iterator: I) -> Self {
@@ -1809,7 +2029,19 @@ impl<T, I> SpecExtend<T, I> for Vec<T>
         vector.spec_extend(iterator);
         vector
     }
+}
 
+impl<T, I, A: Alloc> SpecExtend<T, I> for Vec<T, A>
+    where I: Iterator<Item=T>,
+{
+    default fn spec_extend(&mut self, iter: I) {
+        self.extend_desugared(iter)
+    }
+}
+
+impl<T, I, A: Alloc> SpecExtend<T, I> for Vec<T, A>
+    where I: TrustedLen<Item=T>,
+{
     default fn spec_extend(&mut self, iterator: I) {
         // This is the case for a TrustedLen iterator.
         let (low, high) = iterator.size_hint();
@@ -1836,7 +2068,7 @@ impl<T, I> SpecExtend<T, I> for Vec<T>
     }
 }
 
-impl<T> SpecExtend<T, IntoIter<T>> for Vec<T> {
+impl<T> SpecFromIter<T, IntoIter<T>> for Vec<T> {
     fn from_iter(iterator: IntoIter<T>) -> Self {
         // A common case is passing a vector into a function which immediately
         // re-collects into a vector. We can short circuit this if the IntoIter
@@ -1855,7 +2087,9 @@ impl<T> SpecExtend<T, IntoIter<T>> for Vec<T> {
             vector
         }
     }
+}
 
+impl<T, A: Alloc> SpecExtend<T, IntoIter<T>> for Vec<T, A> {
     fn spec_extend(&mut self, mut iterator: IntoIter<T>) {
         unsafe {
             self.append_elements(iterator.as_slice() as _);
@@ -1864,20 +2098,25 @@ impl<T> SpecExtend<T, IntoIter<T>> for Vec<T> {
     }
 }
 
-impl<'a, T: 'a, I> SpecExtend<&'a T, I> for Vec<T>
+impl<'a, T: 'a, I> SpecFromIter<&'a T, I> for Vec<T>
     where I: Iterator<Item=&'a T>,
           T: Clone,
 {
     default fn from_iter(iterator: I) -> Self {
-        SpecExtend::from_iter(iterator.cloned())
+        SpecFromIter::from_iter(iterator.cloned())
     }
+}
 
+impl<'a, T: 'a, I, A: Alloc> SpecExtend<&'a T, I> for Vec<T, A>
+    where I: Iterator<Item=&'a T>,
+          T: Clone,
+{
     default fn spec_extend(&mut self, iterator: I) {
         self.spec_extend(iterator.cloned())
     }
 }
 
-impl<'a, T: 'a> SpecExtend<&'a T, slice::Iter<'a, T>> for Vec<T>
+impl<'a, T: 'a, A: Alloc> SpecExtend<&'a T, slice::Iter<'a, T>> for Vec<T, A>
     where T: Copy,
 {
     fn spec_extend(&mut self, iterator: slice::Iter<'a, T>) {
@@ -1891,7 +2130,7 @@ impl<'a, T: 'a> SpecExtend<&'a T, slice::Iter<'a, T>> for Vec<T>
     }
 }
 
-impl<T> Vec<T> {
+impl<T, A: Alloc> Vec<T, A> {
     fn extend_desugared<I: Iterator<Item = T>>(&mut self, mut iterator: I) {
         // This is the case for a general iterator.
         //
@@ -1952,7 +2191,7 @@ impl<T> Vec<T> {
     /// ```
     #[inline]
     #[unstable(feature = "splice", reason = "recently added", issue = "32310")]
-    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<I::IntoIter>
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<I::IntoIter, A>
         where R: RangeArgument<usize>, I: IntoIterator<Item=T>
     {
         Splice {
@@ -2007,7 +2246,7 @@ impl<T> Vec<T> {
     /// assert_eq!(odds, vec![1, 3, 5, 9, 11, 13, 15]);
     /// ```
     #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
-    pub fn drain_filter<F>(&mut self, filter: F) -> DrainFilter<T, F>
+    pub fn drain_filter<F>(&mut self, filter: F) -> DrainFilter<T, F, A>
         where F: FnMut(&mut T) -> bool,
     {
         let old_len = self.len();
@@ -2032,19 +2271,19 @@ impl<T> Vec<T> {
 ///
 /// [`copy_from_slice`]: ../../std/primitive.slice.html#method.copy_from_slice
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, T: 'a + Copy> Extend<&'a T> for Vec<T> {
+impl<'a, T: 'a + Copy, A: Alloc> Extend<&'a T> for Vec<T, A> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         self.spec_extend(iter.into_iter())
     }
 }
 
 macro_rules! __impl_slice_eq1 {
-    ($Lhs: ty, $Rhs: ty) => {
-        __impl_slice_eq1! { $Lhs, $Rhs, Sized }
+    ([$($vars:tt)*] $Lhs: ty, $Rhs: ty) => {
+        __impl_slice_eq1! { [$($vars)*] $Lhs, $Rhs, Sized }
     };
-    ($Lhs: ty, $Rhs: ty, $Bound: ident) => {
+    ([$($vars:tt)*] $Lhs: ty, $Rhs: ty, $Bound: ident) => {
         #[stable(feature = "rust1", since = "1.0.0")]
-        impl<'a, 'b, A: $Bound, B> PartialEq<$Rhs> for $Lhs where A: PartialEq<B> {
+        impl<'a, 'b, A: $Bound, B, $($vars)*> PartialEq<$Rhs> for $Lhs where A: PartialEq<B> {
             #[inline]
             fn eq(&self, other: &$Rhs) -> bool { self[..] == other[..] }
             #[inline]
@@ -2053,19 +2292,19 @@ macro_rules! __impl_slice_eq1 {
     }
 }
 
-__impl_slice_eq1! { Vec<A>, Vec<B> }
-__impl_slice_eq1! { Vec<A>, &'b [B] }
-__impl_slice_eq1! { Vec<A>, &'b mut [B] }
-__impl_slice_eq1! { Cow<'a, [A]>, &'b [B], Clone }
-__impl_slice_eq1! { Cow<'a, [A]>, &'b mut [B], Clone }
-__impl_slice_eq1! { Cow<'a, [A]>, Vec<B>, Clone }
+__impl_slice_eq1! { [AllocA: Alloc, AllocB: Alloc] Vec<A, AllocA>, Vec<B, AllocB> }
+__impl_slice_eq1! { [AllocA: Alloc] Vec<A, AllocA>, &'b [B] }
+__impl_slice_eq1! { [AllocA: Alloc] Vec<A, AllocA>, &'b mut [B] }
+__impl_slice_eq1! { [] Cow<'a, [A]>, &'b [B], Clone }
+__impl_slice_eq1! { [] Cow<'a, [A]>, &'b mut [B], Clone }
+__impl_slice_eq1! { [AllocB: Alloc] Cow<'a, [A]>, Vec<B, AllocB>, Clone }
 
 macro_rules! array_impls {
     ($($N: expr)+) => {
         $(
             // NOTE: some less important impls are omitted to reduce code bloat
-            __impl_slice_eq1! { Vec<A>, [B; $N] }
-            __impl_slice_eq1! { Vec<A>, &'b [B; $N] }
+            __impl_slice_eq1! { [AllocA: Alloc] Vec<A, AllocA>, [B; $N] }
+            __impl_slice_eq1! { [AllocA: Alloc] Vec<A, AllocA>, &'b [B; $N] }
             // __impl_slice_eq1! { Vec<A>, &'b mut [B; $N] }
             // __impl_slice_eq1! { Cow<'a, [A]>, [B; $N], Clone }
             // __impl_slice_eq1! { Cow<'a, [A]>, &'b [B; $N], Clone }
@@ -2083,27 +2322,27 @@ array_impls! {
 
 /// Implements comparison of vectors, lexicographically.
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: PartialOrd> PartialOrd for Vec<T> {
+impl<T: PartialOrd, A: Alloc> PartialOrd for Vec<T, A> {
     #[inline]
-    fn partial_cmp(&self, other: &Vec<T>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &Vec<T, A>) -> Option<Ordering> {
         PartialOrd::partial_cmp(&**self, &**other)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Eq> Eq for Vec<T> {}
+impl<T: Eq, A: Alloc> Eq for Vec<T, A> {}
 
 /// Implements ordering of vectors, lexicographically.
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord> Ord for Vec<T> {
+impl<T: Ord, A: Alloc> Ord for Vec<T, A> {
     #[inline]
-    fn cmp(&self, other: &Vec<T>) -> Ordering {
+    fn cmp(&self, other: &Vec<T, A>) -> Ordering {
         Ord::cmp(&**self, &**other)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-unsafe impl<#[may_dangle] T> Drop for Vec<T> {
+unsafe impl<#[may_dangle] T, A: Alloc> Drop for Vec<T, A> {
     fn drop(&mut self) {
         unsafe {
             // use drop for [T]
@@ -2122,35 +2361,35 @@ impl<T> Default for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: fmt::Debug> fmt::Debug for Vec<T> {
+impl<T: fmt::Debug, A: Alloc> fmt::Debug for Vec<T, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> AsRef<Vec<T>> for Vec<T> {
-    fn as_ref(&self) -> &Vec<T> {
+impl<T, A: Alloc> AsRef<Vec<T, A>> for Vec<T, A> {
+    fn as_ref(&self) -> &Vec<T, A> {
         self
     }
 }
 
 #[stable(feature = "vec_as_mut", since = "1.5.0")]
-impl<T> AsMut<Vec<T>> for Vec<T> {
-    fn as_mut(&mut self) -> &mut Vec<T> {
+impl<T, A: Alloc> AsMut<Vec<T, A>> for Vec<T, A> {
+    fn as_mut(&mut self) -> &mut Vec<T, A> {
         self
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> AsRef<[T]> for Vec<T> {
+impl<T, A: Alloc> AsRef<[T]> for Vec<T, A> {
     fn as_ref(&self) -> &[T] {
         self
     }
 }
 
 #[stable(feature = "vec_as_mut", since = "1.5.0")]
-impl<T> AsMut<[T]> for Vec<T> {
+impl<T, A: Alloc> AsMut<[T]> for Vec<T, A> {
     fn as_mut(&mut self) -> &mut [T] {
         self
     }
@@ -2417,18 +2656,18 @@ unsafe impl<#[may_dangle] T> Drop for IntoIter<T> {
 /// [`drain`]: struct.Vec.html#method.drain
 /// [`Vec`]: struct.Vec.html
 #[stable(feature = "drain", since = "1.6.0")]
-pub struct Drain<'a, T: 'a> {
+pub struct Drain<'a, T: 'a, A: Alloc + 'a = Heap> {
     /// Index of tail to preserve
     tail_start: usize,
     /// Length of tail
     tail_len: usize,
     /// Current remaining range to remove
     iter: slice::Iter<'a, T>,
-    vec: Shared<Vec<T>>,
+    vec: Shared<Vec<T, A>>,
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<'a, T: 'a + fmt::Debug> fmt::Debug for Drain<'a, T> {
+impl<'a, T: 'a + fmt::Debug, A: Alloc> fmt::Debug for Drain<'a, T, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("Drain")
          .field(&self.iter.as_slice())
@@ -2437,12 +2676,12 @@ impl<'a, T: 'a + fmt::Debug> fmt::Debug for Drain<'a, T> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl<'a, T: Sync> Sync for Drain<'a, T> {}
+unsafe impl<'a, T: Sync, A: Alloc + Sync> Sync for Drain<'a, T, A> {}
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl<'a, T: Send> Send for Drain<'a, T> {}
+unsafe impl<'a, T: Send, A: Alloc + Send> Send for Drain<'a, T, A> {}
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<'a, T> Iterator for Drain<'a, T> {
+impl<'a, T, A: Alloc> Iterator for Drain<'a, T, A> {
     type Item = T;
 
     #[inline]
@@ -2456,7 +2695,7 @@ impl<'a, T> Iterator for Drain<'a, T> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
+impl<'a, T, A: Alloc> DoubleEndedIterator for Drain<'a, T, A> {
     #[inline]
     fn next_back(&mut self) -> Option<T> {
         self.iter.next_back().map(|elt| unsafe { ptr::read(elt as *const _) })
@@ -2464,7 +2703,7 @@ impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<'a, T> Drop for Drain<'a, T> {
+impl<'a, T, A: Alloc> Drop for Drain<'a, T, A> {
     fn drop(&mut self) {
         // exhaust self first
         while let Some(_) = self.next() {}
@@ -2486,14 +2725,14 @@ impl<'a, T> Drop for Drain<'a, T> {
 
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<'a, T> ExactSizeIterator for Drain<'a, T> {
+impl<'a, T, A: Alloc> ExactSizeIterator for Drain<'a, T, A> {
     fn is_empty(&self) -> bool {
         self.iter.is_empty()
     }
 }
 
 #[unstable(feature = "fused", issue = "35602")]
-impl<'a, T> FusedIterator for Drain<'a, T> {}
+impl<'a, T, A: Alloc> FusedIterator for Drain<'a, T, A> {}
 
 /// A place for insertion at the back of a `Vec`.
 ///
@@ -2503,15 +2742,15 @@ impl<'a, T> FusedIterator for Drain<'a, T> {}
            reason = "struct name and placement protocol are subject to change",
            issue = "30172")]
 #[derive(Debug)]
-pub struct PlaceBack<'a, T: 'a> {
-    vec: &'a mut Vec<T>,
+pub struct PlaceBack<'a, T: 'a, A: Alloc + 'a = Heap> {
+    vec: &'a mut Vec<T, A>,
 }
 
 #[unstable(feature = "collection_placement",
            reason = "placement protocol is subject to change",
            issue = "30172")]
-impl<'a, T> Placer<T> for PlaceBack<'a, T> {
-    type Place = PlaceBack<'a, T>;
+impl<'a, T, A: Alloc> Placer<T> for PlaceBack<'a, T, A> {
+    type Place = PlaceBack<'a, T, A>;
 
     fn make_place(self) -> Self {
         // This will panic or abort if we would allocate > isize::MAX bytes
@@ -2526,7 +2765,7 @@ impl<'a, T> Placer<T> for PlaceBack<'a, T> {
 #[unstable(feature = "collection_placement",
            reason = "placement protocol is subject to change",
            issue = "30172")]
-impl<'a, T> Place<T> for PlaceBack<'a, T> {
+impl<'a, T, A: Alloc> Place<T> for PlaceBack<'a, T, A> {
     fn pointer(&mut self) -> *mut T {
         unsafe { self.vec.as_mut_ptr().offset(self.vec.len as isize) }
     }
@@ -2535,7 +2774,7 @@ impl<'a, T> Place<T> for PlaceBack<'a, T> {
 #[unstable(feature = "collection_placement",
            reason = "placement protocol is subject to change",
            issue = "30172")]
-impl<'a, T> InPlace<T> for PlaceBack<'a, T> {
+impl<'a, T, A: Alloc> InPlace<T> for PlaceBack<'a, T, A> {
     type Owner = &'a mut T;
 
     unsafe fn finalize(mut self) -> &'a mut T {
@@ -2555,13 +2794,13 @@ impl<'a, T> InPlace<T> for PlaceBack<'a, T> {
 /// [`Vec`]: struct.Vec.html
 #[derive(Debug)]
 #[unstable(feature = "splice", reason = "recently added", issue = "32310")]
-pub struct Splice<'a, I: Iterator + 'a> {
-    drain: Drain<'a, I::Item>,
+pub struct Splice<'a, I: Iterator + 'a, A: Alloc + 'a = Heap> {
+    drain: Drain<'a, I::Item, A>,
     replace_with: I,
 }
 
 #[unstable(feature = "splice", reason = "recently added", issue = "32310")]
-impl<'a, I: Iterator> Iterator for Splice<'a, I> {
+impl<'a, I: Iterator, A: Alloc> Iterator for Splice<'a, I, A> {
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -2574,18 +2813,18 @@ impl<'a, I: Iterator> Iterator for Splice<'a, I> {
 }
 
 #[unstable(feature = "splice", reason = "recently added", issue = "32310")]
-impl<'a, I: Iterator> DoubleEndedIterator for Splice<'a, I> {
+impl<'a, I: Iterator, A: Alloc> DoubleEndedIterator for Splice<'a, I, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.drain.next_back()
     }
 }
 
 #[unstable(feature = "splice", reason = "recently added", issue = "32310")]
-impl<'a, I: Iterator> ExactSizeIterator for Splice<'a, I> {}
+impl<'a, I: Iterator, A: Alloc> ExactSizeIterator for Splice<'a, I, A> {}
 
 
 #[unstable(feature = "splice", reason = "recently added", issue = "32310")]
-impl<'a, I: Iterator> Drop for Splice<'a, I> {
+impl<'a, I: Iterator, A: Alloc> Drop for Splice<'a, I, A> {
     fn drop(&mut self) {
         // exhaust drain first
         while let Some(_) = self.drain.next() {}
@@ -2628,7 +2867,7 @@ impl<'a, I: Iterator> Drop for Splice<'a, I> {
 }
 
 /// Private helper methods for `Splice::drop`
-impl<'a, T> Drain<'a, T> {
+impl<'a, T, A: Alloc> Drain<'a, T, A> {
     /// The range from `self.vec.len` to `self.tail_start` contains elements
     /// that have been moved out.
     /// Fill that range as much as possible with new elements from the `replace_with` iterator.
@@ -2669,10 +2908,10 @@ impl<'a, T> Drain<'a, T> {
 /// An iterator produced by calling `drain_filter` on Vec.
 #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
 #[derive(Debug)]
-pub struct DrainFilter<'a, T: 'a, F>
+pub struct DrainFilter<'a, T: 'a, F, A: Alloc + 'a = Heap>
     where F: FnMut(&mut T) -> bool,
 {
-    vec: &'a mut Vec<T>,
+    vec: &'a mut Vec<T, A>,
     idx: usize,
     del: usize,
     old_len: usize,
@@ -2680,7 +2919,7 @@ pub struct DrainFilter<'a, T: 'a, F>
 }
 
 #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
-impl<'a, T, F> Iterator for DrainFilter<'a, T, F>
+impl<'a, T, F, A: Alloc> Iterator for DrainFilter<'a, T, F, A>
     where F: FnMut(&mut T) -> bool,
 {
     type Item = T;
@@ -2708,7 +2947,7 @@ impl<'a, T, F> Iterator for DrainFilter<'a, T, F>
 }
 
 #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
-impl<'a, T, F> Drop for DrainFilter<'a, T, F>
+impl<'a, T, F, A: Alloc> Drop for DrainFilter<'a, T, F, A>
     where F: FnMut(&mut T) -> bool,
 {
     fn drop(&mut self) {
@@ -0,0 +1,73 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Asynchronous values.
+
+#![unstable(feature = "futures_api",
+            reason = "futures in libcore are unstable",
+            issue = "0")]
+
+use marker::Unpin;
+use mem::Pin;
+use task::{Context, Poll};
+
+/// A future represents an asynchronous computation.
+///
+/// A future is a value that may not have finished computing yet. This kind of
+/// "asynchronous value" makes it possible for a thread to continue doing useful
+/// work while it waits for the value to become available.
+///
+/// The primary method of a future, `poll`, attempts to resolve the future into
+/// a final value. This method does not block if the value is not ready.
+/// Instead, the current task is scheduled to be woken up when it's possible to
+/// make further progress by `poll`ing again. The wake up is performed using
+/// the `Waker` found in the `Context` passed to `poll`.
+///
+/// `async fn`s return values implementing this trait.
+pub trait Future {
+    /// The result of the `Future`.
+    type Output;
+
+    /// Attempt to resolve the future to a final value, registering
+    /// the current task for wakeup if the value is not yet available.
+    ///
+    /// # Return value
+    ///
+    /// This function returns:
+    ///
+    /// - `Poll::Pending` if the future is not ready yet
+    /// - `Poll::Ready(val)` with the result `val` of this future if it
+    ///   finished successfully.
+    ///
+    /// Once a future has finished, clients should not `poll` it again.
+    ///
+    /// When a future is not ready yet, `poll` returns `Poll::Pending` and
+    /// arranges for the `Waker` of `cx` to be woken once the future can make
+    /// progress.
+    ///
+    /// The future is passed as a `Pin` because it may borrow from itself,
+    /// as the generators backing `async fn`s do. Once polled, a future must
+    /// not be moved again unless it implements `Unpin`.
+    ///
+    /// # Panics
+    ///
+    /// Once a future has completed (returned `Ready` from `poll`),
+    /// then any future calls to `poll` may panic, block forever, or otherwise
+    /// cause bad behavior.
+    fn poll(this: Pin<Self>, cx: &mut Context) -> Poll<Self::Output>;
+}
+
+impl<'a, F: ?Sized + Future + Unpin> Future for &'a mut F {
+    type Output = F::Output;
+
+    fn poll(mut this: Pin<Self>, cx: &mut Context) -> Poll<Self::Output> {
+        F::poll(Pin::new(&mut **this), cx)
+    }
+}
@@ -186,6 +186,11 @@ pub mod str;
 pub mod hash;
 pub mod fmt;
 
+/* Async */
+
+pub mod future;
+pub mod task;
+
 // note: does not need to be public
 mod char_private;
 mod iter_private;
@@ -570,3 +570,35 @@ unsafe impl<T: ?Sized> Freeze for *const T {}
 unsafe impl<T: ?Sized> Freeze for *mut T {}
 unsafe impl<'a, T: ?Sized> Freeze for &'a T {}
 unsafe impl<'a, T: ?Sized> Freeze for &'a mut T {}
+
+/// Types which can be safely moved after being pinned.
+///
+/// Since Rust itself has no notion of immovable types, and will consider moves
+/// to always be safe, this trait cannot prevent types from moving by itself.
+///
+/// Instead it can be used to prevent moves through the type system, by
+/// controlling the behavior of special pointers like [`Pin`] that wrap the
+/// type. [`Pin`] only allows mutable access to its referent if the referent
+/// implements `Unpin`, so types which don't are guaranteed to stay in place
+/// once pinned.
+///
+/// This trait is automatically implemented for almost every type. The main
+/// exception are immovable (`static`) generators, which may hold references to
+/// their own locals across suspension points.
+///
+/// [`Pin`]: ../mem/struct.Pin.html
+#[cfg_attr(not(stage0), lang = "unpin")]
+#[unstable(feature = "pin", issue = "0")]
+pub trait Unpin {}
+
+#[unstable(feature = "pin", issue = "0")]
+impl Unpin for .. {}
+
+#[unstable(feature = "pin", issue = "0")]
+impl<'a, T: ?Sized + 'a> Unpin for &'a T {}
+#[unstable(feature = "pin", issue = "0")]
+impl<'a, T: ?Sized + 'a> Unpin for &'a mut T {}
+#[unstable(feature = "pin", issue = "0")]
+impl<T: ?Sized> Unpin for *const T {}
+#[unstable(feature = "pin", issue = "0")]
+impl<T: ?Sized> Unpin for *mut T {}
@@ -20,7 +20,7 @@ use cmp;
 use fmt;
 use hash;
 use intrinsics;
-use marker::{Copy, PhantomData, Sized};
+use marker::{Copy, PhantomData, Sized, Unpin};
 use ptr;
 use ops::{Deref, DerefMut};
 
@@ -983,3 +983,91 @@ impl<T: ::hash::Hash> ::hash::Hash for ManuallyDrop<T> {
 pub unsafe fn unreachable() -> ! {
     intrinsics::unreachable()
 }
+
+/// A pinned reference.
+///
+/// A `Pin` is a mutable reference to a value which is guaranteed to never be
+/// moved again, not even after the `Pin` itself is gone. This makes it possible
+/// to safely work with self-referential values such as immovable generators,
+/// which may hold borrows of their own fields.
+///
+/// Only types implementing [`Unpin`] can be pinned and unpinned safely; for
+/// all other types constructing a `Pin` or getting a mutable reference out of
+/// one is `unsafe`, and the caller has to uphold the pinning guarantee.
+///
+/// [`Unpin`]: ../marker/trait.Unpin.html
+#[unstable(feature = "pin", issue = "0")]
+pub struct Pin<'a, T: ?Sized + 'a> {
+    inner: &'a mut T,
+}
+
+impl<'a, T: ?Sized + Unpin> Pin<'a, T> {
+    /// Construct a new `Pin` around a reference to some data of a type that
+    /// implements `Unpin`.
+    #[unstable(feature = "pin", issue = "0")]
+    pub fn new(reference: &'a mut T) -> Pin<'a, T> {
+        Pin { inner: reference }
+    }
+
+    /// Get a mutable reference to the data inside of this `Pin`.
+    #[unstable(feature = "pin", issue = "0")]
+    pub fn get_mut(this: Pin<'a, T>) -> &'a mut T {
+        this.inner
+    }
+}
+
+impl<'a, T: ?Sized> Pin<'a, T> {
+    /// Construct a new `Pin` around a reference to some data of a type that
+    /// may or may not implement `Unpin`.
+    ///
+    /// This constructor is unsafe because we do not know what will happen with
+    /// that data after the reference ends. If you cannot guarantee that the
+    /// data will never move again, calling this constructor is undefined
+    /// behavior.
+    #[unstable(feature = "pin", issue = "0")]
+    pub unsafe fn new_unchecked(reference: &'a mut T) -> Pin<'a, T> {
+        Pin { inner: reference }
+    }
+
+    /// Reborrow a `Pin` for a shorter lifetime.
+    ///
+    /// For example, `Pin::borrow(&mut foo)` is a `Pin` with the lifetime of the
+    /// temporary borrow of `foo`, leaving `foo` usable afterwards.
+    #[unstable(feature = "pin", issue = "0")]
+    pub fn borrow<'b>(this: &'b mut Pin<'a, T>) -> Pin<'b, T> {
+        Pin { inner: this.inner }
+    }
+
+    /// Get a mutable reference to the data inside of this `Pin`.
+    ///
+    /// This function is unsafe. You must guarantee that you will never move
+    /// the data out of the mutable reference you receive when you call this
+    /// function.
+    #[unstable(feature = "pin", issue = "0")]
+    pub unsafe fn get_mut_unchecked(this: Pin<'a, T>) -> &'a mut T {
+        this.inner
+    }
+}
+
+#[unstable(feature = "pin", issue = "0")]
+impl<'a, T: ?Sized> Deref for Pin<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &*self.inner
+    }
+}
+
+#[unstable(feature = "pin", issue = "0")]
+impl<'a, T: ?Sized + Unpin> DerefMut for Pin<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner
+    }
+}
+
+#[unstable(feature = "pin", issue = "0")]
+impl<'a, T: fmt::Debug + ?Sized> fmt::Debug for Pin<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
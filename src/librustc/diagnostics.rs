@@ -2086,4 +2086,7 @@ register_diagnostics! {
     E0566, // conflicting representation hints
     E0623, // lifetime mismatch where both parameters are anonymous regions
     E0628, // generators cannot have explicit arguments
+    E0631, // closures cannot be static
+    E0641, // `#[non_exhaustive]` applied to something other than a struct or enum
+    E0642, // `#[non_exhaustive]` given arguments
 }
@@ -9,10 +9,10 @@
 // except according to those terms.
 
 //! This module implements some validity checks for attributes.
-//! In particular it verifies that `#[inline]` and `#[repr]` attributes are
-//! attached to items that actually support them and if there are
-//! conflicts between multiple such attributes attached to the same
-//! item.
+//! In particular it verifies that `#[inline]`, `#[repr]` and
+//! `#[non_exhaustive]` attributes are attached to items that actually
+//! support them and if there are conflicts between multiple such
+//! attributes attached to the same item.
 
 use session::Session;
 
@@ -52,6 +52,7 @@ impl<'a> CheckAttrVisitor<'a> {
             match &*name.as_str() {
                 "inline" => self.check_inline(attr, target),
                 "repr" => self.check_repr(attr, target),
+                "non_exhaustive" => self.check_non_exhaustive(attr, target),
                 _ => (),
             }
         }
@@ -66,6 +67,27 @@ impl<'a> CheckAttrVisitor<'a> {
         }
     }
 
+    /// Check if a `#[non_exhaustive]` is applied to a struct or enum.
+    fn check_non_exhaustive(&self, attr: &ast::Attribute, target: Target) {
+        match target {
+            Target::Struct | Target::Enum => { /* Valid */ },
+            _ => {
+                struct_span_err!(self.sess, attr.span, E0641,
+                                 "attribute can only be applied to a struct or enum")
+                    .span_label(attr.span, "requires a struct or enum")
+                    .emit();
+                return;
+            }
+        }
+
+        if attr.meta_item_list().is_some() || attr.value_str().is_some() {
+            struct_span_err!(self.sess, attr.span, E0642,
+                             "attribute should be empty")
+                .span_label(attr.span, "not empty")
+                .emit();
+        }
+    }
+
     /// Check if an `#[repr]` attr is valid.
     fn check_repr(&self, attr: &ast::Attribute, target: Target) {
         let words = match attr.meta_item_list() {
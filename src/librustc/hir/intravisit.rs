@@ -510,6 +510,11 @@ pub fn walk_item<'v, V: Visitor<'v>>(visitor: &mut V, item: &'v Item) {
             walk_list!(visitor, visit_ty_param_bound, bounds);
             walk_list!(visitor, visit_trait_item_ref, trait_item_refs);
         }
+        ItemTraitAlias(ref generics, ref bounds) => {
+            visitor.visit_id(item.id);
+            visitor.visit_generics(generics);
+            walk_list!(visitor, visit_ty_param_bound, bounds);
+        }
     }
     walk_list!(visitor, visit_attribute, &item.attrs);
 }
@@ -575,7 +580,7 @@ pub fn walk_ty<'v, V: Visitor<'v>>(visitor: &mut V, typ: &'v Ty) {
             }
             visitor.visit_lifetime(lifetime);
         }
-        TyImplTrait(ref bounds) => {
+        TyImplTraitExistential(ref bounds) | TyImplTraitUniversal(_, ref bounds) => {
             walk_list!(visitor, visit_ty_param_bound, bounds);
         }
         TyTypeof(expression) => {
@@ -619,6 +624,15 @@ pub fn walk_path_parameters<'v, V: Visitor<'v>>(visitor: &mut V,
                                                 path_parameters: &'v PathParameters) {
     walk_list!(visitor, visit_lifetime, &path_parameters.lifetimes);
     walk_list!(visitor, visit_ty, &path_parameters.types);
+    for arg in path_parameters.consts.iter() {
+        match *arg {
+            ConstArg::Param(id, ref path) => {
+                visitor.visit_id(id);
+                visitor.visit_path(path, id);
+            }
+            ConstArg::Expr(body) => visitor.visit_nested_body(body),
+        }
+    }
     walk_list!(visitor, visit_assoc_type_binding, &path_parameters.bindings);
 }
 
@@ -686,6 +700,7 @@ pub fn walk_foreign_item<'v, V: Visitor<'v>>(visitor: &mut V, foreign_item: &'v
             }
         }
         ForeignItemStatic(ref typ, _) => visitor.visit_ty(typ),
+        ForeignItemType => (),
     }
 
     walk_list!(visitor, visit_attribute, &foreign_item.attrs);
@@ -709,6 +724,11 @@ pub fn walk_generics<'v, V: Visitor<'v>>(visitor: &mut V, generics: &'v Generics
         walk_list!(visitor, visit_ty_param_bound, &param.bounds);
         walk_list!(visitor, visit_ty, &param.default);
     }
+    for param in generics.const_params.iter() {
+        visitor.visit_id(param.id);
+        visitor.visit_name(param.span, param.name);
+        visitor.visit_ty(&param.ty);
+    }
     walk_list!(visitor, visit_lifetime_def, &generics.lifetimes);
     visitor.visit_id(generics.where_clause.id);
     walk_list!(visitor, visit_where_predicate, &generics.where_clause.predicates);
@@ -808,8 +828,9 @@ pub fn walk_trait_item<'v, V: Visitor<'v>>(visitor: &mut V, trait_item: &'v Trai
                              trait_item.span,
                              trait_item.id);
         }
-        TraitItemKind::Type(ref bounds, ref default) => {
+        TraitItemKind::Type(ref generics, ref bounds, ref default) => {
             visitor.visit_id(trait_item.id);
+            visitor.visit_generics(generics);
             walk_list!(visitor, visit_ty_param_bound, bounds);
             walk_list!(visitor, visit_ty, default);
         }
@@ -858,8 +879,9 @@ pub fn walk_impl_item<'v, V: Visitor<'v>>(visitor: &mut V, impl_item: &'v ImplIt
                              impl_item.span,
                              impl_item.id);
         }
-        ImplItemKind::Type(ref ty) => {
+        ImplItemKind::Type(ref generics, ref ty) => {
             visitor.visit_id(impl_item.id);
+            visitor.visit_generics(generics);
             visitor.visit_ty(ty);
         }
     }
@@ -102,6 +102,10 @@ pub struct LoweringContext<'a> {
     loop_scopes: Vec<NodeId>,
     is_in_loop_condition: bool,
 
+    // The function whose argument types are being lowered, if any. An
+    // `impl Trait` found there becomes an anonymous type parameter of it.
+    universal_impl_trait_fn: Option<DefId>,
+
     type_def_lifetime_params: DefIdMap<usize>,
 
     current_hir_id_owner: Vec<(DefIndex, u32)>,
@@ -149,6 +153,7 @@ pub fn lower_crate(sess: &Session,
         catch_scopes: Vec::new(),
         loop_scopes: Vec::new(),
         is_in_loop_condition: false,
+        universal_impl_trait_fn: None,
         type_def_lifetime_params: DefIdMap(),
         current_hir_id_owner: vec![(CRATE_DEF_INDEX, 0)],
         item_local_id_counters: NodeMap(),
@@ -196,7 +201,8 @@ impl<'a> LoweringContext<'a> {
                     ItemKind::Union(_, ref generics) |
                     ItemKind::Enum(_, ref generics) |
                     ItemKind::Ty(_, ref generics) |
-                    ItemKind::Trait(_, ref generics, ..) => {
+                    ItemKind::Trait(_, ref generics, ..) |
+                    ItemKind::TraitAlias(ref generics, ..) => {
                         let def_id = self.lctx.resolver.definitions().local_def_id(item.id);
                         let count = generics.lifetimes.len();
                         self.lctx.type_def_lifetime_params.insert(def_id, count);
@@ -629,7 +635,7 @@ impl<'a> LoweringContext<'a> {
                     lifetimes: self.lower_lifetime_defs(&f.lifetimes),
                     unsafety: self.lower_unsafety(f.unsafety),
                     abi: f.abi,
-                    decl: self.lower_fn_decl(&f.decl),
+                    decl: self.lower_fn_decl(&f.decl, None),
                 }))
             }
             TyKind::Never => hir::TyNever,
@@ -684,7 +690,11 @@ impl<'a> LoweringContext<'a> {
                 hir::TyTraitObject(bounds, lifetime_bound)
             }
             TyKind::ImplTrait(ref bounds) => {
-                hir::TyImplTrait(self.lower_bounds(bounds))
+                let bounds = self.lower_bounds(bounds);
+                match self.universal_impl_trait_fn {
+                    Some(fn_def_id) => hir::TyImplTraitUniversal(fn_def_id, bounds),
+                    None => hir::TyImplTraitExistential(bounds),
+                }
             }
             TyKind::Mac(_) => panic!("TyMac should have been expanded by now."),
         };
@@ -935,21 +945,65 @@ impl<'a> LoweringContext<'a> {
                                             data: &AngleBracketedParameterData,
                                             param_mode: ParamMode)
                                             -> hir::PathParameters {
-        let &AngleBracketedParameterData { ref lifetimes, ref types, ref bindings, .. } = data;
+        let &AngleBracketedParameterData {
+            ref lifetimes, ref types, ref consts, ref bindings, ..
+        } = data;
+
+        // The parser can't tell a const parameter used as an argument apart
+        // from a type, so move those over to the const arguments here.
+        let mut lowered_types = Vec::with_capacity(types.len());
+        let mut lowered_consts = Vec::with_capacity(consts.len());
+        for ty in types {
+            if let Some(path) = self.lower_const_param_arg(ty) {
+                let id = self.lower_node_id(ty.id).node_id;
+                lowered_consts.push(hir::ConstArg::Param(id, P(path)));
+            } else {
+                if !lowered_consts.is_empty() {
+                    self.diagnostic().span_err(ty.span,
+                        "type parameters must be declared prior to const parameters");
+                }
+                lowered_types.push(self.lower_ty(ty));
+            }
+        }
+        for expr in consts {
+            let body = self.lower_body(None, |this| this.lower_expr(expr));
+            lowered_consts.push(hir::ConstArg::Expr(body));
+        }
+
         hir::PathParameters {
             lifetimes: self.lower_lifetimes(lifetimes),
-            types: types.iter().map(|ty| self.lower_ty(ty)).collect(),
-            infer_types: types.is_empty() && param_mode == ParamMode::Optional,
+            infer_types: lowered_types.is_empty() && lowered_consts.is_empty() &&
+                         param_mode == ParamMode::Optional,
+            types: lowered_types.into(),
+            consts: lowered_consts.into(),
             bindings: bindings.iter().map(|b| self.lower_ty_binding(b)).collect(),
             parenthesized: false,
         }
     }
 
+    /// Lowers a type argument that resolved to a const parameter, e.g. the
+    /// `N` in `Buf<N>`, to the path of that parameter.
+    fn lower_const_param_arg(&mut self, ty: &Ty) -> Option<hir::Path> {
+        if let TyKind::Path(None, ref path) = ty.node {
+            if path.segments.len() == 1 {
+                if let Some(Def::ConstParam(..)) =
+                        self.resolver.get_resolution(ty.id).map(|d| d.base_def()) {
+                    return Some(self.lower_path(ty.id, path, ParamMode::Explicit, false));
+                }
+            }
+        }
+        None
+    }
+
     fn lower_parenthesized_parameter_data(&mut self,
                                           data: &ParenthesizedParameterData)
                                           -> hir::PathParameters {
         let &ParenthesizedParameterData { ref inputs, ref output, span } = data;
+        // The sugared inputs of `Fn(A) -> B` are not arguments of the
+        // enclosing function.
+        let universal_impl_trait_fn = self.universal_impl_trait_fn.take();
         let inputs = inputs.iter().map(|ty| self.lower_ty(ty)).collect();
+        self.universal_impl_trait_fn = universal_impl_trait_fn;
         let mk_tup = |this: &mut Self, tys, span| {
             P(hir::Ty { node: hir::TyTup(tys), id: this.next_id().node_id, span })
         };
@@ -957,6 +1011,7 @@ impl<'a> LoweringContext<'a> {
         hir::PathParameters {
             lifetimes: hir::HirVec::new(),
             types: hir_vec![mk_tup(self, inputs, span)],
+            consts: hir::HirVec::new(),
             infer_types: false,
             bindings: hir_vec![hir::TypeBinding {
                 id: self.next_id().node_id,
@@ -1011,13 +1066,21 @@ impl<'a> LoweringContext<'a> {
         }).collect()
     }
 
-    fn lower_fn_decl(&mut self, decl: &FnDecl) -> P<hir::FnDecl> {
+    // `fn_def_id` is the function whose signature this is, if `impl Trait`
+    // is allowed to introduce anonymous type parameters in its arguments.
+    fn lower_fn_decl(&mut self, decl: &FnDecl, fn_def_id: Option<DefId>) -> P<hir::FnDecl> {
+        let outer_impl_trait_fn = mem::replace(&mut self.universal_impl_trait_fn, fn_def_id);
+        let inputs = decl.inputs.iter().map(|arg| self.lower_ty(&arg.ty)).collect();
+        self.universal_impl_trait_fn = None;
+        let output = match decl.output {
+            FunctionRetTy::Ty(ref ty) => hir::Return(self.lower_ty(ty)),
+            FunctionRetTy::Default(span) => hir::DefaultReturn(span),
+        };
+        self.universal_impl_trait_fn = outer_impl_trait_fn;
+
         P(hir::FnDecl {
-            inputs: decl.inputs.iter().map(|arg| self.lower_ty(&arg.ty)).collect(),
-            output: match decl.output {
-                FunctionRetTy::Ty(ref ty) => hir::Return(self.lower_ty(ty)),
-                FunctionRetTy::Default(span) => hir::DefaultReturn(span),
-            },
+            inputs,
+            output,
             variadic: decl.variadic,
             has_implicit_self: decl.inputs.get(0).map_or(false, |arg| {
                 match arg.ty.node {
@@ -1029,6 +1092,90 @@ impl<'a> LoweringContext<'a> {
         })
     }
 
+    // Lowers the signature of an `async fn`, turning the declared return
+    // type `T` into `impl ::std::future::Future<Output = T>`.
+    fn lower_async_fn_decl(&mut self,
+                           decl: &FnDecl,
+                           fn_def_id: DefId,
+                           return_impl_trait_id: NodeId)
+                           -> P<hir::FnDecl> {
+        let mut fn_decl = self.lower_fn_decl(decl, Some(fn_def_id));
+        let (output_ty, span) = match fn_decl.output {
+            hir::Return(ref ty) => (ty.clone(), ty.span),
+            hir::DefaultReturn(span) => {
+                (P(hir::Ty {
+                    id: self.next_id().node_id,
+                    node: hir::TyTup(hir_vec![]),
+                    span,
+                }), span)
+            }
+        };
+
+        let mut path = self.std_path(span, &["future", "Future"], false);
+        let mut segments = path.segments.into_vec();
+        segments.last_mut().unwrap().parameters.bindings = hir_vec![hir::TypeBinding {
+            id: self.next_id().node_id,
+            name: Symbol::intern(FN_OUTPUT_NAME),
+            ty: output_ty,
+            span,
+        }];
+        path.segments = segments.into();
+
+        let bound = hir::TraitTyParamBound(hir::PolyTraitRef {
+            bound_lifetimes: hir_vec![],
+            trait_ref: hir::TraitRef {
+                path,
+                ref_id: self.next_id().node_id,
+            },
+            span,
+        }, hir::TraitBoundModifier::None);
+
+        fn_decl.output = hir::Return(P(hir::Ty {
+            id: self.lower_node_id(return_impl_trait_id).node_id,
+            node: hir::TyImplTraitExistential(hir_vec![bound]),
+            span,
+        }));
+        fn_decl
+    }
+
+    // Lowers the body of an `async fn` into
+    // `::std::future::from_generator(static move || { <body> })`.
+    fn lower_async_body(&mut self, closure_id: NodeId, body: &Block) -> hir::Expr {
+        let span = self.allow_internal_unstable(CompilerDesugaringKind::Async, body.span);
+        let closure = self.with_new_scopes(|this| {
+            this.with_parent_def(closure_id, |this| {
+                let body_id = this.lower_body(None, |this| {
+                    // The body is always a generator, even if it never awaits.
+                    this.is_generator = true;
+                    let body = this.lower_block(body, false);
+                    this.expr_block(body, ThinVec::new())
+                });
+                let decl = P(hir::FnDecl {
+                    inputs: hir_vec![],
+                    output: hir::DefaultReturn(span),
+                    variadic: false,
+                    has_implicit_self: false,
+                });
+                let LoweredNodeId { node_id, hir_id } = this.lower_node_id(closure_id);
+                hir::Expr {
+                    id: node_id,
+                    hir_id,
+                    node: hir::ExprClosure(hir::CaptureByValue,
+                                           decl,
+                                           body_id,
+                                           span,
+                                           Some(hir::GeneratorMovability::Static)),
+                    span,
+                    attrs: ThinVec::new(),
+                }
+            })
+        });
+        let from_generator = self.expr_std_path(span,
+                                                &["future", "from_generator"],
+                                                ThinVec::new());
+        self.expr_call(span, P(from_generator), hir_vec![closure])
+    }
+
     fn lower_ty_param_bound(&mut self, tpb: &TyParamBound) -> hir::TyParamBound {
         match *tpb {
             TraitTyParamBound(ref ty, modifier) => {
@@ -1066,6 +1213,15 @@ impl<'a> LoweringContext<'a> {
         }
     }
 
+    fn lower_const_param(&mut self, cp: &ConstParam) -> hir::ConstParam {
+        hir::ConstParam {
+            id: self.lower_node_id(cp.id).node_id,
+            name: self.lower_ident(cp.ident),
+            ty: self.lower_ty(&cp.ty),
+            span: cp.span,
+        }
+    }
+
     fn lower_ty_params(&mut self, tps: &Vec<TyParam>, add_bounds: &NodeMap<Vec<TyParamBound>>)
                        -> hir::HirVec<hir::TyParam> {
         tps.iter().map(|tp| {
@@ -1140,6 +1296,7 @@ impl<'a> LoweringContext<'a> {
         hir::Generics {
             ty_params: self.lower_ty_params(&g.ty_params, &add_bounds),
             lifetimes: self.lower_lifetime_defs(&g.lifetimes),
+            const_params: g.const_params.iter().map(|cp| self.lower_const_param(cp)).collect(),
             where_clause: self.lower_where_clause(&g.where_clause),
             span: g.span,
         }
@@ -1393,13 +1550,32 @@ impl<'a> LoweringContext<'a> {
                 let value = self.lower_body(None, |this| this.lower_expr(e));
                 hir::ItemConst(self.lower_ty(t), value)
             }
-            ItemKind::Fn(ref decl, unsafety, constness, abi, ref generics, ref body) => {
+            ItemKind::Fn(ref decl, unsafety, constness, IsAsync::Async {
+                closure_id,
+                return_impl_trait_id,
+            }, abi, ref generics, ref body) => {
+                self.with_new_scopes(|this| {
+                    let body_id = this.lower_body(Some(decl), |this| {
+                        this.lower_async_body(closure_id, body)
+                    });
+                    let fn_def_id = this.resolver.definitions().local_def_id(id);
+                    hir::ItemFn(this.lower_async_fn_decl(decl, fn_def_id, return_impl_trait_id),
+                                this.lower_unsafety(unsafety),
+                                this.lower_constness(constness),
+                                abi,
+                                this.lower_generics(generics),
+                                body_id)
+                })
+            }
+            ItemKind::Fn(ref decl, unsafety, constness, IsAsync::NotAsync, abi, ref generics,
+                         ref body) => {
                 self.with_new_scopes(|this| {
                     let body_id = this.lower_body(Some(decl), |this| {
                         let body = this.lower_block(body, false);
                         this.expr_block(body, ThinVec::new())
                     });
-                    hir::ItemFn(this.lower_fn_decl(decl),
+                    let fn_def_id = this.resolver.definitions().local_def_id(id);
+                    hir::ItemFn(this.lower_fn_decl(decl, Some(fn_def_id)),
                                               this.lower_unsafety(unsafety),
                                               this.lower_constness(constness),
                                               abi,
@@ -1474,6 +1650,10 @@ impl<'a> LoweringContext<'a> {
                                bounds,
                                items)
             }
+            ItemKind::TraitAlias(ref generics, ref bounds) => {
+                hir::ItemTraitAlias(self.lower_generics(generics),
+                                    self.lower_bounds(bounds))
+            }
             ItemKind::MacroDef(..) | ItemKind::Mac(..) => panic!("Shouldn't still be around"),
         }
 
@@ -1484,6 +1664,7 @@ impl<'a> LoweringContext<'a> {
     fn lower_trait_item(&mut self, i: &TraitItem) -> hir::TraitItem {
         self.with_parent_def(i.id, |this| {
             let LoweredNodeId { node_id, hir_id } = this.lower_node_id(i.id);
+            let fn_def_id = this.resolver.definitions().local_def_id(i.id);
 
             hir::TraitItem {
                 id: node_id,
@@ -1499,7 +1680,7 @@ impl<'a> LoweringContext<'a> {
                     }
                     TraitItemKind::Method(ref sig, None) => {
                         let names = this.lower_fn_args_to_names(&sig.decl);
-                        hir::TraitItemKind::Method(this.lower_method_sig(sig),
+                        hir::TraitItemKind::Method(this.lower_method_sig(sig, fn_def_id),
                                                    hir::TraitMethod::Required(names))
                     }
                     TraitItemKind::Method(ref sig, Some(ref body)) => {
@@ -1507,11 +1688,12 @@ impl<'a> LoweringContext<'a> {
                             let body = this.lower_block(body, false);
                             this.expr_block(body, ThinVec::new())
                         });
-                        hir::TraitItemKind::Method(this.lower_method_sig(sig),
+                        hir::TraitItemKind::Method(this.lower_method_sig(sig, fn_def_id),
                                                    hir::TraitMethod::Provided(body_id))
                     }
-                    TraitItemKind::Type(ref bounds, ref default) => {
-                        hir::TraitItemKind::Type(this.lower_bounds(bounds),
+                    TraitItemKind::Type(ref generics, ref bounds, ref default) => {
+                        hir::TraitItemKind::Type(this.lower_generics(generics),
+                                                 this.lower_bounds(bounds),
                                                  default.as_ref().map(|x| this.lower_ty(x)))
                     }
                     TraitItemKind::Macro(..) => panic!("Shouldn't exist any more"),
@@ -1526,7 +1708,7 @@ impl<'a> LoweringContext<'a> {
             TraitItemKind::Const(_, ref default) => {
                 (hir::AssociatedItemKind::Const, default.is_some())
             }
-            TraitItemKind::Type(_, ref default) => {
+            TraitItemKind::Type(_, _, ref default) => {
                 (hir::AssociatedItemKind::Type, default.is_some())
             }
             TraitItemKind::Method(ref sig, ref default) => {
@@ -1548,6 +1730,7 @@ impl<'a> LoweringContext<'a> {
     fn lower_impl_item(&mut self, i: &ImplItem) -> hir::ImplItem {
         self.with_parent_def(i.id, |this| {
             let LoweredNodeId { node_id, hir_id } = this.lower_node_id(i.id);
+            let fn_def_id = this.resolver.definitions().local_def_id(i.id);
 
             hir::ImplItem {
                 id: node_id,
@@ -1566,9 +1749,11 @@ impl<'a> LoweringContext<'a> {
                             let body = this.lower_block(body, false);
                             this.expr_block(body, ThinVec::new())
                         });
-                        hir::ImplItemKind::Method(this.lower_method_sig(sig), body_id)
+                        hir::ImplItemKind::Method(this.lower_method_sig(sig, fn_def_id), body_id)
+                    }
+                    ImplItemKind::Type(ref generics, ref ty) => {
+                        hir::ImplItemKind::Type(this.lower_generics(generics), this.lower_ty(ty))
                     }
-                    ImplItemKind::Type(ref ty) => hir::ImplItemKind::Type(this.lower_ty(ty)),
                     ImplItemKind::Macro(..) => panic!("Shouldn't exist any more"),
                 },
                 span: i.span,
@@ -1663,13 +1848,16 @@ impl<'a> LoweringContext<'a> {
                 attrs: this.lower_attrs(&i.attrs),
                 node: match i.node {
                     ForeignItemKind::Fn(ref fdec, ref generics) => {
-                        hir::ForeignItemFn(this.lower_fn_decl(fdec),
+                        hir::ForeignItemFn(this.lower_fn_decl(fdec, None),
                                            this.lower_fn_args_to_names(fdec),
                                            this.lower_generics(generics))
                     }
                     ForeignItemKind::Static(ref t, m) => {
                         hir::ForeignItemStatic(this.lower_ty(t), m)
                     }
+                    ForeignItemKind::Ty => {
+                        hir::ForeignItemType
+                    }
                 },
                 vis: this.lower_visibility(&i.vis, None),
                 span: i.span,
@@ -1677,13 +1865,13 @@ impl<'a> LoweringContext<'a> {
         })
     }
 
-    fn lower_method_sig(&mut self, sig: &MethodSig) -> hir::MethodSig {
+    fn lower_method_sig(&mut self, sig: &MethodSig, fn_def_id: DefId) -> hir::MethodSig {
         hir::MethodSig {
             generics: self.lower_generics(&sig.generics),
             abi: sig.abi,
             unsafety: self.lower_unsafety(sig.unsafety),
             constness: self.lower_constness(sig.constness),
-            decl: self.lower_fn_decl(&sig.decl),
+            decl: self.lower_fn_decl(&sig.decl, Some(fn_def_id)),
         }
     }
 
@@ -2039,7 +2227,7 @@ impl<'a> LoweringContext<'a> {
                                arms.iter().map(|x| self.lower_arm(x)).collect(),
                                hir::MatchSource::Normal)
             }
-            ExprKind::Closure(capture_clause, ref decl, ref body, fn_decl_span) => {
+            ExprKind::Closure(capture_clause, movability, ref decl, ref body, fn_decl_span) => {
                 self.with_new_scopes(|this| {
                     this.with_parent_def(e.id, |this| {
                         let mut is_generator = false;
@@ -2048,16 +2236,28 @@ impl<'a> LoweringContext<'a> {
                             is_generator = this.is_generator;
                             e
                         });
-                        if is_generator && !decl.inputs.is_empty() {
-                            span_err!(this.sess, fn_decl_span, E0628,
-                                      "generators cannot have explicit arguments");
-                            this.sess.abort_if_errors();
-                        }
+                        let generator_option = if is_generator {
+                            if !decl.inputs.is_empty() {
+                                span_err!(this.sess, fn_decl_span, E0628,
+                                          "generators cannot have explicit arguments");
+                                this.sess.abort_if_errors();
+                            }
+                            Some(match movability {
+                                Movability::Movable => hir::GeneratorMovability::Movable,
+                                Movability::Static => hir::GeneratorMovability::Static,
+                            })
+                        } else {
+                            if movability == Movability::Static {
+                                span_err!(this.sess, fn_decl_span, E0631,
+                                          "closures cannot be static");
+                            }
+                            None
+                        };
                         hir::ExprClosure(this.lower_capture_clause(capture_clause),
-                                         this.lower_fn_decl(decl),
+                                         this.lower_fn_decl(decl, None),
                                          body_id,
                                          fn_decl_span,
-                                         is_generator)
+                                         generator_option)
                     })
                 })
             }
@@ -2172,6 +2372,7 @@ impl<'a> LoweringContext<'a> {
                     volatile: asm.volatile,
                     alignstack: asm.alignstack,
                     dialect: asm.dialect,
+                    regs: asm.regs.clone(),
                     ctxt: asm.ctxt,
                 };
                 let outputs =
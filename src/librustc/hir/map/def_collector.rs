@@ -16,6 +16,7 @@ use syntax::ext::hygiene::Mark;
 use syntax::visit;
 use syntax::symbol::keywords;
 use syntax::symbol::Symbol;
+use syntax_pos::Span;
 
 use hir::map::{ITEM_LIKE_SPACE, REGULAR_SPACE};
 
@@ -102,7 +103,8 @@ impl<'a> visit::Visitor<'a> for DefCollector<'a> {
         let def_data = match i.node {
             ItemKind::DefaultImpl(..) | ItemKind::Impl(..) =>
                 DefPathData::Impl,
-            ItemKind::Enum(..) | ItemKind::Struct(..) | ItemKind::Union(..) | ItemKind::Trait(..) |
+            ItemKind::Enum(..) | ItemKind::Struct(..) | ItemKind::Union(..) |
+            ItemKind::Trait(..) | ItemKind::TraitAlias(..) |
             ItemKind::ExternCrate(..) | ItemKind::ForeignMod(..) | ItemKind::Ty(..) =>
                 DefPathData::TypeNs(i.ident.name.as_str()),
             ItemKind::Mod(..) if i.ident == keywords::Invalid.ident() => {
@@ -170,6 +172,24 @@ impl<'a> visit::Visitor<'a> for DefCollector<'a> {
                         this.create_def(field.id, DefPathData::Field(name.as_str()), REGULAR_SPACE);
                     }
                 }
+                ItemKind::Fn(ref decl, _, _, IsAsync::Async { closure_id, return_impl_trait_id },
+                             _, ref generics, ref body) => {
+                    // The body of an `async fn` is lowered into a generator
+                    // returned as an `impl Future`, so it belongs to the
+                    // closure and not to the function itself.
+                    this.create_def(return_impl_trait_id, DefPathData::ImplTrait, REGULAR_SPACE);
+                    this.visit_vis(&i.vis);
+                    this.visit_generics(generics);
+                    visit::walk_fn_decl(this, decl);
+                    let closure_def = this.create_def(closure_id,
+                                                      DefPathData::ClosureExpr,
+                                                      REGULAR_SPACE);
+                    this.with_parent(closure_def, |this| this.visit_block(body));
+                    for attr in &i.attrs {
+                        this.visit_attribute(attr);
+                    }
+                    return;
+                }
                 _ => {}
             }
             visit::walk_item(this, i);
@@ -177,9 +197,12 @@ impl<'a> visit::Visitor<'a> for DefCollector<'a> {
     }
 
     fn visit_foreign_item(&mut self, foreign_item: &'a ForeignItem) {
-        let def = self.create_def(foreign_item.id,
-                                  DefPathData::ValueNs(foreign_item.ident.name.as_str()),
-                                  REGULAR_SPACE);
+        let name = foreign_item.ident.name.as_str();
+        let def_data = match foreign_item.node {
+            ForeignItemKind::Ty => DefPathData::TypeNs(name),
+            ForeignItemKind::Fn(..) | ForeignItemKind::Static(..) => DefPathData::ValueNs(name),
+        };
+        let def = self.create_def(foreign_item.id, def_data, REGULAR_SPACE);
 
         self.with_parent(def, |this| {
             visit::walk_foreign_item(this, foreign_item);
@@ -192,6 +215,11 @@ impl<'a> visit::Visitor<'a> for DefCollector<'a> {
                             DefPathData::TypeParam(ty_param.ident.name.as_str()),
                             REGULAR_SPACE);
         }
+        for const_param in generics.const_params.iter() {
+            self.create_def(const_param.id,
+                            DefPathData::ConstParam(const_param.ident.name.as_str()),
+                            REGULAR_SPACE);
+        }
 
         visit::walk_generics(self, generics);
     }
@@ -271,6 +299,15 @@ impl<'a> visit::Visitor<'a> for DefCollector<'a> {
         visit::walk_ty(self, ty);
     }
 
+    fn visit_path_parameters(&mut self, path_span: Span, parameters: &'a PathParameters) {
+        if let PathParameters::AngleBracketed(ref data) = *parameters {
+            for expr in &data.consts {
+                self.visit_const_expr(expr);
+            }
+        }
+        visit::walk_path_parameters(self, path_span, parameters);
+    }
+
     fn visit_lifetime_def(&mut self, def: &'a LifetimeDef) {
         self.create_def(def.lifetime.id,
                         DefPathData::LifetimeDef(def.lifetime.ident.name.as_str()),
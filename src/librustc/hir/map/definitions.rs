@@ -210,6 +210,7 @@ impl DefKey {
             DefPathData::Module(name) |
             DefPathData::MacroDef(name) |
             DefPathData::TypeParam(name) |
+            DefPathData::ConstParam(name) |
             DefPathData::LifetimeDef(name) |
             DefPathData::EnumVariant(name) |
             DefPathData::Field(name) |
@@ -361,6 +362,8 @@ pub enum DefPathData {
     // Subportions of items
     /// A type parameter (generic parameter)
     TypeParam(InternedString),
+    /// A const parameter (generic parameter)
+    ConstParam(InternedString),
     /// A lifetime definition
     LifetimeDef(InternedString),
     /// A variant of a enum
@@ -608,6 +611,7 @@ impl DefPathData {
             Module(name) |
             MacroDef(name) |
             TypeParam(name) |
+            ConstParam(name) |
             LifetimeDef(name) |
             EnumVariant(name) |
             Field(name) |
@@ -632,6 +636,7 @@ impl DefPathData {
             Module(name) |
             MacroDef(name) |
             TypeParam(name) |
+            ConstParam(name) |
             LifetimeDef(name) |
             EnumVariant(name) |
             Field(name) |
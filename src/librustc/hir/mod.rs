@@ -34,7 +34,7 @@ use util::nodemap::{NodeMap, FxHashSet};
 use syntax_pos::{Span, DUMMY_SP};
 use syntax::codemap::{self, Spanned};
 use syntax::abi::Abi;
-use syntax::ast::{Ident, Name, NodeId, DUMMY_NODE_ID, AsmDialect};
+use syntax::ast::{Ident, Name, NodeId, DUMMY_NODE_ID, AsmDialect, InlineAsmRegs};
 use syntax::ast::{Attribute, Lit, StrStyle, FloatTy, IntTy, UintTy, MetaItem};
 use syntax::ext::hygiene::SyntaxContext;
 use syntax::ptr::P;
@@ -231,6 +231,8 @@ pub struct PathParameters {
     pub lifetimes: HirVec<Lifetime>,
     /// The type parameters for this path segment, if present.
     pub types: HirVec<P<Ty>>,
+    /// The const parameters for this path segment, if present.
+    pub consts: HirVec<ConstArg>,
     /// Whether to infer remaining type parameters, if any.
     /// This only applies to expression and pattern paths, and
     /// out of those only the segments with no type parameters
@@ -250,6 +252,7 @@ impl PathParameters {
         Self {
             lifetimes: HirVec::new(),
             types: HirVec::new(),
+            consts: HirVec::new(),
             infer_types: true,
             bindings: HirVec::new(),
             parenthesized: false,
@@ -268,6 +271,17 @@ impl PathParameters {
     }
 }
 
+/// A const argument in a path, e.g. the `3` in `Buf<3>`.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub enum ConstArg {
+    /// A const parameter that is in scope, e.g. the `N` in `Buf<N>`.
+    /// The parser cannot tell these apart from type arguments, so
+    /// they are moved here during lowering once resolved.
+    Param(NodeId, P<Path>),
+    /// A constant expression, e.g. `Buf<3>` or `Buf<{ 2 * 8 }>`.
+    Expr(BodyId),
+}
+
 /// The AST represents all type param bounds as types.
 /// typeck::collect::compute_bounds matches these against
 /// the "special" built-in traits (see middle::lang_items) and
@@ -288,6 +302,14 @@ pub enum TraitBoundModifier {
 
 pub type TyParamBounds = HirVec<TyParamBound>;
 
+/// The origin of a type parameter that was not written in the generics of
+/// its item.
+#[derive(Copy, Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub enum SyntheticTyParamKind {
+    /// An `impl Trait` in argument position.
+    ImplTrait,
+}
+
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub struct TyParam {
     pub name: Name,
@@ -298,12 +320,22 @@ pub struct TyParam {
     pub pure_wrt_drop: bool,
 }
 
-/// Represents lifetimes and type parameters attached to a declaration
-/// of a function, enum, trait, etc.
+/// A const parameter, e.g. the `const N: usize` in `struct Buf<const N: usize>`.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub struct ConstParam {
+    pub name: Name,
+    pub id: NodeId,
+    pub ty: P<Ty>,
+    pub span: Span,
+}
+
+/// Represents lifetimes, type and const parameters attached to a
+/// declaration of a function, enum, trait, etc.
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub struct Generics {
     pub lifetimes: HirVec<LifetimeDef>,
     pub ty_params: HirVec<TyParam>,
+    pub const_params: HirVec<ConstParam>,
     pub where_clause: WhereClause,
     pub span: Span,
 }
@@ -313,6 +345,7 @@ impl Generics {
         Generics {
             lifetimes: HirVec::new(),
             ty_params: HirVec::new(),
+            const_params: HirVec::new(),
             where_clause: WhereClause {
                 id: DUMMY_NODE_ID,
                 predicates: HirVec::new(),
@@ -329,8 +362,13 @@ impl Generics {
         !self.ty_params.is_empty()
     }
 
+    pub fn is_const_parameterized(&self) -> bool {
+        !self.const_params.is_empty()
+    }
+
     pub fn is_parameterized(&self) -> bool {
-        self.is_lt_parameterized() || self.is_type_parameterized()
+        self.is_lt_parameterized() || self.is_type_parameterized() ||
+            self.is_const_parameterized()
     }
 }
 
@@ -598,7 +636,7 @@ pub enum BindingAnnotation {
   /// mode `None`; if you do `let Some(x) = &Some(22)`, it will
   /// ultimately be inferred to be by-reference.
   ///
-  /// Note that implicit reference skipping is not implemented yet (#42640).
+  /// Implicit reference skipping requires `#![feature(match_default_bindings)]`.
   Unannotated,
 
   /// Annotated with `mut x` -- could be either ref or not, similar to `None`.
@@ -1011,9 +1049,9 @@ pub enum Expr_ {
     ///
     /// The final span is the span of the argument block `|...|`
     ///
-    /// This may also be a generator literal, indicated by the final boolean,
-    /// in that case there is an GeneratorClause.
-    ExprClosure(CaptureClause, P<FnDecl>, BodyId, Span, bool),
+    /// This may also be a generator literal, indicated by the final
+    /// `Some`, which then records whether the generator is movable.
+    ExprClosure(CaptureClause, P<FnDecl>, BodyId, Span, Option<GeneratorMovability>),
     /// A block (`{ ... }`)
     ExprBlock(P<Block>),
 
@@ -1195,6 +1233,14 @@ pub enum CaptureClause {
     CaptureByRef,
 }
 
+/// Whether a generator may be moved after it has been resumed. Static
+/// generators may hold borrows of their own locals across suspension points.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug, Copy)]
+pub enum GeneratorMovability {
+    Static,
+    Movable,
+}
+
 // NB: If you change this, you'll probably want to change the corresponding
 // type structure in middle/ty.rs as well.
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
@@ -1253,9 +1299,9 @@ pub enum TraitItemKind {
     Const(P<Ty>, Option<BodyId>),
     /// A method with an optional body
     Method(MethodSig, TraitMethod),
-    /// An associated type with (possibly empty) bounds and optional concrete
-    /// type
-    Type(TyParamBounds, Option<P<Ty>>),
+    /// An associated type with its own (possibly empty) generic parameters,
+    /// (possibly empty) bounds and optional concrete type
+    Type(Generics, TyParamBounds, Option<P<Ty>>),
 }
 
 // The bodies for items are stored "out of line", in a separate
@@ -1287,8 +1333,8 @@ pub enum ImplItemKind {
     Const(P<Ty>, BodyId),
     /// A method implementation with the given signature and body
     Method(MethodSig, BodyId),
-    /// An associated type
-    Type(P<Ty>),
+    /// An associated type, possibly with its own generic parameters
+    Type(Generics, P<Ty>),
 }
 
 // Bind a type to an associated type: `A=Foo`.
@@ -1361,7 +1407,14 @@ pub enum Ty_ {
     TyTraitObject(HirVec<PolyTraitRef>, Lifetime),
     /// An `impl Bound1 + Bound2 + Bound3` type
     /// where `Bound` is a trait or a lifetime.
-    TyImplTrait(TyParamBounds),
+    ///
+    /// This is the form `impl Trait` takes in return position, where it
+    /// stands for a single type chosen by the function body.
+    TyImplTraitExistential(TyParamBounds),
+    /// An `impl Bound1 + Bound2 + Bound3` type in the argument position of
+    /// the function with the given `DefId`, where it stands for an anonymous
+    /// type parameter of that function.
+    TyImplTraitUniversal(DefId, TyParamBounds),
     /// Unused for now
     TyTypeof(BodyId),
     /// TyInfer means the type should be inferred instead of it having been
@@ -1388,6 +1441,7 @@ pub struct InlineAsm {
     pub volatile: bool,
     pub alignstack: bool,
     pub dialect: AsmDialect,
+    pub regs: Option<InlineAsmRegs>,
     pub ctxt: SyntaxContext,
 }
 
@@ -1722,6 +1776,8 @@ pub enum Item_ {
     ItemUnion(VariantData, Generics),
     /// Represents a Trait Declaration
     ItemTrait(Unsafety, Generics, TyParamBounds, HirVec<TraitItemRef>),
+    /// Represents a Trait Alias Declaration, e.g. `trait Foo = Bar + Quux;`
+    ItemTraitAlias(Generics, TyParamBounds),
 
     // Default trait implementations
     ///
@@ -1753,6 +1809,7 @@ impl Item_ {
             ItemStruct(..) => "struct",
             ItemUnion(..) => "union",
             ItemTrait(..) => "trait",
+            ItemTraitAlias(..) => "trait alias",
             ItemImpl(..) |
             ItemDefaultImpl(..) => "item",
         }
@@ -1824,6 +1881,8 @@ pub enum ForeignItem_ {
     /// A foreign static item (`static ext: u8`), with optional mutability
     /// (the boolean is true when mutable)
     ForeignItemStatic(P<Ty>, bool),
+    /// A foreign type
+    ForeignItemType,
 }
 
 impl ForeignItem_ {
@@ -1831,6 +1890,7 @@ impl ForeignItem_ {
         match *self {
             ForeignItemFn(..) => "foreign function",
             ForeignItemStatic(..) => "foreign static item",
+            ForeignItemType => "foreign type",
         }
     }
 }
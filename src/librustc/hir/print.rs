@@ -393,6 +393,7 @@ impl<'a> State<'a> {
                 let generics = hir::Generics {
                     lifetimes: f.lifetimes.clone(),
                     ty_params: hir::HirVec::new(),
+                    const_params: hir::HirVec::new(),
                     where_clause: hir::WhereClause {
                         id: ast::DUMMY_NODE_ID,
                         predicates: hir::HirVec::new(),
@@ -420,7 +421,8 @@ impl<'a> State<'a> {
                     self.print_lifetime(lifetime)?;
                 }
             }
-            hir::TyImplTrait(ref bounds) => {
+            hir::TyImplTraitExistential(ref bounds) |
+            hir::TyImplTraitUniversal(_, ref bounds) => {
                 self.print_bounds("impl ", &bounds[..])?;
             }
             hir::TyArray(ref ty, v) => {
@@ -477,6 +479,13 @@ impl<'a> State<'a> {
                 self.end()?; // end the head-ibox
                 self.end() // end the outer cbox
             }
+            hir::ForeignItemType => {
+                self.head(&visibility_qualified(&item.vis, "type"))?;
+                self.print_name(item.name)?;
+                self.s.word(";")?;
+                self.end()?; // end the head-ibox
+                self.end() // end the outer cbox
+            }
         }
     }
 
@@ -501,14 +510,17 @@ impl<'a> State<'a> {
 
     fn print_associated_type(&mut self,
                              name: ast::Name,
+                             generics: &hir::Generics,
                              bounds: Option<&hir::TyParamBounds>,
                              ty: Option<&hir::Ty>)
                              -> io::Result<()> {
         self.word_space("type")?;
         self.print_name(name)?;
+        self.print_generics(generics)?;
         if let Some(bounds) = bounds {
             self.print_bounds(":", bounds)?;
         }
+        self.print_where_clause(&generics.where_clause)?;
         if let Some(ty) = ty {
             self.s.space()?;
             self.word_space("=")?;
@@ -735,6 +747,18 @@ impl<'a> State<'a> {
                 }
                 self.bclose(item.span)?;
             }
+            hir::ItemTraitAlias(ref generics, ref bounds) => {
+                self.head("")?;
+                self.print_visibility(&item.vis)?;
+                self.word_nbsp("trait")?;
+                self.print_name(item.name)?;
+                self.print_generics(generics)?;
+                self.print_bounds(" =", bounds)?;
+                self.print_where_clause(&generics.where_clause)?;
+                self.s.word(";")?;
+                self.end()?; // end inner head-block
+                self.end()?; // end outer head-block
+            }
         }
         self.ann.post(self, NodeItem(item))
     }
@@ -915,8 +939,9 @@ impl<'a> State<'a> {
                 self.end()?; // need to close a box
                 self.ann.nested(self, Nested::Body(body))?;
             }
-            hir::TraitItemKind::Type(ref bounds, ref default) => {
+            hir::TraitItemKind::Type(ref generics, ref bounds, ref default) => {
                 self.print_associated_type(ti.name,
+                                           generics,
                                            Some(bounds),
                                            default.as_ref().map(|ty| &**ty))?;
             }
@@ -943,8 +968,8 @@ impl<'a> State<'a> {
                 self.end()?; // need to close a box
                 self.ann.nested(self, Nested::Body(body))?;
             }
-            hir::ImplItemKind::Type(ref ty) => {
-                self.print_associated_type(ii.name, None, Some(ty))?;
+            hir::ImplItemKind::Type(ref generics, ref ty) => {
+                self.print_associated_type(ii.name, generics, None, Some(ty))?;
             }
         }
         self.ann.post(self, NodeSubItem(ii.id))
@@ -1343,7 +1368,10 @@ impl<'a> State<'a> {
                 }
                 self.bclose_(expr.span, indent_unit)?;
             }
-            hir::ExprClosure(capture_clause, ref decl, body, _fn_decl_span, _gen) => {
+            hir::ExprClosure(capture_clause, ref decl, body, _fn_decl_span, gen) => {
+                if gen == Some(hir::GeneratorMovability::Static) {
+                    self.word_space("static")?;
+                }
                 self.print_capture_clause(capture_clause)?;
 
                 self.print_closure_args(&decl, body)?;
@@ -1650,6 +1678,14 @@ impl<'a> State<'a> {
                 self.commasep(Inconsistent, &parameters.types, |s, ty| s.print_type(&ty))?;
             }
 
+            for arg in parameters.consts.iter() {
+                start_or_comma(self)?;
+                match *arg {
+                    hir::ConstArg::Param(_, ref path) => self.print_path(path, false)?,
+                    hir::ConstArg::Expr(body) => self.ann.nested(self, Nested::Body(body))?,
+                }
+            }
+
             // FIXME(eddyb) This would leak into error messages, e.g.:
             // "non-exhaustive patterns: `Some::<..>(_)` not covered".
             if parameters.infer_types && false {
@@ -1990,7 +2026,8 @@ impl<'a> State<'a> {
     }
 
     pub fn print_generics(&mut self, generics: &hir::Generics) -> io::Result<()> {
-        let total = generics.lifetimes.len() + generics.ty_params.len();
+        let total = generics.lifetimes.len() + generics.ty_params.len() +
+                    generics.const_params.len();
         if total == 0 {
             return Ok(());
         }
@@ -2006,10 +2043,14 @@ impl<'a> State<'a> {
             if idx < generics.lifetimes.len() {
                 let lifetime = &generics.lifetimes[idx];
                 s.print_lifetime_def(lifetime)
-            } else {
+            } else if idx < generics.lifetimes.len() + generics.ty_params.len() {
                 let idx = idx - generics.lifetimes.len();
                 let param = &generics.ty_params[idx];
                 s.print_ty_param(param)
+            } else {
+                let idx = idx - generics.lifetimes.len() - generics.ty_params.len();
+                let param = &generics.const_params[idx];
+                s.print_const_param(param)
             }
         })?;
 
@@ -2030,6 +2071,13 @@ impl<'a> State<'a> {
         }
     }
 
+    pub fn print_const_param(&mut self, param: &hir::ConstParam) -> io::Result<()> {
+        self.word_space("const")?;
+        self.print_name(param.name)?;
+        self.word_space(":")?;
+        self.print_type(&param.ty)
+    }
+
     pub fn print_where_clause(&mut self, where_clause: &hir::WhereClause) -> io::Result<()> {
         if where_clause.predicates.is_empty() {
             return Ok(());
@@ -2127,6 +2175,7 @@ impl<'a> State<'a> {
         let generics = hir::Generics {
             lifetimes: hir::HirVec::new(),
             ty_params: hir::HirVec::new(),
+            const_params: hir::HirVec::new(),
             where_clause: hir::WhereClause {
                 id: ast::DUMMY_NODE_ID,
                 predicates: hir::HirVec::new(),
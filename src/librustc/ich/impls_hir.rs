@@ -136,6 +136,7 @@ impl_stable_hash_for!(struct hir::PathSegment {
 impl_stable_hash_for!(struct hir::PathParameters {
     lifetimes,
     types,
+    consts,
     infer_types,
     bindings,
     parenthesized
@@ -151,6 +152,10 @@ impl_stable_hash_for!(enum hir::TraitBoundModifier {
     Maybe
 });
 
+impl_stable_hash_for!(enum hir::SyntheticTyParamKind {
+    ImplTrait
+});
+
 impl_stable_hash_for!(struct hir::TyParam {
     name,
     id,
@@ -160,9 +165,22 @@ impl_stable_hash_for!(struct hir::TyParam {
     pure_wrt_drop
 });
 
+impl_stable_hash_for!(enum hir::ConstArg {
+    Param(id, path),
+    Expr(body)
+});
+
+impl_stable_hash_for!(struct hir::ConstParam {
+    name,
+    id,
+    ty,
+    span
+});
+
 impl_stable_hash_for!(struct hir::Generics {
     lifetimes,
     ty_params,
+    const_params,
     where_clause,
     span
 });
@@ -231,7 +249,8 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for hir::T
             hir::TyNever           |
             hir::TyTup(..)         |
             hir::TyTraitObject(..) |
-            hir::TyImplTrait(..)   |
+            hir::TyImplTraitExistential(..) |
+            hir::TyImplTraitUniversal(..) |
             hir::TyTypeof(..)      |
             hir::TyErr             |
             hir::TyInfer           => {
@@ -284,7 +303,8 @@ impl_stable_hash_for!(enum hir::Ty_ {
     TyTup(ts),
     TyPath(qpath),
     TyTraitObject(trait_refs, lifetime),
-    TyImplTrait(bounds),
+    TyImplTraitExistential(bounds),
+    TyImplTraitUniversal(def_id, bounds),
     TyTypeof(body_id),
     TyErr,
     TyInfer
@@ -689,6 +709,11 @@ impl_stable_hash_for!(enum hir::CaptureClause {
     CaptureByRef
 });
 
+impl_stable_hash_for!(enum hir::GeneratorMovability {
+    Static,
+    Movable
+});
+
 impl_stable_hash_for_spanned!(usize);
 
 impl_stable_hash_for!(struct hir::Destination {
@@ -758,7 +783,7 @@ impl_stable_hash_for!(enum hir::TraitMethod {
 impl_stable_hash_for!(enum hir::TraitItemKind {
     Const(t, body),
     Method(sig, method),
-    Type(bounds, rhs)
+    Type(generics, bounds, rhs)
 });
 
 impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for hir::ImplItem {
@@ -791,7 +816,7 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for hir::I
 impl_stable_hash_for!(enum hir::ImplItemKind {
     Const(t, body),
     Method(sig, body),
-    Type(t)
+    Type(generics, t)
 });
 
 impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for hir::Visibility {
@@ -911,6 +936,7 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for hir::I
             hir::ItemMod(..)         |
             hir::ItemDefaultImpl(..) |
             hir::ItemTrait(..)       |
+            hir::ItemTraitAlias(..)  |
             hir::ItemImpl(..)        |
             hir::ItemTy(..)          |
             hir::ItemEnum(..)        |
@@ -959,6 +985,7 @@ impl_stable_hash_for!(enum hir::Item_ {
     ItemStruct(variant_data, generics),
     ItemUnion(variant_data, generics),
     ItemTrait(unsafety, generics, bounds, item_refs),
+    ItemTraitAlias(generics, bounds),
     ItemDefaultImpl(unsafety, trait_ref),
     ItemImpl(unsafety, impl_polarity, impl_defaultness, generics, trait_ref, ty, impl_item_refs)
 });
@@ -1009,7 +1036,8 @@ impl_stable_hash_for!(struct hir::ForeignItem {
 
 impl_stable_hash_for!(enum hir::ForeignItem_ {
     ForeignItemFn(fn_decl, arg_names, generics),
-    ForeignItemStatic(ty, is_mutbl)
+    ForeignItemStatic(ty, is_mutbl),
+    ForeignItemType
 });
 
 impl_stable_hash_for!(enum hir::Stmt_ {
@@ -1072,6 +1100,7 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for hir::I
             volatile,
             alignstack,
             dialect,
+            ref regs,
             ctxt: _, // This is used for error reporting
         } = *self;
 
@@ -1083,6 +1112,7 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for hir::I
         volatile.hash_stable(hcx, hasher);
         alignstack.hash_stable(hcx, hasher);
         dialect.hash_stable(hcx, hasher);
+        regs.hash_stable(hcx, hasher);
     }
 }
 
@@ -1099,6 +1129,8 @@ impl_stable_hash_for!(enum hir::def::Def {
     Enum(def_id),
     Variant(def_id),
     Trait(def_id),
+    TraitAlias(def_id),
+    TyForeign(def_id),
     TyAlias(def_id),
     AssociatedTy(def_id),
     PrimTy(prim_ty),
@@ -1111,6 +1143,7 @@ impl_stable_hash_for!(enum hir::def::Def {
     VariantCtor(def_id, ctor_kind),
     Method(def_id),
     AssociatedConst(def_id),
+    ConstParam(def_id),
     Local(def_id),
     Upvar(def_id, index, expr_id),
     Label(node_id),
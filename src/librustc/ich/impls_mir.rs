@@ -246,6 +246,9 @@ for mir::StatementKind<'gcx> {
                 op.hash_stable(hcx, hasher);
                 lvalues.hash_stable(hcx, hasher);
             }
+            mir::StatementKind::Coverage { index } => {
+                index.hash_stable(hcx, hasher);
+            }
             mir::StatementKind::Nop => {}
             mir::StatementKind::InlineAsm { ref asm, ref outputs, ref inputs } => {
                 asm.hash_stable(hcx, hasher);
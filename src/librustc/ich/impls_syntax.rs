@@ -52,6 +52,23 @@ impl_stable_hash_for!(enum ::syntax::ast::AsmDialect {
     Intel
 });
 
+impl_stable_hash_for!(enum ::syntax::ast::InlineAsmRegOrRegClass {
+    Reg(reg),
+    RegClass(class)
+});
+
+impl_stable_hash_for!(struct ::syntax::ast::InlineAsmOperandReg {
+    reg,
+    late,
+    span
+});
+
+impl_stable_hash_for!(struct ::syntax::ast::InlineAsmRegs {
+    outputs,
+    inputs,
+    clobbers
+});
+
 impl_stable_hash_for!(enum ::syntax::ext::base::MacroKind {
     Bang,
     Attr,
@@ -36,6 +36,7 @@ for ty::subst::Kind<'gcx> {
                                           hasher: &mut StableHasher<W>) {
         self.as_type().hash_stable(hcx, hasher);
         self.as_region().hash_stable(hcx, hasher);
+        self.as_const().hash_stable(hcx, hasher);
     }
 }
 
@@ -325,6 +326,12 @@ for ::middle::const_val::ConstVal<'gcx> {
                 def_id.hash_stable(hcx, hasher);
                 substs.hash_stable(hcx, hasher);
             }
+            Param(param) => {
+                param.hash_stable(hcx, hasher);
+            }
+            Infer(..) => {
+                bug!("ConstVal::Infer should not appear in incr. comp. fingerprint")
+            }
         }
     }
 }
@@ -340,7 +347,7 @@ impl_stable_hash_for!(struct ty::Const<'tcx> {
 
 impl_stable_hash_for!(struct ty::ClosureSubsts<'tcx> { substs });
 
-impl_stable_hash_for!(struct ty::GeneratorInterior<'tcx> { witness });
+impl_stable_hash_for!(struct ty::GeneratorInterior<'tcx> { witness, movable });
 
 impl_stable_hash_for!(struct ty::GenericPredicates<'tcx> {
     parent,
@@ -366,8 +373,10 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for ty::Ge
             parent,
             parent_regions,
             parent_types,
+            parent_consts,
             ref regions,
             ref types,
+            ref consts,
 
             // Reverse map to each `TypeParameterDef`'s `index` field, from
             // `def_id.index` (`def_id.krate` is the same as the item's).
@@ -379,8 +388,10 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for ty::Ge
         parent.hash_stable(hcx, hasher);
         parent_regions.hash_stable(hcx, hasher);
         parent_types.hash_stable(hcx, hasher);
+        parent_consts.hash_stable(hcx, hasher);
         regions.hash_stable(hcx, hasher);
         types.hash_stable(hcx, hasher);
+        consts.hash_stable(hcx, hasher);
         has_self.hash_stable(hcx, hasher);
         has_late_bound_regions.hash_stable(hcx, hasher);
     }
@@ -411,7 +422,14 @@ impl_stable_hash_for!(struct ty::TypeParameterDef {
     index,
     has_default,
     object_lifetime_default,
-    pure_wrt_drop
+    pure_wrt_drop,
+    synthetic
+});
+
+impl_stable_hash_for!(struct ty::ConstParameterDef {
+    name,
+    def_id,
+    index
 });
 
 
@@ -585,6 +603,9 @@ for ty::TypeVariants<'gcx>
             TyParam(param_ty) => {
                 param_ty.hash_stable(hcx, hasher);
             }
+            TyForeign(def_id) => {
+                def_id.hash_stable(hcx, hasher);
+            }
 
             TyError     |
             TyInfer(..) => {
@@ -599,6 +620,11 @@ impl_stable_hash_for!(struct ty::ParamTy {
     name
 });
 
+impl_stable_hash_for!(struct ty::ParamConst {
+    idx,
+    name
+});
+
 impl_stable_hash_for!(struct ty::TypeAndMut<'tcx> {
     ty,
     mutbl
@@ -653,7 +679,8 @@ impl_stable_hash_for!(enum ty::fast_reject::SimplifiedType {
     GeneratorSimplifiedType(def_id),
     AnonSimplifiedType(def_id),
     FunctionSimplifiedType(params),
-    ParameterSimplifiedType
+    ParameterSimplifiedType,
+    ForeignSimplifiedType(def_id)
 });
 
 impl_stable_hash_for!(struct ty::Instance<'tcx> {
@@ -40,6 +40,7 @@ use super::InferCtxt;
 use super::{MiscVariable, TypeTrace};
 
 use hir::def_id::DefId;
+use middle::const_val::ConstVal;
 use ty::{IntType, UintType};
 use ty::{self, Ty, TyCtxt};
 use ty::error::TypeError;
@@ -124,6 +125,49 @@ impl<'infcx, 'gcx, 'tcx> InferCtxt<'infcx, 'gcx, 'tcx> {
         }
     }
 
+    pub fn super_combine_consts<R>(&self,
+                                   relation: &mut R,
+                                   a: &'tcx ty::Const<'tcx>,
+                                   b: &'tcx ty::Const<'tcx>)
+                                   -> RelateResult<'tcx, &'tcx ty::Const<'tcx>>
+        where R: TypeRelation<'infcx, 'gcx, 'tcx>
+    {
+        let a_is_expected = relation.a_is_expected();
+        let a = self.shallow_resolve_const(a);
+        let b = self.shallow_resolve_const(b);
+
+        match (a.val, b.val) {
+            (ConstVal::Infer(ty::InferConst::Var(a_vid)),
+             ConstVal::Infer(ty::InferConst::Var(b_vid))) => {
+                self.const_unification_table
+                    .borrow_mut()
+                    .unify_var_var(a_vid, b_vid)
+                    .map_err(|e| const_unification_error(a_is_expected, e))?;
+                Ok(a)
+            }
+            (ConstVal::Infer(ty::InferConst::Var(vid)), _) => {
+                self.unify_const_variable(a_is_expected, vid, b)
+            }
+            (_, ConstVal::Infer(ty::InferConst::Var(vid))) => {
+                self.unify_const_variable(!a_is_expected, vid, a)
+            }
+            _ => ty::relate::super_relate_consts(relation, a, b)
+        }
+    }
+
+    fn unify_const_variable(&self,
+                            vid_is_expected: bool,
+                            vid: ty::ConstVid<'tcx>,
+                            value: &'tcx ty::Const<'tcx>)
+                            -> RelateResult<'tcx, &'tcx ty::Const<'tcx>>
+    {
+        self.const_unification_table
+            .borrow_mut()
+            .unify_var_value(vid, value)
+            .map_err(|e| const_unification_error(vid_is_expected, e))?;
+        Ok(value)
+    }
+
     fn unify_integral_variable(&self,
                                vid_is_expected: bool,
                                vid: ty::IntVid,
@@ -488,6 +532,14 @@ impl<'tcx, T:Clone + PartialEq> RelateResultCompare<'tcx, T> for RelateResult<'t
     }
 }
 
+fn const_unification_error<'tcx>(a_is_expected: bool,
+                                 v: (&'tcx ty::Const<'tcx>, &'tcx ty::Const<'tcx>))
+                                 -> TypeError<'tcx>
+{
+    let (a, b) = v;
+    TypeError::ConstMismatch(ty::relate::expected_found_bool(a_is_expected, &a, &b))
+}
+
 fn int_unification_error<'tcx>(a_is_expected: bool, v: (ty::IntVarValue, ty::IntVarValue))
                                -> TypeError<'tcx>
 {
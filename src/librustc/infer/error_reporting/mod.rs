@@ -112,7 +112,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             match item.node {
                 hir::ImplItemKind::Method(..) => "method body",
                 hir::ImplItemKind::Const(..) |
-                hir::ImplItemKind::Type(_) => "associated item"
+                hir::ImplItemKind::Type(..) => "associated item"
             }
         }
 
@@ -41,6 +41,7 @@
 //! variable only once, and it does so as soon as it can, so it is reasonable to ask what the type
 //! inferencer knows "so far".
 
+use middle::const_val::ConstVal;
 use ty::{self, Ty, TyCtxt, TypeFoldable};
 use ty::fold::TypeFolder;
 use ty::subst::Substs;
@@ -56,6 +57,8 @@ pub struct TypeFreshener<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
     infcx: &'a InferCtxt<'a, 'gcx, 'tcx>,
     freshen_count: u32,
     freshen_map: FxHashMap<ty::InferTy, Ty<'tcx>>,
+    const_freshen_count: u32,
+    const_freshen_map: FxHashMap<ty::ConstVid<'tcx>, &'tcx ty::Const<'tcx>>,
     closure_set: Vec<DefId>,
 }
 
@@ -66,6 +69,8 @@ impl<'a, 'gcx, 'tcx> TypeFreshener<'a, 'gcx, 'tcx> {
             infcx,
             freshen_count: 0,
             freshen_map: FxHashMap(),
+            const_freshen_count: 0,
+            const_freshen_map: FxHashMap(),
             closure_set: vec![],
         }
     }
@@ -302,6 +307,7 @@ impl<'a, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for TypeFreshener<'a, 'gcx, 'tcx> {
             ty::TyAdt(..) |
             ty::TyStr |
             ty::TyError |
+            ty::TyForeign(..) |
             ty::TyArray(..) |
             ty::TySlice(..) |
             ty::TyRawPtr(..) |
@@ -318,4 +324,41 @@ impl<'a, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for TypeFreshener<'a, 'gcx, 'tcx> {
             }
         }
     }
+
+    fn fold_const(&mut self, c: &'tcx ty::Const<'tcx>) -> &'tcx ty::Const<'tcx> {
+        match c.val {
+            ConstVal::Infer(ty::InferConst::Var(v)) => {
+                let opt_const = self.infcx.const_unification_table.borrow_mut().probe(v);
+                if let Some(resolved) = opt_const {
+                    return resolved.fold_with(self);
+                }
+
+                match self.const_freshen_map.entry(v) {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        let index = self.const_freshen_count;
+                        self.const_freshen_count += 1;
+                        let fresh = self.infcx.tcx.mk_const(ty::Const {
+                            val: ConstVal::Infer(ty::InferConst::Fresh(index)),
+                            ty: c.ty,
+                        });
+                        entry.insert(fresh);
+                        fresh
+                    }
+                }
+            }
+
+            ConstVal::Infer(ty::InferConst::Fresh(index)) => {
+                if index >= self.const_freshen_count {
+                    bug!("Encountered a freshend const with id {} \
+                          but our counter is only at {}",
+                         index,
+                         self.const_freshen_count);
+                }
+                c
+            }
+
+            _ => c.super_fold_with(self),
+        }
+    }
 }
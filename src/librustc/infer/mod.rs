@@ -19,6 +19,7 @@ pub use self::freshen::TypeFreshener;
 pub use self::region_inference::{GenericKind, VerifyBound};
 
 use hir::def_id::DefId;
+use middle::const_val::ConstVal;
 use middle::free_region::{FreeRegionMap, RegionRelations};
 use middle::region;
 use middle::lang_items;
@@ -70,7 +71,7 @@ pub type InferResult<'tcx, T> = Result<InferOk<'tcx, T>, TypeError<'tcx>>;
 
 pub type Bound<T> = Option<T>;
 pub type UnitResult<'tcx> = RelateResult<'tcx, ()>; // "unify result"
-pub type FixupResult<T> = Result<T, FixupError>; // "fixup result"
+pub type FixupResult<'tcx, T> = Result<T, FixupError<'tcx>>; // "fixup result"
 
 pub struct InferCtxt<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
     pub tcx: TyCtxt<'a, 'gcx, 'tcx>,
@@ -98,6 +99,9 @@ pub struct InferCtxt<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
     // Map from floating variable to the kind of float it represents
     float_unification_table: RefCell<UnificationTable<ty::FloatVid>>,
 
+    // Map from const variable to the value it was unified with
+    const_unification_table: RefCell<UnificationTable<ty::ConstVid<'tcx>>>,
+
     // For region variables.
     region_vars: RegionVarBindings<'a, 'gcx, 'tcx>,
 
@@ -311,13 +315,14 @@ pub enum RegionVariableOrigin {
 }
 
 #[derive(Copy, Clone, Debug)]
-pub enum FixupError {
+pub enum FixupError<'tcx> {
     UnresolvedIntTy(IntVid),
     UnresolvedFloatTy(FloatVid),
-    UnresolvedTy(TyVid)
+    UnresolvedTy(TyVid),
+    UnresolvedConst(ty::ConstVid<'tcx>),
 }
 
-impl fmt::Display for FixupError {
+impl<'tcx> fmt::Display for FixupError<'tcx> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::FixupError::*;
 
@@ -330,7 +335,8 @@ impl fmt::Display for FixupError {
                 write!(f, "cannot determine the type of this number; \
                            add a suffix to specify the type explicitly")
             }
-            UnresolvedTy(_) => write!(f, "unconstrained type")
+            UnresolvedTy(_) => write!(f, "unconstrained type"),
+            UnresolvedConst(_) => write!(f, "unconstrained const value"),
         }
     }
 }
@@ -379,6 +385,7 @@ impl<'a, 'gcx, 'tcx> InferCtxtBuilder<'a, 'gcx, 'tcx> {
             type_variables: RefCell::new(type_variable::TypeVariableTable::new()),
             int_unification_table: RefCell::new(UnificationTable::new()),
             float_unification_table: RefCell::new(UnificationTable::new()),
+            const_unification_table: RefCell::new(UnificationTable::new()),
             region_vars: RegionVarBindings::new(tcx),
             selection_cache: traits::SelectionCache::new(),
             evaluation_cache: traits::EvaluationCache::new(),
@@ -412,6 +419,7 @@ pub struct CombinedSnapshot<'a, 'tcx:'a> {
     type_snapshot: type_variable::Snapshot,
     int_snapshot: unify::Snapshot<ty::IntVid>,
     float_snapshot: unify::Snapshot<ty::FloatVid>,
+    const_snapshot: unify::Snapshot<ty::ConstVid<'tcx>>,
     region_vars_snapshot: RegionSnapshot,
     was_in_snapshot: bool,
     _in_progress_tables: Option<Ref<'a, ty::TypeckTables<'tcx>>>,
@@ -720,6 +728,7 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
             type_snapshot: self.type_variables.borrow_mut().snapshot(),
             int_snapshot: self.int_unification_table.borrow_mut().snapshot(),
             float_snapshot: self.float_unification_table.borrow_mut().snapshot(),
+            const_snapshot: self.const_unification_table.borrow_mut().snapshot(),
             region_vars_snapshot: self.region_vars.start_snapshot(),
             was_in_snapshot: in_snapshot,
             // Borrow tables "in progress" (i.e. during typeck)
@@ -736,6 +745,7 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                                type_snapshot,
                                int_snapshot,
                                float_snapshot,
+                               const_snapshot,
                                region_vars_snapshot,
                                was_in_snapshot,
                                _in_progress_tables } = snapshot;
@@ -754,6 +764,9 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         self.float_unification_table
             .borrow_mut()
             .rollback_to(float_snapshot);
+        self.const_unification_table
+            .borrow_mut()
+            .rollback_to(const_snapshot);
         self.region_vars
             .rollback_to(region_vars_snapshot);
     }
@@ -764,6 +777,7 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                                type_snapshot,
                                int_snapshot,
                                float_snapshot,
+                               const_snapshot,
                                region_vars_snapshot,
                                was_in_snapshot,
                                _in_progress_tables } = snapshot;
@@ -782,6 +796,9 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         self.float_unification_table
             .borrow_mut()
             .commit(float_snapshot);
+        self.const_unification_table
+            .borrow_mut()
+            .commit(const_snapshot);
         self.region_vars
             .commit(region_vars_snapshot);
     }
@@ -979,6 +996,16 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
             .new_key(None)
     }
 
+    pub fn next_const_var_id(&self) -> ty::ConstVid<'tcx> {
+        self.const_unification_table
+            .borrow_mut()
+            .new_key(None)
+    }
+
+    pub fn next_const_var(&self, ty: Ty<'tcx>) -> &'tcx ty::Const<'tcx> {
+        self.tcx.mk_const_var(self.next_const_var_id(), ty)
+    }
+
     pub fn next_region_var(&self, origin: RegionVariableOrigin)
                            -> ty::Region<'tcx> {
         self.tcx.mk_region(ty::ReVar(self.region_vars.new_region_var(origin)))
@@ -1027,8 +1054,21 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         self.tcx.mk_var(ty_var_id)
     }
 
+    /// Create a const inference variable for the given
+    /// const parameter definition. The substitutions are
+    /// for actual parameters that may be referred to by
+    /// the type of this const parameter.
+    pub fn const_var_for_def(&self,
+                             span: Span,
+                             def: &ty::ConstParameterDef,
+                             substs: &[Kind<'tcx>])
+                             -> &'tcx ty::Const<'tcx> {
+        let ty = self.tcx.type_of(def.def_id).subst_spanned(self.tcx, substs, Some(span));
+        self.next_const_var(ty)
+    }
+
     /// Given a set of generics defined on a type or impl, returns a substitution mapping each
-    /// type/region parameter to a fresh inference variable.
+    /// type/region/const parameter to a fresh inference variable.
     pub fn fresh_substs_for_item(&self,
                                  span: Span,
                                  def_id: DefId)
@@ -1037,6 +1077,8 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
             self.region_var_for_def(span, def)
         }, |def, substs| {
             self.type_var_for_def(span, def, substs)
+        }, |def, substs| {
+            self.const_var_for_def(span, def, substs)
         })
     }
 
@@ -1142,6 +1184,19 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         }
     }
 
+    pub fn shallow_resolve_const(&self, c: &'tcx ty::Const<'tcx>) -> &'tcx ty::Const<'tcx> {
+        match c.val {
+            ConstVal::Infer(ty::InferConst::Var(v)) => {
+                self.const_unification_table
+                    .borrow_mut()
+                    .probe(v)
+                    .map(|c| self.shallow_resolve_const(c))
+                    .unwrap_or(c)
+            }
+            _ => c
+        }
+    }
+
     pub fn resolve_type_vars_if_possible<T>(&self, value: &T) -> T
         where T: TypeFoldable<'tcx>
     {
@@ -1180,7 +1235,7 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         value.fold_with(&mut r)
     }
 
-    pub fn fully_resolve<T:TypeFoldable<'tcx>>(&self, value: &T) -> FixupResult<T> {
+    pub fn fully_resolve<T:TypeFoldable<'tcx>>(&self, value: &T) -> FixupResult<'tcx, T> {
         /*!
          * Attempts to resolve all type/region variables in
          * `value`. Region inference must have been run already (e.g.,
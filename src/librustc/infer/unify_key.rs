@@ -12,6 +12,8 @@ use syntax::ast;
 use ty::{self, IntVarValue, Ty, TyCtxt};
 use rustc_data_structures::unify::{Combine, UnifyKey};
 
+use std::marker::PhantomData;
+
 pub trait ToType {
     fn to_type<'a, 'gcx, 'tcx>(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> Ty<'tcx>;
 }
@@ -23,6 +25,15 @@ impl UnifyKey for ty::IntVid {
     fn tag(_: Option<ty::IntVid>) -> &'static str { "IntVid" }
 }
 
+impl<'tcx> UnifyKey for ty::ConstVid<'tcx> {
+    type Value = Option<&'tcx ty::Const<'tcx>>;
+    fn index(&self) -> u32 { self.index }
+    fn from_index(i: u32) -> ty::ConstVid<'tcx> {
+        ty::ConstVid { index: i, phantom: PhantomData }
+    }
+    fn tag(_: Option<ty::ConstVid<'tcx>>) -> &'static str { "ConstVid" }
+}
+
 #[derive(PartialEq, Copy, Clone, Debug)]
 pub struct RegionVidKey {
     /// The minimum region vid in the unification set. This is needed
@@ -38,6 +38,10 @@ pub enum ConstVal<'tcx> {
     Function(DefId, &'tcx Substs<'tcx>),
     Aggregate(ConstAggregate<'tcx>),
     Unevaluated(DefId, &'tcx Substs<'tcx>),
+    /// A const generic parameter, e.g. the `N` in `struct Buf<const N: usize>`.
+    Param(ty::ParamConst),
+    /// A const inference variable.
+    Infer(ty::InferConst<'tcx>),
 }
 
 #[derive(Copy, Clone, Debug, Hash, RustcEncodable, Eq, PartialEq)]
@@ -236,6 +236,7 @@ language_item_table! {
     CloneTraitLangItem,              "clone",                   clone_trait;
     SyncTraitLangItem,               "sync",                    sync_trait;
     FreezeTraitLangItem,             "freeze",                  freeze_trait;
+    UnpinTraitLangItem,              "unpin",                   unpin_trait;
 
     DropTraitLangItem,               "drop",                    drop_trait;
 
@@ -313,6 +314,8 @@ language_item_table! {
     NonZeroItem,                     "non_zero",                non_zero;
 
     DebugTraitLangItem,              "debug_trait",             debug_trait;
+
+    TerminationTraitLangItem,        "termination",             termination;
 }
 
 impl<'a, 'tcx, 'gcx> TyCtxt<'a, 'tcx, 'gcx> {
@@ -329,12 +329,13 @@ impl<'a, 'tcx> Visitor<'tcx> for LifetimeContext<'a, 'tcx> {
             hir::ItemStruct(_, ref generics) |
             hir::ItemUnion(_, ref generics) |
             hir::ItemTrait(_, ref generics, ..) |
+            hir::ItemTraitAlias(ref generics, ..) |
             hir::ItemImpl(_, _, _, ref generics, ..) => {
                 // These kinds of items have only early bound lifetime parameters.
-                let mut index = if let hir::ItemTrait(..) = item.node {
-                    1 // Self comes before lifetimes
-                } else {
-                    0
+                let mut index = match item.node {
+                    hir::ItemTrait(..) |
+                    hir::ItemTraitAlias(..) => 1, // Self comes before lifetimes
+                    _ => 0,
                 };
                 let lifetimes = generics.lifetimes.iter().map(|def| {
                     Region::early(&mut index, def)
@@ -361,6 +362,9 @@ impl<'a, 'tcx> Visitor<'tcx> for LifetimeContext<'a, 'tcx> {
             hir::ForeignItemStatic(..) => {
                 intravisit::walk_foreign_item(self, item);
             }
+            hir::ForeignItemType => {
+                intravisit::walk_foreign_item(self, item);
+            }
         }
     }
 
@@ -396,6 +400,15 @@ impl<'a, 'tcx> Visitor<'tcx> for LifetimeContext<'a, 'tcx> {
                 };
                 self.with(scope, |_, this| this.visit_ty(&mt.ty));
             }
+            hir::TyImplTraitUniversal(..) => {
+                // The bounds of the anonymous type parameter cannot refer
+                // to the fresh late-bound lifetimes of the arguments.
+                let scope = Scope::Elision {
+                    elide: Elide::Error(vec![]),
+                    s: self.scope
+                };
+                self.with(scope, |_, this| intravisit::walk_ty(this, ty));
+            }
             _ => {
                 intravisit::walk_ty(self, ty)
             }
@@ -403,24 +416,42 @@ impl<'a, 'tcx> Visitor<'tcx> for LifetimeContext<'a, 'tcx> {
     }
 
     fn visit_trait_item(&mut self, trait_item: &'tcx hir::TraitItem) {
-        if let hir::TraitItemKind::Method(ref sig, _) = trait_item.node {
-            self.visit_early_late(
-                Some(self.hir_map.get_parent(trait_item.id)),
-                &sig.decl, &sig.generics,
-                |this| intravisit::walk_trait_item(this, trait_item))
-        } else {
-            intravisit::walk_trait_item(self, trait_item);
+        match trait_item.node {
+            hir::TraitItemKind::Method(ref sig, _) => {
+                self.visit_early_late(
+                    Some(self.hir_map.get_parent(trait_item.id)),
+                    &sig.decl, &sig.generics,
+                    |this| intravisit::walk_trait_item(this, trait_item))
+            }
+            hir::TraitItemKind::Type(ref generics, ..) => {
+                self.visit_assoc_ty(
+                    self.hir_map.get_parent(trait_item.id),
+                    generics,
+                    |this| intravisit::walk_trait_item(this, trait_item))
+            }
+            hir::TraitItemKind::Const(..) => {
+                intravisit::walk_trait_item(self, trait_item);
+            }
         }
     }
 
     fn visit_impl_item(&mut self, impl_item: &'tcx hir::ImplItem) {
-        if let hir::ImplItemKind::Method(ref sig, _) = impl_item.node {
-            self.visit_early_late(
-                Some(self.hir_map.get_parent(impl_item.id)),
-                &sig.decl, &sig.generics,
-                |this| intravisit::walk_impl_item(this, impl_item))
-        } else {
-            intravisit::walk_impl_item(self, impl_item);
+        match impl_item.node {
+            hir::ImplItemKind::Method(ref sig, _) => {
+                self.visit_early_late(
+                    Some(self.hir_map.get_parent(impl_item.id)),
+                    &sig.decl, &sig.generics,
+                    |this| intravisit::walk_impl_item(this, impl_item))
+            }
+            hir::ImplItemKind::Type(ref generics, _) => {
+                self.visit_assoc_ty(
+                    self.hir_map.get_parent(impl_item.id),
+                    generics,
+                    |this| intravisit::walk_impl_item(this, impl_item))
+            }
+            hir::ImplItemKind::Const(..) => {
+                intravisit::walk_impl_item(self, impl_item);
+            }
         }
     }
 
@@ -670,7 +701,8 @@ fn compute_object_lifetime_defaults(sess: &Session, hir_map: &Map)
             hir::ItemUnion(_, ref generics) |
             hir::ItemEnum(_, ref generics) |
             hir::ItemTy(_, ref generics) |
-            hir::ItemTrait(_, ref generics, ..) => {
+            hir::ItemTrait(_, ref generics, ..) |
+            hir::ItemTraitAlias(ref generics, ..) => {
                 let result = object_lifetime_defaults_for_item(hir_map, generics);
 
                 // Debugging aid.
@@ -818,20 +850,7 @@ impl<'a, 'tcx> LifetimeContext<'a, 'tcx> {
         insert_late_bound_lifetimes(self.map, decl, generics);
 
         // Find the start of nested early scopes, e.g. in methods.
-        let mut index = 0;
-        if let Some(parent_id) = parent_id {
-            let parent = self.hir_map.expect_item(parent_id);
-            if let hir::ItemTrait(..) = parent.node {
-                index += 1; // Self comes first.
-            }
-            match parent.node {
-                hir::ItemTrait(_, ref generics, ..) |
-                hir::ItemImpl(_, _, _, ref generics, ..) => {
-                    index += (generics.lifetimes.len() + generics.ty_params.len()) as u32;
-                }
-                _ => {}
-            }
-        }
+        let mut index = parent_id.map_or(0, |parent_id| self.parent_generics_count(parent_id));
 
         let lifetimes = generics.lifetimes.iter().map(|def| {
             if self.map.late_bound.contains(&def.lifetime.id) {
@@ -851,6 +870,43 @@ impl<'a, 'tcx> LifetimeContext<'a, 'tcx> {
         });
     }
 
+    /// Visits an associated type in a trait or impl. Its lifetime parameters
+    /// are all early-bound and come after the generics of the parent.
+    fn visit_assoc_ty<F>(&mut self,
+                         parent_id: ast::NodeId,
+                         generics: &'tcx hir::Generics,
+                         walk: F) where
+        F: for<'b, 'c> FnOnce(&'b mut LifetimeContext<'c, 'tcx>),
+    {
+        let mut index = self.parent_generics_count(parent_id);
+        let lifetimes = generics.lifetimes.iter().map(|def| {
+            Region::early(&mut index, def)
+        }).collect();
+
+        let scope = Scope::Binder {
+            lifetimes,
+            s: self.scope
+        };
+        self.with(scope, move |old_scope, this| {
+            this.check_lifetime_defs(old_scope, &generics.lifetimes);
+            this.hack(walk); // FIXME(#37666) workaround in place of `walk(this)`
+        });
+    }
+
+    /// Returns the number of generic parameters (including `Self`) that the
+    /// trait or impl `parent_id` contributes to the generics of its items.
+    fn parent_generics_count(&self, parent_id: ast::NodeId) -> u32 {
+        let parent = self.hir_map.expect_item(parent_id);
+        match parent.node {
+            hir::ItemTrait(_, ref generics, ..) => {
+                // Self comes first.
+                1 + generics_count(generics)
+            }
+            hir::ItemImpl(_, _, _, ref generics, ..) => generics_count(generics),
+            _ => 0
+        }
+    }
+
     fn resolve_lifetime_ref(&mut self, lifetime_ref: &hir::Lifetime) {
         // Walk up the scope chain, tracking the number of fn scopes
         // that we pass through, until we find a lifetime with the
@@ -955,7 +1011,8 @@ impl<'a, 'tcx> LifetimeContext<'a, 'tcx> {
             Def::Union(def_id) |
             Def::Enum(def_id) |
             Def::TyAlias(def_id) |
-            Def::Trait(def_id) if depth == 0 => Some(def_id),
+            Def::Trait(def_id) |
+            Def::TraitAlias(def_id) if depth == 0 => Some(def_id),
             _ => None
         };
 
@@ -1516,6 +1573,10 @@ impl<'a, 'tcx> LifetimeContext<'a, 'tcx> {
 
 ///////////////////////////////////////////////////////////////////////////
 
+fn generics_count(generics: &hir::Generics) -> u32 {
+    (generics.lifetimes.len() + generics.ty_params.len() + generics.const_params.len()) as u32
+}
+
 /// Detects late-bound lifetimes and inserts them into
 /// `map.late_bound`.
 ///
@@ -1567,6 +1628,11 @@ fn insert_late_bound_lifetimes(map: &mut NamedRegionMap,
             appears_in_where_clause.visit_lifetime_def(lifetime_def);
         }
     }
+    // The bounds of an `impl Trait` argument are the bounds of an
+    // anonymous type parameter, so they count as where-clauses too.
+    for arg_ty in &decl.inputs {
+        ImplTraitArgCollector { bounds: &mut appears_in_where_clause }.visit_ty(arg_ty);
+    }
 
     debug!("insert_late_bound_lifetimes: appears_in_where_clause={:?}",
            appears_in_where_clause.regions);
@@ -1618,6 +1684,11 @@ fn insert_late_bound_lifetimes(map: &mut NamedRegionMap,
                     // (defined above)
                 }
 
+                hir::TyImplTraitUniversal(..) => {
+                    // the bounds of an `impl Trait` argument are
+                    // where-clauses, which do not constrain either
+                }
+
                 hir::TyPath(hir::QPath::Resolved(None, ref path)) => {
                     // consider only the lifetimes on the final
                     // segment; I am not sure it's even currently
@@ -1655,10 +1726,28 @@ fn insert_late_bound_lifetimes(map: &mut NamedRegionMap,
         }
 
         fn visit_ty(&mut self, ty: &hir::Ty) {
-            if let hir::TyImplTrait(_) = ty.node {
+            if let hir::TyImplTraitExistential(_) = ty.node {
                 self.impl_trait = true;
             }
             intravisit::walk_ty(self, ty);
         }
     }
+
+    struct ImplTraitArgCollector<'a> {
+        bounds: &'a mut AllCollector,
+    }
+
+    impl<'a, 'v> Visitor<'v> for ImplTraitArgCollector<'a> {
+        fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'v> {
+            NestedVisitorMap::None
+        }
+
+        fn visit_ty(&mut self, ty: &'v hir::Ty) {
+            if let hir::TyImplTraitUniversal(..) = ty.node {
+                self.bounds.visit_ty(ty);
+            } else {
+                intravisit::walk_ty(self, ty);
+            }
+        }
+    }
 }
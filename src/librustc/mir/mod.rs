@@ -922,6 +922,11 @@ pub enum StatementKind<'tcx> {
     /// (The starting point(s) arise implicitly from borrows.)
     EndRegion(region::Scope),
 
+    /// Increment the coverage counter `index` of the enclosing function. The
+    /// source region counted is the span of the statement itself. These are
+    /// only inserted when "-Z instrument-coverage" is passed.
+    Coverage { index: u32 },
+
     /// No-op. Useful for deleting instructions without affecting statement indices.
     Nop,
 }
@@ -993,6 +998,7 @@ impl<'tcx> Debug for Statement<'tcx> {
             InlineAsm { ref asm, ref outputs, ref inputs } => {
                 write!(fmt, "asm!({:?} : {:?} : {:?})", asm, outputs, inputs)
             },
+            Coverage { index } => write!(fmt, "Coverage(counter {})", index),
             Nop => write!(fmt, "nop"),
         }
     }
@@ -1528,7 +1534,9 @@ fn fmt_const_val<W: Write>(fmt: &mut W, const_val: &ConstVal) -> fmt::Result {
         Variant(def_id) |
         Function(def_id, _) => write!(fmt, "{}", item_path_str(def_id)),
         Aggregate(_) => bug!("`ConstVal::{:?}` should not be in MIR", const_val),
-        Unevaluated(..) => write!(fmt, "{:?}", const_val)
+        Param(p) => write!(fmt, "{}", p),
+        Unevaluated(..) |
+        Infer(_) => write!(fmt, "{:?}", const_val)
     }
 }
 
@@ -1714,6 +1722,8 @@ impl<'tcx> TypeFoldable<'tcx> for Statement<'tcx> {
                 Validate(op.clone(),
                          lvals.iter().map(|operand| operand.fold_with(folder)).collect()),
 
+            Coverage { index } => Coverage { index },
+
             Nop => Nop,
         };
         Statement {
@@ -1742,6 +1752,7 @@ impl<'tcx> TypeFoldable<'tcx> for Statement<'tcx> {
             Validate(ref _op, ref lvalues) =>
                 lvalues.iter().any(|ty_and_lvalue| ty_and_lvalue.visit_with(visitor)),
 
+            Coverage { .. } |
             Nop => false,
         }
     }
@@ -381,6 +381,7 @@ macro_rules! make_mir_visitor {
                             self.visit_operand(input, location);
                         }
                     }
+                    StatementKind::Coverage { .. } => {}
                     StatementKind::Nop => {}
                 }
             }
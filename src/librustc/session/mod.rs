@@ -413,8 +413,29 @@ impl Session {
         self.opts.debugging_opts.emit_end_regions ||
             (self.opts.debugging_opts.mir_emit_validate > 0)
     }
-    pub fn lto(&self) -> bool {
-        self.opts.cg.lto
+    /// Calculates the flavor of LTO to use for this compilation.
+    pub fn lto(&self) -> config::Lto {
+        // An explicit `-C lto` always wins.
+        if self.opts.cg.lto != config::Lto::No {
+            return self.opts.cg.lto
+        }
+
+        // Otherwise ThinLTO between the local codegen units only makes sense
+        // if there's more than one codegen unit to begin with, and it isn't
+        // compatible with incremental compilation yet.
+        if self.opts.cg.codegen_units == 1 || self.opts.incremental.is_some() {
+            return config::Lto::No
+        }
+
+        // `-Z thinlto` explicitly turns it on or off. By default it's enabled
+        // for optimized builds. Note that trans skips local ThinLTO if the
+        // LLVM we're built against is older than 5.0 and doesn't support it.
+        match self.opts.debugging_opts.thinlto {
+            Some(true) => config::Lto::ThinLocal,
+            Some(false) => config::Lto::No,
+            None if self.opts.optimize != config::OptLevel::No => config::Lto::ThinLocal,
+            None => config::Lto::No,
+        }
     }
     /// Returns the panic strategy for this compile session. If the user explicitly selected one
     /// using '-C panic', use that, otherwise use the panic strategy defined by the target.
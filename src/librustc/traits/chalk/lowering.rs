@@ -0,0 +1,232 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lowering of impls and where-clauses into program clauses.
+//!
+//! An impl such as
+//!
+//!     impl<T> Clone for Vec<T> where T: Clone { ... }
+//!
+//! is lowered into the clause
+//!
+//!     forall<T> { Implemented(Vec<T>: Clone) :- Implemented(T: Clone) }
+//!
+//! and every where-clause of the parameter environment becomes a fact,
+//! i.e. a clause without conditions. The variables a clause quantifies
+//! over are the generics of its `ClauseSource`: they are left as type
+//! parameters here and replaced with fresh inference variables each
+//! time the solver uses the clause.
+
+use hir;
+use hir::def_id::DefId;
+use hir::itemlikevisit::ItemLikeVisitor;
+use ty::{self, TyCtxt};
+use ty::fold::{TypeFoldable, TypeFolder, TypeVisitor};
+use std::fmt;
+use syntax::ast;
+use syntax_pos::Span;
+
+/// A goal that is proven directly by program clauses, as opposed to
+/// goals built out of other goals.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DomainGoal<'tcx> {
+    /// `T: Trait<..>`
+    Implemented(ty::PolyTraitPredicate<'tcx>),
+
+    /// `<T as Trait<..>>::Item == U`
+    ProjectionEq(ty::PolyProjectionPredicate<'tcx>),
+
+    /// `'a: 'b` or `T: 'a`. Regions are not considered during
+    /// selection, so these always hold as far as the solver is concerned.
+    Outlives(ty::Predicate<'tcx>),
+
+    /// Any other predicate. These are not lowered yet and are proven by
+    /// the regular evaluation code.
+    Builtin(ty::Predicate<'tcx>),
+}
+
+/// The item a program clause was lowered from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClauseSource<'tcx> {
+    /// The clause comes from an impl and quantifies over its generics.
+    Impl(DefId),
+
+    /// The clause is a where-clause of the parameter environment.
+    WhereClause(ty::Predicate<'tcx>),
+}
+
+/// A clause of the form `consequence :- conditions`, read as "the
+/// consequence holds if all of the conditions hold".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramClause<'tcx> {
+    pub source: ClauseSource<'tcx>,
+    pub consequence: DomainGoal<'tcx>,
+    pub conditions: Vec<DomainGoal<'tcx>>,
+}
+
+pub trait Lower<T> {
+    fn lower(&self) -> T;
+}
+
+impl<'tcx> Lower<DomainGoal<'tcx>> for ty::Predicate<'tcx> {
+    fn lower(&self) -> DomainGoal<'tcx> {
+        match *self {
+            ty::Predicate::Trait(ref predicate) => DomainGoal::Implemented(predicate.clone()),
+            ty::Predicate::Projection(ref predicate) => {
+                DomainGoal::ProjectionEq(predicate.clone())
+            }
+            ty::Predicate::RegionOutlives(..) |
+            ty::Predicate::TypeOutlives(..) => DomainGoal::Outlives(self.clone()),
+            ty::Predicate::Equate(..) |
+            ty::Predicate::Subtype(..) |
+            ty::Predicate::WellFormed(..) |
+            ty::Predicate::ObjectSafe(..) |
+            ty::Predicate::ClosureKind(..) |
+            ty::Predicate::ConstEvaluatable(..) => DomainGoal::Builtin(self.clone()),
+        }
+    }
+}
+
+/// Lowers `impl_def_id` into a program clause. Inherent and negative
+/// impls have no clause.
+pub fn program_clauses_for_impl<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
+                                                impl_def_id: DefId)
+                                                -> Option<ProgramClause<'tcx>>
+{
+    if tcx.impl_polarity(impl_def_id) == hir::ImplPolarity::Negative {
+        return None;
+    }
+
+    let trait_ref = match tcx.impl_trait_ref(impl_def_id) {
+        Some(trait_ref) => trait_ref,
+        None => return None,
+    };
+
+    let conditions = tcx.predicates_of(impl_def_id).predicates.iter()
+        .map(|predicate| predicate.lower())
+        .collect();
+
+    Some(ProgramClause {
+        source: ClauseSource::Impl(impl_def_id),
+        consequence: DomainGoal::Implemented(ty::Binder(ty::TraitPredicate { trait_ref })),
+        conditions,
+    })
+}
+
+/// Lowers the where-clauses of `param_env` into facts.
+pub fn program_clauses_for_env<'tcx>(param_env: ty::ParamEnv<'tcx>) -> Vec<ProgramClause<'tcx>> {
+    param_env.caller_bounds.iter().map(|predicate| {
+        ProgramClause {
+            source: ClauseSource::WhereClause(predicate.clone()),
+            consequence: predicate.lower(),
+            conditions: vec![],
+        }
+    }).collect()
+}
+
+impl<'tcx> TypeFoldable<'tcx> for DomainGoal<'tcx> {
+    fn super_fold_with<'gcx: 'tcx, F: TypeFolder<'gcx, 'tcx>>(&self, folder: &mut F) -> Self {
+        match *self {
+            DomainGoal::Implemented(ref p) => DomainGoal::Implemented(p.fold_with(folder)),
+            DomainGoal::ProjectionEq(ref p) => DomainGoal::ProjectionEq(p.fold_with(folder)),
+            DomainGoal::Outlives(ref p) => DomainGoal::Outlives(p.fold_with(folder)),
+            DomainGoal::Builtin(ref p) => DomainGoal::Builtin(p.fold_with(folder)),
+        }
+    }
+
+    fn super_visit_with<V: TypeVisitor<'tcx>>(&self, visitor: &mut V) -> bool {
+        match *self {
+            DomainGoal::Implemented(ref p) => p.visit_with(visitor),
+            DomainGoal::ProjectionEq(ref p) => p.visit_with(visitor),
+            DomainGoal::Outlives(ref p) => p.visit_with(visitor),
+            DomainGoal::Builtin(ref p) => p.visit_with(visitor),
+        }
+    }
+}
+
+impl<'tcx> TypeFoldable<'tcx> for ProgramClause<'tcx> {
+    fn super_fold_with<'gcx: 'tcx, F: TypeFolder<'gcx, 'tcx>>(&self, folder: &mut F) -> Self {
+        ProgramClause {
+            source: self.source,
+            consequence: self.consequence.fold_with(folder),
+            conditions: self.conditions.fold_with(folder),
+        }
+    }
+
+    fn super_visit_with<V: TypeVisitor<'tcx>>(&self, visitor: &mut V) -> bool {
+        self.consequence.visit_with(visitor) || self.conditions.visit_with(visitor)
+    }
+}
+
+impl<'tcx> fmt::Display for DomainGoal<'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DomainGoal::Implemented(ref p) => write!(f, "Implemented({})", p),
+            DomainGoal::ProjectionEq(ref p) => write!(f, "ProjectionEq({})", p),
+            DomainGoal::Outlives(ref p) => write!(f, "Outlives({})", p),
+            DomainGoal::Builtin(ref p) => write!(f, "{}", p),
+        }
+    }
+}
+
+impl<'tcx> fmt::Display for ProgramClause<'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.consequence)?;
+        for (i, condition) in self.conditions.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " :- " } else { ", " }, condition)?;
+        }
+        write!(f, ".")
+    }
+}
+
+/// For unit testing: reports the program clauses of every item that
+/// carries a `#[rustc_dump_program_clauses]` attribute.
+pub fn dump_program_clauses<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+    tcx.hir.krate().visit_all_item_likes(&mut ClauseDumper { tcx });
+}
+
+struct ClauseDumper<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+}
+
+impl<'a, 'tcx> ClauseDumper<'a, 'tcx> {
+    fn process_attrs(&mut self, node_id: ast::NodeId, span: Span) {
+        let def_id = self.tcx.hir.local_def_id(node_id);
+        if !self.tcx.has_attr(def_id, "rustc_dump_program_clauses") {
+            return;
+        }
+
+        let mut clauses = program_clauses_for_env(self.tcx.param_env(def_id));
+        if let hir::map::NodeItem(&hir::Item { node: hir::ItemImpl(..), .. }) =
+            self.tcx.hir.get(node_id) {
+            clauses.extend(program_clauses_for_impl(self.tcx, def_id));
+        }
+
+        let mut err = self.tcx.sess.struct_span_err(span, "program clause dump");
+        for clause in clauses {
+            err.note(&format!("{}", clause));
+        }
+        err.emit();
+    }
+}
+
+impl<'a, 'tcx> ItemLikeVisitor<'tcx> for ClauseDumper<'a, 'tcx> {
+    fn visit_item(&mut self, item: &'tcx hir::Item) {
+        self.process_attrs(item.id, item.span);
+    }
+
+    fn visit_trait_item(&mut self, trait_item: &'tcx hir::TraitItem) {
+        self.process_attrs(trait_item.id, trait_item.span);
+    }
+
+    fn visit_impl_item(&mut self, impl_item: &'tcx hir::ImplItem) {
+        self.process_attrs(impl_item.id, impl_item.span);
+    }
+}
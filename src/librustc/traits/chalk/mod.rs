@@ -0,0 +1,35 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An experimental trait solver in the style of [chalk], enabled with
+//! `-Z chalk`.
+//!
+//! Rather than assembling candidates with ad-hoc rules, this solver
+//! first *lowers* impls and where-clauses into program clauses (see
+//! `lowering`) and then answers goals against those clauses with a
+//! tabled engine (see `solve`). The result is still expressed as a
+//! `SelectionCandidate`, so confirmation, and hence the `Vtable`
+//! produced by `SelectionContext::select`, is shared with the regular
+//! solver.
+//!
+//! Goals that the lowering does not cover yet -- builtin traits such as
+//! `Sized` and `Copy`, auto traits, closures, trait objects and
+//! projections in the `Self` position -- are still handled by the
+//! code in `select.rs`.
+//!
+//! [chalk]: https://github.com/nikomatsakis/chalk
+
+pub use self::lowering::{ClauseSource, DomainGoal, Lower, ProgramClause};
+pub use self::lowering::{dump_program_clauses, program_clauses_for_env};
+pub use self::lowering::program_clauses_for_impl;
+pub use self::solve::{Answer, SlgSolver, is_builtin_goal};
+
+mod lowering;
+mod solve;
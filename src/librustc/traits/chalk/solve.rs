@@ -0,0 +1,410 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A tabled solver for the program clauses produced by `lowering`.
+//!
+//! The engine follows the structure of SLG resolution. Every trait goal
+//! it encounters gets a *table*, keyed by the goal with its inference
+//! variables freshened. Goals are solved depth-first against the
+//! program clauses, but when a goal is reached again while its table is
+//! still being filled in, the recursive occurrence is answered from the
+//! table instead of being solved again. This is what lets recursive
+//! impls such as
+//!
+//!     impl<T: Foo> Foo for Box<T> where Box<T>: Bar { }
+//!     impl<T: Foo> Bar for Box<T> { }
+//!
+//! terminate where the regular solver overflows. The goal that leads
+//! such a cycle is then re-solved until its answer stops changing, at
+//! which point its table is marked complete. Goals that only took part
+//! in a cycle led by another goal keep an incomplete table and are
+//! solved again the next time they are needed.
+//!
+//! Unlike a full SLG engine, a table records a single `Answer` rather
+//! than a stream of substitutions, and only tables for goals without
+//! inference variables outlive the goal being solved. This is enough
+//! for selection, which only needs to know which clause applies:
+//! unifying with that clause and proving its conditions is still done
+//! by confirmation and the fulfillment context.
+
+use infer::InferOk;
+use traits::{PredicateObligation, SelectionContext, TraitObligation};
+use traits::project;
+use ty::{self, TyCtxt, TypeFoldable};
+use ty::subst::Subst;
+use util::nodemap::FxHashMap;
+
+use super::lowering::{ClauseSource, DomainGoal, Lower, ProgramClause};
+use super::lowering::{program_clauses_for_env, program_clauses_for_impl};
+
+use std::cmp;
+use std::mem;
+
+/// The answer to a goal. Answers are ordered so that the answer to a
+/// conjunction of goals is the minimum of their answers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Answer {
+    /// The goal cannot hold.
+    NoSolution,
+
+    /// The goal may hold, but not enough is known about its inference
+    /// variables to tell.
+    Ambiguous,
+
+    /// The goal holds.
+    Proven,
+}
+
+impl Answer {
+    pub fn may_apply(self) -> bool {
+        self != Answer::NoSolution
+    }
+}
+
+struct Table {
+    answer: Answer,
+    complete: bool,
+}
+
+struct StackEntry<'tcx> {
+    key: ty::PolyTraitPredicate<'tcx>,
+
+    /// The lowest stack depth whose provisional answer was used while
+    /// solving this goal. If this is the depth of the entry itself, the
+    /// entry is the leader of any cycle it is part of.
+    min_depth: usize,
+
+    /// Whether a recursive occurrence of this goal was answered from
+    /// its (provisional) table.
+    used_provisional: bool,
+}
+
+pub struct SlgSolver<'tcx> {
+    tables: FxHashMap<ty::PolyTraitPredicate<'tcx>, Table>,
+    stack: Vec<StackEntry<'tcx>>,
+}
+
+/// Returns true if `goal` is not covered by the lowering yet, and so
+/// has to be answered by the regular selection code.
+pub fn is_builtin_goal<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
+                                       goal: &ty::PolyTraitPredicate<'tcx>)
+                                       -> bool
+{
+    let def_id = goal.def_id();
+    let lang_items = tcx.lang_items();
+    if lang_items.sized_trait() == Some(def_id) ||
+        lang_items.copy_trait() == Some(def_id) ||
+        lang_items.clone_trait() == Some(def_id) ||
+        lang_items.unsize_trait() == Some(def_id) ||
+        lang_items.gen_trait() == Some(def_id) ||
+        lang_items.fn_trait_kind(def_id).is_some() ||
+        tcx.trait_has_default_impl(def_id) ||
+        tcx.is_trait_alias(def_id)
+    {
+        return true;
+    }
+
+    match goal.skip_binder().self_ty().sty {
+        ty::TyDynamic(..) |
+        ty::TyProjection(..) |
+        ty::TyAnon(..) |
+        ty::TyInfer(ty::TyVar(_)) |
+        ty::TyError => true,
+        _ => false,
+    }
+}
+
+impl<'tcx> SlgSolver<'tcx> {
+    pub fn new() -> SlgSolver<'tcx> {
+        SlgSolver {
+            tables: FxHashMap(),
+            stack: vec![],
+        }
+    }
+
+    /// Returns the clauses that may be used to prove `obligation`,
+    /// together with the answer for their conditions.
+    pub fn candidates<'cx, 'gcx>(&mut self,
+                                 selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
+                                 obligation: &TraitObligation<'tcx>)
+                                 -> Vec<(ClauseSource<'tcx>, Answer)>
+    {
+        let goal = selcx.infcx().resolve_type_vars_if_possible(&obligation.predicate);
+        let mut candidates = vec![];
+        self.with_table(selcx, obligation, goal, |this, selcx| {
+            candidates = this.solve_clauses(selcx, obligation, goal);
+            combine(&candidates, &goal)
+        });
+        debug!("SlgSolver::candidates({:?}) = {:?}", goal, candidates);
+        candidates
+    }
+
+    fn solve_trait_goal<'cx, 'gcx>(&mut self,
+                                   selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
+                                   root: &TraitObligation<'tcx>,
+                                   goal: ty::PolyTraitPredicate<'tcx>)
+                                   -> Answer
+    {
+        let goal = selcx.infcx().resolve_type_vars_if_possible(&goal);
+        if goal.references_error() || is_builtin_goal(selcx.tcx(), &goal) {
+            return evaluate_builtin(selcx, root, ty::Predicate::Trait(goal));
+        }
+
+        self.with_table(selcx, root, goal, |this, selcx| {
+            let candidates = this.solve_clauses(selcx, root, goal);
+            combine(&candidates, &goal)
+        })
+    }
+
+    /// Solves `goal` with `solve`, going through its table.
+    fn with_table<'cx, 'gcx, F>(&mut self,
+                                selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
+                                root: &TraitObligation<'tcx>,
+                                goal: ty::PolyTraitPredicate<'tcx>,
+                                mut solve: F)
+                                -> Answer
+        where F: FnMut(&mut Self, &mut SelectionContext<'cx, 'gcx, 'tcx>) -> Answer
+    {
+        let key = selcx.infcx().freshen(goal);
+
+        if let Some(table) = self.tables.get(&key) {
+            if table.complete {
+                return table.answer;
+            }
+        }
+
+        if let Some(depth) = self.stack.iter().position(|entry| entry.key == key) {
+            // A recursive occurrence: answer it with what the table holds
+            // so far, and record that everything above `depth` on the
+            // stack now depends on that provisional answer.
+            debug!("with_table: cycle on {:?} at depth {}", key, depth);
+            self.stack[depth].used_provisional = true;
+            for entry in &mut self.stack[depth + 1..] {
+                entry.min_depth = cmp::min(entry.min_depth, depth);
+            }
+            return self.tables[&key].answer;
+        }
+
+        let depth = self.stack.len();
+        if depth >= selcx.tcx().sess.recursion_limit.get() {
+            selcx.infcx().report_overflow_error(root, true);
+        }
+
+        self.stack.push(StackEntry {
+            key,
+            min_depth: depth,
+            used_provisional: false,
+        });
+        self.tables.entry(key).or_insert(Table {
+            answer: Answer::NoSolution,
+            complete: false,
+        });
+
+        let answer = loop {
+            let answer = solve(self, selcx);
+            let previous = mem::replace(&mut self.tables.get_mut(&key).unwrap().answer, answer);
+            let entry = self.stack.last_mut().unwrap();
+            if !entry.used_provisional || answer == previous {
+                break answer;
+            }
+
+            // The answer improved after a recursive occurrence was
+            // answered with the old one; solve again until it is stable.
+            entry.used_provisional = false;
+        };
+
+        let entry = self.stack.pop().unwrap();
+        if entry.min_depth < depth {
+            // Part of a cycle led further down the stack: the table stays
+            // incomplete, and our parent depends on the same answer.
+            let parent = self.stack.last_mut().unwrap();
+            parent.min_depth = cmp::min(parent.min_depth, entry.min_depth);
+            if goal.needs_infer() {
+                self.tables.remove(&key);
+            }
+        } else if goal.needs_infer() {
+            self.tables.remove(&key);
+        } else {
+            self.tables.get_mut(&key).unwrap().complete = true;
+        }
+
+        answer
+    }
+
+    /// Solves `goal` against every program clause whose consequence may
+    /// match it, returning the clauses that may apply.
+    fn solve_clauses<'cx, 'gcx>(&mut self,
+                                selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
+                                root: &TraitObligation<'tcx>,
+                                goal: ty::PolyTraitPredicate<'tcx>)
+                                -> Vec<(ClauseSource<'tcx>, Answer)>
+    {
+        let tcx = selcx.tcx();
+        let trait_def_id = goal.def_id();
+
+        let mut clauses: Vec<_> = program_clauses_for_env(root.param_env).into_iter()
+            .filter(|clause| match clause.consequence {
+                DomainGoal::Implemented(ref p) => p.def_id() == trait_def_id,
+                _ => false,
+            })
+            .collect();
+        tcx.for_each_relevant_impl(trait_def_id, goal.skip_binder().self_ty(), |impl_def_id| {
+            clauses.extend(program_clauses_for_impl(tcx, impl_def_id));
+        });
+
+        clauses.into_iter().filter_map(|clause| {
+            let answer = self.solve_clause(selcx, root, goal, &clause);
+            if answer.may_apply() {
+                Some((clause.source, answer))
+            } else {
+                None
+            }
+        }).collect()
+    }
+
+    /// Tries to prove `goal` with `clause`. Any unification this does is
+    /// rolled back afterwards.
+    fn solve_clause<'cx, 'gcx>(&mut self,
+                               selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
+                               root: &TraitObligation<'tcx>,
+                               goal: ty::PolyTraitPredicate<'tcx>,
+                               clause: &ProgramClause<'tcx>)
+                               -> Answer
+    {
+        let infcx = selcx.infcx();
+        let tcx = selcx.tcx();
+        infcx.probe(|snapshot| {
+            let clause = match clause.source {
+                ClauseSource::Impl(impl_def_id) => {
+                    let substs = infcx.fresh_substs_for_item(root.cause.span, impl_def_id);
+                    clause.subst(tcx, substs)
+                }
+                ClauseSource::WhereClause(..) => clause.clone(),
+            };
+            let clause = project::normalize_with_depth(selcx,
+                                                       root.param_env,
+                                                       root.cause.clone(),
+                                                       root.recursion_depth + 1,
+                                                       &clause);
+            let consequence = match clause.value.consequence {
+                DomainGoal::Implemented(ref p) => p.clone(),
+                _ => return Answer::NoSolution,
+            };
+
+            let at = infcx.at(&root.cause, root.param_env);
+            let unified = match clause.value.source {
+                ClauseSource::Impl(..) => {
+                    let (skol_goal, skol_map) =
+                        infcx.skolemize_late_bound_regions(&goal, snapshot);
+                    at.eq(skol_goal.trait_ref, consequence.skip_binder().trait_ref)
+                      .ok()
+                      .and_then(|ok| {
+                          infcx.leak_check(false, root.cause.span, &skol_map, snapshot)
+                               .ok()
+                               .map(|_| ok)
+                      })
+                }
+                ClauseSource::WhereClause(..) => {
+                    at.sup(goal.to_poly_trait_ref(), consequence.to_poly_trait_ref()).ok()
+                }
+            };
+            let InferOk { obligations, .. } = match unified {
+                Some(ok) => ok,
+                None => return Answer::NoSolution,
+            };
+
+            let conditions = clause.value.conditions.into_iter()
+                .chain(clause.obligations.iter().map(|o| o.predicate.lower()))
+                .chain(obligations.iter().map(|o| o.predicate.lower()));
+            let mut answer = Answer::Proven;
+            for condition in conditions {
+                answer = cmp::min(answer, self.solve_domain_goal(selcx, root, condition));
+                if answer == Answer::NoSolution {
+                    break;
+                }
+            }
+            answer
+        })
+    }
+
+    fn solve_domain_goal<'cx, 'gcx>(&mut self,
+                                    selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
+                                    root: &TraitObligation<'tcx>,
+                                    goal: DomainGoal<'tcx>)
+                                    -> Answer
+    {
+        match goal {
+            DomainGoal::Implemented(predicate) => self.solve_trait_goal(selcx, root, predicate),
+            DomainGoal::ProjectionEq(predicate) => {
+                match project::poly_project_and_unify_type(selcx, &root.with(predicate)) {
+                    Ok(Some(obligations)) => self.solve_obligations(selcx, root, &obligations),
+                    Ok(None) => Answer::Ambiguous,
+                    Err(_) => Answer::NoSolution,
+                }
+            }
+            // We do not consider region relationships when selecting.
+            DomainGoal::Outlives(_) => Answer::Proven,
+            DomainGoal::Builtin(predicate) => evaluate_builtin(selcx, root, predicate),
+        }
+    }
+
+    fn solve_obligations<'cx, 'gcx>(&mut self,
+                                    selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
+                                    root: &TraitObligation<'tcx>,
+                                    obligations: &[PredicateObligation<'tcx>])
+                                    -> Answer
+    {
+        let mut answer = Answer::Proven;
+        for obligation in obligations {
+            answer = cmp::min(answer,
+                              self.solve_domain_goal(selcx, root, obligation.predicate.lower()));
+            if answer == Answer::NoSolution {
+                break;
+            }
+        }
+        answer
+    }
+}
+
+/// Combines the answers of the clauses that may prove `goal`.
+fn combine<'tcx>(candidates: &[(ClauseSource<'tcx>, Answer)],
+                 goal: &ty::PolyTraitPredicate<'tcx>)
+                 -> Answer
+{
+    if candidates.is_empty() {
+        return Answer::NoSolution;
+    }
+
+    // If the goal still has inference variables, several applicable
+    // clauses may each constrain them differently.
+    let proven = candidates.iter().any(|&(_, answer)| answer == Answer::Proven);
+    if proven && (candidates.len() == 1 || !goal.needs_infer()) {
+        Answer::Proven
+    } else {
+        Answer::Ambiguous
+    }
+}
+
+/// Answers a goal the lowering does not cover with the regular
+/// evaluation code.
+fn evaluate_builtin<'cx, 'gcx, 'tcx>(selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
+                                     root: &TraitObligation<'tcx>,
+                                     predicate: ty::Predicate<'tcx>)
+                                     -> Answer
+{
+    let obligation = root.with(predicate);
+    if selcx.evaluate_obligation_conservatively(&obligation) {
+        Answer::Proven
+    } else if selcx.evaluate_obligation(&obligation) {
+        Answer::Ambiguous
+    } else {
+        Answer::NoSolution
+    }
+}
@@ -42,12 +42,15 @@ pub use self::select::IntercrateAmbiguityCause;
 pub use self::specialize::{OverlapError, specialization_graph, translate_substs};
 pub use self::specialize::{SpecializesCache, find_associated_item};
 pub use self::util::elaborate_predicates;
+pub use self::util::expand_trait_aliases;
+pub use self::util::TraitAliasExpansion;
 pub use self::util::supertraits;
 pub use self::util::Supertraits;
 pub use self::util::supertrait_def_ids;
 pub use self::util::SupertraitDefIds;
 pub use self::util::transitive_bounds;
 
+pub mod chalk;
 mod coherence;
 mod error_reporting;
 mod fulfill;
@@ -677,7 +680,8 @@ pub fn get_vtable_methods<'a, 'tcx>(
             // regions for those
             let substs = Substs::for_item(tcx, def_id,
                                           |_, _| tcx.types.re_erased,
-                                          |def, _| trait_ref.substs().type_for_def(def));
+                                          |def, _| trait_ref.substs().type_for_def(def),
+                                          |def, _| trait_ref.substs().const_for_def(def));
 
             // the trait type may have higher-ranked lifetimes in it;
             // so erase them if they appear, so that we get the type
@@ -40,6 +40,9 @@ pub enum ObjectSafetyViolation {
 
     /// Associated const
     AssociatedConst(ast::Name),
+
+    /// Associated type with generic parameters of its own
+    GenericAssociatedType(ast::Name),
 }
 
 impl ObjectSafetyViolation {
@@ -57,8 +60,13 @@ impl ObjectSafetyViolation {
                          in its arguments or return type", name).into(),
             ObjectSafetyViolation::Method(name, MethodViolationCode::Generic) =>
                 format!("method `{}` has generic type parameters", name).into(),
+            ObjectSafetyViolation::Method(name, MethodViolationCode::ImplTraitReturn) =>
+                format!("method `{}` returns `impl Trait`", name).into(),
             ObjectSafetyViolation::AssociatedConst(name) =>
                 format!("the trait cannot contain associated consts like `{}`", name).into(),
+            ObjectSafetyViolation::GenericAssociatedType(name) =>
+                format!("the trait cannot contain generic associated types like `{}`",
+                        name).into(),
         }
     }
 }
@@ -74,6 +82,9 @@ pub enum MethodViolationCode {
 
     /// e.g., `fn foo<A>()`
     Generic,
+
+    /// e.g., `fn foo(&self) -> impl Iterator<Item = u8>`
+    ImplTraitReturn,
 }
 
 impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
@@ -131,6 +142,13 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             .filter(|item| item.kind == ty::AssociatedKind::Const)
             .map(|item| ObjectSafetyViolation::AssociatedConst(item.name)));
 
+        // An object type has no way to specify the type a generic
+        // associated type projects to.
+        violations.extend(self.associated_items(trait_def_id)
+            .filter(|item| item.kind == ty::AssociatedKind::Type)
+            .filter(|item| self.generics_of(item.def_id).own_count() != 0)
+            .map(|item| ObjectSafetyViolation::GenericAssociatedType(item.name)));
+
         debug!("object_safety_violations_for_trait(trait_def_id={:?}) = {:?}",
                trait_def_id,
                violations);
@@ -272,8 +290,14 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             return Some(MethodViolationCode::ReferencesSelf);
         }
 
-        // We can't monomorphize things like `fn foo<A>(...)`.
-        if !self.generics_of(method.def_id).types.is_empty() {
+        // The returned type is chosen by each impl, so it is erased too.
+        if self.impl_trait_assoc_of_method(method.def_id).is_some() {
+            return Some(MethodViolationCode::ImplTraitReturn);
+        }
+
+        // We can't monomorphize things like `fn foo<A>(...)` or `fn foo<const N: usize>(...)`.
+        let generics = self.generics_of(method.def_id);
+        if !generics.types.is_empty() || !generics.consts.is_empty() {
             return Some(MethodViolationCode::Generic);
         }
 
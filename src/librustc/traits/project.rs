@@ -915,10 +915,12 @@ fn assemble_candidates_from_trait_def<'cx, 'gcx, 'tcx>(
     debug!("assemble_candidates_from_trait_def(..)");
 
     let tcx = selcx.tcx();
-    // Check whether the self-type is itself a projection.
+    // Check whether the self-type is itself a projection. The predicates of
+    // the associated type include those of its trait and, for a generic
+    // associated type, the bounds declared on the associated type itself.
     let (def_id, substs) = match obligation_trait_ref.self_ty().sty {
         ty::TyProjection(ref data) => {
-            (data.trait_ref(tcx).def_id, data.substs)
+            (data.item_def_id, data.substs)
         }
         ty::TyAnon(def_id, substs) => (def_id, substs),
         ty::TyInfer(ty::TyVar(_)) => {
@@ -1453,10 +1455,26 @@ fn confirm_impl_candidate<'cx, 'gcx, 'tcx>(
                assoc_ty.item.name,
                obligation.predicate);
         tcx.types.err
+    } else if tcx.is_impl_trait_assoc(assoc_ty.item.def_id) {
+        // The `impl Trait` return type of a method stays anonymized; its
+        // underlying type is only revealed once type-checking is done.
+        tcx.mk_anon(assoc_ty.item.def_id, Substs::identity_for_item(tcx, assoc_ty.item.def_id))
     } else {
         tcx.type_of(assoc_ty.item.def_id)
     };
     let substs = translate_substs(selcx.infcx(), param_env, impl_def_id, substs, assoc_ty.node);
+
+    // Add the arguments for the parameters of a generic associated type,
+    // which come after those of the trait in the projection.
+    let trait_def_id = tcx.associated_item(obligation.predicate.item_def_id).container.id();
+    let substs = obligation.predicate.substs.rebase_onto(tcx, trait_def_id, substs);
+    if substs.len() != tcx.generics_of(assoc_ty.item.def_id).count() {
+        // The associated type in the impl does not have the same
+        // parameters as the one in the trait. This error is reported
+        // by `compare_ty_impl`.
+        return Progress::error(tcx);
+    }
+
     Progress {
         ty: ty.subst(tcx, substs),
         obligations: nested,
@@ -13,6 +13,7 @@
 use self::SelectionCandidate::*;
 use self::EvaluationResult::*;
 
+use super::chalk;
 use super::coherence;
 use super::DerivedObligationCause;
 use super::project;
@@ -248,6 +249,9 @@ enum SelectionCandidate<'tcx> {
     BuiltinObjectCandidate,
 
     BuiltinUnsizeCandidate,
+
+    /// A trait alias, which holds for any type satisfying its bounds.
+    TraitAliasCandidate(DefId),
 }
 
 impl<'a, 'tcx> ty::Lift<'tcx> for SelectionCandidate<'a> {
@@ -268,6 +272,7 @@ impl<'a, 'tcx> ty::Lift<'tcx> for SelectionCandidate<'a> {
             BuiltinUnsizeCandidate => BuiltinUnsizeCandidate,
             ClosureCandidate => ClosureCandidate,
             GeneratorCandidate => GeneratorCandidate,
+            TraitAliasCandidate(def_id) => TraitAliasCandidate(def_id),
 
             ParamCandidate(ref trait_ref) => {
                 return tcx.lift(trait_ref).map(ParamCandidate);
@@ -514,7 +519,12 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
         let tcx = self.tcx();
 
         let stack = self.push_stack(TraitObligationStackList::empty(), obligation);
-        let ret = match self.candidate_from_obligation(&stack)? {
+        let candidate = if self.use_chalk(obligation) {
+            self.chalk_candidate(&stack)?
+        } else {
+            self.candidate_from_obligation(&stack)?
+        };
+        let ret = match candidate {
             None => None,
             Some(candidate) => {
                 let mut candidate = self.confirm_candidate(obligation, candidate)?;
@@ -1144,7 +1154,7 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
 
         // Winnow, but record the exact outcome of evaluation, which
         // is needed for specialization.
-        let mut candidates: Vec<_> = candidates.into_iter().filter_map(|c| {
+        let candidates: Vec<_> = candidates.into_iter().filter_map(|c| {
             let eval = self.evaluate_candidate(stack, &c);
             if eval.may_apply() {
                 Some(EvaluatedCandidate {
@@ -1156,6 +1166,15 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
             }
         }).collect();
 
+        self.winnow_evaluated_candidates(candidates)
+    }
+
+    /// Picks the candidate to use among candidates that may each apply,
+    /// dropping duplicates and candidates that are specialized or
+    /// shadowed by a where-clause.
+    fn winnow_evaluated_candidates(&mut self, mut candidates: Vec<EvaluatedCandidate<'tcx>>)
+                                   -> SelectionResult<'tcx, SelectionCandidate<'tcx>>
+    {
         // If there are STILL multiple candidate, we can further
         // reduce the list by dropping duplicates -- including
         // resolving specializations.
@@ -1203,6 +1222,46 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
         self.filter_negative_impls(candidates.pop().unwrap().candidate)
     }
 
+    /// Whether `obligation` is selected with the experimental solver
+    /// enabled by `-Z chalk`. Coherence and the goals the solver does not
+    /// lower yet always use the regular solver.
+    fn use_chalk(&self, obligation: &TraitObligation<'tcx>) -> bool {
+        if !self.tcx().sess.opts.debugging_opts.chalk || self.intercrate {
+            return false;
+        }
+
+        let predicate = self.infcx.resolve_type_vars_if_possible(&obligation.predicate);
+        !predicate.references_error() && !chalk::is_builtin_goal(self.tcx(), &predicate)
+    }
+
+    /// Picks a candidate for `stack.obligation` by solving it against
+    /// the lowered program clauses. Only the choice of impl or
+    /// where-clause comes from the solver; confirmation is shared with
+    /// the regular solver.
+    fn chalk_candidate<'o>(&mut self,
+                           stack: &TraitObligationStack<'o, 'tcx>)
+                           -> SelectionResult<'tcx, SelectionCandidate<'tcx>>
+    {
+        let mut solver = chalk::SlgSolver::new();
+        let candidates = solver.candidates(self, stack.obligation).into_iter()
+            .map(|(source, answer)| {
+                let candidate = match source {
+                    chalk::ClauseSource::Impl(impl_def_id) => ImplCandidate(impl_def_id),
+                    chalk::ClauseSource::WhereClause(predicate) => {
+                        ParamCandidate(predicate.to_opt_poly_trait_ref().unwrap())
+                    }
+                };
+                let evaluation = match answer {
+                    chalk::Answer::Proven => EvaluatedToOk,
+                    _ => EvaluatedToAmbig,
+                };
+                EvaluatedCandidate { candidate, evaluation }
+            })
+            .collect();
+
+        self.winnow_evaluated_candidates(candidates)
+    }
+
     fn is_knowable<'o>(&mut self,
                        stack: &TraitObligationStack<'o, 'tcx>)
                        -> bool
@@ -1350,6 +1409,10 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
                                                    &mut candidates)?;
          } else if lang_items.unsize_trait() == Some(def_id) {
              self.assemble_candidates_for_unsizing(obligation, &mut candidates);
+         } else if self.tcx().is_trait_alias(def_id) {
+             // Trait aliases have no impls; they hold whenever their
+             // bounds do.
+             candidates.vec.push(TraitAliasCandidate(def_id));
          } else {
              if lang_items.clone_trait() == Some(def_id) {
                  // Same builtin conditions as `Copy`, i.e. every type which has builtin support
@@ -1421,9 +1484,10 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
                skol_trait_predicate,
                skol_map);
 
+        // The predicates of the associated type include those of its trait
+        // and, for a generic associated type, its own bounds.
         let (def_id, substs) = match skol_trait_predicate.trait_ref.self_ty().sty {
-            ty::TyProjection(ref data) =>
-                (data.trait_ref(self.tcx()).def_id, data.substs),
+            ty::TyProjection(ref data) => (data.item_def_id, data.substs),
             ty::TyAnon(def_id, substs) => (def_id, substs),
             _ => {
                 span_bug!(
@@ -1707,6 +1771,12 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
                     // say nothing; a candidate may be added by
                     // `assemble_candidates_from_object_ty`.
                 }
+                ty::TyForeign(..) => {
+                    // Since the contents of foreign types is unknown,
+                    // we don't add any `..` impl. Default traits could
+                    // still be provided by a manual implementation for
+                    // this trait and type.
+                }
                 ty::TyParam(..) |
                 ty::TyProjection(..) => {
                     // In these cases, we don't know what the actual
@@ -1727,6 +1797,12 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
                     // the defaulted impl might apply, we don't know
                     candidates.ambiguous = true;
                 }
+                ty::TyGenerator(_, _, interior)
+                    if !interior.movable &&
+                       self.tcx().lang_items().unpin_trait() == Some(def_id) => {
+                    // Static generators may point into themselves once
+                    // resumed, so they are never `Unpin`.
+                }
                 _ => {
                     candidates.vec.push(DefaultImplCandidate(def_id.clone()))
                 }
@@ -1940,6 +2016,7 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
                 FnPointerCandidate |
                 BuiltinObjectCandidate |
                 BuiltinUnsizeCandidate |
+                TraitAliasCandidate(..) |
                 BuiltinCandidate { .. } => {
                     // We have a where-clause so don't go around looking
                     // for impls.
@@ -2024,7 +2101,7 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
                 Where(ty::Binder(Vec::new()))
             }
 
-            ty::TyStr | ty::TySlice(_) | ty::TyDynamic(..) => Never,
+            ty::TyStr | ty::TySlice(_) | ty::TyDynamic(..) | ty::TyForeign(..) => Never,
 
             ty::TyTuple(tys, _) => {
                 Where(ty::Binder(tys.last().into_iter().cloned().collect()))
@@ -2069,7 +2146,7 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
             }
 
             ty::TyDynamic(..) | ty::TyStr | ty::TySlice(..) |
-            ty::TyClosure(..) | ty::TyGenerator(..) |
+            ty::TyClosure(..) | ty::TyGenerator(..) | ty::TyForeign(..) |
             ty::TyRef(_, ty::TypeAndMut { ty: _, mutbl: hir::MutMutable }) => {
                 Never
             }
@@ -2135,6 +2212,7 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
 
             ty::TyDynamic(..) |
             ty::TyParam(..) |
+            ty::TyForeign(..) |
             ty::TyProjection(..) |
             ty::TyInfer(ty::TyVar(_)) |
             ty::TyInfer(ty::FreshTy(_)) |
@@ -2308,6 +2386,11 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
                 Ok(VtableFnPointer(data))
             }
 
+            TraitAliasCandidate(alias_def_id) => {
+                let data = self.confirm_trait_alias_candidate(obligation, alias_def_id);
+                Ok(VtableBuiltin(data))
+            }
+
             ProjectionCandidate => {
                 self.confirm_projection_candidate(obligation);
                 Ok(VtableParam(Vec::new()))
@@ -2460,6 +2543,35 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
         }
     }
 
+    /// A trait alias holds if its bounds, which are its predicates, hold.
+    fn confirm_trait_alias_candidate(&mut self,
+                                     obligation: &TraitObligation<'tcx>,
+                                     alias_def_id: DefId)
+                                     -> VtableBuiltinData<PredicateObligation<'tcx>>
+    {
+        debug!("confirm_trait_alias_candidate({:?}, {:?})",
+               obligation,
+               alias_def_id);
+
+        let obligations = self.in_snapshot(|this, snapshot| {
+            let poly_trait_ref = obligation.predicate.to_poly_trait_ref();
+            let (trait_ref, skol_map) =
+                this.infcx().skolemize_late_bound_regions(&poly_trait_ref, snapshot);
+            let cause = obligation.derived_cause(ImplDerivedObligation);
+            this.impl_or_trait_obligations(cause,
+                                           obligation.recursion_depth + 1,
+                                           obligation.param_env,
+                                           alias_def_id,
+                                           &trait_ref.substs,
+                                           skol_map,
+                                           snapshot)
+        });
+
+        debug!("confirm_trait_alias_candidate: obligations={:?}", obligations);
+
+        VtableBuiltinData { nested: obligations }
+    }
+
     fn confirm_impl_candidate(&mut self,
                               obligation: &TraitObligation<'tcx>,
                               impl_def_id: DefId)
@@ -281,6 +281,61 @@ pub fn transitive_bounds<'cx, 'gcx, 'tcx>(tcx: TyCtxt<'cx, 'gcx, 'tcx>,
     elaborate_trait_refs(tcx, bounds).filter_to_traits()
 }
 
+///////////////////////////////////////////////////////////////////////////
+// Trait alias expansion
+///////////////////////////////////////////////////////////////////////////
+
+/// The traits and projections that a list of trait references stands for
+/// once every trait alias in it has been replaced by its bounds.
+pub struct TraitAliasExpansion<'tcx> {
+    pub trait_refs: Vec<ty::PolyTraitRef<'tcx>>,
+    pub projections: Vec<ty::PolyProjectionPredicate<'tcx>>,
+}
+
+/// Recursively replaces each trait alias in `trait_refs` by the traits and
+/// projections it is an alias for, keeping the order in which they were
+/// written. For example, given `trait Handler = Fn(Request) -> Response +
+/// Send;`, `Handler` expands to the trait references `Fn<(Request,)>` and
+/// `Send`, plus the projection `<Self as FnOnce<(Request,)>>::Output ==
+/// Response`. References that are not aliases are returned unchanged, and
+/// duplicates (as well as cyclic aliases) are only visited once. Region
+/// bounds of aliases are dropped.
+pub fn expand_trait_aliases<'cx, 'gcx, 'tcx, I>(tcx: TyCtxt<'cx, 'gcx, 'tcx>,
+                                                trait_refs: I)
+                                                -> TraitAliasExpansion<'tcx>
+    where I: IntoIterator<Item = ty::PolyTraitRef<'tcx>>
+{
+    let mut expansion = TraitAliasExpansion {
+        trait_refs: vec![],
+        projections: vec![],
+    };
+    let mut visited = PredicateSet::new(tcx);
+    let mut stack: Vec<_> = trait_refs.into_iter().collect();
+    stack.reverse();
+
+    while let Some(trait_ref) = stack.pop() {
+        if !visited.insert(&trait_ref.to_predicate()) {
+            continue;
+        }
+        if !tcx.is_trait_alias(trait_ref.def_id()) {
+            expansion.trait_refs.push(trait_ref);
+            continue;
+        }
+
+        // Push the bounds in reverse so they are popped in source order.
+        let predicates = tcx.super_predicates_of(trait_ref.def_id());
+        for predicate in predicates.predicates.iter().rev() {
+            match predicate.subst_supertrait(tcx, &trait_ref) {
+                ty::Predicate::Trait(data) => stack.push(data.to_poly_trait_ref()),
+                ty::Predicate::Projection(data) => expansion.projections.push(data),
+                _ => {}
+            }
+        }
+    }
+
+    expansion
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // Iterator over def-ids of supertraits
 
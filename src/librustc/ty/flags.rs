@@ -31,6 +31,12 @@ impl FlagComputation {
         result
     }
 
+    pub fn for_const(c: &ty::Const) -> FlagComputation {
+        let mut result = FlagComputation::new();
+        result.add_const(c);
+        result
+    }
+
     fn add_flags(&mut self, flags: TypeFlags) {
         self.flags = self.flags | (flags & TypeFlags::NOMINAL_FLAGS);
     }
@@ -63,7 +69,8 @@ impl FlagComputation {
             &ty::TyFloat(_) |
             &ty::TyUint(_) |
             &ty::TyNever |
-            &ty::TyStr => {
+            &ty::TyStr |
+            &ty::TyForeign(..) => {
             }
 
             // You might think that we could just return TyError for
@@ -239,6 +246,18 @@ impl FlagComputation {
                 self.add_flags(TypeFlags::HAS_PROJECTION);
                 self.add_substs(substs);
             }
+            ConstVal::Param(_) => {
+                self.add_flags(TypeFlags::HAS_LOCAL_NAMES);
+                self.add_flags(TypeFlags::HAS_PARAMS);
+            }
+            ConstVal::Infer(infer) => {
+                self.add_flags(TypeFlags::HAS_LOCAL_NAMES);
+                self.add_flags(TypeFlags::HAS_TY_INFER);
+                match infer {
+                    ty::InferConst::Fresh(_) => {}
+                    ty::InferConst::Var(_) => self.add_flags(TypeFlags::KEEP_IN_LOCAL_TCX),
+                }
+            }
         }
     }
 
@@ -259,5 +278,9 @@ impl FlagComputation {
         for r in substs.regions() {
             self.add_region(r);
         }
+
+        for c in substs.consts() {
+            self.add_const(c);
+        }
     }
 }
@@ -41,6 +41,7 @@
 
 use middle::const_val::ConstVal;
 use ty::{self, Binder, Ty, TyCtxt, TypeFlags};
+use ty::flags::FlagComputation;
 
 use std::fmt;
 use util::nodemap::{FxHashMap, FxHashSet};
@@ -621,7 +622,9 @@ impl<'tcx> TypeVisitor<'tcx> for HasTypeFlagsVisitor {
                 return true;
             }
         }
-        c.super_visit_with(self)
+        let flags = FlagComputation::for_const(c).flags;
+        debug!("HasTypeFlagsVisitor: c={:?} c.flags={:?} self.flags={:?}", c, flags, self.flags);
+        flags.intersects(self.flags)
     }
 }
 
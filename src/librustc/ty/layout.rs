@@ -844,7 +844,11 @@ impl<'a, 'tcx> Struct {
                         return Err(LayoutError::Unknown(ty));
                     }
                 }
-                if count.val.to_const_int().unwrap().to_u64().unwrap() != 0 {
+                let count = match count.val.to_const_int().and_then(|c| c.to_u64()) {
+                    Some(count) => count,
+                    None => return Err(LayoutError::Unknown(ty)),
+                };
+                if count != 0 {
                     Struct::non_zero_field_paths(
                         tcx,
                         param_env,
@@ -1141,6 +1145,10 @@ impl<'a, 'tcx> Layout {
                         Int(dl.ptr_sized_integer())
                     }
                     ty::TyDynamic(..) => Pointer,
+                    // Pointers to extern types carry no metadata.
+                    ty::TyForeign(..) => {
+                        return Ok(Scalar { value: Pointer, non_zero: non_zero });
+                    }
                     _ => return Err(LayoutError::Unknown(unsized_part))
                 };
                 Ok(FatPointer { metadata: meta, non_zero: non_zero })
@@ -1192,9 +1200,12 @@ impl<'a, 'tcx> Layout {
                     }
                 }
 
+                let count = match count.val.to_const_int().and_then(|c| c.to_u64()) {
+                    Some(count) => count,
+                    None => return Err(LayoutError::Unknown(ty)),
+                };
                 let element = element.layout(tcx, param_env)?;
                 let element_size = element.size(dl);
-                let count = count.val.to_const_int().unwrap().to_u64().unwrap();
                 if element_size.checked_mul(count, dl).is_none() {
                     return Err(LayoutError::SizeOverflow(ty));
                 }
@@ -1234,7 +1245,7 @@ impl<'a, 'tcx> Layout {
                     non_zero: false
                 }
             }
-            ty::TyDynamic(..) => {
+            ty::TyDynamic(..) | ty::TyForeign(..) => {
                 let mut unit = Struct::new(dl, &vec![], &ReprOptions::default(),
                   StructKind::AlwaysSizedUnivariant, ty)?;
                 unit.sized = false;
@@ -2247,7 +2258,8 @@ impl<'a, 'tcx> TyLayout<'tcx> {
             ty::TyFnPtr(_) |
             ty::TyNever |
             ty::TyFnDef(..) |
-            ty::TyDynamic(..) => {
+            ty::TyDynamic(..) |
+            ty::TyForeign(..) => {
                 bug!("TyLayout::field_type({:?}): not applicable", self)
             }
 
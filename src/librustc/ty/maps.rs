@@ -1139,6 +1139,11 @@ define_maps! { <'tcx>
     [] fn generics_of: GenericsOfItem(DefId) -> &'tcx ty::Generics,
     [] fn predicates_of: PredicatesOfItem(DefId) -> ty::GenericPredicates<'tcx>,
 
+    /// The subset of `predicates_of` written in the source of a local
+    /// item, leaving out the bounds implied by its input types.
+    [] fn explicit_predicates_of: ExplicitPredicatesOfItem(DefId)
+        -> ty::GenericPredicates<'tcx>,
+
     /// Maps from the def-id of a trait to the list of
     /// super-predicates. This is a subset of the full list of
     /// predicates. We store these in a separate map because we must
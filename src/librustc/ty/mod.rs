@@ -38,7 +38,7 @@ use std::collections::BTreeMap;
 use std::cmp;
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::iter::FromIterator;
+use std::iter::{self, FromIterator};
 use std::ops::Deref;
 use std::rc::Rc;
 use std::slice;
@@ -61,13 +61,14 @@ use hir;
 pub use self::sty::{Binder, DebruijnIndex};
 pub use self::sty::{FnSig, GenSig, PolyFnSig, PolyGenSig};
 pub use self::sty::{InferTy, ParamTy, ProjectionTy, ExistentialPredicate};
+pub use self::sty::{InferConst, ParamConst};
 pub use self::sty::{ClosureSubsts, GeneratorInterior, TypeAndMut};
 pub use self::sty::{TraitRef, TypeVariants, PolyTraitRef};
 pub use self::sty::{ExistentialTraitRef, PolyExistentialTraitRef};
 pub use self::sty::{ExistentialProjection, PolyExistentialProjection, Const};
 pub use self::sty::{BoundRegion, EarlyBoundRegion, FreeRegion, Region};
 pub use self::sty::RegionKind;
-pub use self::sty::{TyVid, IntVid, FloatVid, RegionVid, SkolemizedRegionVid};
+pub use self::sty::{TyVid, IntVid, FloatVid, ConstVid, RegionVid, SkolemizedRegionVid};
 pub use self::sty::BoundRegion::*;
 pub use self::sty::InferTy::*;
 pub use self::sty::RegionKind::*;
@@ -676,6 +677,10 @@ pub struct TypeParameterDef {
     /// on generic parameter `T`, asserts data behind the parameter
     /// `T` won't be accessed during the parent type's `Drop` impl.
     pub pure_wrt_drop: bool,
+
+    /// Set for parameters the user did not declare, such as the
+    /// anonymous parameter behind an `impl Trait` argument.
+    pub synthetic: Option<hir::SyntheticTyParamKind>,
 }
 
 #[derive(Copy, Clone, RustcEncodable, RustcDecodable)]
@@ -690,6 +695,13 @@ pub struct RegionParameterDef {
     pub pure_wrt_drop: bool,
 }
 
+#[derive(Copy, Clone, RustcEncodable, RustcDecodable)]
+pub struct ConstParameterDef {
+    pub name: Name,
+    pub def_id: DefId,
+    pub index: u32,
+}
+
 impl RegionParameterDef {
     pub fn to_early_bound_region_data(&self) -> ty::EarlyBoundRegion {
         ty::EarlyBoundRegion {
@@ -710,15 +722,20 @@ impl ty::EarlyBoundRegion {
     }
 }
 
-/// Information about the formal type/lifetime parameters associated
+/// Information about the formal type/lifetime/const parameters associated
 /// with an item or method. Analogous to hir::Generics.
+///
+/// The parameters are laid out in `Substs` as `Self` (if any), then the
+/// regions, then the types and finally the consts.
 #[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
 pub struct Generics {
     pub parent: Option<DefId>,
     pub parent_regions: u32,
     pub parent_types: u32,
+    pub parent_consts: u32,
     pub regions: Vec<RegionParameterDef>,
     pub types: Vec<TypeParameterDef>,
+    pub consts: Vec<ConstParameterDef>,
 
     /// Reverse map to each `TypeParameterDef`'s `index` field, from
     /// `def_id.index` (`def_id.krate` is the same as the item's).
@@ -730,11 +747,18 @@ pub struct Generics {
 
 impl Generics {
     pub fn parent_count(&self) -> usize {
-        self.parent_regions as usize + self.parent_types as usize
+        self.parent_regions as usize + self.parent_types as usize + self.parent_consts as usize
     }
 
     pub fn own_count(&self) -> usize {
-        self.regions.len() + self.types.len()
+        self.regions.len() + self.types.len() + self.consts.len()
+    }
+
+    /// Whether this item, or any of its parents, has type or const
+    /// parameters, i.e. needs to be monomorphized.
+    pub fn requires_monomorphization(&self) -> bool {
+        self.parent_types != 0 || !self.types.is_empty() ||
+            self.parent_consts != 0 || !self.consts.is_empty()
     }
 
     pub fn count(&self) -> usize {
@@ -750,6 +774,11 @@ impl Generics {
         assert_eq!(self.parent_count(), 0);
         &self.types[param.idx as usize - self.has_self as usize - self.regions.len()]
     }
+
+    pub fn const_param(&self, param: &ParamConst) -> &ConstParameterDef {
+        assert_eq!(self.parent_count(), 0);
+        &self.consts[param.idx as usize - self.regions.len() - self.types.len()]
+    }
 }
 
 /// Bounds on generics.
@@ -1014,7 +1043,7 @@ pub struct ProjectionPredicate<'tcx> {
 pub type PolyProjectionPredicate<'tcx> = Binder<ProjectionPredicate<'tcx>>;
 
 impl<'tcx> PolyProjectionPredicate<'tcx> {
-    pub fn to_poly_trait_ref(&self, tcx: TyCtxt) -> PolyTraitRef<'tcx> {
+    pub fn to_poly_trait_ref<'a, 'gcx>(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> PolyTraitRef<'tcx> {
         // Note: unlike with TraitRef::to_poly_trait_ref(),
         // self.0.trait_ref is permitted to have escaping regions.
         // This is because here `self` has a `Binder` and so does our
@@ -1266,6 +1295,7 @@ bitflags! {
         const IS_FUNDAMENTAL      = 1 << 2,
         const IS_UNION            = 1 << 3,
         const IS_BOX              = 1 << 4,
+        const IS_NON_EXHAUSTIVE   = 1 << 5,
     }
 }
 
@@ -1454,7 +1484,8 @@ impl<'a, 'gcx, 'tcx> AdtDef {
            did: DefId,
            kind: AdtKind,
            variants: Vec<VariantDef>,
-           repr: ReprOptions) -> Self {
+           repr: ReprOptions,
+           non_exhaustive: bool) -> Self {
         let mut flags = AdtFlags::NO_ADT_FLAGS;
         let attrs = tcx.get_attrs(did);
         if attr::contains_name(&attrs, "fundamental") {
@@ -1466,6 +1497,9 @@ impl<'a, 'gcx, 'tcx> AdtDef {
         if Some(did) == tcx.lang_items().owned_box() {
             flags = flags | AdtFlags::IS_BOX;
         }
+        if non_exhaustive {
+            flags = flags | AdtFlags::IS_NON_EXHAUSTIVE;
+        }
         match kind {
             AdtKind::Enum => flags = flags | AdtFlags::IS_ENUM,
             AdtKind::Union => flags = flags | AdtFlags::IS_UNION,
@@ -1494,6 +1528,14 @@ impl<'a, 'gcx, 'tcx> AdtDef {
         self.flags.intersects(AdtFlags::IS_ENUM)
     }
 
+    /// Returns whether the ADT is marked `#[non_exhaustive]`: outside of the
+    /// defining crate, matches on it need a wildcard arm and it cannot be
+    /// built with a struct expression.
+    #[inline]
+    pub fn is_non_exhaustive(&self) -> bool {
+        self.flags.intersects(AdtFlags::IS_NON_EXHAUSTIVE)
+    }
+
     /// Returns the kind of the ADT - Struct or Enum.
     #[inline]
     pub fn adt_kind(&self) -> AdtKind {
@@ -1725,7 +1767,7 @@ impl<'a, 'gcx, 'tcx> AdtDef {
                 vec![]
             }
 
-            TyStr | TyDynamic(..) | TySlice(_) | TyError => {
+            TyStr | TyDynamic(..) | TySlice(_) | TyForeign(..) | TyError => {
                 // these are never sized - return the target type
                 vec![ty]
             }
@@ -2058,6 +2100,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         let is_associated_item = if let Some(node_id) = self.hir.as_local_node_id(def_id) {
             match self.hir.get(node_id) {
                 hir_map::NodeTraitItem(_) | hir_map::NodeImplItem(_) => true,
+                hir_map::NodeTy(_) => self.is_impl_trait_assoc(def_id),
                 _ => false,
             }
         } else {
@@ -2125,6 +2168,54 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         }
     }
 
+    /// Returns true if `def_id` is the `impl Trait` return type of a method
+    /// of a trait or of a trait impl. Such a type stands for an anonymous
+    /// associated type of that trait or impl, whose parameters are those of
+    /// the method.
+    pub fn is_impl_trait_assoc(self, def_id: DefId) -> bool {
+        if self.def_key(def_id).disambiguated_data.data != hir_map::DefPathData::ImplTrait {
+            return false;
+        }
+
+        let node_id = match self.hir.as_local_node_id(def_id) {
+            Some(node_id) => node_id,
+            None => {
+                return match self.describe_def(def_id) {
+                    Some(Def::AssociatedTy(_)) => true,
+                    _ => false,
+                };
+            }
+        };
+
+        let method_id = self.hir.get_parent_node(node_id);
+        let decl = match self.hir.get(method_id) {
+            hir_map::NodeTraitItem(&hir::TraitItem {
+                node: hir::TraitItemKind::Method(ref sig, _), ..
+            }) => &sig.decl,
+            hir_map::NodeImplItem(&hir::ImplItem {
+                node: hir::ImplItemKind::Method(ref sig, _), ..
+            }) => {
+                match self.hir.expect_item(self.hir.get_parent(method_id)).node {
+                    hir::ItemImpl(.., Some(_), _, _) => &sig.decl,
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        };
+        impl_trait_return_id(decl) == Some(node_id)
+    }
+
+    /// Returns the anonymous associated type standing for the `impl Trait`
+    /// return type of the trait or trait impl method `def_id`, if any.
+    pub fn impl_trait_assoc_of_method(self, def_id: DefId) -> Option<AssociatedItem> {
+        let container = self.associated_item(def_id).container.id();
+        self.associated_items(container).find(|item| {
+            item.kind == AssociatedKind::Type &&
+                self.is_impl_trait_assoc(item.def_id) &&
+                self.parent_def_id(item.def_id) == Some(def_id)
+        })
+    }
+
     #[inline] // FIXME(#35870) Avoid closures being unexported due to impl Trait.
     pub fn associated_items(self, def_id: DefId)
                             -> impl Iterator<Item = ty::AssociatedItem> + 'a {
@@ -2238,6 +2329,11 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         self.trait_def(trait_def_id).has_default_impl
     }
 
+    /// Returns true if `trait_def_id` refers to a trait alias.
+    pub fn is_trait_alias(self, trait_def_id: DefId) -> bool {
+        self.trait_def(trait_def_id).is_alias
+    }
+
     pub fn generator_layout(self, def_id: DefId) -> &'tcx GeneratorLayout<'tcx> {
         self.optimized_mir(def_id).generator_layout.as_ref().unwrap()
     }
@@ -2316,6 +2412,9 @@ fn associated_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId)
     -> AssociatedItem
 {
     let id = tcx.hir.as_local_node_id(def_id).unwrap();
+    if let hir_map::NodeTy(_) = tcx.hir.get(id) {
+        return impl_trait_assoc_item(tcx, def_id);
+    }
     let parent_id = tcx.hir.get_parent(id);
     let parent_def_id = tcx.hir.local_def_id(parent_id);
     let parent_item = tcx.hir.expect_item(parent_id);
@@ -2347,6 +2446,46 @@ fn associated_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId)
               parent_item.node)
 }
 
+/// Builds the anonymous associated type for the `impl Trait` return type
+/// `def_id` of a trait or trait impl method.
+fn impl_trait_assoc_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId)
+    -> AssociatedItem
+{
+    assert!(tcx.is_impl_trait_assoc(def_id));
+    let method = tcx.associated_item(tcx.parent_def_id(def_id).unwrap());
+    AssociatedItem {
+        name: impl_trait_assoc_name(&method.name.as_str()),
+        kind: AssociatedKind::Type,
+        vis: method.vis,
+        defaultness: match method.container {
+            TraitContainer(_) => hir::Defaultness::Default { has_value: false },
+            ImplContainer(_) => method.defaultness,
+        },
+        def_id,
+        container: method.container,
+        method_has_self_argument: false
+    }
+}
+
+/// The name of the anonymous associated type standing for the `impl Trait`
+/// return type of the method `method_name`. It cannot be written in
+/// source, so it never clashes with a declared associated type.
+pub fn impl_trait_assoc_name(method_name: &str) -> Name {
+    Symbol::intern(&format!("{}::{{{{impl-Trait}}}}", method_name))
+}
+
+/// The id of the `impl Trait` that makes up the whole return type of
+/// `decl`, if there is one.
+fn impl_trait_return_id(decl: &hir::FnDecl) -> Option<NodeId> {
+    match decl.output {
+        hir::Return(ref ty) => match ty.node {
+            hir::TyImplTraitExistential(..) => Some(ty.id),
+            _ => None,
+        },
+        hir::DefaultReturn(_) => None,
+    }
+}
+
 /// Calculates the Sized-constraint.
 ///
 /// In fact, there are only a few options for the types in the constraint:
@@ -2409,19 +2548,30 @@ fn associated_item_def_ids<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                      -> Rc<Vec<DefId>> {
     let id = tcx.hir.as_local_node_id(def_id).unwrap();
     let item = tcx.hir.expect_item(id);
+    // Methods returning `impl Trait` are followed by the anonymous
+    // associated type standing for their return type.
     let vec: Vec<_> = match item.node {
         hir::ItemTrait(.., ref trait_item_refs) => {
-            trait_item_refs.iter()
-                           .map(|trait_item_ref| trait_item_ref.id)
-                           .map(|id| tcx.hir.local_def_id(id.node_id))
-                           .collect()
-        }
-        hir::ItemImpl(.., ref impl_item_refs) => {
-            impl_item_refs.iter()
-                          .map(|impl_item_ref| impl_item_ref.id)
-                          .map(|id| tcx.hir.local_def_id(id.node_id))
-                          .collect()
+            trait_item_refs.iter().flat_map(|trait_item_ref| {
+                let impl_trait_id = match tcx.hir.trait_item(trait_item_ref.id).node {
+                    hir::TraitItemKind::Method(ref sig, _) => impl_trait_return_id(&sig.decl),
+                    _ => None,
+                };
+                iter::once(trait_item_ref.id.node_id).chain(impl_trait_id)
+            }).map(|id| tcx.hir.local_def_id(id)).collect()
+        }
+        hir::ItemImpl(.., ref trait_ref, _, ref impl_item_refs) => {
+            impl_item_refs.iter().flat_map(|impl_item_ref| {
+                let impl_trait_id = match tcx.hir.impl_item(impl_item_ref.id).node {
+                    hir::ImplItemKind::Method(ref sig, _) if trait_ref.is_some() => {
+                        impl_trait_return_id(&sig.decl)
+                    }
+                    _ => None,
+                };
+                iter::once(impl_item_ref.id.node_id).chain(impl_trait_id)
+            }).map(|id| tcx.hir.local_def_id(id)).collect()
         }
+        hir::ItemTraitAlias(..) => vec![],
         _ => span_bug!(item.span, "associated_item_def_ids: not impl or trait")
     };
     Rc::new(vec)
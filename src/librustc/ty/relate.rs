@@ -94,6 +94,14 @@ pub trait TypeRelation<'a, 'gcx: 'a+'tcx, 'tcx: 'a> : Sized {
     fn binders<T>(&mut self, a: &ty::Binder<T>, b: &ty::Binder<T>)
                   -> RelateResult<'tcx, ty::Binder<T>>
         where T: Relate<'tcx>;
+
+    /// Relations that work with inference variables override this to
+    /// unify const inference variables before comparing the values.
+    fn consts(&mut self, a: &'tcx ty::Const<'tcx>, b: &'tcx ty::Const<'tcx>)
+              -> RelateResult<'tcx, &'tcx ty::Const<'tcx>>
+    {
+        super_relate_consts(self, a, b)
+    }
 }
 
 pub trait Relate<'tcx>: TypeFoldable<'tcx> {
@@ -145,6 +153,8 @@ pub fn relate_substs<'a, 'gcx, 'tcx, R>(relation: &mut R,
             Ok(Kind::from(relation.relate_with_variance(variance, &a_ty, &b_ty)?))
         } else if let (Some(a_r), Some(b_r)) = (a.as_region(), b.as_region()) {
             Ok(Kind::from(relation.relate_with_variance(variance, &a_r, &b_r)?))
+        } else if let (Some(a_c), Some(b_c)) = (a.as_const(), b.as_const()) {
+            Ok(Kind::from(relation.relate(&a_c, &b_c)?))
         } else {
             bug!()
         }
@@ -368,6 +378,12 @@ pub fn super_relate_tys<'a, 'gcx, 'tcx, R>(relation: &mut R,
             Ok(a)
         }
 
+        (&ty::TyForeign(a_id), &ty::TyForeign(b_id))
+            if a_id == b_id =>
+        {
+            Ok(tcx.mk_foreign(a_id))
+        }
+
         (&ty::TyParam(ref a_p), &ty::TyParam(ref b_p))
             if a_p.idx == b_p.idx =>
         {
@@ -433,27 +449,21 @@ pub fn super_relate_tys<'a, 'gcx, 'tcx, R>(relation: &mut R,
             let t = relation.relate(&a_t, &b_t)?;
             assert_eq!(sz_a.ty, tcx.types.usize);
             assert_eq!(sz_b.ty, tcx.types.usize);
+
+            // Lengths that are const parameters or inference variables
+            // can't be evaluated, so relate them as constants instead.
+            let is_concrete = |x: &'tcx ty::Const<'tcx>| match x.val {
+                ConstVal::Param(_) | ConstVal::Infer(_) => false,
+                _ => true,
+            };
+            if !is_concrete(sz_a) || !is_concrete(sz_b) {
+                let sz = relation.relate(&sz_a, &sz_b)?;
+                return Ok(tcx.mk_ty(ty::TyArray(t, sz)));
+            }
+
             let to_u64 = |x: &'tcx ty::Const<'tcx>| -> Result<u64, ErrorReported> {
-                match x.val {
+                match evaluate_const(tcx, x)?.val {
                     ConstVal::Integral(x) => Ok(x.to_u64().unwrap()),
-                    ConstVal::Unevaluated(def_id, substs) => {
-                        // FIXME(eddyb) get the right param_env.
-                        let param_env = ty::ParamEnv::empty(Reveal::UserFacing);
-                        match tcx.lift_to_global(&substs) {
-                            Some(substs) => {
-                                match tcx.const_eval(param_env.and((def_id, substs))) {
-                                    Ok(&ty::Const { val: ConstVal::Integral(x), .. }) => {
-                                        return Ok(x.to_u64().unwrap());
-                                    }
-                                    _ => {}
-                                }
-                            }
-                            None => {}
-                        }
-                        tcx.sess.delay_span_bug(tcx.def_span(def_id),
-                            "array length could not be evaluated");
-                        Err(ErrorReported)
-                    }
                     _ => bug!("arrays should not have {:?} as length", x)
                 }
             };
@@ -525,6 +535,73 @@ pub fn super_relate_tys<'a, 'gcx, 'tcx, R>(relation: &mut R,
     }
 }
 
+/// Evaluates `constant` if it is an unevaluated constant expression.
+fn evaluate_const<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
+                                  constant: &'tcx ty::Const<'tcx>)
+                                  -> Result<&'tcx ty::Const<'tcx>, ErrorReported> {
+    if let ConstVal::Unevaluated(def_id, substs) = constant.val {
+        // FIXME(eddyb) get the right param_env.
+        let param_env = ty::ParamEnv::empty(Reveal::UserFacing);
+        if let Some(substs) = tcx.lift_to_global(&substs) {
+            if let Ok(evaluated) = tcx.const_eval(param_env.and((def_id, substs))) {
+                return Ok(evaluated);
+            }
+        }
+        tcx.sess.delay_span_bug(tcx.def_span(def_id), "constant could not be evaluated");
+        return Err(ErrorReported);
+    }
+    Ok(constant)
+}
+
+/// The common case of relating two constants: const parameters are only
+/// equal to themselves, and other constants are compared by value.
+pub fn super_relate_consts<'a, 'gcx, 'tcx, R>(relation: &mut R,
+                                              a: &'tcx ty::Const<'tcx>,
+                                              b: &'tcx ty::Const<'tcx>)
+                                              -> RelateResult<'tcx, &'tcx ty::Const<'tcx>>
+    where R: TypeRelation<'a, 'gcx, 'tcx>, 'gcx: 'a+'tcx, 'tcx: 'a
+{
+    let tcx = relation.tcx();
+    if a == b {
+        return Ok(a);
+    }
+
+    let ty = relation.relate(&a.ty, &b.ty)?;
+    if let (ConstVal::Unevaluated(a_def_id, a_substs),
+            ConstVal::Unevaluated(b_def_id, b_substs)) = (a.val, b.val) {
+        if a_def_id == b_def_id {
+            let substs = relation.relate_with_variance(ty::Invariant, &a_substs, &b_substs)?;
+            return Ok(tcx.mk_const(ty::Const {
+                val: ConstVal::Unevaluated(a_def_id, substs),
+                ty,
+            }));
+        }
+    }
+
+    match (evaluate_const(tcx, a), evaluate_const(tcx, b)) {
+        (Ok(a_val), Ok(b_val)) => {
+            if a_val.val == b_val.val {
+                Ok(a)
+            } else {
+                Err(TypeError::ConstMismatch(expected_found(relation, &a, &b)))
+            }
+        }
+        // We reported an error or will ICE, so just keep going.
+        (Err(ErrorReported), _) | (_, Err(ErrorReported)) => Ok(a),
+    }
+}
+
+impl<'tcx> Relate<'tcx> for &'tcx ty::Const<'tcx> {
+    fn relate<'a, 'gcx, R>(relation: &mut R,
+                           a: &&'tcx ty::Const<'tcx>,
+                           b: &&'tcx ty::Const<'tcx>)
+                           -> RelateResult<'tcx, &'tcx ty::Const<'tcx>>
+        where R: TypeRelation<'a, 'gcx, 'tcx>, 'gcx: 'a+'tcx, 'tcx: 'a
+    {
+        relation.consts(*a, *b)
+    }
+}
+
 impl<'tcx> Relate<'tcx> for &'tcx ty::Slice<ty::ExistentialPredicate<'tcx>> {
     fn relate<'a, 'gcx, R>(relation: &mut R,
                            a: &Self,
@@ -569,8 +646,9 @@ impl<'tcx> Relate<'tcx> for ty::GeneratorInterior<'tcx> {
                            -> RelateResult<'tcx, ty::GeneratorInterior<'tcx>>
         where R: TypeRelation<'a, 'gcx, 'tcx>, 'gcx: 'a+'tcx, 'tcx: 'a
     {
-        let interior = relation.relate(&a.witness, &b.witness)?;
-        Ok(ty::GeneratorInterior::new(interior))
+        assert_eq!(a.movable, b.movable);
+        let witness = relation.relate(&a.witness, &b.witness)?;
+        Ok(ty::GeneratorInterior::new(witness, a.movable))
     }
 }
 
@@ -22,6 +22,7 @@ use ty::subst::Kind;
 use std::fmt;
 use std::iter;
 use std::cmp::Ordering;
+use std::marker::PhantomData;
 use syntax::abi;
 use syntax::ast::{self, Name};
 use syntax::symbol::keywords;
@@ -106,6 +107,10 @@ pub enum TypeVariants<'tcx> {
     /// definition and not a concrete use of it.
     TyAdt(&'tcx AdtDef, &'tcx Substs<'tcx>),
 
+    /// An unsized FFI type that is opaque to Rust, written as
+    /// `extern { type T; }`.
+    TyForeign(DefId),
+
     /// The pointee of a string slice. Written as `str`.
     TyStr,
 
@@ -296,11 +301,14 @@ impl<'a, 'gcx, 'tcx> ClosureSubsts<'tcx> {
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, RustcEncodable, RustcDecodable)]
 pub struct GeneratorInterior<'tcx> {
     pub witness: Ty<'tcx>,
+    /// False for `static` generators, which may borrow their own locals
+    /// across yields and must therefore not be moved once resumed.
+    pub movable: bool,
 }
 
 impl<'tcx> GeneratorInterior<'tcx> {
-    pub fn new(witness: Ty<'tcx>) -> GeneratorInterior<'tcx> {
-        GeneratorInterior { witness }
+    pub fn new(witness: Ty<'tcx>, movable: bool) -> GeneratorInterior<'tcx> {
+        GeneratorInterior { witness, movable }
     }
 
     pub fn as_slice(&self) -> &'tcx Slice<Ty<'tcx>> {
@@ -616,11 +624,15 @@ impl<'a, 'tcx> ProjectionTy<'tcx> {
     /// Extracts the underlying trait reference from this projection.
     /// For example, if this is a projection of `<T as Iterator>::Item`,
     /// then this function would return a `T: Iterator` trait reference.
-    pub fn trait_ref(&self, tcx: TyCtxt) -> ty::TraitRef<'tcx> {
+    ///
+    /// The substitutions of a projection of a generic associated type
+    /// also contain the arguments for the parameters of the associated
+    /// type itself; those are not part of the trait reference.
+    pub fn trait_ref<'gcx>(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> ty::TraitRef<'tcx> {
         let def_id = tcx.associated_item(self.item_def_id).container.id();
         ty::TraitRef {
             def_id,
-            substs: self.substs,
+            substs: self.substs.truncate_to(tcx, tcx.generics_of(def_id)),
         }
     }
 
@@ -726,6 +738,26 @@ impl<'a, 'gcx, 'tcx> ParamTy {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
+pub struct ParamConst {
+    pub idx: u32,
+    pub name: Name,
+}
+
+impl<'a, 'gcx, 'tcx> ParamConst {
+    pub fn new(index: u32, name: Name) -> ParamConst {
+        ParamConst { idx: index, name: name }
+    }
+
+    pub fn for_def(def: &ty::ConstParameterDef) -> ParamConst {
+        ParamConst::new(def.index, def.name)
+    }
+
+    pub fn to_const(self, tcx: TyCtxt<'a, 'gcx, 'tcx>, ty: Ty<'tcx>) -> &'tcx Const<'tcx> {
+        tcx.mk_const(Const { val: ConstVal::Param(self), ty })
+    }
+}
+
 /// A [De Bruijn index][dbi] is a standard means of representing
 /// regions (and perhaps later types) in a higher-ranked setting. In
 /// particular, imagine a type like this:
@@ -898,6 +930,12 @@ pub struct FloatVid {
     pub index: u32,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
+pub struct ConstVid<'tcx> {
+    pub index: u32,
+    pub phantom: PhantomData<&'tcx ()>,
+}
+
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Copy)]
 pub struct RegionVid {
     pub index: u32,
@@ -922,6 +960,15 @@ pub enum InferTy {
     FreshFloatTy(u32),
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
+pub enum InferConst<'tcx> {
+    Var(ConstVid<'tcx>),
+
+    /// A `Fresh` const is the equivalent of `FreshTy` for constants.
+    /// See `infer::freshen` for more details.
+    Fresh(u32),
+}
+
 /// A `ProjectionPredicate` for an `ExistentialTraitRef`.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, RustcEncodable, RustcDecodable)]
 pub struct ExistentialProjection<'tcx> {
@@ -1450,6 +1497,7 @@ impl<'a, 'gcx, 'tcx> TyS<'tcx> {
             TySlice(_) |
             TyRawPtr(_) |
             TyNever |
+            TyForeign(..) |
             TyTuple(..) |
             TyParam(_) |
             TyInfer(_) |
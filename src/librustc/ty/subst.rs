@@ -11,6 +11,7 @@
 // Type substitutions.
 
 use hir::def_id::DefId;
+use middle::const_val::ConstVal;
 use ty::{self, Slice, Region, Ty, TyCtxt};
 use ty::fold::{TypeFoldable, TypeFolder, TypeVisitor};
 
@@ -25,19 +26,20 @@ use std::marker::PhantomData;
 use std::mem;
 
 /// An entity in the Rust typesystem, which can be one of
-/// several kinds (only types and lifetimes for now).
+/// several kinds (types, lifetimes and constants).
 /// To reduce memory usage, a `Kind` is a interned pointer,
 /// with the lowest 2 bits being reserved for a tag to
-/// indicate the type (`Ty` or `Region`) it points to.
+/// indicate the type (`Ty`, `Region` or `Const`) it points to.
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Kind<'tcx> {
     ptr: NonZero<usize>,
-    marker: PhantomData<(Ty<'tcx>, ty::Region<'tcx>)>
+    marker: PhantomData<(Ty<'tcx>, ty::Region<'tcx>, &'tcx ty::Const<'tcx>)>
 }
 
 const TAG_MASK: usize = 0b11;
 const TYPE_TAG: usize = 0b00;
 const REGION_TAG: usize = 0b01;
+const CONST_TAG: usize = 0b10;
 
 impl<'tcx> From<Ty<'tcx>> for Kind<'tcx> {
     fn from(ty: Ty<'tcx>) -> Kind<'tcx> {
@@ -69,6 +71,21 @@ impl<'tcx> From<ty::Region<'tcx>> for Kind<'tcx> {
     }
 }
 
+impl<'tcx> From<&'tcx ty::Const<'tcx>> for Kind<'tcx> {
+    fn from(c: &'tcx ty::Const<'tcx>) -> Kind<'tcx> {
+        // Ensure we can use the tag bits.
+        assert_eq!(mem::align_of_val(c) & TAG_MASK, 0);
+
+        let ptr = c as *const _ as usize;
+        Kind {
+            ptr: unsafe {
+                NonZero::new_unchecked(ptr | CONST_TAG)
+            },
+            marker: PhantomData
+        }
+    }
+}
+
 impl<'tcx> Kind<'tcx> {
     #[inline]
     unsafe fn downcast<T>(self, tag: usize) -> Option<&'tcx T> {
@@ -93,6 +110,13 @@ impl<'tcx> Kind<'tcx> {
             self.downcast(REGION_TAG)
         }
     }
+
+    #[inline]
+    pub fn as_const(self) -> Option<&'tcx ty::Const<'tcx>> {
+        unsafe {
+            self.downcast(CONST_TAG)
+        }
+    }
 }
 
 impl<'tcx> fmt::Debug for Kind<'tcx> {
@@ -101,6 +125,8 @@ impl<'tcx> fmt::Debug for Kind<'tcx> {
             write!(f, "{:?}", ty)
         } else if let Some(r) = self.as_region() {
             write!(f, "{:?}", r)
+        } else if let Some(c) = self.as_const() {
+            write!(f, "{:?}", c)
         } else {
             write!(f, "<unknown @ {:p}>", self.ptr.get() as *const ())
         }
@@ -113,6 +139,8 @@ impl<'tcx> TypeFoldable<'tcx> for Kind<'tcx> {
             Kind::from(ty.fold_with(folder))
         } else if let Some(r) = self.as_region() {
             Kind::from(r.fold_with(folder))
+        } else if let Some(c) = self.as_const() {
+            Kind::from(c.fold_with(folder))
         } else {
             bug!()
         }
@@ -123,6 +151,8 @@ impl<'tcx> TypeFoldable<'tcx> for Kind<'tcx> {
             ty.visit_with(visitor)
         } else if let Some(r) = self.as_region() {
             r.visit_with(visitor)
+        } else if let Some(c) = self.as_const() {
+            c.visit_with(visitor)
         } else {
             bug!()
         }
@@ -140,6 +170,10 @@ impl<'tcx> Encodable for Kind<'tcx> {
                 e.emit_enum_variant("Region", REGION_TAG, 1, |e| {
                     e.emit_enum_variant_arg(0, |e| r.encode(e))
                 })
+            } else if let Some(c) = self.as_const() {
+                e.emit_enum_variant("Const", CONST_TAG, 1, |e| {
+                    e.emit_enum_variant_arg(0, |e| c.encode(e))
+                })
             } else {
                 bug!()
             }
@@ -150,10 +184,11 @@ impl<'tcx> Encodable for Kind<'tcx> {
 impl<'tcx> Decodable for Kind<'tcx> {
     fn decode<D: Decoder>(d: &mut D) -> Result<Kind<'tcx>, D::Error> {
         d.read_enum("Kind", |d| {
-            d.read_enum_variant(&["Ty", "Region"], |d, tag| {
+            d.read_enum_variant(&["Ty", "Region", "Const"], |d, tag| {
                 match tag {
                     TYPE_TAG => Ty::decode(d).map(Kind::from),
                     REGION_TAG => Region::decode(d).map(Kind::from),
+                    CONST_TAG => <&'tcx ty::Const<'tcx>>::decode(d).map(Kind::from),
                     _ => Err(d.error("invalid Kind tag"))
                 }
             })
@@ -170,64 +205,73 @@ impl<'a, 'gcx, 'tcx> Substs<'tcx> {
                              -> &'tcx Substs<'tcx> {
         Substs::for_item(tcx, def_id, |def, _| {
             tcx.mk_region(ty::ReEarlyBound(def.to_early_bound_region_data()))
-        }, |def, _| tcx.mk_param_from_def(def))
+        }, |def, _| tcx.mk_param_from_def(def),
+           |def, _| tcx.mk_const_param_from_def(def))
     }
 
     /// Creates a Substs for generic parameter definitions,
-    /// by calling closures to obtain each region and type.
+    /// by calling closures to obtain each region, type and const.
     /// The closures get to observe the Substs as they're
     /// being built, which can be used to correctly
     /// substitute defaults of type parameters.
-    pub fn for_item<FR, FT>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
-                            def_id: DefId,
-                            mut mk_region: FR,
-                            mut mk_type: FT)
-                            -> &'tcx Substs<'tcx>
+    pub fn for_item<FR, FT, FC>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
+                                def_id: DefId,
+                                mut mk_region: FR,
+                                mut mk_type: FT,
+                                mut mk_const: FC)
+                                -> &'tcx Substs<'tcx>
     where FR: FnMut(&ty::RegionParameterDef, &[Kind<'tcx>]) -> ty::Region<'tcx>,
-          FT: FnMut(&ty::TypeParameterDef, &[Kind<'tcx>]) -> Ty<'tcx> {
+          FT: FnMut(&ty::TypeParameterDef, &[Kind<'tcx>]) -> Ty<'tcx>,
+          FC: FnMut(&ty::ConstParameterDef, &[Kind<'tcx>]) -> &'tcx ty::Const<'tcx> {
         let defs = tcx.generics_of(def_id);
         let mut substs = Vec::with_capacity(defs.count());
-        Substs::fill_item(&mut substs, tcx, defs, &mut mk_region, &mut mk_type);
+        Substs::fill_item(&mut substs, tcx, defs, &mut mk_region, &mut mk_type, &mut mk_const);
         tcx.intern_substs(&substs)
     }
 
-    pub fn extend_to<FR, FT>(&self,
-                             tcx: TyCtxt<'a, 'gcx, 'tcx>,
-                             def_id: DefId,
-                             mut mk_region: FR,
-                             mut mk_type: FT)
-                             -> &'tcx Substs<'tcx>
+    pub fn extend_to<FR, FT, FC>(&self,
+                                 tcx: TyCtxt<'a, 'gcx, 'tcx>,
+                                 def_id: DefId,
+                                 mut mk_region: FR,
+                                 mut mk_type: FT,
+                                 mut mk_const: FC)
+                                 -> &'tcx Substs<'tcx>
     where FR: FnMut(&ty::RegionParameterDef, &[Kind<'tcx>]) -> ty::Region<'tcx>,
-          FT: FnMut(&ty::TypeParameterDef, &[Kind<'tcx>]) -> Ty<'tcx>
+          FT: FnMut(&ty::TypeParameterDef, &[Kind<'tcx>]) -> Ty<'tcx>,
+          FC: FnMut(&ty::ConstParameterDef, &[Kind<'tcx>]) -> &'tcx ty::Const<'tcx>
     {
         let defs = tcx.generics_of(def_id);
         let mut result = Vec::with_capacity(defs.count());
         result.extend(self[..].iter().cloned());
-        Substs::fill_single(&mut result, defs, &mut mk_region, &mut mk_type);
+        Substs::fill_single(&mut result, defs, &mut mk_region, &mut mk_type, &mut mk_const);
         tcx.intern_substs(&result)
     }
 
-    fn fill_item<FR, FT>(substs: &mut Vec<Kind<'tcx>>,
-                         tcx: TyCtxt<'a, 'gcx, 'tcx>,
-                         defs: &ty::Generics,
-                         mk_region: &mut FR,
-                         mk_type: &mut FT)
+    fn fill_item<FR, FT, FC>(substs: &mut Vec<Kind<'tcx>>,
+                             tcx: TyCtxt<'a, 'gcx, 'tcx>,
+                             defs: &ty::Generics,
+                             mk_region: &mut FR,
+                             mk_type: &mut FT,
+                             mk_const: &mut FC)
     where FR: FnMut(&ty::RegionParameterDef, &[Kind<'tcx>]) -> ty::Region<'tcx>,
-          FT: FnMut(&ty::TypeParameterDef, &[Kind<'tcx>]) -> Ty<'tcx> {
+          FT: FnMut(&ty::TypeParameterDef, &[Kind<'tcx>]) -> Ty<'tcx>,
+          FC: FnMut(&ty::ConstParameterDef, &[Kind<'tcx>]) -> &'tcx ty::Const<'tcx> {
 
         if let Some(def_id) = defs.parent {
             let parent_defs = tcx.generics_of(def_id);
-            Substs::fill_item(substs, tcx, parent_defs, mk_region, mk_type);
+            Substs::fill_item(substs, tcx, parent_defs, mk_region, mk_type, mk_const);
         }
-        Substs::fill_single(substs, defs, mk_region, mk_type)
+        Substs::fill_single(substs, defs, mk_region, mk_type, mk_const)
     }
 
-    fn fill_single<FR, FT>(substs: &mut Vec<Kind<'tcx>>,
-                           defs: &ty::Generics,
-                           mk_region: &mut FR,
-                           mk_type: &mut FT)
+    fn fill_single<FR, FT, FC>(substs: &mut Vec<Kind<'tcx>>,
+                               defs: &ty::Generics,
+                               mk_region: &mut FR,
+                               mk_type: &mut FT,
+                               mk_const: &mut FC)
     where FR: FnMut(&ty::RegionParameterDef, &[Kind<'tcx>]) -> ty::Region<'tcx>,
-          FT: FnMut(&ty::TypeParameterDef, &[Kind<'tcx>]) -> Ty<'tcx> {
+          FT: FnMut(&ty::TypeParameterDef, &[Kind<'tcx>]) -> Ty<'tcx>,
+          FC: FnMut(&ty::ConstParameterDef, &[Kind<'tcx>]) -> &'tcx ty::Const<'tcx> {
         // Handle Self first, before all regions.
         let mut types = defs.types.iter();
         if defs.parent.is_none() && defs.has_self {
@@ -248,6 +292,12 @@ impl<'a, 'gcx, 'tcx> Substs<'tcx> {
             assert_eq!(def.index as usize, substs.len());
             substs.push(Kind::from(ty));
         }
+
+        for def in &defs.consts {
+            let c = mk_const(def, substs);
+            assert_eq!(def.index as usize, substs.len());
+            substs.push(Kind::from(c));
+        }
     }
 
     pub fn is_noop(&self) -> bool {
@@ -264,6 +314,11 @@ impl<'a, 'gcx, 'tcx> Substs<'tcx> {
         self.iter().filter_map(|k| k.as_region())
     }
 
+    #[inline]
+    pub fn consts(&'a self) -> impl DoubleEndedIterator<Item=&'tcx ty::Const<'tcx>> + 'a {
+        self.iter().filter_map(|k| k.as_const())
+    }
+
     #[inline]
     pub fn type_at(&self, i: usize) -> Ty<'tcx> {
         self[i].as_type().unwrap_or_else(|| {
@@ -278,6 +333,13 @@ impl<'a, 'gcx, 'tcx> Substs<'tcx> {
         })
     }
 
+    #[inline]
+    pub fn const_at(&self, i: usize) -> &'tcx ty::Const<'tcx> {
+        self[i].as_const().unwrap_or_else(|| {
+            bug!("expected const for param #{} in {:?}", i, self);
+        })
+    }
+
     #[inline]
     pub fn type_for_def(&self, ty_param_def: &ty::TypeParameterDef) -> Ty<'tcx> {
         self.type_at(ty_param_def.index as usize)
@@ -288,6 +350,11 @@ impl<'a, 'gcx, 'tcx> Substs<'tcx> {
         self.region_at(def.index as usize)
     }
 
+    #[inline]
+    pub fn const_for_def(&self, def: &ty::ConstParameterDef) -> &'tcx ty::Const<'tcx> {
+        self.const_at(def.index as usize)
+    }
+
     /// Transform from substitutions for a child of `source_ancestor`
     /// (e.g. a trait or impl) to substitutions for the same child
     /// in a different item, with `target_substs` as the base for
@@ -452,9 +519,41 @@ impl<'a, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for SubstFolder<'a, 'gcx, 'tcx> {
 
         return t1;
     }
+
+    fn fold_const(&mut self, c: &'tcx ty::Const<'tcx>) -> &'tcx ty::Const<'tcx> {
+        if !c.needs_subst() {
+            return c;
+        }
+
+        if let ConstVal::Param(p) = c.val {
+            self.const_for_param(p, c)
+        } else {
+            c.super_fold_with(self)
+        }
+    }
 }
 
 impl<'a, 'gcx, 'tcx> SubstFolder<'a, 'gcx, 'tcx> {
+    fn const_for_param(&self, p: ty::ParamConst, source_const: &'tcx ty::Const<'tcx>)
+                       -> &'tcx ty::Const<'tcx> {
+        // Look up the const in the substitutions. It really should be in there.
+        match self.substs.get(p.idx as usize).and_then(|k| k.as_const()) {
+            Some(c) => c,
+            None => {
+                let span = self.span.unwrap_or(DUMMY_SP);
+                span_bug!(
+                    span,
+                    "Const parameter `{:?}` ({:?}/{}) out of range \
+                         when substituting (root type={:?}) substs={:?}",
+                    p,
+                    source_const,
+                    p.idx,
+                    self.root_ty,
+                    self.substs);
+            }
+        }
+    }
+
     fn ty_for_param(&self, p: ty::ParamTy, source_ty: Ty<'tcx>) -> Ty<'tcx> {
         // Look up the type in the substitutions. It really should be in there.
         let opt_ty = self.substs.get(p.idx as usize)
@@ -34,6 +34,10 @@ pub struct TraitDef {
 
     pub has_default_impl: bool,
 
+    /// If `true`, this is a trait alias (`trait Foo = Bar + Baz;`), which
+    /// has no items of its own and holds for any type satisfying its bounds.
+    pub is_alias: bool,
+
     /// The ICH of this trait's DefPath, cached here so it doesn't have to be
     /// recomputed all the time.
     pub def_path_hash: DefPathHash,
@@ -50,6 +54,7 @@ impl<'a, 'gcx, 'tcx> TraitDef {
                unsafety: hir::Unsafety,
                paren_sugar: bool,
                has_default_impl: bool,
+               is_alias: bool,
                def_path_hash: DefPathHash)
                -> TraitDef {
         TraitDef {
@@ -57,6 +62,7 @@ impl<'a, 'gcx, 'tcx> TraitDef {
             paren_sugar,
             unsafety,
             has_default_impl,
+            is_alias,
             def_path_hash,
         }
     }
@@ -214,6 +214,24 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
             let cause = self.cause(traits::ProjectionWf(data));
             self.out.push(traits::Obligation::new(cause, self.param_env, predicate));
         }
+
+        // For a generic associated type, the arguments for its own
+        // parameters must be WF, and its where-clauses and bounds must hold.
+        let own_substs = &data.substs[trait_ref.substs.len()..];
+        if !own_substs.is_empty() {
+            let obligations = self.nominal_own_obligations(data.item_def_id, data.substs);
+            self.out.extend(obligations);
+
+            let cause = self.cause(traits::MiscObligation);
+            let param_env = self.param_env;
+            self.out.extend(
+                own_substs.iter()
+                          .filter_map(|kind| kind.as_type())
+                          .filter(|ty| !ty.has_escaping_regions())
+                          .map(|ty| traits::Obligation::new(cause.clone(),
+                                                            param_env,
+                                                            ty::Predicate::WellFormed(ty))));
+        }
     }
 
     /// Pushes the obligations required for a constant value to be WF
@@ -228,7 +246,9 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
             ConstVal::Bool(_) |
             ConstVal::Char(_) |
             ConstVal::Variant(_) |
-            ConstVal::Function(..) => {}
+            ConstVal::Function(..) |
+            ConstVal::Param(_) |
+            ConstVal::Infer(_) => {}
             ConstVal::Aggregate(ConstAggregate::Struct(fields)) => {
                 for &(_, v) in fields {
                     self.compute_const(v);
@@ -284,7 +304,8 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
                 ty::TyError |
                 ty::TyStr |
                 ty::TyNever |
-                ty::TyParam(_) => {
+                ty::TyParam(_) |
+                ty::TyForeign(..) => {
                     // WfScalar, WfParameter, etc
                 }
 
@@ -436,6 +457,26 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
                   .collect()
     }
 
+    /// Like `nominal_obligations`, but only for the predicates declared on
+    /// `def_id` itself and not on its parent.
+    fn nominal_own_obligations(&mut self,
+                               def_id: DefId,
+                               substs: &Substs<'tcx>)
+                               -> Vec<traits::PredicateObligation<'tcx>>
+    {
+        let predicates =
+            self.infcx.tcx.predicates_of(def_id)
+                          .instantiate_own(self.infcx.tcx, substs);
+        let cause = self.cause(traits::ItemObligation(def_id));
+        predicates.predicates
+                  .into_iter()
+                  .map(|pred| traits::Obligation::new(cause.clone(),
+                                                      self.param_env,
+                                                      pred))
+                  .filter(|pred| !pred.has_escaping_regions())
+                  .collect()
+    }
+
     fn from_object_ty(&mut self, ty: Ty<'tcx>,
                       data: ty::Binder<&'tcx ty::Slice<ty::ExistentialPredicate<'tcx>>>,
                       region: ty::Region<'tcx>) {
@@ -14,7 +14,7 @@ use middle::const_val::ConstVal;
 use middle::region::{self, BlockRemainder};
 use ty::subst::{self, Subst};
 use ty::{BrAnon, BrEnv, BrFresh, BrNamed};
-use ty::{TyBool, TyChar, TyAdt};
+use ty::{TyBool, TyChar, TyAdt, TyForeign};
 use ty::{TyError, TyStr, TyArray, TySlice, TyFloat, TyFnDef, TyFnPtr};
 use ty::{TyParam, TyRawPtr, TyRef, TyNever, TyTuple};
 use ty::{TyClosure, TyGenerator, TyProjection, TyAnon};
@@ -84,6 +84,7 @@ pub fn parameterized(f: &mut fmt::Formatter,
     let mut has_self = false;
     let mut num_regions = 0;
     let mut num_types = 0;
+    let mut num_consts = 0;
     let mut is_value_path = false;
     let fn_trait_kind = ty::tls::with(|tcx| {
         // Unfortunately, some kinds of items (e.g., closures) don't have
@@ -124,6 +125,7 @@ pub fn parameterized(f: &mut fmt::Formatter,
             generics = tcx.generics_of(def_id);
             num_regions = generics.regions.len();
             num_types = generics.types.len();
+            num_consts = generics.consts.len();
 
             if has_self {
                 write!(f, "<{} as ", substs.type_at(0))?;
@@ -140,6 +142,7 @@ pub fn parameterized(f: &mut fmt::Formatter,
                 // Types and traits.
                 num_regions = generics.regions.len();
                 num_types = generics.types.len();
+                num_consts = generics.consts.len();
             }
         }
 
@@ -221,6 +224,11 @@ pub fn parameterized(f: &mut fmt::Formatter,
         write!(f, "{}", ty)?;
     }
 
+    for ct in substs.consts().take(num_consts) {
+        start_or_continue(f, "<", ", ")?;
+        write!(f, "{}", ct)?;
+    }
+
     for projection in projections {
         start_or_continue(f, "<", ", ")?;
         ty::tls::with(|tcx|
@@ -252,6 +260,11 @@ pub fn parameterized(f: &mut fmt::Formatter,
             write!(f, "{}", ty)?;
         }
 
+        for ct in substs.consts().skip(num_consts) {
+            start_or_continue(f, "::<", ", ")?;
+            write!(f, "{}", ct)?;
+        }
+
         start_or_continue(f, "", ">")?;
     }
 
@@ -347,6 +360,15 @@ impl fmt::Debug for ty::TypeParameterDef {
     }
 }
 
+impl fmt::Debug for ty::ConstParameterDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ConstParameterDef({}, {:?}, {})",
+               self.name,
+               self.def_id,
+               self.index)
+    }
+}
+
 impl fmt::Debug for ty::RegionParameterDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "RegionParameterDef({}, {:?}, {})",
@@ -619,6 +641,12 @@ impl fmt::Debug for ty::FloatVid {
     }
 }
 
+impl<'tcx> fmt::Debug for ty::ConstVid<'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "_#{}c", self.index)
+    }
+}
+
 impl fmt::Debug for ty::RegionVid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "'_#{}r", self.index)
@@ -644,6 +672,15 @@ impl fmt::Debug for ty::InferTy {
     }
 }
 
+impl<'tcx> fmt::Debug for ty::InferConst<'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ty::InferConst::Var(ref v) => v.fmt(f),
+            ty::InferConst::Fresh(v) => write!(f, "FreshConst({:?})", v),
+        }
+    }
+}
+
 impl fmt::Debug for ty::IntVarValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -822,6 +859,7 @@ impl<'tcx> fmt::Display for ty::TypeVariants<'tcx> {
                     Ok(())
                 })
             }
+            TyForeign(def_id) => parameterized(f, subst::Substs::empty(), def_id, &[]),
             TyStr => write!(f, "str"),
             TyGenerator(did, substs, interior) => ty::tls::with(|tcx| {
                 let upvar_tys = substs.upvar_tys(did, tcx);
@@ -900,6 +938,12 @@ impl<'tcx> fmt::Display for ty::TypeVariants<'tcx> {
                     ConstVal::Unevaluated(_def_id, substs) => {
                         write!(f, "<unevaluated{:?}>", &substs[..])?;
                     }
+                    ConstVal::Param(param) => {
+                        write!(f, "{}", param)?;
+                    }
+                    ConstVal::Infer(_) => {
+                        write!(f, "_")?;
+                    }
                     _ => {
                         write!(f, "{:?}", sz)?;
                     }
@@ -962,6 +1006,32 @@ impl fmt::Debug for ty::ParamTy {
     }
 }
 
+impl fmt::Display for ty::ParamConst {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl fmt::Debug for ty::ParamConst {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/#{}", self, self.idx)
+    }
+}
+
+impl<'tcx> fmt::Display for ty::Const<'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.val {
+            ConstVal::Integral(ConstInt::Usize(n)) => write!(f, "{}", n),
+            ConstVal::Integral(n) => write!(f, "{}", n),
+            ConstVal::Bool(b) => write!(f, "{}", b),
+            ConstVal::Char(c) => write!(f, "{:?}", c),
+            ConstVal::Param(param) => write!(f, "{}", param),
+            ConstVal::Infer(_) => write!(f, "_"),
+            ref val => write!(f, "{:?}", val),
+        }
+    }
+}
+
 impl<'tcx, T, U> fmt::Display for ty::OutlivesPredicate<T,U>
     where T: fmt::Display, U: fmt::Display
 {
@@ -1016,12 +1086,38 @@ impl<'tcx> fmt::Display for ty::ProjectionTy<'tcx> {
         // FIXME(tschottdorf): use something like
         //   parameterized(f, self.substs, self.item_def_id, &[])
         // (which currently ICEs).
-        let (trait_ref, item_name) = ty::tls::with(|tcx|
-            (self.trait_ref(tcx), tcx.associated_item(self.item_def_id).name)
-        );
+        let (trait_def_id, num_trait_params, item_name) = ty::tls::with(|tcx| {
+            let trait_def_id = tcx.associated_item(self.item_def_id).container.id();
+            (trait_def_id,
+             tcx.generics_of(trait_def_id).count(),
+             tcx.associated_item(self.item_def_id).name)
+        });
+        // The trait reference only prints the trait's own parameters, so
+        // there is no need to truncate the substs of a projection of a
+        // generic associated type here.
+        let trait_ref = ty::TraitRef {
+            def_id: trait_def_id,
+            substs: self.substs,
+        };
         write!(f, "{:?}::{}",
                trait_ref,
-               item_name)
+               item_name)?;
+
+        let own_params = &self.substs[num_trait_params..];
+        for (i, kind) in own_params.iter().enumerate() {
+            write!(f, "{}", if i == 0 { "<" } else { ", " })?;
+            if let Some(ty) = kind.as_type() {
+                write!(f, "{}", ty)?;
+            } else if let Some(r) = kind.as_region() {
+                write!(f, "{}", r)?;
+            } else if let Some(c) = kind.as_const() {
+                write!(f, "{}", c)?;
+            }
+        }
+        if !own_params.is_empty() {
+            write!(f, ">")?;
+        }
+        Ok(())
     }
 }
 
@@ -12,7 +12,7 @@ use rustc::middle::allocator::AllocatorKind;
 use rustc_errors;
 use syntax::abi::Abi;
 use syntax::ast::{Crate, Attribute, LitKind, StrStyle, ExprKind};
-use syntax::ast::{Unsafety, Constness, Generics, Mutability, Ty, Mac, Arg};
+use syntax::ast::{Unsafety, Constness, IsAsync, Generics, Mutability, Ty, Mac, Arg};
 use syntax::ast::{self, Ident, Item, ItemKind, TyKind, Visibility, Expr};
 use syntax::attr;
 use syntax::codemap::dummy_spanned;
@@ -144,6 +144,7 @@ impl<'a> AllocFnFactory<'a> {
         let kind = ItemKind::Fn(self.cx.fn_decl(abi_args, output_ty),
                                 Unsafety::Unsafe,
                                 dummy_spanned(Constness::NotConst),
+                                IsAsync::NotAsync,
                                 Abi::Rust,
                                 Generics::default(),
                                 self.cx.block_expr(output_expr));
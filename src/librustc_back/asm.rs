@@ -0,0 +1,231 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The registers and register classes which operands of checked inline
+//! assembly may be placed in, per target architecture.
+//!
+//! Type checking uses these tables to validate the operands of `asm!` and
+//! translation uses them to build the LLVM constraint strings.
+
+/// The kinds of values a register class can hold.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AsmValueKind {
+    /// Integers, raw pointers, references and function pointers.
+    Int,
+    /// `f32` and `f64`.
+    Float,
+    /// SIMD vectors.
+    Vector,
+}
+
+pub struct AsmRegClass {
+    pub name: &'static str,
+    /// The LLVM constraint code allocating any register of the class.
+    pub constraint: &'static str,
+    /// The registers of the class which may be named explicitly, the first
+    /// name of each entry is the one passed to LLVM, the others are aliases.
+    pub regs: &'static [&'static [&'static str]],
+    pub kinds: &'static [AsmValueKind],
+    /// The size in bits of the largest value the class can hold, or `None`
+    /// for the pointer width of the target.
+    pub max_bits: Option<u64>,
+}
+
+pub struct AsmArch {
+    pub classes: &'static [AsmRegClass],
+    /// Registers which are used by the compiler itself and can therefore
+    /// neither be used for operands nor be clobbered, with the reason why.
+    pub reserved: &'static [(&'static str, &'static str)],
+}
+
+impl AsmArch {
+    pub fn class(&self, name: &str) -> Option<&'static AsmRegClass> {
+        self.classes.iter().find(|class| class.name == name)
+    }
+
+    /// Looks up an explicitly named register, returning its class and its
+    /// canonical name.
+    pub fn reg(&self, name: &str) -> Option<(&'static AsmRegClass, &'static str)> {
+        for class in self.classes {
+            for names in class.regs {
+                if names.contains(&name) {
+                    return Some((class, names[0]));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn reserved_reason(&self, name: &str) -> Option<&'static str> {
+        self.reserved.iter().find(|&&(reg, _)| reg == name).map(|&(_, reason)| reason)
+    }
+}
+
+/// Returns the register tables for the architecture `arch` (as in the `arch`
+/// field of the target specification), or `None` if checked inline assembly
+/// isn't supported there.
+pub fn arch(arch: &str) -> Option<&'static AsmArch> {
+    match arch {
+        "x86" => Some(&X86),
+        "x86_64" => Some(&X86_64),
+        "arm" => Some(&ARM),
+        "aarch64" => Some(&AARCH64),
+        _ => None,
+    }
+}
+
+use self::AsmValueKind::*;
+
+static X86: AsmArch = AsmArch {
+    classes: &[
+        AsmRegClass {
+            name: "reg",
+            constraint: "r",
+            regs: &[&["eax", "ax", "al"], &["ebx", "bx", "bl"], &["ecx", "cx", "cl"],
+                    &["edx", "dx", "dl"], &["esi", "si"], &["edi", "di"]],
+            kinds: &[Int],
+            max_bits: None,
+        },
+        AsmRegClass {
+            name: "reg_abcd",
+            constraint: "Q",
+            regs: &[],
+            kinds: &[Int],
+            max_bits: None,
+        },
+        AsmRegClass {
+            name: "xmm_reg",
+            constraint: "x",
+            regs: &[&["xmm0"], &["xmm1"], &["xmm2"], &["xmm3"],
+                    &["xmm4"], &["xmm5"], &["xmm6"], &["xmm7"]],
+            kinds: &[Int, Float, Vector],
+            max_bits: Some(128),
+        },
+    ],
+    reserved: &[
+        ("esp", "the stack pointer cannot be used as an operand for inline asm"),
+        ("ebp", "the frame pointer cannot be used as an operand for inline asm"),
+        ("eip", "the instruction pointer cannot be used as an operand for inline asm"),
+    ],
+};
+
+static X86_64: AsmArch = AsmArch {
+    classes: &[
+        AsmRegClass {
+            name: "reg",
+            constraint: "r",
+            regs: &[&["rax", "eax", "ax", "al"], &["rcx", "ecx", "cx", "cl"],
+                    &["rdx", "edx", "dx", "dl"], &["rsi", "esi", "si", "sil"],
+                    &["rdi", "edi", "di", "dil"], &["r8", "r8d", "r8w", "r8b"],
+                    &["r9", "r9d", "r9w", "r9b"], &["r10", "r10d", "r10w", "r10b"],
+                    &["r11", "r11d", "r11w", "r11b"], &["r12", "r12d", "r12w", "r12b"],
+                    &["r13", "r13d", "r13w", "r13b"], &["r14", "r14d", "r14w", "r14b"],
+                    &["r15", "r15d", "r15w", "r15b"]],
+            kinds: &[Int],
+            max_bits: None,
+        },
+        AsmRegClass {
+            name: "reg_abcd",
+            constraint: "Q",
+            regs: &[],
+            kinds: &[Int],
+            max_bits: None,
+        },
+        AsmRegClass {
+            name: "xmm_reg",
+            constraint: "x",
+            regs: &[&["xmm0"], &["xmm1"], &["xmm2"], &["xmm3"],
+                    &["xmm4"], &["xmm5"], &["xmm6"], &["xmm7"],
+                    &["xmm8"], &["xmm9"], &["xmm10"], &["xmm11"],
+                    &["xmm12"], &["xmm13"], &["xmm14"], &["xmm15"]],
+            kinds: &[Int, Float, Vector],
+            max_bits: Some(128),
+        },
+    ],
+    reserved: &[
+        ("rsp", "the stack pointer cannot be used as an operand for inline asm"),
+        ("esp", "the stack pointer cannot be used as an operand for inline asm"),
+        ("rbp", "the frame pointer cannot be used as an operand for inline asm"),
+        ("ebp", "the frame pointer cannot be used as an operand for inline asm"),
+        ("rbx", "rbx is used internally by LLVM and cannot be used as an operand for inline asm"),
+        ("ebx", "rbx is used internally by LLVM and cannot be used as an operand for inline asm"),
+        ("rip", "the instruction pointer cannot be used as an operand for inline asm"),
+    ],
+};
+
+static ARM: AsmArch = AsmArch {
+    classes: &[
+        AsmRegClass {
+            name: "reg",
+            constraint: "r",
+            regs: &[&["r0", "a1"], &["r1", "a2"], &["r2", "a3"], &["r3", "a4"],
+                    &["r4", "v1"], &["r5", "v2"], &["r8", "v5"], &["r10", "sl"],
+                    &["r12", "ip"], &["r14", "lr"]],
+            kinds: &[Int],
+            max_bits: Some(32),
+        },
+        AsmRegClass {
+            name: "dreg",
+            constraint: "w",
+            regs: &[&["d0"], &["d1"], &["d2"], &["d3"], &["d4"], &["d5"], &["d6"], &["d7"],
+                    &["d8"], &["d9"], &["d10"], &["d11"], &["d12"], &["d13"], &["d14"], &["d15"]],
+            kinds: &[Int, Float, Vector],
+            max_bits: Some(64),
+        },
+    ],
+    reserved: &[
+        ("r6", "r6 is used internally by LLVM and cannot be used as an operand for inline asm"),
+        ("r7", "the frame pointer cannot be used as an operand for inline asm"),
+        ("r9", "r9 is reserved by some platforms and cannot be used as an operand for inline asm"),
+        ("r11", "the frame pointer cannot be used as an operand for inline asm"),
+        ("r13", "the stack pointer cannot be used as an operand for inline asm"),
+        ("sp", "the stack pointer cannot be used as an operand for inline asm"),
+        ("r15", "the program counter cannot be used as an operand for inline asm"),
+        ("pc", "the program counter cannot be used as an operand for inline asm"),
+    ],
+};
+
+static AARCH64: AsmArch = AsmArch {
+    classes: &[
+        AsmRegClass {
+            name: "reg",
+            constraint: "r",
+            regs: &[&["x0", "w0"], &["x1", "w1"], &["x2", "w2"], &["x3", "w3"],
+                    &["x4", "w4"], &["x5", "w5"], &["x6", "w6"], &["x7", "w7"],
+                    &["x8", "w8"], &["x9", "w9"], &["x10", "w10"], &["x11", "w11"],
+                    &["x12", "w12"], &["x13", "w13"], &["x14", "w14"], &["x15", "w15"],
+                    &["x16", "w16"], &["x17", "w17"], &["x20", "w20"], &["x21", "w21"],
+                    &["x22", "w22"], &["x23", "w23"], &["x24", "w24"], &["x25", "w25"],
+                    &["x26", "w26"], &["x27", "w27"], &["x28", "w28"], &["x30", "lr"]],
+            kinds: &[Int],
+            max_bits: Some(64),
+        },
+        AsmRegClass {
+            name: "vreg",
+            constraint: "w",
+            regs: &[&["v0"], &["v1"], &["v2"], &["v3"], &["v4"], &["v5"], &["v6"], &["v7"],
+                    &["v8"], &["v9"], &["v10"], &["v11"], &["v12"], &["v13"], &["v14"], &["v15"],
+                    &["v16"], &["v17"], &["v18"], &["v19"], &["v20"], &["v21"], &["v22"],
+                    &["v23"], &["v24"], &["v25"], &["v26"], &["v27"], &["v28"], &["v29"],
+                    &["v30"], &["v31"]],
+            kinds: &[Int, Float, Vector],
+            max_bits: Some(128),
+        },
+    ],
+    reserved: &[
+        ("x18", "x18 is reserved by some platforms and cannot be used as an operand for \
+                 inline asm"),
+        ("x19", "x19 is used internally by LLVM and cannot be used as an operand for inline asm"),
+        ("x29", "the frame pointer cannot be used as an operand for inline asm"),
+        ("fp", "the frame pointer cannot be used as an operand for inline asm"),
+        ("sp", "the stack pointer cannot be used as an operand for inline asm"),
+        ("xzr", "the zero register cannot be used as an operand for inline asm"),
+    ],
+};
@@ -39,6 +39,7 @@ extern crate serialize;
 
 extern crate serialize as rustc_serialize; // used by deriving
 
+pub mod asm;
 pub mod tempdir;
 pub mod target;
 pub mod slice;
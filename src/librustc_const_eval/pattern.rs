@@ -118,7 +118,9 @@ fn print_const_val(value: &ConstVal, f: &mut fmt::Formatter) -> fmt::Result {
         ConstVal::Variant(_) |
         ConstVal::Function(..) |
         ConstVal::Aggregate(_) |
-        ConstVal::Unevaluated(..) => bug!("{:?} not printable in a pattern", value)
+        ConstVal::Unevaluated(..) |
+        ConstVal::Param(_) |
+        ConstVal::Infer(_) => bug!("{:?} not printable in a pattern", value)
     }
 }
 
@@ -301,6 +303,42 @@ impl<'a, 'tcx> PatternContext<'a, 'tcx> {
     }
 
     pub fn lower_pattern(&mut self, pat: &'tcx hir::Pat) -> Pattern<'tcx> {
+        // When implicit dereferences have been inserted in this pattern, the
+        // unadjusted lowered pattern has the type that results *after*
+        // dereferencing. For example, in this code:
+        //
+        // ```
+        // match &&Some(0i32) {
+        //     Some(n) => { ... },
+        //     _ => { ... },
+        // }
+        // ```
+        //
+        // the type assigned to `Some(n)` in `unadjusted_pat` would be
+        // `Option<i32>` (this is determined in `rustc_typeck::check::_match`),
+        // and the adjustments would be `vec![&&Option<i32>, &Option<i32>]`.
+        //
+        // Applying the adjustments, we instead output `&&Some(n)` as the HAIR
+        // pattern, by wrapping the unadjusted pattern in `PatternKind::Deref`
+        // while consuming the adjustments in reverse order, so that the
+        // outermost `Deref` gets the least-dereferenced type.
+        let unadjusted_pat = self.lower_pattern_unadjusted(pat);
+        match self.tables.pat_adjustments().get(pat.hir_id) {
+            None => unadjusted_pat,
+            Some(adjustments) => {
+                adjustments.iter().rev().fold(unadjusted_pat, |pat, ref_ty| {
+                    debug!("{:?}: wrapping pattern with type {:?}", pat, ref_ty);
+                    Pattern {
+                        span: pat.span,
+                        ty: *ref_ty,
+                        kind: Box::new(PatternKind::Deref { subpattern: pat }),
+                    }
+                })
+            }
+        }
+    }
+
+    fn lower_pattern_unadjusted(&mut self, pat: &'tcx hir::Pat) -> Pattern<'tcx> {
         let mut ty = self.tables.node_id_to_type(pat.hir_id);
 
         let kind = match pat.node {
@@ -789,7 +789,8 @@ pub fn phase_2_configure_and_expand<F>(sess: &Session,
                                          &mut resolver,
                                          sess.opts.test,
                                          krate,
-                                         sess.diagnostic())
+                                         sess.diagnostic(),
+                                         &sess.features.borrow())
     });
 
     // If we're in rustdoc we're always compiling as an rlib, but that'll trip a
@@ -1028,6 +1029,7 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
 
     passes.push_pass(MIR_OPTIMIZED, mir::transform::generator::StateTransform);
     passes.push_pass(MIR_OPTIMIZED, mir::transform::add_call_guards::CriticalCallEdges);
+    passes.push_pass(MIR_OPTIMIZED, mir::transform::instrument_coverage::InstrumentCoverage);
     passes.push_pass(MIR_OPTIMIZED, mir::transform::dump_mir::Marker("PreTrans"));
 
     TyCtxt::create_and_enter(sess,
@@ -673,7 +673,7 @@ impl fold::Folder for ReplaceBodyWithLoop {
     fn fold_item_kind(&mut self, i: ast::ItemKind) -> ast::ItemKind {
         let is_const = match i {
             ast::ItemKind::Static(..) | ast::ItemKind::Const(..) => true,
-            ast::ItemKind::Fn(ref decl, _, ref constness, _, _, _) =>
+            ast::ItemKind::Fn(ref decl, _, ref constness, _, _, _, _) =>
                 constness.node == ast::Constness::Const || Self::should_ignore_fn(decl),
             _ => false,
         };
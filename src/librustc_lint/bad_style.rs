@@ -116,7 +116,8 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NonCamelCaseTypes {
             hir::ItemEnum(..) |
             hir::ItemStruct(..) |
             hir::ItemUnion(..) => self.check_case(cx, "type", it.name, it.span),
-            hir::ItemTrait(..) => self.check_case(cx, "trait", it.name, it.span),
+            hir::ItemTrait(..) |
+            hir::ItemTraitAlias(..) => self.check_case(cx, "trait", it.name, it.span),
             _ => (),
         }
     }
@@ -116,7 +116,8 @@ fn main() {
                                 "asmparser",
                                 "mcjit",
                                 "interpreter",
-                                "instrumentation"];
+                                "instrumentation",
+                                "coverage"];
 
     let components = output(Command::new(&llvm_config).arg("--components"));
     let mut components = components.split_whitespace().collect::<Vec<_>>();
@@ -166,6 +167,7 @@ fn main() {
     cfg.file("../rustllvm/PassWrapper.cpp")
        .file("../rustllvm/RustWrapper.cpp")
        .file("../rustllvm/ArchiveWrapper.cpp")
+       .file("../rustllvm/CoverageMappingWrapper.cpp")
        .cpp(true)
        .cpp_link_stdlib(None) // we handle this below
        .compile("librustllvm.a");
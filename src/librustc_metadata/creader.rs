@@ -843,7 +843,9 @@ impl<'a> CrateLoader<'a> {
     }
 
     fn inject_profiler_runtime(&mut self) {
-        if self.sess.opts.debugging_opts.profile {
+        if self.sess.opts.debugging_opts.profile ||
+           self.sess.opts.debugging_opts.instrument_coverage ||
+           self.sess.opts.cg.profile_generate.enabled() {
             info!("loading profiler");
 
             let symbol = Symbol::intern("profiler_builtins");
@@ -433,16 +433,18 @@ impl<'tcx> EntryKind<'tcx> {
             EntryKind::ForeignImmStatic => Def::Static(did, false),
             EntryKind::MutStatic |
             EntryKind::ForeignMutStatic => Def::Static(did, true),
-            EntryKind::Struct(_, _) => Def::Struct(did),
-            EntryKind::Union(_, _) => Def::Union(did),
+            EntryKind::Struct(..) => Def::Struct(did),
+            EntryKind::Union(..) => Def::Union(did),
             EntryKind::Fn(_) |
             EntryKind::ForeignFn(_) => Def::Fn(did),
             EntryKind::Method(_) => Def::Method(did),
             EntryKind::Type => Def::TyAlias(did),
+            EntryKind::ForeignType => Def::TyForeign(did),
             EntryKind::AssociatedType(_) => Def::AssociatedTy(did),
             EntryKind::Mod(_) => Def::Mod(did),
             EntryKind::Variant(_) => Def::Variant(did),
             EntryKind::Trait(_) => Def::Trait(did),
+            EntryKind::TraitAlias(_) => Def::TraitAlias(did),
             EntryKind::Enum(..) => Def::Enum(did),
             EntryKind::MacroDef(_) => Def::Macro(did, MacroKind::Bang),
             EntryKind::GlobalAsm => Def::GlobalAsm(did),
@@ -513,6 +515,14 @@ impl<'a, 'tcx> CrateMetadata {
     pub fn get_trait_def(&self, item_id: DefIndex) -> ty::TraitDef {
         let data = match self.entry(item_id).kind {
             EntryKind::Trait(data) => data.decode(self),
+            EntryKind::TraitAlias(_) => {
+                return ty::TraitDef::new(self.local_def_id(item_id),
+                                         hir::Unsafety::Normal,
+                                         false,
+                                         false,
+                                         true,
+                                         self.def_path_table.def_path_hash(item_id));
+            }
             _ => bug!(),
         };
 
@@ -520,14 +530,15 @@ impl<'a, 'tcx> CrateMetadata {
                           data.unsafety,
                           data.paren_sugar,
                           data.has_default_impl,
+                          false,
                           self.def_path_table.def_path_hash(item_id))
     }
 
     fn get_variant(&self, item: &Entry, index: DefIndex) -> ty::VariantDef {
         let data = match item.kind {
             EntryKind::Variant(data) |
-            EntryKind::Struct(data, _) |
-            EntryKind::Union(data, _) => data.decode(self),
+            EntryKind::Struct(data, ..) |
+            EntryKind::Union(data, ..) => data.decode(self),
             _ => bug!(),
         };
 
@@ -554,9 +565,9 @@ impl<'a, 'tcx> CrateMetadata {
         let item = self.entry(item_id);
         let did = self.local_def_id(item_id);
         let kind = match item.kind {
-            EntryKind::Enum(_) => ty::AdtKind::Enum,
-            EntryKind::Struct(_, _) => ty::AdtKind::Struct,
-            EntryKind::Union(_, _) => ty::AdtKind::Union,
+            EntryKind::Enum(..) => ty::AdtKind::Enum,
+            EntryKind::Struct(..) => ty::AdtKind::Struct,
+            EntryKind::Union(..) => ty::AdtKind::Union,
             _ => bug!("get_adt_def called on a non-ADT {:?}", did),
         };
         let variants = if let ty::AdtKind::Enum = kind {
@@ -569,14 +580,18 @@ impl<'a, 'tcx> CrateMetadata {
         } else {
             vec![self.get_variant(&item, item_id)]
         };
-        let (kind, repr) = match item.kind {
-            EntryKind::Enum(repr) => (ty::AdtKind::Enum, repr),
-            EntryKind::Struct(_, repr) => (ty::AdtKind::Struct, repr),
-            EntryKind::Union(_, repr) => (ty::AdtKind::Union, repr),
+        let (kind, repr, non_exhaustive) = match item.kind {
+            EntryKind::Enum(repr, non_exhaustive) => (ty::AdtKind::Enum, repr, non_exhaustive),
+            EntryKind::Struct(_, repr, non_exhaustive) => {
+                (ty::AdtKind::Struct, repr, non_exhaustive)
+            }
+            EntryKind::Union(_, repr, non_exhaustive) => {
+                (ty::AdtKind::Union, repr, non_exhaustive)
+            }
             _ => bug!("get_adt_def called on a non-ADT {:?}", did),
         };
 
-        tcx.alloc_adt_def(did, kind, variants, repr)
+        tcx.alloc_adt_def(did, kind, variants, repr, non_exhaustive)
     }
 
     pub fn get_predicates(&self,
@@ -592,6 +607,9 @@ impl<'a, 'tcx> CrateMetadata {
                                 -> ty::GenericPredicates<'tcx> {
         match self.entry(item_id).kind {
             EntryKind::Trait(data) => data.decode(self).super_predicates.decode((self, tcx)),
+            EntryKind::TraitAlias(data) => {
+                data.decode(self).super_predicates.decode((self, tcx))
+            }
             _ => bug!(),
         }
     }
@@ -829,8 +847,18 @@ impl<'a, 'tcx> CrateMetadata {
     pub fn get_associated_item(&self, id: DefIndex) -> ty::AssociatedItem {
         let item = self.entry(id);
         let def_key = self.def_key(id);
-        let parent = self.local_def_id(def_key.parent.unwrap());
-        let name = def_key.disambiguated_data.data.get_opt_name().unwrap();
+        let mut parent = def_key.parent.unwrap();
+        let name = match def_key.disambiguated_data.data.get_opt_name() {
+            Some(name) => Symbol::intern(&name),
+            None => {
+                // The anonymous associated type standing for the `impl Trait`
+                // return type of a method; it is nested inside the method.
+                let method = parent;
+                parent = self.def_key(method).parent.unwrap();
+                ty::impl_trait_assoc_name(&self.item_name(method))
+            }
+        };
+        let parent = self.local_def_id(parent);
 
         let (kind, container, has_self) = match item.kind {
             EntryKind::AssociatedConst(container, _) => {
@@ -847,7 +875,7 @@ impl<'a, 'tcx> CrateMetadata {
         };
 
         ty::AssociatedItem {
-            name: Symbol::intern(&name),
+            name,
             kind,
             vis: item.visibility.decode(self),
             defaultness: container.defaultness(),
@@ -863,8 +891,8 @@ impl<'a, 'tcx> CrateMetadata {
 
     pub fn get_ctor_kind(&self, node_id: DefIndex) -> CtorKind {
         match self.entry(node_id).kind {
-            EntryKind::Struct(data, _) |
-            EntryKind::Union(data, _) |
+            EntryKind::Struct(data, ..) |
+            EntryKind::Union(data, ..) |
             EntryKind::Variant(data) => data.decode(self).ctor_kind,
             _ => CtorKind::Fictive,
         }
@@ -872,7 +900,7 @@ impl<'a, 'tcx> CrateMetadata {
 
     pub fn get_struct_ctor_def_id(&self, node_id: DefIndex) -> Option<DefId> {
         match self.entry(node_id).kind {
-            EntryKind::Struct(data, _) => {
+            EntryKind::Struct(data, ..) => {
                 data.decode(self).struct_ctor.map(|index| self.local_def_id(index))
             }
             _ => None,
@@ -1077,7 +1105,7 @@ impl<'a, 'tcx> CrateMetadata {
             EntryKind::ForeignFn(data) => data.decode(self).sig,
             EntryKind::Method(data) => data.decode(self).fn_data.sig,
             EntryKind::Variant(data) |
-            EntryKind::Struct(data, _) => data.decode(self).ctor_sig.unwrap(),
+            EntryKind::Struct(data, ..) => data.decode(self).ctor_sig.unwrap(),
             EntryKind::Closure(data) => data.decode(self).sig,
             _ => bug!(),
         };
@@ -648,10 +648,17 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
             }
         }
 
+        // The constructor of a `#[non_exhaustive]` struct cannot be used
+        // outside of the defining crate.
+        let adt_def = tcx.adt_def(adt_def_id);
+        if adt_def.is_non_exhaustive() && ctor_vis == ty::Visibility::Public {
+            ctor_vis = ty::Visibility::Restricted(DefId::local(CRATE_DEF_INDEX));
+        }
+
         let repr_options = get_repr_options(&tcx, adt_def_id);
 
         Entry {
-            kind: EntryKind::Struct(self.lazy(&data), repr_options),
+            kind: EntryKind::Struct(self.lazy(&data), repr_options, adt_def.is_non_exhaustive()),
             visibility: self.lazy(&ctor_vis),
             span: self.lazy(&tcx.def_span(def_id)),
             attributes: LazySeq::empty(),
@@ -817,8 +824,8 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
             (Some(body), true)
         } else if let hir::ImplItemKind::Method(ref sig, body) = ast_item.node {
             let generics = self.tcx.generics_of(def_id);
-            let types = generics.parent_types as usize + generics.types.len();
-            let needs_inline = types > 0 || attr::requests_inline(&ast_item.attrs);
+            let needs_inline = generics.requires_monomorphization() ||
+                               attr::requests_inline(&ast_item.attrs);
             let is_const_fn = sig.constness == hir::Constness::Const;
             let ast = if is_const_fn { Some(body) } else { None };
             let always_encode_mir = self.tcx.sess.opts.debugging_opts.always_encode_mir;
@@ -928,7 +935,10 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
             hir::ItemForeignMod(_) => EntryKind::ForeignMod,
             hir::ItemGlobalAsm(..) => EntryKind::GlobalAsm,
             hir::ItemTy(..) => EntryKind::Type,
-            hir::ItemEnum(..) => EntryKind::Enum(get_repr_options(&tcx, def_id)),
+            hir::ItemEnum(..) => {
+                EntryKind::Enum(get_repr_options(&tcx, def_id),
+                                tcx.adt_def(def_id).is_non_exhaustive())
+            }
             hir::ItemStruct(ref struct_def, _) => {
                 let variant = tcx.adt_def(def_id).struct_variant();
 
@@ -948,7 +958,7 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                     discr: variant.discr,
                     struct_ctor,
                     ctor_sig: None,
-                }), repr_options)
+                }), repr_options, tcx.adt_def(def_id).is_non_exhaustive())
             }
             hir::ItemUnion(..) => {
                 let variant = tcx.adt_def(def_id).struct_variant();
@@ -959,7 +969,7 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                     discr: variant.discr,
                     struct_ctor: None,
                     ctor_sig: None,
-                }), repr_options)
+                }), repr_options, tcx.adt_def(def_id).is_non_exhaustive())
             }
             hir::ItemDefaultImpl(..) => {
                 let data = ImplData {
@@ -1018,6 +1028,13 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
 
                 EntryKind::Trait(self.lazy(&data))
             }
+            hir::ItemTraitAlias(..) => {
+                let data = TraitAliasData {
+                    super_predicates: self.lazy(&tcx.super_predicates_of(def_id)),
+                };
+
+                EntryKind::TraitAlias(self.lazy(&data))
+            }
             hir::ItemExternCrate(_) |
             hir::ItemUse(..) => bug!("cannot encode info for item {:?}", item),
         };
@@ -1088,7 +1105,8 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                 hir::ItemStruct(..) |
                 hir::ItemUnion(..) |
                 hir::ItemImpl(..) |
-                hir::ItemTrait(..) => Some(self.encode_generics(def_id)),
+                hir::ItemTrait(..) |
+                hir::ItemTraitAlias(..) => Some(self.encode_generics(def_id)),
                 _ => None,
             },
             predicates: match item.node {
@@ -1100,7 +1118,8 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                 hir::ItemStruct(..) |
                 hir::ItemUnion(..) |
                 hir::ItemImpl(..) |
-                hir::ItemTrait(..) => Some(self.encode_predicates(def_id)),
+                hir::ItemTrait(..) |
+                hir::ItemTraitAlias(..) => Some(self.encode_predicates(def_id)),
                 _ => None,
             },
 
@@ -1117,7 +1136,7 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                 }
                 hir::ItemConst(..) => self.encode_optimized_mir(def_id),
                 hir::ItemFn(_, _, constness, _, ref generics, _) => {
-                    let tps_len = generics.ty_params.len();
+                    let tps_len = generics.ty_params.len() + generics.const_params.len();
                     let needs_inline = tps_len > 0 || attr::requests_inline(&item.attrs);
                     let always_encode_mir = self.tcx.sess.opts.debugging_opts.always_encode_mir;
                     if needs_inline || constness == hir::Constness::Const || always_encode_mir {
@@ -1186,6 +1205,65 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
         }
     }
 
+    fn encode_info_for_const_param(&mut self, def_id: DefId) -> Entry<'tcx> {
+        debug!("IsolatedEncoder::encode_info_for_const_param({:?})", def_id);
+        let tcx = self.tcx;
+        Entry {
+            kind: EntryKind::Type,
+            visibility: self.lazy(&ty::Visibility::Public),
+            span: self.lazy(&tcx.def_span(def_id)),
+            attributes: LazySeq::empty(),
+            children: LazySeq::empty(),
+            stability: None,
+            deprecation: None,
+
+            ty: Some(self.encode_item_type(def_id)),
+            inherent_impls: LazySeq::empty(),
+            variances: LazySeq::empty(),
+            generics: None,
+            predicates: None,
+
+            ast: None,
+            mir: None,
+        }
+    }
+
+    fn encode_info_for_impl_trait_assoc(&mut self, def_id: DefId) -> Entry<'tcx> {
+        debug!("IsolatedEncoder::encode_info_for_impl_trait_assoc({:?})", def_id);
+        let tcx = self.tcx;
+        let assoc_item = tcx.associated_item(def_id);
+
+        let container = match (assoc_item.container, assoc_item.defaultness) {
+            (ty::TraitContainer(_), _) => AssociatedContainer::TraitRequired,
+            (ty::ImplContainer(_), hir::Defaultness::Final) => AssociatedContainer::ImplFinal,
+            (ty::ImplContainer(_), _) => AssociatedContainer::ImplDefault,
+        };
+
+        Entry {
+            kind: EntryKind::AssociatedType(container),
+            visibility: self.lazy(&assoc_item.vis),
+            span: self.lazy(&tcx.def_span(def_id)),
+            attributes: LazySeq::empty(),
+            children: LazySeq::empty(),
+            stability: None,
+            deprecation: None,
+
+            // In an impl this is also an anonymized type, whose
+            // underlying type is revealed after type-checking.
+            ty: match assoc_item.container {
+                ty::TraitContainer(_) => None,
+                ty::ImplContainer(_) => Some(self.encode_item_type(def_id)),
+            },
+            inherent_impls: LazySeq::empty(),
+            variances: LazySeq::empty(),
+            generics: Some(self.encode_generics(def_id)),
+            predicates: Some(self.encode_predicates(def_id)),
+
+            ast: None,
+            mir: None,
+        }
+    }
+
     fn encode_info_for_anon_ty(&mut self, def_id: DefId) -> Entry<'tcx> {
         debug!("IsolatedEncoder::encode_info_for_anon_ty({:?})", def_id);
         let tcx = self.tcx;
@@ -1419,6 +1497,7 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
             }
             hir::ForeignItemStatic(_, true) => EntryKind::ForeignMutStatic,
             hir::ForeignItemStatic(_, false) => EntryKind::ForeignImmStatic,
+            hir::ForeignItemType => EntryKind::ForeignType,
         };
 
         Entry {
@@ -1431,7 +1510,10 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
             deprecation: self.encode_deprecation(def_id),
 
             ty: Some(self.encode_item_type(def_id)),
-            inherent_impls: LazySeq::empty(),
+            inherent_impls: match nitem.node {
+                hir::ForeignItemType => self.encode_inherent_implementations(def_id),
+                _ => LazySeq::empty(),
+            },
             variances: match nitem.node {
                 hir::ForeignItemFn(..) => self.encode_variances_of(def_id),
                 _ => LazySeq::empty(),
@@ -1493,6 +1575,17 @@ impl<'a, 'b, 'tcx> Visitor<'tcx> for EncodeVisitor<'a, 'b, 'tcx> {
         intravisit::walk_ty(self, ty);
         self.index.encode_info_for_ty(ty);
     }
+    fn visit_path_parameters(&mut self,
+                             path_span: syntax_pos::Span,
+                             parameters: &'tcx hir::PathParameters) {
+        intravisit::walk_path_parameters(self, path_span, parameters);
+        for arg in &parameters.consts {
+            if let hir::ConstArg::Expr(body) = *arg {
+                let def_id = self.index.tcx.hir.body_owner_def_id(body);
+                self.index.record(def_id, IsolatedEncoder::encode_info_for_embedded_const, def_id);
+            }
+        }
+    }
     fn visit_macro_def(&mut self, macro_def: &'tcx hir::MacroDef) {
         let def_id = self.index.tcx.hir.local_def_id(macro_def.id);
         self.index.record(def_id, IsolatedEncoder::encode_info_for_macro_def, macro_def);
@@ -1517,13 +1610,26 @@ impl<'a, 'b, 'tcx> IndexBuilder<'a, 'b, 'tcx> {
             let has_default = Untracked(ty_param.default.is_some());
             self.record(def_id, IsolatedEncoder::encode_info_for_ty_param, (def_id, has_default));
         }
+        for const_param in &generics.const_params {
+            let def_id = self.tcx.hir.local_def_id(const_param.id);
+            self.record(def_id, IsolatedEncoder::encode_info_for_const_param, def_id);
+        }
     }
 
     fn encode_info_for_ty(&mut self, ty: &hir::Ty) {
         match ty.node {
-            hir::TyImplTrait(_) => {
+            hir::TyImplTraitExistential(_) => {
+                let def_id = self.tcx.hir.local_def_id(ty.id);
+                // Associated types are recorded along with their trait or impl.
+                if !self.tcx.is_impl_trait_assoc(def_id) {
+                    self.record(def_id, IsolatedEncoder::encode_info_for_anon_ty, def_id);
+                }
+            }
+            hir::TyImplTraitUniversal(..) => {
                 let def_id = self.tcx.hir.local_def_id(ty.id);
-                self.record(def_id, IsolatedEncoder::encode_info_for_anon_ty, def_id);
+                self.record(def_id,
+                            IsolatedEncoder::encode_info_for_ty_param,
+                            (def_id, Untracked(false)));
             }
             hir::TyArray(_, len) => {
                 let def_id = self.tcx.hir.body_owner_def_id(len);
@@ -1559,6 +1665,7 @@ impl<'a, 'b, 'tcx> IndexBuilder<'a, 'b, 'tcx> {
             hir::ItemExternCrate(..) |
             hir::ItemUse(..) |
             hir::ItemDefaultImpl(..) |
+            hir::ItemTraitAlias(..) |
             hir::ItemTy(..) => {
                 // no sub-item recording needed in these cases
             }
@@ -1588,16 +1695,28 @@ impl<'a, 'b, 'tcx> IndexBuilder<'a, 'b, 'tcx> {
             }
             hir::ItemImpl(..) => {
                 for &trait_item_def_id in self.tcx.associated_item_def_ids(def_id).iter() {
-                    self.record(trait_item_def_id,
-                                IsolatedEncoder::encode_info_for_impl_item,
-                                trait_item_def_id);
+                    if self.tcx.is_impl_trait_assoc(trait_item_def_id) {
+                        self.record(trait_item_def_id,
+                                    IsolatedEncoder::encode_info_for_impl_trait_assoc,
+                                    trait_item_def_id);
+                    } else {
+                        self.record(trait_item_def_id,
+                                    IsolatedEncoder::encode_info_for_impl_item,
+                                    trait_item_def_id);
+                    }
                 }
             }
             hir::ItemTrait(..) => {
                 for &item_def_id in self.tcx.associated_item_def_ids(def_id).iter() {
-                    self.record(item_def_id,
-                                IsolatedEncoder::encode_info_for_trait_item,
-                                item_def_id);
+                    if self.tcx.is_impl_trait_assoc(item_def_id) {
+                        self.record(item_def_id,
+                                    IsolatedEncoder::encode_info_for_impl_trait_assoc,
+                                    item_def_id);
+                    } else {
+                        self.record(item_def_id,
+                                    IsolatedEncoder::encode_info_for_trait_item,
+                                    item_def_id);
+                    }
                 }
             }
         }
@@ -291,13 +291,15 @@ pub enum EntryKind<'tcx> {
     ForeignImmStatic,
     ForeignMutStatic,
     ForeignMod,
+    ForeignType,
     GlobalAsm,
     Type,
-    Enum(ReprOptions),
+    // The `bool` of ADTs records whether they are `#[non_exhaustive]`.
+    Enum(ReprOptions, bool),
     Field,
     Variant(Lazy<VariantData<'tcx>>),
-    Struct(Lazy<VariantData<'tcx>>, ReprOptions),
-    Union(Lazy<VariantData<'tcx>>, ReprOptions),
+    Struct(Lazy<VariantData<'tcx>>, ReprOptions, bool),
+    Union(Lazy<VariantData<'tcx>>, ReprOptions, bool),
     Fn(Lazy<FnData<'tcx>>),
     ForeignFn(Lazy<FnData<'tcx>>),
     Mod(Lazy<ModData>),
@@ -305,6 +307,7 @@ pub enum EntryKind<'tcx> {
     Closure(Lazy<ClosureData<'tcx>>),
     Generator(Lazy<GeneratorData<'tcx>>),
     Trait(Lazy<TraitData<'tcx>>),
+    TraitAlias(Lazy<TraitAliasData<'tcx>>),
     Impl(Lazy<ImplData<'tcx>>),
     DefaultImpl(Lazy<ImplData<'tcx>>),
     Method(Lazy<MethodData<'tcx>>),
@@ -323,6 +326,7 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for EntryK
             EntryKind::ForeignImmStatic |
             EntryKind::ForeignMutStatic |
             EntryKind::ForeignMod       |
+            EntryKind::ForeignType      |
             EntryKind::GlobalAsm        |
             EntryKind::Field |
             EntryKind::Type => {
@@ -331,16 +335,18 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for EntryK
             EntryKind::Const(qualif) => {
                 qualif.hash_stable(hcx, hasher);
             }
-            EntryKind::Enum(ref repr_options) => {
+            EntryKind::Enum(ref repr_options, non_exhaustive) => {
                 repr_options.hash_stable(hcx, hasher);
+                non_exhaustive.hash_stable(hcx, hasher);
             }
             EntryKind::Variant(ref variant_data) => {
                 variant_data.hash_stable(hcx, hasher);
             }
-            EntryKind::Struct(ref variant_data, ref repr_options) |
-            EntryKind::Union(ref variant_data, ref repr_options)  => {
+            EntryKind::Struct(ref variant_data, ref repr_options, non_exhaustive) |
+            EntryKind::Union(ref variant_data, ref repr_options, non_exhaustive)  => {
                 variant_data.hash_stable(hcx, hasher);
                 repr_options.hash_stable(hcx, hasher);
+                non_exhaustive.hash_stable(hcx, hasher);
             }
             EntryKind::Fn(ref fn_data) |
             EntryKind::ForeignFn(ref fn_data) => {
@@ -361,6 +367,9 @@ impl<'a, 'gcx, 'tcx> HashStable<StableHashingContext<'a, 'gcx, 'tcx>> for EntryK
             EntryKind::Trait(ref trait_data) => {
                 trait_data.hash_stable(hcx, hasher);
             }
+            EntryKind::TraitAlias(ref trait_alias_data) => {
+                trait_alias_data.hash_stable(hcx, hasher);
+            }
             EntryKind::DefaultImpl(ref impl_data) |
             EntryKind::Impl(ref impl_data) => {
                 impl_data.hash_stable(hcx, hasher);
@@ -439,6 +448,15 @@ impl_stable_hash_for!(struct TraitData<'tcx> {
     super_predicates
 });
 
+#[derive(RustcEncodable, RustcDecodable)]
+pub struct TraitAliasData<'tcx> {
+    pub super_predicates: Lazy<ty::GenericPredicates<'tcx>>,
+}
+
+impl_stable_hash_for!(struct TraitAliasData<'tcx> {
+    super_predicates
+});
+
 #[derive(RustcEncodable, RustcDecodable)]
 pub struct ImplData<'tcx> {
     pub polarity: hir::ImplPolarity,
@@ -207,6 +207,7 @@ impl<'c, 'b, 'a: 'b+'c, 'gcx, 'tcx: 'a> DataflowResultsConsumer<'b, 'gcx>
                 // flow_state already handled).
             }
             StatementKind::Nop |
+            StatementKind::Coverage { .. } |
             StatementKind::Validate(..) |
             StatementKind::StorageLive(..) => {
                 // ignored by borrowck
@@ -146,6 +146,7 @@ impl<'a, 'tcx> BitDenotation for Borrows<'a, 'tcx> {
             mir::StatementKind::StorageLive(..) |
             mir::StatementKind::StorageDead(..) |
             mir::StatementKind::Validate(..) |
+            mir::StatementKind::Coverage { .. } |
             mir::StatementKind::Nop => {}
 
         }
@@ -217,6 +217,7 @@ impl<'a, 'tcx> MoveDataBuilder<'a, 'tcx> {
             StatementKind::InlineAsm { .. } |
             StatementKind::EndRegion(_) |
             StatementKind::Validate(..) |
+            StatementKind::Coverage { .. } |
             StatementKind::Nop => {}
         }
     }
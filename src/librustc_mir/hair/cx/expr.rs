@@ -673,6 +673,18 @@ fn convert_path_expr<'a, 'gcx, 'tcx>(cx: &mut Cx<'a, 'gcx, 'tcx>,
 
         Def::Local(..) | Def::Upvar(..) => convert_var(cx, expr, def),
 
+        Def::ConstParam(def_id) => {
+            let node_id = cx.tcx.hir.as_local_node_id(def_id).unwrap();
+            let item_def_id = cx.tcx.hir.local_def_id(cx.tcx.hir.get_parent_node(node_id));
+            let generics = cx.tcx.generics_of(item_def_id);
+            let param = generics.consts.iter().find(|param| param.def_id == def_id).unwrap();
+            ExprKind::Literal {
+                literal: Literal::Value {
+                    value: cx.tcx.mk_const_param_from_def(param),
+                },
+            }
+        }
+
         _ => span_bug!(expr.span, "def `{:?}` not yet implemented", def),
     }
 }
@@ -402,7 +402,8 @@ impl<'a, 'tcx> CloneShimBuilder<'a, 'tcx> {
             tcx,
             self.def_id,
             |_, _| tcx.types.re_erased,
-            |_, _| ty
+            |_, _| ty,
+            |_, _| bug!("`Clone::clone` has no const parameters")
         );
 
         // `func == Clone::clone(&ty) -> ty`
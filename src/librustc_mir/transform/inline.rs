@@ -248,6 +248,7 @@ impl<'a, 'tcx> Inliner<'a, 'tcx> {
                 match stmt.kind {
                     StatementKind::StorageLive(_) |
                     StatementKind::StorageDead(_) |
+                    StatementKind::Coverage { .. } |
                     StatementKind::Nop => {}
                     _ => cost += INSTR_COST
                 }
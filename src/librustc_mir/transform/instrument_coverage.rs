@@ -0,0 +1,99 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! This module provides one pass, `InstrumentCoverage`, that inserts a
+//! `Coverage` statement at the start of each basic block of a function
+//! when "-Z instrument-coverage" is passed.
+//!
+//! Every counter covers the source region spanned by the statements and
+//! the terminator of the block it was inserted into. Since each arm of a
+//! `match`, each branch of an `if` and each closure body end up in blocks
+//! of their own, this gives precise region coverage for them, unlike the
+//! line tables `-Z profile` relies on.
+//!
+//! Code expanded from macros is attributed to the macro invocation in the
+//! function body. Blocks with no span inside the body (e.g. inlined code or
+//! compiler-generated cleanup) are not counted.
+
+use rustc::mir::transform::{MirPass, MirSource};
+use rustc::mir::{Mir, Statement, StatementKind, TerminatorKind};
+use rustc::ty::TyCtxt;
+use syntax_pos::Span;
+
+pub struct InstrumentCoverage;
+
+impl MirPass for InstrumentCoverage {
+    fn run_pass<'a, 'tcx>(&self,
+                          tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                          source: MirSource,
+                          mir: &mut Mir<'tcx>) {
+        if !tcx.sess.opts.debugging_opts.instrument_coverage {
+            return
+        }
+
+        match source {
+            MirSource::Fn(_) => {}
+            // Constants and statics are evaluated at compile time, promoted
+            // rvalues are counted as part of their parent, and generator drop
+            // shims have no source of their own.
+            MirSource::Const(_) |
+            MirSource::Static(..) |
+            MirSource::Promoted(..) |
+            MirSource::GeneratorDrop(_) => return,
+        }
+
+        let body_span = mir.span;
+        let mut next_index = 0;
+        for block_data in mir.basic_blocks_mut() {
+            if block_data.is_cleanup {
+                continue
+            }
+            if let TerminatorKind::Unreachable = block_data.terminator().kind {
+                continue
+            }
+
+            let terminator_info = block_data.terminator().source_info;
+            let region = block_data.statements.iter()
+                .map(|statement| statement.source_info.span)
+                .chain(Some(terminator_info.span))
+                .filter_map(|span| span_in_body(span, body_span))
+                .fold(None, |region: Option<Span>, span| Some(match region {
+                    Some(region) => Span::new(region.lo().min(span.lo()),
+                                              region.hi().max(span.hi()),
+                                              region.ctxt()),
+                    None => span,
+                }));
+
+            if let Some(region) = region {
+                let mut source_info = terminator_info;
+                source_info.span = region;
+                block_data.statements.insert(0, Statement {
+                    source_info,
+                    kind: StatementKind::Coverage { index: next_index },
+                });
+                next_index += 1;
+            }
+        }
+    }
+}
+
+/// Walks up the macro backtrace of `span` until it lies in the same
+/// expansion as the function body, returning `None` if it never does.
+fn span_in_body(mut span: Span, body_span: Span) -> Option<Span> {
+    loop {
+        if span.ctxt() == body_span.ctxt() {
+            return if body_span.contains(span) { Some(span) } else { None };
+        }
+        span = match span.ctxt().outer().expn_info() {
+            Some(info) => info.call_site,
+            None => return None,
+        };
+    }
+}
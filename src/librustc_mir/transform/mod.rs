@@ -42,6 +42,7 @@ pub mod instcombine;
 pub mod copy_prop;
 pub mod generator;
 pub mod inline;
+pub mod instrument_coverage;
 pub mod nll;
 
 pub(crate) fn provide(providers: &mut Providers) {
@@ -426,6 +426,7 @@ impl<'a, 'gcx, 'tcx> TypeChecker<'a, 'gcx, 'tcx> {
             StatementKind::InlineAsm { .. } |
             StatementKind::EndRegion(_) |
             StatementKind::Validate(..) |
+            StatementKind::Coverage { .. } |
             StatementKind::Nop => {}
         }
     }
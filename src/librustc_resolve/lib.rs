@@ -52,9 +52,9 @@ use syntax::util::lev_distance::find_best_match_for_name;
 use syntax::visit::{self, FnKind, Visitor};
 use syntax::attr;
 use syntax::ast::{Arm, BindingMode, Block, Crate, Expr, ExprKind};
-use syntax::ast::{FnDecl, ForeignItem, ForeignItemKind, Generics};
+use syntax::ast::{FnDecl, ForeignItem, ForeignItemKind, Generics, IsAsync};
 use syntax::ast::{Item, ItemKind, ImplItem, ImplItemKind};
-use syntax::ast::{Local, Mutability, Pat, PatKind, Path};
+use syntax::ast::{Local, Mutability, Pat, PatKind, Path, PathParameters};
 use syntax::ast::{QSelf, TraitItemKind, TraitRef, Ty, TyKind};
 use syntax::feature_gate::{feature_err, emit_feature_err, GateIssue};
 
@@ -463,19 +463,21 @@ impl<'a> PathSource<'a> {
         match self {
             PathSource::Type => match def {
                 Def::Struct(..) | Def::Union(..) | Def::Enum(..) |
-                Def::Trait(..) | Def::TyAlias(..) | Def::AssociatedTy(..) |
-                Def::PrimTy(..) | Def::TyParam(..) | Def::SelfTy(..) => true,
+                Def::Trait(..) | Def::TraitAlias(..) | Def::TyAlias(..) |
+                Def::AssociatedTy(..) | Def::PrimTy(..) | Def::TyParam(..) |
+                Def::SelfTy(..) | Def::TyForeign(..) => true,
                 _ => false,
             },
             PathSource::Trait => match def {
-                Def::Trait(..) => true,
+                Def::Trait(..) | Def::TraitAlias(..) => true,
                 _ => false,
             },
             PathSource::Expr(..) => match def {
                 Def::StructCtor(_, CtorKind::Const) | Def::StructCtor(_, CtorKind::Fn) |
                 Def::VariantCtor(_, CtorKind::Const) | Def::VariantCtor(_, CtorKind::Fn) |
                 Def::Const(..) | Def::Static(..) | Def::Local(..) | Def::Upvar(..) |
-                Def::Fn(..) | Def::Method(..) | Def::AssociatedConst(..) => true,
+                Def::Fn(..) | Def::Method(..) | Def::AssociatedConst(..) |
+                Def::ConstParam(..) => true,
                 _ => false,
             },
             PathSource::Pat => match def {
@@ -641,6 +643,12 @@ impl<'a, 'tcx> Visitor<'tcx> for Resolver<'a> {
     }
     fn visit_ty(&mut self, ty: &'tcx Ty) {
         match ty.node {
+            TyKind::Path(None, ref path) if self.is_const_param_arg(path) => {
+                let ident = path.segments[0].identifier;
+                let def = self.resolve_ident_in_lexical_scope(ident, ValueNS, true, path.span)
+                              .map_or(Def::Err, |d| d.def());
+                self.record_def(ty.id, PathResolution::new(def));
+            }
             TyKind::Path(ref qself, ref path) => {
                 self.smart_resolve_path(ty.id, qself.as_ref(), path, PathSource::Type);
             }
@@ -661,6 +669,21 @@ impl<'a, 'tcx> Visitor<'tcx> for Resolver<'a> {
         }
         visit::walk_ty(self, ty);
     }
+    fn visit_path_parameters(&mut self, path_span: Span, parameters: &'tcx PathParameters) {
+        match *parameters {
+            PathParameters::AngleBracketed(ref data) => {
+                walk_list!(self, visit_ty, &data.types);
+                self.with_constant_rib(|this| {
+                    walk_list!(this, visit_expr, &data.consts);
+                });
+                walk_list!(self, visit_lifetime, &data.lifetimes);
+                walk_list!(self, visit_assoc_type_binding, &data.bindings);
+            }
+            PathParameters::Parenthesized(..) => {
+                visit::walk_path_parameters(self, path_span, parameters);
+            }
+        }
+    }
     fn visit_poly_trait_ref(&mut self,
                             tref: &'tcx ast::PolyTraitRef,
                             m: &'tcx ast::TraitBoundModifier) {
@@ -692,6 +715,7 @@ impl<'a, 'tcx> Visitor<'tcx> for Resolver<'a> {
                 HasTypeParameters(generics, ItemRibKind)
             }
             ForeignItemKind::Static(..) => NoTypeParameters,
+            ForeignItemKind::Ty => NoTypeParameters,
         };
         self.with_type_parameter_rib(type_parameters, |this| {
             visit::walk_foreign_item(this, foreign_item);
@@ -733,6 +757,15 @@ impl<'a, 'tcx> Visitor<'tcx> for Resolver<'a> {
 
         // Resolve the function body.
         match function_kind {
+            FnKind::ItemFn(_, _, _, _, IsAsync::Async { closure_id, .. }, _, _, body) => {
+                // The body of an async fn is lowered into a generator, so the
+                // arguments are captured as upvars of that closure.
+                self.ribs[ValueNS].push(Rib::new(ClosureRibKind(closure_id)));
+                self.label_ribs.push(Rib::new(ClosureRibKind(closure_id)));
+                self.visit_block(body);
+                self.label_ribs.pop();
+                self.ribs[ValueNS].pop();
+            }
             FnKind::ItemFn(.., body) |
             FnKind::Method(.., body) => {
                 self.visit_block(body);
@@ -770,6 +803,7 @@ impl<'a, 'tcx> Visitor<'tcx> for Resolver<'a> {
             // Allow all following defaults to refer to this type parameter.
             default_ban_rib.bindings.remove(&Ident::with_empty_ctxt(param.ident.name));
         }
+        for param in &generics.const_params { self.visit_ty(&param.ty); }
         for lt in &generics.lifetimes { self.visit_lifetime_def(lt); }
         for p in &generics.where_clause.predicates { self.visit_where_predicate(p); }
     }
@@ -1867,8 +1901,10 @@ impl<'a> Resolver<'a> {
                                         visit::walk_trait_item(this, trait_item)
                                     });
                                 }
-                                TraitItemKind::Type(..) => {
-                                    this.with_type_parameter_rib(NoTypeParameters, |this| {
+                                TraitItemKind::Type(ref generics, ..) => {
+                                    let type_parameters =
+                                        HasTypeParameters(generics, NormalRibKind);
+                                    this.with_type_parameter_rib(type_parameters, |this| {
                                         visit::walk_trait_item(this, trait_item)
                                     });
                                 }
@@ -1879,6 +1915,17 @@ impl<'a> Resolver<'a> {
                 });
             }
 
+            ItemKind::TraitAlias(ref generics, ref bounds) => {
+                // Create a new rib for the trait-wide type parameters.
+                self.with_type_parameter_rib(HasTypeParameters(generics, ItemRibKind), |this| {
+                    let local_def_id = this.definitions.local_def_id(item.id);
+                    this.with_self_rib(Def::SelfTy(Some(local_def_id), None), |this| {
+                        this.visit_generics(generics);
+                        walk_list!(this, visit_ty_param_bound, bounds);
+                    });
+                });
+            }
+
             ItemKind::Mod(_) | ItemKind::ForeignMod(_) => {
                 self.with_scope(item.id, |this| {
                     visit::walk_item(this, item);
@@ -1938,7 +1985,28 @@ impl<'a> Resolver<'a> {
                     function_type_rib.bindings.insert(ident, def);
                     self.record_def(type_parameter.id, PathResolution::new(def));
                 }
+
+                // Const parameters live in the value namespace.
+                let mut function_value_rib = Rib::new(rib_kind);
+                for const_parameter in &generics.const_params {
+                    let ident = const_parameter.ident.modern();
+                    debug!("with_type_parameter_rib: const {}", const_parameter.id);
+
+                    if seen_bindings.contains_key(&ident) {
+                        let span = seen_bindings.get(&ident).unwrap();
+                        let err =
+                            ResolutionError::NameAlreadyUsedInTypeParameterList(ident.name, span);
+                        resolve_error(self, const_parameter.span, err);
+                    }
+                    seen_bindings.entry(ident).or_insert(const_parameter.span);
+
+                    let def_id = self.definitions.local_def_id(const_parameter.id);
+                    let def = Def::ConstParam(def_id);
+                    function_value_rib.bindings.insert(ident, def);
+                    self.record_def(const_parameter.id, PathResolution::new(def));
+                }
                 self.ribs[TypeNS].push(function_type_rib);
+                self.ribs[ValueNS].push(function_value_rib);
             }
 
             NoTypeParameters => {
@@ -1950,6 +2018,24 @@ impl<'a> Resolver<'a> {
 
         if let HasTypeParameters(..) = type_parameters {
             self.ribs[TypeNS].pop();
+            self.ribs[ValueNS].pop();
+        }
+    }
+
+    /// Checks whether a single-segment type path actually names a const
+    /// parameter, e.g. the `N` in `Buf<N>`, which the parser can't tell
+    /// apart from a type argument.
+    fn is_const_param_arg(&mut self, path: &Path) -> bool {
+        if path.segments.len() != 1 || path.segments[0].parameters.is_some() {
+            return false;
+        }
+        let ident = path.segments[0].identifier;
+        if self.resolve_ident_in_lexical_scope(ident, TypeNS, false, path.span).is_some() {
+            return false;
+        }
+        match self.resolve_ident_in_lexical_scope(ident, ValueNS, false, path.span) {
+            Some(LexicalScopeBinding::Def(Def::ConstParam(..))) => true,
+            _ => false,
         }
     }
 
@@ -2084,7 +2170,7 @@ impl<'a> Resolver<'a> {
                                             visit::walk_impl_item(this, impl_item);
                                         });
                                     }
-                                    ImplItemKind::Type(ref ty) => {
+                                    ImplItemKind::Type(ref generics, _) => {
                                         // If this is a trait impl, ensure the type
                                         // exists in trait
                                         this.check_trait_item(impl_item.ident,
@@ -2092,7 +2178,13 @@ impl<'a> Resolver<'a> {
                                                             impl_item.span,
                                             |n, s| ResolutionError::TypeNotMemberOfTrait(n, s));
 
-                                        this.visit_ty(ty);
+                                        // The associated type may have type
+                                        // parameters of its own.
+                                        let type_parameters =
+                                            HasTypeParameters(generics, NormalRibKind);
+                                        this.with_type_parameter_rib(type_parameters, |this| {
+                                            visit::walk_impl_item(this, impl_item);
+                                        });
                                     }
                                     ImplItemKind::Macro(_) =>
                                         panic!("unexpanded macro in resolve!"),
@@ -3002,7 +3094,7 @@ impl<'a> Resolver<'a> {
                     }
                 }
             }
-            Def::TyParam(..) | Def::SelfTy(..) => {
+            Def::TyParam(..) | Def::ConstParam(..) | Def::SelfTy(..) => {
                 for rib in ribs {
                     match rib.kind {
                         NormalRibKind | MethodRibKind(_) | ClosureRibKind(..) |
@@ -263,6 +263,7 @@ impl<'l, 'tcx: 'l, 'll, O: DumpOutput + 'll> DumpVisitor<'l, 'tcx, 'll, O> {
             HirDef::Union(..) |
             HirDef::Enum(..) |
             HirDef::TyAlias(..) |
+            HirDef::TyForeign(..) |
             HirDef::Trait(_) => {
                 let span = self.span_from_span(sub_span.expect("No span found for type ref"));
                 self.dumper.dump_ref(Ref {
@@ -298,6 +299,7 @@ impl<'l, 'tcx: 'l, 'll, O: DumpOutput + 'll> DumpVisitor<'l, 'tcx, 'll, O> {
             HirDef::SelfTy(..) |
             HirDef::Label(_) |
             HirDef::TyParam(..) |
+            HirDef::ConstParam(..) |
             HirDef::Method(..) |
             HirDef::AssociatedTy(..) |
             HirDef::AssociatedConst(..) |
@@ -1010,7 +1012,7 @@ impl<'l, 'tcx: 'l, 'll, O: DumpOutput + 'll> DumpVisitor<'l, 'tcx, 'll, O> {
                                     ast::Visibility::Public,
                                     trait_item.span);
             }
-            ast::TraitItemKind::Type(ref bounds, ref default_ty) => {
+            ast::TraitItemKind::Type(ref generics, ref bounds, ref default_ty) => {
                 // FIXME do something with _bounds (for type refs)
                 let name = trait_item.ident.name.to_string();
                 let qualname = format!("::{}", self.tcx.node_path_str(trait_item.id));
@@ -1025,7 +1027,7 @@ impl<'l, 'tcx: 'l, 'll, O: DumpOutput + 'll> DumpVisitor<'l, 'tcx, 'll, O> {
                         id,
                         span,
                         name,
-                        qualname,
+                        qualname: qualname.clone(),
                         value: self.span.snippet(trait_item.span),
                         parent: Some(::id_from_def_id(trait_id)),
                         children: vec![],
@@ -1040,6 +1042,7 @@ impl<'l, 'tcx: 'l, 'll, O: DumpOutput + 'll> DumpVisitor<'l, 'tcx, 'll, O> {
                     });
                 }
 
+                self.process_generic_params(generics, trait_item.span, &qualname, trait_item.id);
                 if let &Some(ref default_ty) = default_ty {
                     self.visit_ty(default_ty)
                 }
@@ -1069,10 +1072,12 @@ impl<'l, 'tcx: 'l, 'll, O: DumpOutput + 'll> DumpVisitor<'l, 'tcx, 'll, O> {
                                     impl_item.vis.clone(),
                                     impl_item.span);
             }
-            ast::ImplItemKind::Type(ref ty) => {
+            ast::ImplItemKind::Type(ref generics, ref ty) => {
                 // FIXME uses of the assoc type should ideally point to this
                 // 'def' and the name here should be a ref to the def in the
                 // trait.
+                let qualname = format!("::{}", self.tcx.node_path_str(impl_item.id));
+                self.process_generic_params(generics, impl_item.span, &qualname, impl_item.id);
                 self.visit_ty(ty)
             }
             ast::ImplItemKind::Macro(_) => {}
@@ -1362,7 +1367,7 @@ impl<'l, 'tcx: 'l, 'll, O: DumpOutput + 'll> Visitor<'l> for DumpVisitor<'l, 'tc
                                    ty),
                 }
             }
-            ast::ExprKind::Closure(_, ref decl, ref body, _fn_decl_span) => {
+            ast::ExprKind::Closure(_, _, ref decl, ref body, _fn_decl_span) => {
                 let mut id = String::from("$");
                 id.push_str(&ex.id.to_string());
 
@@ -1536,6 +1541,7 @@ impl<'l, 'tcx: 'l, 'll, O: DumpOutput + 'll> Visitor<'l> for DumpVisitor<'l, 'tc
 
                 self.visit_ty(ty);
             }
+            ast::ForeignItemKind::Ty => {}
         }
     }
 }
@@ -22,8 +22,9 @@ use rustc::ty::Ty;
 
 use mir::lvalue::Alignment;
 
+use rustc_back;
 use std::ffi::CString;
-use syntax::ast::AsmDialect;
+use syntax::ast::{self, AsmDialect};
 use libc::{c_uint, c_char};
 
 // Take an inline assembly expression and splat it out via LLVM
@@ -59,8 +60,44 @@ pub fn trans_inline_asm<'a, 'tcx>(
         inputs = indirect_outputs;
     }
 
-    let clobbers = ia.clobbers.iter()
-                              .map(|s| format!("~{{{}}}", &s));
+    let mut clobbers = ia.clobbers.iter()
+                                  .map(|s| format!("~{{{}}}", &s))
+                                  .collect::<Vec<_>>();
+
+    // Checked inline assembly leaves the constraints to us, type checking
+    // made sure all the registers are valid.
+    let (output_constraints, input_constraints) = match ia.regs {
+        Some(ref regs) => {
+            let arch = rustc_back::asm::arch(&bcx.sess().target.target.arch)
+                .expect("checked inline assembly on an unsupported target");
+            let reg_constraint = |reg: &ast::InlineAsmOperandReg| {
+                match reg.reg {
+                    ast::InlineAsmRegOrRegClass::Reg(name) => {
+                        let (_, name) = arch.reg(&name.as_str()).unwrap();
+                        format!("{{{}}}", name)
+                    }
+                    ast::InlineAsmRegOrRegClass::RegClass(name) => {
+                        arch.class(&name.as_str()).unwrap().constraint.to_string()
+                    }
+                }
+            };
+            for &(name, _) in &regs.clobbers {
+                let (_, name) = arch.reg(&name.as_str()).unwrap();
+                clobbers.push(format!("~{{{}}}", name));
+            }
+            (regs.outputs.iter().map(|reg| {
+                // Outputs written before all inputs are read may not share a
+                // register with any of them.
+                let modifier = if reg.late { "=" } else { "=&" };
+                format!("{}{}", modifier, reg_constraint(reg))
+            }).collect::<Vec<_>>(),
+             regs.inputs.iter().map(reg_constraint).collect::<Vec<_>>())
+        }
+        None => {
+            (ia.outputs.iter().map(|out| out.constraint.to_string()).collect(),
+             ia.inputs.iter().map(|s| s.to_string()).collect())
+        }
+    };
 
     // Default per-arch clobbers
     // Basically what clang does
@@ -70,8 +107,8 @@ pub fn trans_inline_asm<'a, 'tcx>(
     };
 
     let all_constraints =
-        ia.outputs.iter().map(|out| out.constraint.to_string())
-          .chain(ia.inputs.iter().map(|s| s.to_string()))
+        output_constraints.into_iter()
+          .chain(input_constraints)
           .chain(ext_constraints)
           .chain(clobbers)
           .chain(arch_clobbers.iter().map(|s| s.to_string()))
@@ -9,9 +9,9 @@
 // except according to those terms.
 
 use back::link;
-use back::write;
-use back::symbol_export;
-use rustc::session::config;
+use back::write::{self, DiagnosticHandlers};
+use back::symbol_export::{self, SymbolExportLevel};
+use rustc::session::config::{self, Lto};
 use errors::{FatalError, Handler};
 use llvm;
 use llvm::archive_ro::ArchiveRO;
@@ -20,14 +20,17 @@ use rustc::util::common::time;
 use rustc::util::common::path2cstr;
 use rustc::hir::def_id::LOCAL_CRATE;
 use back::write::{ModuleConfig, with_llvm_pmb, CodegenContext};
+use {ModuleTranslation, ModuleLlvm, ModuleSource, ModuleKind};
 
 use libc;
 use flate2::read::DeflateDecoder;
 
-use std::io::Read;
 use std::ffi::CString;
+use std::io::Read;
 use std::path::Path;
 use std::ptr::read_unaligned;
+use std::slice;
+use std::sync::Arc;
 
 pub fn crate_type_allows_lto(crate_type: config::CrateType) -> bool {
     match crate_type {
@@ -41,33 +44,83 @@ pub fn crate_type_allows_lto(crate_type: config::CrateType) -> bool {
     }
 }
 
-pub fn run(cgcx: &CodegenContext,
-           diag_handler: &Handler,
-           llmod: ModuleRef,
-           tm: TargetMachineRef,
-           config: &ModuleConfig,
-           temp_no_opt_bc_filename: &Path) -> Result<(), FatalError> {
-    if cgcx.opts.cg.prefer_dynamic {
-        diag_handler.struct_err("cannot prefer dynamic linking when performing LTO")
-                    .note("only 'staticlib', 'bin', and 'cdylib' outputs are \
-                           supported with LTO")
-                    .emit();
-        return Err(FatalError)
+/// A module which is ready to be optimized with the LTO-specific pass
+/// pipeline and then code generated.
+pub enum LtoModuleTranslation {
+    /// The single module every other module of the crate graph was linked
+    /// into.
+    Fat(Option<ModuleTranslation>),
+
+    /// One of the modules taking part in ThinLTO, which is optimized
+    /// independently of all the other ones.
+    Thin(ThinModule),
+}
+
+impl LtoModuleTranslation {
+    pub fn name(&self) -> &str {
+        match *self {
+            LtoModuleTranslation::Fat(_) => "everything",
+            LtoModuleTranslation::Thin(ref m) => m.name(),
+        }
     }
 
-    // Make sure we actually can run LTO
-    for crate_type in cgcx.crate_types.iter() {
-        if !crate_type_allows_lto(*crate_type) {
-            let e = diag_handler.fatal("lto can only be run for executables, cdylibs and \
-                                        static library outputs");
-            return Err(e)
+    /// Optimize this module within the given codegen context, returning the
+    /// module which is then ready to be code generated.
+    pub unsafe fn optimize(&mut self,
+                           cgcx: &CodegenContext,
+                           diag_handler: &Handler,
+                           tm: TargetMachineRef,
+                           config: &ModuleConfig)
+        -> Result<ModuleTranslation, FatalError>
+    {
+        match *self {
+            LtoModuleTranslation::Fat(ref mut module) => {
+                let mtrans = module.take().expect("fat LTO module optimized twice");
+                let llmod = mtrans.llvm().expect("can't lto pretranslated module").llmod;
+                run_pass_manager(cgcx, tm, llmod, config, false);
+                Ok(mtrans)
+            }
+            LtoModuleTranslation::Thin(ref mut thin) => {
+                thin.optimize(cgcx, diag_handler, tm, config)
+            }
         }
     }
 
-    let export_threshold =
-        symbol_export::crates_export_threshold(&cgcx.crate_types);
+    /// A "gauge" of how costly it is to optimize this module, used to sort
+    /// LTO modules by how expensive they are to process.
+    pub fn cost(&self) -> u64 {
+        match *self {
+            // Only one module with fat LTO, so the cost doesn't matter.
+            LtoModuleTranslation::Fat(_) => 0,
+            LtoModuleTranslation::Thin(ref m) => m.cost(),
+        }
+    }
+}
+
+/// Performs the crate-wide part of LTO for the given (already optimized)
+/// modules of the local crate, returning the modules which are now ready to
+/// be optimized once more and then code generated.
+///
+/// For fat LTO this links the bitcode of all upstream crates into the single
+/// local module. For ThinLTO this computes the combined summary index of all
+/// modules involved, which later drives importing between them.
+pub fn run(cgcx: &CodegenContext, modules: Vec<ModuleTranslation>)
+    -> Result<Vec<LtoModuleTranslation>, FatalError>
+{
+    let diag_handler = cgcx.create_diag_handler();
+    let export_threshold = match cgcx.lto {
+        // We're just doing LTO for our one crate
+        Lto::ThinLocal => SymbolExportLevel::Rust,
+
+        // We're doing LTO for the entire crate graph
+        Lto::Fat | Lto::Thin => {
+            symbol_export::crates_export_threshold(&cgcx.crate_types)
+        }
+
+        Lto::No => panic!("didn't request LTO but we're doing LTO"),
+    };
 
-    let symbol_filter = &|&(ref name, _, level): &(String, _, _)| {
+    let symbol_filter = &|&(ref name, _, level): &(String, _, SymbolExportLevel)| {
         if symbol_export::is_below_threshold(level, export_threshold) {
             let mut bytes = Vec::with_capacity(name.len() + 1);
             bytes.extend(name.bytes());
@@ -83,91 +136,99 @@ pub fn run(cgcx: &CodegenContext,
         .filter_map(symbol_filter)
         .collect();
 
-    // For each of our upstream dependencies, find the corresponding rlib and
-    // load the bitcode from the archive. Then merge it into the current LLVM
-    // module that we've got.
-    for &(cnum, ref path) in cgcx.each_linked_rlib_for_lto.iter() {
-        symbol_white_list.extend(
-            cgcx.exported_symbols.exported_symbols(cnum)
-                                 .iter()
-                                 .filter_map(symbol_filter));
-
-        let archive = ArchiveRO::open(&path).expect("wanted an rlib");
-        let bytecodes = archive.iter().filter_map(|child| {
-            child.ok().and_then(|c| c.name().map(|name| (name, c)))
-        }).filter(|&(name, _)| name.ends_with("bytecode.deflate"));
-        for (name, data) in bytecodes {
-            let bc_encoded = data.data();
-
-            let bc_decoded = if is_versioned_bytecode_format(bc_encoded) {
-                time(cgcx.time_passes, &format!("decode {}", name), || {
-                    // Read the version
-                    let version = extract_bytecode_format_version(bc_encoded);
-
-                    if version == 1 {
-                        // The only version existing so far
-                        let data_size = extract_compressed_bytecode_size_v1(bc_encoded);
-                        let compressed_data = &bc_encoded[
-                            link::RLIB_BYTECODE_OBJECT_V1_DATA_OFFSET..
-                            (link::RLIB_BYTECODE_OBJECT_V1_DATA_OFFSET + data_size as usize)];
-
-                        let mut inflated = Vec::new();
-                        let res = DeflateDecoder::new(compressed_data)
-                            .read_to_end(&mut inflated);
-                        if res.is_err() {
-                            let msg = format!("failed to decompress bc of `{}`",
-                                              name);
-                            Err(diag_handler.fatal(&msg))
-                        } else {
-                            Ok(inflated)
-                        }
-                    } else {
-                        Err(diag_handler.fatal(&format!("Unsupported bytecode format version {}",
-                                                        version)))
-                    }
-                })?
-            } else {
-                time(cgcx.time_passes, &format!("decode {}", name), || {
-                    // the object must be in the old, pre-versioning format, so
-                    // simply inflate everything and let LLVM decide if it can
-                    // make sense of it
-                    let mut inflated = Vec::new();
-                    let res = DeflateDecoder::new(bc_encoded)
-                        .read_to_end(&mut inflated);
-                    if res.is_err() {
-                        let msg = format!("failed to decompress bc of `{}`",
-                                          name);
-                        Err(diag_handler.fatal(&msg))
-                    } else {
-                        Ok(inflated)
-                    }
-                })?
-            };
-
-            let ptr = bc_decoded.as_ptr();
-            debug!("linking {}", name);
-            time(cgcx.time_passes, &format!("ll link {}", name), || unsafe {
-                if llvm::LLVMRustLinkInExternalBitcode(llmod,
-                                                       ptr as *const libc::c_char,
-                                                       bc_decoded.len() as libc::size_t) {
-                    Ok(())
-                } else {
-                    let msg = format!("failed to load bc of `{}`", name);
-                    Err(write::llvm_err(&diag_handler, msg))
-                }
-            })?;
+    // If we're performing LTO for the entire crate graph, then for each of our
+    // upstream dependencies, find the corresponding rlib and load the bitcode
+    // from the archive.
+    //
+    // We save off all the bytecode and LLVM module ids for later processing
+    // with either fat or thin LTO
+    let mut upstream_modules = Vec::new();
+    if cgcx.lto != Lto::ThinLocal {
+        if cgcx.opts.cg.prefer_dynamic {
+            diag_handler.struct_err("cannot prefer dynamic linking when performing LTO")
+                        .note("only 'staticlib', 'bin', and 'cdylib' outputs are \
+                               supported with LTO")
+                        .emit();
+            return Err(FatalError)
+        }
+
+        // Make sure we actually can run LTO
+        for crate_type in cgcx.crate_types.iter() {
+            if !crate_type_allows_lto(*crate_type) {
+                let e = diag_handler.fatal("lto can only be run for executables, cdylibs and \
+                                            static library outputs");
+                return Err(e)
+            }
+        }
+
+        for &(cnum, ref path) in cgcx.each_linked_rlib_for_lto.iter() {
+            symbol_white_list.extend(
+                cgcx.exported_symbols.exported_symbols(cnum)
+                                     .iter()
+                                     .filter_map(symbol_filter));
+
+            let archive = ArchiveRO::open(&path).expect("wanted an rlib");
+            let bytecodes = archive.iter().filter_map(|child| {
+                child.ok().and_then(|c| c.name().map(|name| (name, c)))
+            }).filter(|&(name, _)| name.ends_with("bytecode.deflate"));
+            for (name, data) in bytecodes {
+                let bc = decode_bytecode(cgcx, &diag_handler, name, data.data())?;
+                let id = name.trim_right_matches(".bytecode.deflate").to_string();
+                upstream_modules.push((bc, CString::new(id).unwrap()));
+            }
         }
     }
 
+    let arr = symbol_white_list.iter().map(|c| c.as_ptr()).collect::<Vec<_>>();
+    match cgcx.lto {
+        Lto::Fat => {
+            fat_lto(cgcx, &diag_handler, modules, upstream_modules, &arr)
+        }
+        Lto::Thin |
+        Lto::ThinLocal => {
+            thin_lto(&diag_handler, modules, upstream_modules, &arr)
+        }
+        Lto::No => unreachable!(),
+    }
+}
+
+fn fat_lto(cgcx: &CodegenContext,
+           diag_handler: &Handler,
+           mut modules: Vec<ModuleTranslation>,
+           upstream_modules: Vec<(Vec<u8>, CString)>,
+           symbol_white_list: &[*const libc::c_char])
+    -> Result<Vec<LtoModuleTranslation>, FatalError>
+{
+    info!("going for a fat lto");
+
+    // Fat LTO is restricted to a single codegen unit, so everything is linked
+    // into that one module.
+    assert_eq!(modules.len(), 1);
+    let module = modules.pop().unwrap();
+    let llmod = module.llvm().expect("can't lto pretranslated module").llmod;
+
+    for (bc_decoded, name) in upstream_modules {
+        let ptr = bc_decoded.as_ptr();
+        let name = name.to_string_lossy();
+        debug!("linking {}", name);
+        time(cgcx.time_passes, &format!("ll link {}", name), || unsafe {
+            if llvm::LLVMRustLinkInExternalBitcode(llmod,
+                                                   ptr as *const libc::c_char,
+                                                   bc_decoded.len() as libc::size_t) {
+                Ok(())
+            } else {
+                let msg = format!("failed to load bc of `{}`", name);
+                Err(write::llvm_err(&diag_handler, msg))
+            }
+        })?;
+    }
+
     // Internalize everything but the exported symbols of the current module
-    let arr: Vec<*const libc::c_char> = symbol_white_list.iter()
-                                                         .map(|c| c.as_ptr())
-                                                         .collect();
-    let ptr = arr.as_ptr();
+    let ptr = symbol_white_list.as_ptr();
     unsafe {
         llvm::LLVMRustRunRestrictionPass(llmod,
                                          ptr as *const *const libc::c_char,
-                                         arr.len() as libc::size_t);
+                                         symbol_white_list.len() as libc::size_t);
     }
 
     if cgcx.no_landing_pads {
@@ -176,13 +237,145 @@ pub fn run(cgcx: &CodegenContext,
         }
     }
 
-    if cgcx.opts.cg.save_temps {
-        let cstr = path2cstr(temp_no_opt_bc_filename);
-        unsafe {
-            llvm::LLVMWriteBitcodeToFile(llmod, cstr.as_ptr());
+    Ok(vec![LtoModuleTranslation::Fat(Some(module))])
+}
+
+/// Prepare "thin" LTO to get run on these modules.
+///
+/// The general structure of ThinLTO is quite different from the structure of
+/// "fat" LTO above. With "fat" LTO all LLVM modules in question are merged into
+/// one giant LLVM module, and then we run more optimization passes over this
+/// big module after internalizing most symbols. Thin LTO, on the other hand,
+/// avoid this large bottleneck through more targeted optimization.
+///
+/// At a high level Thin LTO looks like:
+///
+///     1. Prepare a "summary" of each LLVM module in question which describes
+///        the values inside, cost of the values, etc.
+///     2. Merge the summaries of all modules in question into one "index"
+///     3. Perform some global analysis on this index
+///     4. For each module, use the index and analysis calculated previously to
+///        perform local transformations on the module, for example inlining
+///        small functions from other modules.
+///     5. Run thin-specific optimization passes over each module, and then code
+///        generate everything at the end.
+///
+/// The summary for each module is intended to be quite cheap, and the global
+/// index is relatively quite cheap to create as well. As a result, the goal of
+/// ThinLTO is to reduce the bottleneck on LTO and enable LTO to be used in more
+/// situations. For example one cheap optimization is that we can parallelize
+/// all codegen modules, easily making use of all the cores on a machine.
+///
+/// With all that in mind, the function here is designed at specifically just
+/// calculating the *index* for ThinLTO. This index will then be shared amongst
+/// all of the `LtoModuleTranslation` units returned below and destroyed once
+/// they all go out of scope.
+fn thin_lto(diag_handler: &Handler,
+            modules: Vec<ModuleTranslation>,
+            upstream_modules: Vec<(Vec<u8>, CString)>,
+            symbol_white_list: &[*const libc::c_char])
+    -> Result<Vec<LtoModuleTranslation>, FatalError>
+{
+    unsafe {
+        info!("going for that thin, thin LTO");
+
+        if !llvm::LLVMRustThinLTOAvailable() {
+            return Err(diag_handler.fatal("ThinLTO is not available with the \
+                                           LLVM this compiler was built against"))
         }
+
+        let mut thin_buffers = Vec::new();
+        let mut module_names = Vec::new();
+
+        // FIXME: right now we serialize all in-memory modules before working
+        // with them and ThinLTO. We really shouldn't do this, however, and
+        // instead figure out how to extract a summary from an in-memory module
+        // and then merge that into the global index. It turns out that this
+        // loop is by far the most expensive portion of this small bit of
+        // global analysis!
+        for (i, module) in modules.iter().enumerate() {
+            info!("local module: {} - {}", i, module.name);
+            let llmod = module.llvm().expect("can't lto pretranslated module").llmod;
+            thin_buffers.push(ThinBuffer::new(llmod));
+            module_names.push(CString::new(module.name.clone()).unwrap());
+        }
+
+        // The bitcode stored in rlibs doesn't carry a ThinLTO summary, so
+        // upstream modules are loaded into a scratch context and serialized
+        // once more to get one.
+        for (bc, name) in upstream_modules {
+            info!("upstream module: {:?}", name);
+            let llcx = llvm::LLVMContextCreate();
+            let llmod = llvm::LLVMRustParseBitcodeForThinLTO(llcx,
+                                                             bc.as_ptr(),
+                                                             bc.len(),
+                                                             name.as_ptr());
+            if llmod.is_null() {
+                llvm::LLVMContextDispose(llcx);
+                let msg = format!("failed to parse upstream bitcode of `{}`",
+                                  name.to_string_lossy());
+                return Err(write::llvm_err(&diag_handler, msg))
+            }
+            thin_buffers.push(ThinBuffer::new(llmod));
+            llvm::LLVMDisposeModule(llmod);
+            llvm::LLVMContextDispose(llcx);
+            module_names.push(name);
+        }
+
+        // The in-memory modules are no longer needed, everything is read back
+        // from the serialized buffers from here on out.
+        drop(modules);
+
+        let thin_modules = thin_buffers.iter().zip(module_names.iter())
+            .map(|(buffer, name)| {
+                llvm::ThinLTOModule {
+                    identifier: name.as_ptr(),
+                    data: buffer.data().as_ptr(),
+                    len: buffer.data().len(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Delegate to the C++ bindings to create some data here. Once this is
+        // a tried-and-true interface we may wish to try to upstream some of
+        // this to LLVM itself, right now we reimplement a lot of what they do
+        // upstream...
+        let data = llvm::LLVMRustCreateThinLTOData(
+            thin_modules.as_ptr(),
+            thin_modules.len() as u32,
+            symbol_white_list.as_ptr(),
+            symbol_white_list.len() as u32,
+        );
+        if data.is_null() {
+            let msg = format!("failed to prepare thin LTO context");
+            return Err(write::llvm_err(&diag_handler, msg))
+        }
+        let data = ThinData(data);
+        info!("thin LTO data created");
+
+        // Throw our data in an `Arc` as we'll be sharing it across threads. We
+        // also put all memory referenced by the C++ data (buffers, ids, etc)
+        // into the arc as well. After this we'll create a thin module
+        // translation per module in this data.
+        let shared = Arc::new(ThinShared {
+            data,
+            thin_buffers,
+            module_names,
+        });
+        Ok((0..shared.module_names.len()).map(|i| {
+            LtoModuleTranslation::Thin(ThinModule {
+                shared: shared.clone(),
+                idx: i,
+            })
+        }).collect())
     }
+}
 
+fn run_pass_manager(cgcx: &CodegenContext,
+                    tm: TargetMachineRef,
+                    llmod: ModuleRef,
+                    config: &ModuleConfig,
+                    thin: bool) {
     // Now we have one massive module inside of llmod. Time to run the
     // LTO-specific optimization passes that LLVM provides.
     //
@@ -197,9 +390,13 @@ pub fn run(cgcx: &CodegenContext,
         llvm::LLVMRustAddPass(pm, pass);
 
         with_llvm_pmb(llmod, config, &mut |b| {
-            llvm::LLVMPassManagerBuilderPopulateLTOPassManager(b, pm,
-                /* Internalize = */ False,
-                /* RunInliner = */ True);
+            if thin {
+                llvm::LLVMRustPassManagerBuilderPopulateThinLTOPassManager(b, pm);
+            } else {
+                llvm::LLVMPassManagerBuilderPopulateLTOPassManager(b, pm,
+                    /* Internalize = */ False,
+                    /* RunInliner = */ True);
+            }
         });
 
         let pass = llvm::LLVMRustFindAndCreatePass("verify\0".as_ptr() as *const _);
@@ -212,7 +409,217 @@ pub fn run(cgcx: &CodegenContext,
         llvm::LLVMDisposePassManager(pm);
     }
     debug!("lto done");
-    Ok(())
+}
+
+/// Writes the bitcode of an LTO module to `path` before the LTO passes run on
+/// it, used to implement `-C save-temps`.
+pub fn save_temp_bitcode(mtrans: &ModuleTranslation, path: &Path) {
+    if let Some(llvm) = mtrans.llvm() {
+        let cstr = path2cstr(path);
+        unsafe {
+            llvm::LLVMWriteBitcodeToFile(llvm.llmod, cstr.as_ptr());
+        }
+    }
+}
+
+struct ThinData(*mut llvm::ThinLTOData);
+
+unsafe impl Send for ThinData {}
+unsafe impl Sync for ThinData {}
+
+impl Drop for ThinData {
+    fn drop(&mut self) {
+        unsafe {
+            llvm::LLVMRustFreeThinLTOData(self.0);
+        }
+    }
+}
+
+/// An in-memory serialized LLVM module along with its ThinLTO summary.
+pub struct ThinBuffer(*mut llvm::ThinLTOBuffer);
+
+unsafe impl Send for ThinBuffer {}
+unsafe impl Sync for ThinBuffer {}
+
+impl ThinBuffer {
+    pub fn new(m: ModuleRef) -> ThinBuffer {
+        unsafe {
+            let buffer = llvm::LLVMRustThinLTOBufferCreate(m);
+            ThinBuffer(buffer)
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let ptr = llvm::LLVMRustThinLTOBufferPtr(self.0) as *const _;
+            let len = llvm::LLVMRustThinLTOBufferLen(self.0);
+            slice::from_raw_parts(ptr, len)
+        }
+    }
+}
+
+impl Drop for ThinBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            llvm::LLVMRustThinLTOBufferFree(self.0);
+        }
+    }
+}
+
+struct ThinShared {
+    data: ThinData,
+    thin_buffers: Vec<ThinBuffer>,
+    module_names: Vec<CString>,
+}
+
+/// One module taking part in ThinLTO, along with the global analysis shared
+/// between all of them.
+pub struct ThinModule {
+    shared: Arc<ThinShared>,
+    idx: usize,
+}
+
+impl ThinModule {
+    fn name(&self) -> &str {
+        self.shared.module_names[self.idx].to_str().unwrap()
+    }
+
+    fn cost(&self) -> u64 {
+        // Yes, that's correct, we're using the size of the bytecode as an
+        // indicator for how costly this codegen unit is.
+        self.data().len() as u64
+    }
+
+    fn data(&self) -> &[u8] {
+        self.shared.thin_buffers[self.idx].data()
+    }
+
+    unsafe fn optimize(&mut self,
+                       cgcx: &CodegenContext,
+                       diag_handler: &Handler,
+                       tm: TargetMachineRef,
+                       config: &ModuleConfig)
+        -> Result<ModuleTranslation, FatalError>
+    {
+        // Right now the implementation we've got only works over serialized
+        // modules, so we create a fresh new LLVM context and parse the module
+        // into that context. One day, however, we may do this for upstream
+        // crates but for locally translated modules we may be able to reuse
+        // that LLVM Context and Module.
+        let llcx = llvm::LLVMContextCreate();
+        let llmod = llvm::LLVMRustParseBitcodeForThinLTO(
+            llcx,
+            self.data().as_ptr(),
+            self.data().len(),
+            self.shared.module_names[self.idx].as_ptr(),
+        );
+        if llmod.is_null() {
+            llvm::LLVMContextDispose(llcx);
+            let msg = format!("failed to parse bitcode for thin LTO module");
+            return Err(write::llvm_err(&diag_handler, msg));
+        }
+        let mtrans = ModuleTranslation {
+            source: ModuleSource::Translated(ModuleLlvm {
+                llmod,
+                llcx,
+            }),
+            name: self.name().to_string(),
+            symbol_name_hash: 0,
+            kind: ModuleKind::Regular,
+        };
+        let _handlers = DiagnosticHandlers::new(cgcx, diag_handler, llcx);
+
+        // Like with "fat" LTO, get some better optimizations if landing pads
+        // are disabled by removing all landing pads.
+        if cgcx.no_landing_pads {
+            llvm::LLVMRustMarkAllFunctionsNounwind(llmod);
+        }
+
+        // Up next comes the per-module local analyses that we do for Thin LTO.
+        // Each of these functions is basically copied from the LLVM
+        // implementation and then tailored to suit this implementation. Ideally
+        // each of these would be supported by upstream LLVM but that's perhaps
+        // a patch for another day!
+        //
+        // You can find some more comments about these functions in the LLVM
+        // bindings we've got (currently `PassWrapper.cpp`)
+        if !llvm::LLVMRustPrepareThinLTORename(self.shared.data.0, llmod) {
+            let msg = format!("failed to prepare thin LTO module");
+            return Err(write::llvm_err(&diag_handler, msg))
+        }
+        if !llvm::LLVMRustPrepareThinLTOResolveWeak(self.shared.data.0, llmod) {
+            let msg = format!("failed to prepare thin LTO module");
+            return Err(write::llvm_err(&diag_handler, msg))
+        }
+        if !llvm::LLVMRustPrepareThinLTOInternalize(self.shared.data.0, llmod) {
+            let msg = format!("failed to prepare thin LTO module");
+            return Err(write::llvm_err(&diag_handler, msg))
+        }
+        if !llvm::LLVMRustPrepareThinLTOImport(self.shared.data.0, llmod) {
+            let msg = format!("failed to prepare thin LTO module");
+            return Err(write::llvm_err(&diag_handler, msg))
+        }
+
+        // Alright now that we've done everything related to the ThinLTO
+        // analysis it's time to run some optimizations! Here we use the same
+        // `run_pass_manager` as the "fat" LTO above except that we tell it to
+        // populate a thin-specific pass manager, which presumably LLVM treats a
+        // little differently.
+        info!("running thin lto passes over {}", mtrans.name);
+        run_pass_manager(cgcx, tm, llmod, config, true);
+
+        Ok(mtrans)
+    }
+}
+
+fn decode_bytecode(cgcx: &CodegenContext,
+                   diag_handler: &Handler,
+                   name: &str,
+                   bc_encoded: &[u8]) -> Result<Vec<u8>, FatalError> {
+    if is_versioned_bytecode_format(bc_encoded) {
+        time(cgcx.time_passes, &format!("decode {}", name), || {
+            // Read the version
+            let version = extract_bytecode_format_version(bc_encoded);
+
+            if version == 1 {
+                // The only version existing so far
+                let data_size = extract_compressed_bytecode_size_v1(bc_encoded);
+                let compressed_data = &bc_encoded[
+                    link::RLIB_BYTECODE_OBJECT_V1_DATA_OFFSET..
+                    (link::RLIB_BYTECODE_OBJECT_V1_DATA_OFFSET + data_size as usize)];
+
+                let mut inflated = Vec::new();
+                let res = DeflateDecoder::new(compressed_data)
+                    .read_to_end(&mut inflated);
+                if res.is_err() {
+                    let msg = format!("failed to decompress bc of `{}`",
+                                      name);
+                    Err(diag_handler.fatal(&msg))
+                } else {
+                    Ok(inflated)
+                }
+            } else {
+                Err(diag_handler.fatal(&format!("Unsupported bytecode format version {}",
+                                                version)))
+            }
+        })
+    } else {
+        time(cgcx.time_passes, &format!("decode {}", name), || {
+            // the object must be in the old, pre-versioning format, so
+            // simply inflate everything and let LLVM decide if it can
+            // make sense of it
+            let mut inflated = Vec::new();
+            let res = DeflateDecoder::new(bc_encoded)
+                .read_to_end(&mut inflated);
+            if res.is_err() {
+                let msg = format!("failed to decompress bc of `{}`",
+                                  name);
+                Err(diag_handler.fatal(&msg))
+            } else {
+                Ok(inflated)
+            }
+        })
+    }
 }
 
 fn is_versioned_bytecode_format(bc: &[u8]) -> bool {
@@ -16,11 +16,11 @@ use rustc_incremental::{save_trans_partition, in_incr_comp_dir};
 use rustc::dep_graph::DepGraph;
 use rustc::middle::cstore::{LinkMeta, EncodedMetadata};
 use rustc::session::config::{self, OutputFilenames, OutputType, OutputTypes, Passes, SomePasses,
-                             AllPasses, Sanitizer};
+                             AllPasses, Sanitizer, Lto, SwitchWithOptPath};
 use rustc::session::Session;
 use time_graph::{self, TimeGraph};
 use llvm;
-use llvm::{ModuleRef, TargetMachineRef, PassManagerRef, DiagnosticInfoRef};
+use llvm::{ModuleRef, TargetMachineRef, PassManagerRef, DiagnosticInfoRef, ContextRef};
 use llvm::SMDiagnosticRef;
 use {CrateTranslation, ModuleSource, ModuleTranslation, CompiledModule, ModuleKind};
 use CrateInfo;
@@ -41,7 +41,9 @@ use std::fmt;
 use std::fs;
 use std::io;
 use std::io::Write;
+use std::mem;
 use std::path::{Path, PathBuf};
+use std::ptr;
 use std::str;
 use std::sync::Arc;
 use std::sync::mpsc::{channel, Sender, Receiver};
@@ -139,6 +141,17 @@ fn get_llvm_opt_size(optimize: config::OptLevel) -> llvm::CodeGenOptSize {
 }
 
 pub fn create_target_machine(sess: &Session) -> TargetMachineRef {
+    target_machine_factory(sess)().unwrap_or_else(|err| {
+        panic!(llvm_err(sess.diagnostic(), err))
+    })
+}
+
+/// Returns a function which creates LLVM target machines configured for this
+/// session. The function can be called from any thread, which allows worker
+/// threads to create their own target machine for each module they work on.
+pub fn target_machine_factory(sess: &Session)
+    -> Arc<Fn() -> Result<TargetMachineRef, String> + Send + Sync>
+{
     let reloc_model = get_reloc_model(sess);
 
     let opt_level = get_llvm_opt_level(sess.opts.optimize);
@@ -167,40 +180,42 @@ pub fn create_target_machine(sess: &Session) -> TargetMachineRef {
 
     let triple = &sess.target.target.llvm_target;
 
-    let tm = unsafe {
-        let triple = CString::new(triple.as_bytes()).unwrap();
-        let cpu = match sess.opts.cg.target_cpu {
-            Some(ref s) => &**s,
-            None => &*sess.target.target.options.cpu
-        };
-        let cpu = CString::new(cpu.as_bytes()).unwrap();
-        let features = CString::new(target_feature(sess).as_bytes()).unwrap();
-        llvm::LLVMRustCreateTargetMachine(
-            triple.as_ptr(), cpu.as_ptr(), features.as_ptr(),
-            code_model,
-            reloc_model,
-            opt_level,
-            use_softfp,
-            is_pie_binary(sess),
-            ffunction_sections,
-            fdata_sections,
-        )
+    let triple = CString::new(triple.as_bytes()).unwrap();
+    let cpu = match sess.opts.cg.target_cpu {
+        Some(ref s) => &**s,
+        None => &*sess.target.target.options.cpu
     };
+    let cpu = CString::new(cpu.as_bytes()).unwrap();
+    let features = CString::new(target_feature(sess).as_bytes()).unwrap();
+    let is_pie_binary = is_pie_binary(sess);
+
+    Arc::new(move || {
+        let tm = unsafe {
+            llvm::LLVMRustCreateTargetMachine(
+                triple.as_ptr(), cpu.as_ptr(), features.as_ptr(),
+                code_model,
+                reloc_model,
+                opt_level,
+                use_softfp,
+                is_pie_binary,
+                ffunction_sections,
+                fdata_sections,
+            )
+        };
 
-    if tm.is_null() {
-        let msg = format!("Could not create LLVM TargetMachine for triple: {}",
-                          triple);
-        panic!(llvm_err(sess.diagnostic(), msg));
-    } else {
-        return tm;
-    };
+        if tm.is_null() {
+            Err(format!("Could not create LLVM TargetMachine for triple: {}",
+                        triple.to_str().unwrap()))
+        } else {
+            Ok(tm)
+        }
+    })
 }
 
 
-/// Module-specific configuration for `optimize_and_codegen`.
+/// Module-specific configuration for `optimize` and `codegen`.
+#[derive(Clone)]
 pub struct ModuleConfig {
-    /// LLVM TargetMachine to use for codegen.
-    tm: TargetMachineRef,
     /// Names of additional optimization passes to run.
     passes: Vec<String>,
     /// Some(level) to optimize at a certain level, or None to run
@@ -210,6 +225,12 @@ pub struct ModuleConfig {
     /// Some(level) to optimize binary size, or None to not affect program size.
     opt_size: Option<llvm::CodeGenOptSize>,
 
+    /// Some(dir) to instrument the module for profile-guided optimization,
+    /// writing the raw profiles into `dir` (or the working directory).
+    pgo_gen: Option<Option<PathBuf>>,
+    /// Some(path) to optimize the module with the `.profdata` file at `path`.
+    pgo_use: Option<PathBuf>,
+
     // Flags indicating which outputs to produce.
     emit_no_opt_bc: bool,
     emit_bc: bool,
@@ -233,16 +254,16 @@ pub struct ModuleConfig {
     obj_is_bitcode: bool,
 }
 
-unsafe impl Send for ModuleConfig { }
-
 impl ModuleConfig {
-    fn new(sess: &Session, passes: Vec<String>) -> ModuleConfig {
+    fn new(passes: Vec<String>) -> ModuleConfig {
         ModuleConfig {
-            tm: create_target_machine(sess),
             passes,
             opt_level: None,
             opt_size: None,
 
+            pgo_gen: None,
+            pgo_use: None,
+
             emit_no_opt_bc: false,
             emit_bc: false,
             emit_lto_bc: false,
@@ -286,53 +307,21 @@ impl ModuleConfig {
         self.merge_functions = sess.opts.optimize == config::OptLevel::Default ||
                                sess.opts.optimize == config::OptLevel::Aggressive;
     }
-
-    fn clone(&self, sess: &Session) -> ModuleConfig {
-        ModuleConfig {
-            tm: create_target_machine(sess),
-            passes: self.passes.clone(),
-            opt_level: self.opt_level,
-            opt_size: self.opt_size,
-
-            emit_no_opt_bc: self.emit_no_opt_bc,
-            emit_bc: self.emit_bc,
-            emit_lto_bc: self.emit_lto_bc,
-            emit_ir: self.emit_ir,
-            emit_asm: self.emit_asm,
-            emit_obj: self.emit_obj,
-            obj_is_bitcode: self.obj_is_bitcode,
-
-            no_verify: self.no_verify,
-            no_prepopulate_passes: self.no_prepopulate_passes,
-            no_builtins: self.no_builtins,
-            time_passes: self.time_passes,
-            vectorize_loop: self.vectorize_loop,
-            vectorize_slp: self.vectorize_slp,
-            merge_functions: self.merge_functions,
-            inline_threshold: self.inline_threshold,
-        }
-    }
-}
-
-impl Drop for ModuleConfig {
-    fn drop(&mut self) {
-        unsafe {
-            llvm::LLVMRustDisposeTargetMachine(self.tm);
-        }
-    }
 }
 
-/// Additional resources used by optimize_and_codegen (not module specific)
+/// Additional resources used by `optimize` and `codegen` (not module specific)
 #[derive(Clone)]
 pub struct CodegenContext {
     // Resouces needed when running LTO
     pub time_passes: bool,
-    pub lto: bool,
+    pub lto: Lto,
     pub no_landing_pads: bool,
     pub exported_symbols: Arc<ExportedSymbols>,
     pub opts: Arc<config::Options>,
     pub crate_types: Vec<config::CrateType>,
     pub each_linked_rlib_for_lto: Vec<(CrateNum, PathBuf)>,
+    // Creates a new LLVM target machine for each module being worked on.
+    pub tm_factory: Arc<Fn() -> Result<TargetMachineRef, String> + Send + Sync>,
     // Handler to use for diagnostics produced during codegen.
     pub diag_emitter: SharedEmitter,
     // LLVM passes added by plugins.
@@ -352,7 +341,7 @@ pub struct CodegenContext {
 }
 
 impl CodegenContext {
-    fn create_diag_handler(&self) -> Handler {
+    pub fn create_diag_handler(&self) -> Handler {
         Handler::with_emitter(true, false, Box::new(self.diag_emitter.clone()))
     }
 }
@@ -362,6 +351,39 @@ struct HandlerFreeVars<'a> {
     diag_handler: &'a Handler,
 }
 
+/// Installs our inline asm and diagnostic handlers into an LLVM context for as
+/// long as this value is alive.
+pub struct DiagnosticHandlers<'a> {
+    inner: Box<HandlerFreeVars<'a>>,
+    llcx: ContextRef,
+}
+
+impl<'a> DiagnosticHandlers<'a> {
+    pub fn new(cgcx: &'a CodegenContext,
+               diag_handler: &'a Handler,
+               llcx: ContextRef) -> DiagnosticHandlers<'a> {
+        let data = Box::new(HandlerFreeVars { cgcx, diag_handler });
+        unsafe {
+            let arg = &*data as &HandlerFreeVars as *const HandlerFreeVars as *mut c_void;
+            llvm::LLVMRustSetInlineAsmDiagnosticHandler(llcx, inline_asm_handler, arg);
+            llvm::LLVMContextSetDiagnosticHandler(llcx, diagnostic_handler, arg);
+        }
+        DiagnosticHandlers {
+            inner: data,
+            llcx,
+        }
+    }
+}
+
+impl<'a> Drop for DiagnosticHandlers<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            llvm::LLVMRustSetInlineAsmDiagnosticHandler(self.llcx, inline_asm_handler, 0 as *mut _);
+            llvm::LLVMContextSetDiagnosticHandler(self.llcx, diagnostic_handler, 0 as *mut _);
+        }
+    }
+}
+
 unsafe extern "C" fn report_inline_asm<'a, 'b>(cgcx: &'a CodegenContext,
                                                msg: &'b str,
                                                cookie: c_uint) {
@@ -371,6 +393,9 @@ unsafe extern "C" fn report_inline_asm<'a, 'b>(cgcx: &'a CodegenContext,
 unsafe extern "C" fn inline_asm_handler(diag: SMDiagnosticRef,
                                         user: *const c_void,
                                         cookie: c_uint) {
+    if user.is_null() {
+        return
+    }
     let HandlerFreeVars { cgcx, .. } = *(user as *const HandlerFreeVars);
 
     let msg = llvm::build_string(|s| llvm::LLVMRustWriteSMDiagnosticToString(diag, s))
@@ -380,6 +405,9 @@ unsafe extern "C" fn inline_asm_handler(diag: SMDiagnosticRef,
 }
 
 unsafe extern "C" fn diagnostic_handler(info: DiagnosticInfoRef, user: *mut c_void) {
+    if user.is_null() {
+        return
+    }
     let HandlerFreeVars { cgcx, diag_handler, .. } = *(user as *const HandlerFreeVars);
 
     match llvm::diagnostic::Diagnostic::unpack(info) {
@@ -411,30 +439,22 @@ unsafe extern "C" fn diagnostic_handler(info: DiagnosticInfoRef, user: *mut c_vo
 }
 
 // Unsafe due to LLVM calls.
-unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
-                               diag_handler: &Handler,
-                               mtrans: ModuleTranslation,
-                               config: ModuleConfig,
-                               output_names: OutputFilenames)
-    -> Result<CompiledModule, FatalError>
+unsafe fn optimize(cgcx: &CodegenContext,
+                   diag_handler: &Handler,
+                   mtrans: &ModuleTranslation,
+                   tm: TargetMachineRef,
+                   config: &ModuleConfig,
+                   output_names: &OutputFilenames)
+    -> Result<(), FatalError>
 {
     let (llmod, llcx) = match mtrans.source {
         ModuleSource::Translated(ref llvm) => (llvm.llmod, llvm.llcx),
         ModuleSource::Preexisting(_) => {
-            bug!("optimize_and_codegen: called with ModuleSource::Preexisting")
+            bug!("optimize: called with ModuleSource::Preexisting")
         }
     };
 
-    let tm = config.tm;
-
-    let fv = HandlerFreeVars {
-        cgcx,
-        diag_handler,
-    };
-    let fv = &fv as *const HandlerFreeVars as *mut c_void;
-
-    llvm::LLVMRustSetInlineAsmDiagnosticHandler(llcx, inline_asm_handler, fv);
-    llvm::LLVMContextSetDiagnosticHandler(llcx, diagnostic_handler, fv);
+    let _handlers = DiagnosticHandlers::new(cgcx, diag_handler, llcx);
 
     let module_name = mtrans.name.clone();
     let module_name = Some(&module_name[..]);
@@ -477,7 +497,7 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         if !config.no_prepopulate_passes {
             llvm::LLVMRustAddAnalysisPasses(tm, fpm, llmod);
             llvm::LLVMRustAddAnalysisPasses(tm, mpm, llmod);
-            with_llvm_pmb(llmod, &config, &mut |b| {
+            with_llvm_pmb(llmod, config, &mut |b| {
                 llvm::LLVMPassManagerBuilderPopulateFunctionPassManager(b, fpm);
                 llvm::LLVMPassManagerBuilderPopulateModulePassManager(b, mpm);
             })
@@ -509,25 +529,30 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         // Deallocate managers that we're now done with
         llvm::LLVMDisposePassManager(fpm);
         llvm::LLVMDisposePassManager(mpm);
+    }
+    Ok(())
+}
 
-        if cgcx.lto {
-            time(cgcx.time_passes, "all lto passes", || {
-                let temp_no_opt_bc_filename =
-                    output_names.temp_path_ext("no-opt.lto.bc", module_name);
-                lto::run(cgcx,
-                         diag_handler,
-                         llmod,
-                         tm,
-                         &config,
-                         &temp_no_opt_bc_filename)
-            })?;
-            if config.emit_lto_bc {
-                let out = output_names.temp_path_ext("lto.bc", module_name);
-                let out = path2cstr(&out);
-                llvm::LLVMWriteBitcodeToFile(llmod, out.as_ptr());
-            }
+// Unsafe due to LLVM calls.
+unsafe fn codegen(cgcx: &CodegenContext,
+                  diag_handler: &Handler,
+                  mtrans: ModuleTranslation,
+                  tm: TargetMachineRef,
+                  config: &ModuleConfig,
+                  output_names: &OutputFilenames)
+    -> Result<CompiledModule, FatalError>
+{
+    let (llmod, llcx) = match mtrans.source {
+        ModuleSource::Translated(ref llvm) => (llvm.llmod, llvm.llcx),
+        ModuleSource::Preexisting(_) => {
+            bug!("codegen: called with ModuleSource::Preexisting")
         }
-    }
+    };
+
+    let handlers = DiagnosticHandlers::new(cgcx, diag_handler, llcx);
+
+    let module_name = mtrans.name.clone();
+    let module_name = Some(&module_name[..]);
 
     // A codegen-specific pass manager is used to generate object
     // files for an LLVM module.
@@ -653,6 +678,9 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         }
     }
 
+    // The handlers refer to the module's LLVM context, so they have to be
+    // removed before the module is disposed of.
+    drop(handlers);
     Ok(mtrans.into_compiled_module(config.emit_obj, config.emit_bc))
 }
 
@@ -687,9 +715,9 @@ pub fn start_async_translation(sess: &Session,
     };
 
     // Figure out what we actually need to build.
-    let mut modules_config = ModuleConfig::new(sess, sess.opts.cg.passes.clone());
-    let mut metadata_config = ModuleConfig::new(sess, vec![]);
-    let mut allocator_config = ModuleConfig::new(sess, vec![]);
+    let mut modules_config = ModuleConfig::new(sess.opts.cg.passes.clone());
+    let mut metadata_config = ModuleConfig::new(vec![]);
+    let mut allocator_config = ModuleConfig::new(vec![]);
 
     if let Some(ref sanitizer) = sess.opts.debugging_opts.sanitizer {
         match *sanitizer {
@@ -711,9 +739,18 @@ pub fn start_async_translation(sess: &Session,
         modules_config.passes.push("insert-gcov-profiling".to_owned())
     }
 
+    if sess.opts.debugging_opts.instrument_coverage {
+        modules_config.passes.push("instrprof".to_owned())
+    }
+
     modules_config.opt_level = Some(get_llvm_opt_level(sess.opts.optimize));
     modules_config.opt_size = Some(get_llvm_opt_size(sess.opts.optimize));
 
+    if let SwitchWithOptPath::Enabled(ref dir) = sess.opts.cg.profile_generate {
+        modules_config.pgo_gen = Some(dir.clone());
+    }
+    modules_config.pgo_use = sess.opts.cg.profile_use.clone();
+
     // Save all versions of the bytecode if we're saving our temporaries.
     if sess.opts.cg.save_temps {
         modules_config.emit_no_opt_bc = true;
@@ -1003,14 +1040,30 @@ pub fn dump_incremental_data(trans: &CrateTranslation) {
 }
 
 struct WorkItem {
-    mtrans: ModuleTranslation,
+    kind: WorkItemKind,
     config: ModuleConfig,
     output_names: OutputFilenames
 }
 
+enum WorkItemKind {
+    /// Optimize a newly translated, totally unoptimized module.
+    Optimize(ModuleTranslation),
+    /// Run the LTO passes on a module and then code generate it.
+    LTO(lto::LtoModuleTranslation),
+}
+
+impl WorkItem {
+    fn name(&self) -> String {
+        match self.kind {
+            WorkItemKind::Optimize(ref m) => format!("optimize: {}", m.name),
+            WorkItemKind::LTO(ref m) => format!("lto: {}", m.name()),
+        }
+    }
+}
+
 impl fmt::Debug for WorkItem {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "WorkItem({})", self.mtrans.name)
+        write!(f, "WorkItem({})", self.name())
     }
 }
 
@@ -1020,19 +1073,82 @@ fn build_work_item(mtrans: ModuleTranslation,
                    -> WorkItem
 {
     WorkItem {
-        mtrans,
+        kind: WorkItemKind::Optimize(mtrans),
         config,
         output_names,
     }
 }
 
+enum WorkItemResult {
+    Compiled(CompiledModule),
+    NeedsLTO(ModuleTranslation, ModuleConfig, OutputFilenames),
+}
+
+impl fmt::Debug for WorkItemResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WorkItemResult::Compiled(ref m) => write!(f, "Compiled({:?})", m),
+            WorkItemResult::NeedsLTO(ref m, ..) => write!(f, "NeedsLTO({})", m.name),
+        }
+    }
+}
+
 fn execute_work_item(cgcx: &CodegenContext, work_item: WorkItem)
-    -> Result<CompiledModule, FatalError>
+    -> Result<WorkItemResult, FatalError>
 {
     let diag_handler = cgcx.create_diag_handler();
-    let module_name = work_item.mtrans.name.clone();
+    let WorkItem { kind, config, output_names } = work_item;
+
+    let tm = match (cgcx.tm_factory)() {
+        Ok(tm) => tm,
+        Err(e) => return Err(diag_handler.fatal(&e)),
+    };
+
+    let result = match kind {
+        WorkItemKind::Optimize(mtrans) => {
+            execute_optimize_work_item(cgcx, &diag_handler, mtrans, tm, config, output_names)
+        }
+        WorkItemKind::LTO(lto) => {
+            execute_lto_work_item(cgcx, &diag_handler, lto, tm, config, output_names)
+        }
+    };
+
+    unsafe {
+        llvm::LLVMRustDisposeTargetMachine(tm);
+    }
+    result
+}
+
+fn execute_lto_work_item(cgcx: &CodegenContext,
+                         diag_handler: &Handler,
+                         mut lto: lto::LtoModuleTranslation,
+                         tm: TargetMachineRef,
+                         config: ModuleConfig,
+                         output_names: OutputFilenames)
+    -> Result<WorkItemResult, FatalError>
+{
+    unsafe {
+        let mtrans = lto.optimize(cgcx, diag_handler, tm, &config)?;
+        if config.emit_lto_bc {
+            let out = output_names.temp_path_ext("lto.bc", Some(&mtrans.name));
+            lto::save_temp_bitcode(&mtrans, &out);
+        }
+        codegen(cgcx, diag_handler, mtrans, tm, &config, &output_names)
+            .map(WorkItemResult::Compiled)
+    }
+}
+
+fn execute_optimize_work_item(cgcx: &CodegenContext,
+                              diag_handler: &Handler,
+                              mtrans: ModuleTranslation,
+                              tm: TargetMachineRef,
+                              config: ModuleConfig,
+                              output_names: OutputFilenames)
+    -> Result<WorkItemResult, FatalError>
+{
+    let module_name = mtrans.name.clone();
 
-    let pre_existing = match work_item.mtrans.source {
+    let pre_existing = match mtrans.source {
         ModuleSource::Translated(_) => None,
         ModuleSource::Preexisting(ref wp) => Some(wp.clone()),
     };
@@ -1041,13 +1157,13 @@ fn execute_work_item(cgcx: &CodegenContext, work_item: WorkItem)
         let incr_comp_session_dir = cgcx.incr_comp_session_dir
                                         .as_ref()
                                         .unwrap();
-        let name = &work_item.mtrans.name;
+        let name = &mtrans.name;
         for (kind, saved_file) in wp.saved_files {
-            let obj_out = work_item.output_names.temp_path(kind, Some(name));
+            let obj_out = output_names.temp_path(kind, Some(name));
             let source_file = in_incr_comp_dir(&incr_comp_session_dir,
                                                &saved_file);
             debug!("copying pre-existing module `{}` from {:?} to {}",
-                   work_item.mtrans.name,
+                   mtrans.name,
                    source_file,
                    obj_out.display());
             match link_or_copy(&source_file, &obj_out) {
@@ -1061,23 +1177,55 @@ fn execute_work_item(cgcx: &CodegenContext, work_item: WorkItem)
             }
         }
 
-        Ok(CompiledModule {
+        Ok(WorkItemResult::Compiled(CompiledModule {
             name: module_name,
             kind: ModuleKind::Regular,
             pre_existing: true,
-            symbol_name_hash: work_item.mtrans.symbol_name_hash,
-            emit_bc: work_item.config.emit_bc,
-            emit_obj: work_item.config.emit_obj,
-        })
+            symbol_name_hash: mtrans.symbol_name_hash,
+            emit_bc: config.emit_bc,
+            emit_obj: config.emit_obj,
+        }))
     } else {
         debug!("llvm-optimizing {:?}", module_name);
 
         unsafe {
-            optimize_and_codegen(cgcx,
-                                 &diag_handler,
-                                 work_item.mtrans,
-                                 work_item.config,
-                                 work_item.output_names)
+            optimize(cgcx, diag_handler, &mtrans, tm, &config, &output_names)?;
+
+            // After we've done the initial round of optimizations we need to
+            // decide whether to synchronously codegen this module or ship it
+            // back to the coordinator thread for further LTO processing (which
+            // has to wait for all the initial modules to be optimized).
+            //
+            // Here we dispatch based on the `cgcx.lto` and kind of module we're
+            // translating...
+            let needs_lto = match cgcx.lto {
+                Lto::No => false,
+
+                // Here we've got a full crate graph LTO requested, so all
+                // modules take part in it.
+                Lto::Fat | Lto::Thin => true,
+
+                // ThinLTO between the codegen units of this crate is only an
+                // optimization, so if the LLVM we're built against doesn't
+                // support ThinLTO we just skip it.
+                Lto::ThinLocal => {
+                    llvm::LLVMRustThinLTOAvailable()
+                }
+            };
+
+            // Metadata and allocator modules never take part in LTO.
+            let needs_lto = needs_lto && match mtrans.kind {
+                ModuleKind::Regular => true,
+                ModuleKind::Metadata |
+                ModuleKind::Allocator => false,
+            };
+
+            if needs_lto {
+                Ok(WorkItemResult::NeedsLTO(mtrans, config, output_names))
+            } else {
+                codegen(cgcx, diag_handler, mtrans, tm, &config, &output_names)
+                    .map(WorkItemResult::Compiled)
+            }
         }
     }
 }
@@ -1086,7 +1234,7 @@ fn execute_work_item(cgcx: &CodegenContext, work_item: WorkItem)
 enum Message {
     Token(io::Result<Acquired>),
     Done {
-        result: Result<CompiledModule, ()>,
+        result: Result<WorkItemResult, ()>,
         worker_id: usize,
     },
     TranslationDone {
@@ -1147,6 +1295,7 @@ fn start_executing_work(sess: &Session,
         crate_types: sess.crate_types.borrow().clone(),
         each_linked_rlib_for_lto,
         lto: sess.lto(),
+        tm_factory: target_machine_factory(sess),
         no_landing_pads: sess.no_landing_pads(),
         opts: Arc::new(sess.opts.clone()),
         time_passes: sess.time_passes(),
@@ -1303,6 +1452,12 @@ fn start_executing_work(sess: &Session,
         let mut compiled_metadata_module = None;
         let mut compiled_allocator_module = None;
 
+        // This is where we collect modules which have been optimized but
+        // still need to go through LTO once everything else is done, along
+        // with the configuration to use for the resulting LTO work items.
+        let mut needs_lto = vec![];
+        let mut lto_config = None;
+
         // This flag tracks whether all items have gone through translations
         let mut translation_done = false;
 
@@ -1323,8 +1478,48 @@ fn start_executing_work(sess: &Session,
         while !translation_done ||
               work_items.len() > 0 ||
               running > 0 ||
+              needs_lto.len() > 0 ||
               main_thread_worker_state != MainThreadWorkerState::Idle {
 
+            // Once every module has been translated and optimized, it's time
+            // to perform LTO on the ones that need it. This generates a new
+            // batch of work items which are then processed just like the
+            // initial ones.
+            if translation_done &&
+               work_items.len() == 0 &&
+               running == 0 &&
+               needs_lto.len() > 0 &&
+               main_thread_worker_state == MainThreadWorkerState::Idle {
+                let modules = mem::replace(&mut needs_lto, Vec::new());
+                let (config, output_names): (ModuleConfig, OutputFilenames) =
+                    lto_config.take().unwrap();
+                let lto_modules = match time(cgcx.time_passes, "LTO analysis", || {
+                    lto::run(&cgcx, modules)
+                }) {
+                    Ok(lto_modules) => lto_modules,
+                    Err(FatalError) => {
+                        shared_emitter.fatal("aborting due to LTO failure");
+                        // Exit the coordinator thread
+                        panic!("aborting due to LTO failure")
+                    }
+                };
+                for module in lto_modules {
+                    let cost = module.cost();
+                    let item = WorkItem {
+                        kind: WorkItemKind::LTO(module),
+                        config: config.clone(),
+                        output_names: output_names.clone(),
+                    };
+                    let insertion_index =
+                        work_items.binary_search_by_key(&cost, |&(_, cost)| cost);
+                    let insertion_index = match insertion_index {
+                        Ok(idx) | Err(idx) => idx
+                    };
+                    work_items.insert(insertion_index, (item, cost));
+                    helper.request_token();
+                }
+            }
+
             // While there are still CGUs to be translated, the coordinator has
             // to decide how to utilize the compiler processes implicit Token:
             // For translating more CGU or for running them through LLVM.
@@ -1471,7 +1666,22 @@ fn start_executing_work(sess: &Session,
                 //
                 // Note that if the thread failed that means it panicked, so we
                 // abort immediately.
-                Message::Done { result: Ok(compiled_module), worker_id } => {
+                Message::Done { result: Ok(WorkItemResult::NeedsLTO(mtrans, config, names)),
+                                worker_id } => {
+                    if main_thread_worker_state == MainThreadWorkerState::LLVMing {
+                        main_thread_worker_state = MainThreadWorkerState::Idle;
+                    } else {
+                        running -= 1;
+                    }
+
+                    free_worker_ids.push(worker_id);
+                    needs_lto.push(mtrans);
+                    if lto_config.is_none() {
+                        lto_config = Some((config, names));
+                    }
+                }
+                Message::Done { result: Ok(WorkItemResult::Compiled(compiled_module)),
+                                worker_id } => {
                     if main_thread_worker_state == MainThreadWorkerState::LLVMing {
                         main_thread_worker_state = MainThreadWorkerState::Idle;
                     } else {
@@ -1565,13 +1775,13 @@ fn spawn_work(cgcx: CodegenContext, work: WorkItem) {
         // we exit.
         struct Bomb {
             coordinator_send: Sender<Message>,
-            result: Option<CompiledModule>,
+            result: Option<WorkItemResult>,
             worker_id: usize,
         }
         impl Drop for Bomb {
             fn drop(&mut self) {
                 let result = match self.result.take() {
-                    Some(compiled_module) => Ok(compiled_module),
+                    Some(result) => Ok(result),
                     None => Err(())
                 };
 
@@ -1655,10 +1865,25 @@ pub unsafe fn with_llvm_pmb(llmod: ModuleRef,
     let opt_size = config.opt_size.unwrap_or(llvm::CodeGenOptSizeNone);
     let inline_threshold = config.inline_threshold;
 
+    // LLVM picks the default file name pattern for the raw profiles itself,
+    // we only ever point it at the right directory.
+    let pgo_gen_path = config.pgo_gen.as_ref().map(|dir| {
+        let path = match *dir {
+            Some(ref dir) => dir.join("default_%m.profraw"),
+            None => PathBuf::from("default_%m.profraw"),
+        };
+        path2cstr(&path)
+    });
+    let pgo_use_path = config.pgo_use.as_ref().map(|path| path2cstr(path));
+
     llvm::LLVMRustConfigurePassManagerBuilder(builder, opt_level,
                                               config.merge_functions,
                                               config.vectorize_slp,
-                                              config.vectorize_loop);
+                                              config.vectorize_loop,
+                                              pgo_gen_path.as_ref().map_or(ptr::null(),
+                                                                           |s| s.as_ptr()),
+                                              pgo_use_path.as_ref().map_or(ptr::null(),
+                                                                           |s| s.as_ptr()));
     llvm::LLVMPassManagerBuilderSetSizeLevel(builder, opt_size as u32);
 
     if opt_size != llvm::CodeGenOptSizeNone {
@@ -1898,9 +2123,9 @@ impl OngoingCrateTranslation {
                                             cost: u64,
                                             is_last: bool) {
         let module_config = match mtrans.kind {
-            ModuleKind::Regular => self.regular_module_config.clone(sess),
-            ModuleKind::Metadata => self.metadata_module_config.clone(sess),
-            ModuleKind::Allocator => self.allocator_module_config.clone(sess),
+            ModuleKind::Regular => self.regular_module_config.clone(),
+            ModuleKind::Metadata => self.metadata_module_config.clone(),
+            ModuleKind::Allocator => self.allocator_module_config.clone(),
         };
 
         let llvm_work_item = build_work_item(mtrans,
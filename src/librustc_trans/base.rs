@@ -36,8 +36,7 @@ use back::write::{self, OngoingCrateTranslation};
 use llvm::{ContextRef, Linkage, ModuleRef, ValueRef, Vector, get_param};
 use llvm;
 use metadata;
-use rustc::hir::def_id::LOCAL_CRATE;
-use rustc::middle::lang_items::StartFnLangItem;
+use rustc::hir::def_id::{DefId, LOCAL_CRATE};
 use rustc::middle::cstore::{EncodedMetadata, EncodedMetadataHashes};
 use rustc::ty::{self, Ty, TyCtxt};
 use rustc::dep_graph::AssertDepGraphSafe;
@@ -61,6 +60,7 @@ use common::{type_is_zero_size, val_ty};
 use common;
 use consts;
 use context::{self, LocalCrateContext, SharedCrateContext, Stats};
+use coverageinfo;
 use debuginfo;
 use declare;
 use machine;
@@ -689,13 +689,14 @@ fn maybe_create_entry_wrapper(ccx: &CrateContext) {
 
     let et = ccx.sess().entry_type.get().unwrap();
     match et {
-        config::EntryMain => create_entry_fn(ccx, span, main_llfn, true),
-        config::EntryStart => create_entry_fn(ccx, span, main_llfn, false),
+        config::EntryMain => create_entry_fn(ccx, span, main_def_id, main_llfn, true),
+        config::EntryStart => create_entry_fn(ccx, span, main_def_id, main_llfn, false),
         config::EntryNone => {}    // Do nothing.
     }
 
     fn create_entry_fn(ccx: &CrateContext,
                        sp: Span,
+                       main_def_id: DefId,
                        rust_main: ValueRef,
                        use_start_lang_item: bool) {
         let llfty = Type::func(&[ccx.isize_ty(), Type::i8p(ccx).ptr_to()], &ccx.isize_ty());
@@ -718,8 +719,7 @@ fn maybe_create_entry_wrapper(ccx: &CrateContext) {
         debuginfo::gdb::insert_reference_to_gdb_debug_scripts_section_global(ccx, &bld);
 
         let (start_fn, args) = if use_start_lang_item {
-            let start_def_id = ccx.tcx().require_lang_item(StartFnLangItem);
-            let start_instance = Instance::mono(ccx.tcx(), start_def_id);
+            let start_instance = monomorphize::resolve_start_fn(ccx.shared(), main_def_id);
             let start_fn = callee::get_fn(ccx, start_instance);
             (start_fn, vec![bld.pointercast(rust_main, Type::i8p(ccx).ptr_to()), get_param(llfn, 0),
                 get_param(llfn, 1)])
@@ -917,7 +917,7 @@ pub fn find_exported_symbols(tcx: TyCtxt, reachable: &NodeSet) -> NodeSet {
                 let def_id = tcx.hir.local_def_id(id);
                 let generics = tcx.generics_of(def_id);
                 let attributes = tcx.get_attrs(def_id);
-                (generics.parent_types == 0 && generics.types.is_empty()) &&
+                !generics.requires_monomorphization() &&
                 // Functions marked with #[inline] are only ever translated
                 // with "internal" linkage and are never exported.
                 !attr::requests_inline(&attributes)
@@ -935,6 +935,11 @@ pub fn trans_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                              -> OngoingCrateTranslation {
     check_for_rustc_errors_attr(tcx);
 
+    if tcx.sess.opts.debugging_opts.instrument_coverage &&
+       !unsafe { llvm::LLVMRustCoverageAvailable() } {
+        tcx.sess.fatal("`-Z instrument-coverage` requires LLVM 5.0 or later");
+    }
+
     // Be careful with this krate: obviously it gives access to the
     // entire contents of the krate. So if you push any subtasks of
     // `TransCrate`, you need to be careful to register "reads" of the
@@ -1011,7 +1016,7 @@ pub fn trans_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     let (translation_items, codegen_units) =
         collect_and_partition_translation_items(&shared_ccx, &exported_symbols);
 
-    assert!(codegen_units.len() <= 1 || !tcx.sess.lto());
+    assert!(codegen_units.len() <= 1 || tcx.sess.lto() != config::Lto::Fat);
 
     let linker_info = LinkerInfo::new(&shared_ccx, &exported_symbols);
     let subsystem = attr::first_attr_value_str_by_name(&krate.attrs,
@@ -1046,15 +1051,16 @@ pub fn trans_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
     // Translate an allocator shim, if any
     //
-    // If LTO is enabled and we've got some previous LLVM module we translated
-    // above, then we can just translate directly into that LLVM module. If not,
-    // however, we need to create a separate module and trans into that. Note
-    // that the separate translation is critical for the standard library where
-    // the rlib's object file doesn't have allocator functions but the dylib
-    // links in an object file that has allocator functions. When we're
-    // compiling a final LTO artifact, though, there's no need to worry about
-    // this as we're not working with this dual "rlib/dylib" functionality.
-    let allocator_module = if tcx.sess.lto() {
+    // If fat LTO is enabled and we've got some previous LLVM module we
+    // translated above, then we can just translate directly into that LLVM
+    // module. If not, however, we need to create a separate module and trans
+    // into that. Note that the separate translation is critical for the
+    // standard library where the rlib's object file doesn't have allocator
+    // functions but the dylib links in an object file that has allocator
+    // functions. When we're compiling a final fat LTO artifact, though,
+    // there's no need to worry about this as we're not working with this dual
+    // "rlib/dylib" functionality.
+    let allocator_module = if tcx.sess.lto() == config::Lto::Fat {
         None
     } else if let Some(kind) = tcx.sess.allocator_kind.get() {
         unsafe {
@@ -1237,6 +1243,10 @@ pub fn trans_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 }
             }
 
+            // Emit the coverage map, which has to be kept alive through
+            // llvm.used as well
+            coverageinfo::finalize(&ccx);
+
             // Create the llvm.used variable
             // This variable has type [N x i8*] and is stored in the llvm.metadata section
             if !ccx.used_statics().borrow().is_empty() {
@@ -1264,9 +1274,9 @@ pub fn trans_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 llmod: ccx.llmod(),
             };
 
-            // In LTO mode we inject the allocator shim into the existing
+            // In fat LTO mode we inject the allocator shim into the existing
             // module.
-            if ccx.sess().lto() {
+            if ccx.sess().lto() == config::Lto::Fat {
                 if let Some(kind) = ccx.sess().allocator_kind.get() {
                     time(ccx.sess().time_passes(), "write allocator module", || {
                         unsafe {
@@ -195,6 +195,7 @@ use rustc::hir::map as hir_map;
 use rustc::hir::def_id::DefId;
 use rustc::middle::const_val::ConstVal;
 use rustc::middle::lang_items::{ExchangeMallocFnLangItem};
+use rustc::session::config;
 use rustc::traits;
 use rustc::ty::subst::Substs;
 use rustc::ty::{self, TypeFoldable, Ty, TyCtxt};
@@ -211,6 +212,7 @@ use trans_item::{TransItem, DefPathBasedNames, InstantiationMode};
 
 use rustc_data_structures::bitvec::BitVector;
 use back::symbol_export::ExportedSymbols;
+use syntax::ast;
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub enum TransItemCollectionMode {
@@ -880,6 +882,7 @@ impl<'b, 'a, 'v> ItemLikeVisitor<'v> for RootCollector<'b, 'a, 'v> {
             hir::ItemTy(..)          |
             hir::ItemDefaultImpl(..) |
             hir::ItemTrait(..)       |
+            hir::ItemTraitAlias(..)  |
             hir::ItemMod(..)         => {
                 // Nothing to do, just keep recursing...
             }
@@ -936,6 +939,7 @@ impl<'b, 'a, 'v> ItemLikeVisitor<'v> for RootCollector<'b, 'a, 'v> {
 
                     let instance = Instance::mono(tcx, def_id);
                     self.output.push(TransItem::Fn(instance));
+                    self.push_start_fn_root(item.id, def_id);
                 }
             }
         }
@@ -968,9 +972,30 @@ impl<'b, 'a, 'v> ItemLikeVisitor<'v> for RootCollector<'b, 'a, 'v> {
     }
 }
 
+impl<'b, 'a, 'v> RootCollector<'b, 'a, 'v> {
+    /// The `start` lang item is generic over the return type of `main`, so
+    /// it has to be instantiated in the crate defining `main`.
+    fn push_start_fn_root(&mut self, node_id: ast::NodeId, main_def_id: DefId) {
+        let sess = self.scx.sess();
+        match *sess.entry_fn.borrow() {
+            Some((entry_id, _)) if entry_id == node_id => {}
+            _ => return,
+        }
+        if sess.entry_type.get() != Some(config::EntryMain) {
+            return;
+        }
+
+        let instance = monomorphize::resolve_start_fn(self.scx, main_def_id);
+        if instance.substs.types().next().is_some() {
+            debug!("RootCollector: start fn {}", instance);
+            self.output.push(TransItem::Fn(instance));
+        }
+    }
+}
+
 fn item_has_type_parameters<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId) -> bool {
     let generics = tcx.generics_of(def_id);
-    generics.parent_types as usize + generics.types.len() > 0
+    generics.requires_monomorphization()
 }
 
 fn create_trans_items_for_default_impls<'a, 'tcx>(scx: &SharedCrateContext<'a, 'tcx>,
@@ -984,7 +1009,7 @@ fn create_trans_items_for_default_impls<'a, 'tcx>(scx: &SharedCrateContext<'a, '
                       ref generics,
                       ..,
                       ref impl_item_refs) => {
-            if generics.is_type_parameterized() {
+            if generics.is_type_parameterized() || generics.is_const_parameterized() {
                 return
             }
 
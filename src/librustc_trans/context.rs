@@ -14,6 +14,7 @@ use rustc::dep_graph::{DepGraph, DepGraphSafe};
 use rustc::hir;
 use rustc::hir::def_id::DefId;
 use rustc::traits;
+use coverageinfo;
 use debuginfo;
 use callee;
 use back::symbol_export::ExportedSymbols;
@@ -142,6 +143,10 @@ pub struct LocalCrateContext<'a, 'tcx: 'a> {
 
     dbg_cx: Option<debuginfo::CrateDebugContext<'tcx>>,
 
+    /// Coverage regions of the instrumented functions, present only when
+    /// "-Z instrument-coverage" is passed.
+    coverage_cx: Option<coverageinfo::CrateCoverageContext>,
+
     eh_personality: Cell<Option<ValueRef>>,
     eh_unwind_resume: Cell<Option<ValueRef>>,
     rust_try_fn: Cell<Option<ValueRef>>,
@@ -326,6 +331,21 @@ impl<'b, 'tcx> SharedCrateContext<'b, 'tcx> {
         ty.is_sized(self.tcx, ty::ParamEnv::empty(traits::Reveal::All), DUMMY_SP)
     }
 
+    /// Returns true if pointers to `ty` need to carry metadata, i.e. if `ty`
+    /// is unsized and its unsized tail is not an extern type.
+    pub fn type_has_metadata(&self, ty: Ty<'tcx>) -> bool {
+        if self.type_is_sized(ty) {
+            return false;
+        }
+
+        let tail = self.tcx.struct_tail(ty);
+        match tail.sty {
+            ty::TyForeign(..) => false,
+            ty::TyStr | ty::TySlice(..) | ty::TyDynamic(..) => true,
+            _ => bug!("unexpected unsized tail: {:?}", tail.sty),
+        }
+    }
+
     pub fn type_is_freeze(&self, ty: Ty<'tcx>) -> bool {
         ty.is_freeze(self.tcx, ty::ParamEnv::empty(traits::Reveal::All), DUMMY_SP)
     }
@@ -382,6 +402,12 @@ impl<'a, 'tcx> LocalCrateContext<'a, 'tcx> {
                 None
             };
 
+            let coverage_cx = if shared.tcx.sess.opts.debugging_opts.instrument_coverage {
+                Some(coverageinfo::CrateCoverageContext::new())
+            } else {
+                None
+            };
+
             let local_ccx = LocalCrateContext {
                 llmod,
                 llcx,
@@ -402,6 +428,7 @@ impl<'a, 'tcx> LocalCrateContext<'a, 'tcx> {
                 opaque_vec_type: Type::from_ref(ptr::null_mut()),
                 str_slice_type: Type::from_ref(ptr::null_mut()),
                 dbg_cx,
+                coverage_cx,
                 eh_personality: Cell::new(None),
                 eh_unwind_resume: Cell::new(None),
                 rust_try_fn: Cell::new(None),
@@ -561,6 +588,10 @@ impl<'b, 'tcx> CrateContext<'b, 'tcx> {
         &self.local().dbg_cx
     }
 
+    pub fn coverage_cx<'a>(&'a self) -> &'a Option<coverageinfo::CrateCoverageContext> {
+        &self.local().coverage_cx
+    }
+
     pub fn rust_try_fn<'a>(&'a self) -> &'a Cell<Option<ValueRef>> {
         &self.local().rust_try_fn
     }
@@ -772,6 +803,7 @@ fn declare_intrinsic(ccx: &CrateContext, key: &str) -> Option<ValueRef> {
     ifn!("llvm.trap", fn() -> void);
     ifn!("llvm.debugtrap", fn() -> void);
     ifn!("llvm.frameaddress", fn(t_i32) -> i8p);
+    ifn!("llvm.instrprof.increment", fn(i8p, t_i64, t_i32, t_i32) -> void);
 
     ifn!("llvm.powi.f32", fn(t_f32, t_i32) -> t_f32);
     ifn!("llvm.powi.f64", fn(t_f64, t_i32) -> t_f64);
@@ -0,0 +1,263 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! # Source-based code coverage
+//!
+//! When "-Z instrument-coverage" is passed, the `InstrumentCoverage` MIR
+//! pass inserts `Coverage` statements that each stand for a counter and the
+//! source region it counts. This module lowers them to calls of the
+//! `llvm.instrprof.increment` intrinsic and, once a codegen unit is done,
+//! writes the regions of all its instrumented functions into the coverage
+//! mapping section (`__llvm_covmap`) that `llvm-cov` reads back.
+//!
+//! The layout of that section is:
+//!
+//! ```text
+//! { header: { i32 NRecords, i32 FilenamesSize, i32 CoverageSize, i32 Version },
+//!   records: [NRecords x <{ i64 NameRef, i32 DataSize, i64 FuncHash }>],
+//!   data: [filenames, mapping of each function, padding to 8 bytes] }
+//! ```
+
+use llvm::{self, ValueRef};
+use builder::Builder;
+use common::{CrateContext, C_array, C_bytes, C_struct, C_u32, C_u64, val_ty};
+use consts;
+use monomorphize::Instance;
+use type_::Type;
+
+use rustc::mir;
+use libc::c_uint;
+
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::ffi::CString;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// The coverage regions of all functions instrumented in a codegen unit.
+pub struct CrateCoverageContext {
+    functions: RefCell<Vec<FunctionCoverage>>,
+}
+
+impl CrateCoverageContext {
+    pub fn new() -> CrateCoverageContext {
+        CrateCoverageContext {
+            functions: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+struct FunctionCoverage {
+    /// The PGO name of the function, i.e. its symbol name.
+    name: CString,
+    hash: u64,
+    file_name: String,
+    regions: Vec<CodeRegion>,
+}
+
+#[derive(Hash)]
+struct CodeRegion {
+    counter: u32,
+    start_line: u32,
+    start_col: u32,
+    end_line: u32,
+    end_col: u32,
+}
+
+/// What is needed to lower the `Coverage` statements of one function.
+pub struct FunctionCoverageContext {
+    name_var: ValueRef,
+    hash: u64,
+    num_counters: u32,
+}
+
+/// Records the coverage regions of `mir` and creates the name variable the
+/// counters of `llfn` are keyed on. Returns `None` if the function is not
+/// instrumented.
+pub fn create_function_coverage_context<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                                  instance: Instance<'tcx>,
+                                                  llfn: ValueRef,
+                                                  mir: &mir::Mir<'tcx>)
+                                                  -> Option<FunctionCoverageContext> {
+    let coverage_cx = match *ccx.coverage_cx() {
+        Some(ref coverage_cx) => coverage_cx,
+        None => return None,
+    };
+
+    let codemap = ccx.sess().codemap();
+    let mut regions = Vec::new();
+    for block_data in mir.basic_blocks() {
+        for statement in &block_data.statements {
+            if let mir::StatementKind::Coverage { index } = statement.kind {
+                let span = statement.source_info.span;
+                let lo = codemap.lookup_char_pos(span.lo());
+                let hi = codemap.lookup_char_pos(span.hi());
+                // Lines are 1-based already but columns are not. Since `hi`
+                // points just past the region, the end column is exclusive,
+                // which is what LLVM expects.
+                regions.push(CodeRegion {
+                    counter: index,
+                    start_line: lo.line as u32,
+                    start_col: lo.col.0 as u32 + 1,
+                    end_line: hi.line as u32,
+                    end_col: hi.col.0 as u32 + 1,
+                });
+            }
+        }
+    }
+    if regions.is_empty() {
+        return None;
+    }
+    regions.sort_by_key(|region| region.counter);
+
+    let file_name = codemap.lookup_char_pos(mir.span.lo()).file.name.clone();
+    let name = CString::new(ccx.tcx().symbol_name(instance).to_string()).unwrap();
+    let num_counters = regions.len() as u32;
+
+    // The hash only has to tell apart different versions of the function,
+    // so that stale profiles are not matched against a new coverage map.
+    let mut hasher = DefaultHasher::new();
+    regions.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    let name_var = unsafe {
+        llvm::LLVMRustCoverageCreatePGOFuncNameVar(llfn, name.as_ptr())
+    };
+
+    coverage_cx.functions.borrow_mut().push(FunctionCoverage {
+        name,
+        hash,
+        file_name,
+        regions,
+    });
+
+    Some(FunctionCoverageContext {
+        name_var,
+        hash,
+        num_counters,
+    })
+}
+
+/// Emits the increment of the counter `index` of the current function.
+pub fn increment_counter(bcx: &Builder, fcx: &FunctionCoverageContext, index: u32) {
+    let ccx = bcx.ccx;
+    let llfn = ccx.get_intrinsic("llvm.instrprof.increment");
+    let name = consts::ptrcast(fcx.name_var, Type::i8p(ccx));
+    bcx.call(llfn, &[name,
+                     C_u64(ccx, fcx.hash),
+                     C_u32(ccx, fcx.num_counters),
+                     C_u32(ccx, index)], None);
+}
+
+/// Writes the coverage map of the codegen unit, if any function in it has
+/// been instrumented.
+pub fn finalize(ccx: &CrateContext) {
+    let coverage_cx = match *ccx.coverage_cx() {
+        Some(ref coverage_cx) => coverage_cx,
+        None => return,
+    };
+    let functions = coverage_cx.functions.borrow();
+    if functions.is_empty() {
+        return;
+    }
+
+    let mut file_names = Vec::new();
+    let mut records = Vec::new();
+    let mut mappings = Vec::new();
+    for function in functions.iter() {
+        let file_id = match file_names.iter().position(|f| *f == function.file_name) {
+            Some(file_id) => file_id,
+            None => {
+                file_names.push(function.file_name.clone());
+                file_names.len() - 1
+            }
+        };
+
+        let mapping = write_mapping(file_id as c_uint, &function.regions);
+        let name_ref = unsafe {
+            llvm::LLVMRustCoverageComputeHash(function.name.as_ptr())
+        };
+        records.push(C_struct(ccx, &[C_u64(ccx, name_ref),
+                                     C_u32(ccx, mapping.len() as u32),
+                                     C_u64(ccx, function.hash)], true));
+        mappings.extend(mapping);
+    }
+
+    // `llvm-cov` resolves relative file names against its own working
+    // directory, so make them absolute.
+    let working_dir = Path::new(&ccx.sess().working_dir.0[..]);
+    let file_names = file_names.iter().map(|name| {
+        CString::new(working_dir.join(name).to_string_lossy().into_owned()).unwrap()
+    }).collect::<Vec<_>>();
+    let file_name_ptrs = file_names.iter().map(|name| name.as_ptr()).collect::<Vec<_>>();
+    let filenames = llvm::build_byte_buffer(|s| unsafe {
+        llvm::LLVMRustCoverageWriteFilenamesSectionToBuffer(file_name_ptrs.as_ptr(),
+                                                            file_name_ptrs.len(),
+                                                            s);
+    });
+
+    let version = unsafe { llvm::LLVMRustCoverageMappingVersion() };
+    let header = C_struct(ccx, &[C_u32(ccx, records.len() as u32),
+                                 C_u32(ccx, filenames.len() as u32),
+                                 C_u32(ccx, mappings.len() as u32),
+                                 C_u32(ccx, version)], false);
+
+    let mut data = filenames;
+    data.extend(mappings);
+    while data.len() % 8 != 0 {
+        data.push(0);
+    }
+
+    let covmap = C_struct(ccx, &[header,
+                                 C_array(val_ty(records[0]), &records),
+                                 C_bytes(ccx, &data)], false);
+
+    unsafe {
+        let name = CString::new("__llvm_coverage_mapping").unwrap();
+        let section = llvm::build_string(|s| {
+            llvm::LLVMRustCoverageWriteSectionNameToString(ccx.llmod(), s);
+        }).expect("non-UTF8 coverage section name from LLVM");
+        let section = CString::new(section).unwrap();
+
+        let g = llvm::LLVMAddGlobal(ccx.llmod(), val_ty(covmap).to_ref(), name.as_ptr());
+        llvm::LLVMSetInitializer(g, covmap);
+        llvm::LLVMSetGlobalConstant(g, llvm::True);
+        llvm::LLVMRustSetLinkage(g, llvm::Linkage::InternalLinkage);
+        llvm::LLVMSetSection(g, section.as_ptr());
+        llvm::LLVMSetAlignment(g, 8);
+
+        // Nothing refers to the coverage map, keep it from being removed.
+        let cast = consts::ptrcast(g, Type::i8p(ccx));
+        ccx.used_statics().borrow_mut().push(cast);
+    }
+}
+
+/// Encodes the regions of a single function, which all lie in the file
+/// `file_id` of the filenames table.
+fn write_mapping(file_id: c_uint, regions: &[CodeRegion]) -> Vec<u8> {
+    let virtual_file_mapping = [file_id];
+    let regions = regions.iter().map(|region| {
+        llvm::CounterMappingRegion {
+            counter: region.counter,
+            file_id: 0,
+            start_line: region.start_line,
+            start_col: region.start_col,
+            end_line: region.end_line,
+            end_col: region.end_col,
+        }
+    }).collect::<Vec<_>>();
+    llvm::build_byte_buffer(|s| unsafe {
+        llvm::LLVMRustCoverageWriteMappingToBuffer(virtual_file_mapping.as_ptr(),
+                                                   virtual_file_mapping.len() as c_uint,
+                                                   regions.as_ptr(),
+                                                   regions.len() as c_uint,
+                                                   s);
+    })
+}
@@ -52,6 +52,7 @@ pub fn push_debuginfo_type_name<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
             push_item_name(cx, def.did, qualified, output);
             push_type_params(cx, substs, output);
         },
+        ty::TyForeign(def_id) => push_item_name(cx, def_id, qualified, output),
         ty::TyTuple(component_types, _) => {
             output.push('(');
             for &component_type in component_types {
@@ -119,6 +119,7 @@ mod collector;
 mod common;
 mod consts;
 mod context;
+mod coverageinfo;
 mod debuginfo;
 mod declare;
 mod glue;
@@ -173,6 +174,13 @@ impl ModuleTranslation {
             emit_bc,
         }
     }
+
+    pub fn llvm(&self) -> Option<&ModuleLlvm> {
+        match self.source {
+            ModuleSource::Translated(ref llvm) => Some(llvm),
+            ModuleSource::Preexisting(_) => None,
+        }
+    }
 }
 
 impl Drop for ModuleTranslation {
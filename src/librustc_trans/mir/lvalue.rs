@@ -152,10 +152,10 @@ impl<'a, 'tcx> LvalueRef<'tcx> {
                         ptr_val, adt::struct_llfields_index(st, ix)), alignment);
             }
 
-        // If the type of the last field is [T] or str, then we don't need to do
+        // If the type of the last field is [T], str or a foreign type, then we don't need to do
         // any adjusments
         match fty.sty {
-            ty::TySlice(..) | ty::TyStr => {
+            ty::TySlice(..) | ty::TyStr | ty::TyForeign(..) => {
                 return (bcx.struct_gep(
                         ptr_val, adt::struct_llfields_index(st, ix)), alignment);
             }
@@ -328,7 +328,8 @@ impl<'a, 'tcx> MirContext<'a, 'tcx> {
                 let ((llprojected, align), llextra) = match projection.elem {
                     mir::ProjectionElem::Deref => bug!(),
                     mir::ProjectionElem::Field(ref field, _) => {
-                        let llextra = if self.ccx.shared().type_is_sized(projected_ty.to_ty(tcx)) {
+                        let field_ty = projected_ty.to_ty(tcx);
+                        let llextra = if !self.ccx.shared().type_has_metadata(field_ty) {
                             ptr::null_mut()
                         } else {
                             tr_base.llextra
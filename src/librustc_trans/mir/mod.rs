@@ -21,6 +21,7 @@ use rustc::session::config::FullDebugInfo;
 use base;
 use builder::Builder;
 use common::{self, CrateContext, Funclet};
+use coverageinfo;
 use debuginfo::{self, declare_local, VariableAccess, VariableKind, FunctionDebugContext};
 use monomorphize::Instance;
 use abi::FnType;
@@ -48,6 +49,9 @@ pub struct MirContext<'a, 'tcx:'a> {
 
     debug_context: debuginfo::FunctionDebugContext,
 
+    /// Present if the function is instrumented for coverage reports.
+    coverage_context: Option<coverageinfo::FunctionCoverageContext>,
+
     llfn: ValueRef,
 
     ccx: &'a CrateContext<'a, 'tcx>,
@@ -204,6 +208,8 @@ pub fn trans_mir<'a, 'tcx: 'a>(
     debug!("fn_ty: {:?}", fn_ty);
     let debug_context =
         debuginfo::create_function_debug_context(ccx, instance, sig, llfn, mir);
+    let coverage_context =
+        coverageinfo::create_function_coverage_context(ccx, instance, llfn, mir);
     let bcx = Builder::new_block(ccx, llfn, "start");
 
     if mir.basic_blocks().iter().any(|bb| bb.is_cleanup) {
@@ -241,6 +247,7 @@ pub fn trans_mir<'a, 'tcx: 'a>(
         scopes,
         locals: IndexVec::new(),
         debug_context,
+        coverage_context,
         param_substs: {
             assert!(!instance.substs.needs_infer());
             instance.substs
@@ -13,6 +13,7 @@ use rustc::mir;
 use base;
 use asm;
 use common;
+use coverageinfo;
 use builder::Builder;
 
 use super::MirContext;
@@ -86,6 +87,12 @@ impl<'a, 'tcx> MirContext<'a, 'tcx> {
                 asm::trans_inline_asm(&bcx, asm, outputs, input_vals);
                 bcx
             }
+            mir::StatementKind::Coverage { index } => {
+                if let Some(ref coverage_context) = self.coverage_context {
+                    coverageinfo::increment_counter(&bcx, coverage_context, index);
+                }
+                bcx
+            }
             mir::StatementKind::EndRegion(_) |
             mir::StatementKind::Validate(..) |
             mir::StatementKind::Nop => bcx,
@@ -13,7 +13,7 @@ use common::*;
 use glue;
 
 use rustc::hir::def_id::DefId;
-use rustc::middle::lang_items::DropInPlaceFnLangItem;
+use rustc::middle::lang_items::{DropInPlaceFnLangItem, StartFnLangItem};
 use rustc::traits;
 use rustc::ty::adjustment::CustomCoerceUnsized;
 use rustc::ty::subst::{Kind, Subst, Substs};
@@ -219,6 +219,25 @@ pub fn resolve_drop_in_place<'a, 'tcx>(
     resolve(scx, def_id, substs)
 }
 
+/// Resolves the `start` lang item used to run `main_def_id`. The lang item
+/// is generic over the return type of `main` when that type is reported
+/// through the `Termination` trait.
+pub fn resolve_start_fn<'a, 'tcx>(
+    scx: &SharedCrateContext<'a, 'tcx>,
+    main_def_id: DefId)
+    -> ty::Instance<'tcx>
+{
+    let tcx = scx.tcx();
+    let start_def_id = tcx.require_lang_item(StartFnLangItem);
+    let substs = if tcx.generics_of(start_def_id).types.is_empty() {
+        Substs::empty()
+    } else {
+        let main_ret_ty = tcx.erase_late_bound_regions(&tcx.fn_sig(main_def_id).output());
+        tcx.intern_substs(&[Kind::from(main_ret_ty)])
+    };
+    resolve(scx, start_def_id, substs)
+}
+
 pub fn custom_coerce_unsize_info<'scx, 'tcx>(scx: &SharedCrateContext<'scx, 'tcx>,
                                              source_ty: Ty<'tcx>,
                                              target_ty: Ty<'tcx>)
@@ -414,6 +414,7 @@ impl<'a, 'tcx> DefPathBasedNames<'a, 'tcx> {
                 self.push_def_path(adt_def.did, output);
                 self.push_type_params(substs, iter::empty(), output);
             },
+            ty::TyForeign(did) => self.push_def_path(did, output),
             ty::TyTuple(component_types, _) => {
                 output.push('(');
                 for &component_type in component_types {
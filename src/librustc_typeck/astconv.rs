@@ -22,7 +22,6 @@ use rustc::ty::subst::{Kind, Subst, Substs};
 use rustc::traits;
 use rustc::ty::{self, Ty, TyCtxt, ToPredicate, TypeFoldable};
 use rustc::ty::wf::object_region_bounds;
-use rustc_back::slice;
 use require_c_abi_if_variadic;
 use util::common::ErrorReported;
 use util::nodemap::FxHashSet;
@@ -55,6 +54,12 @@ pub trait AstConv<'gcx, 'tcx> {
         self.ty_infer(span)
     }
 
+    /// What const should we use for an omitted const argument?
+    fn ct_infer_for_def(&self,
+                        def: &ty::ConstParameterDef,
+                        ty: Ty<'tcx>,
+                        span: Span) -> &'tcx ty::Const<'tcx>;
+
     /// Projecting an associated type from a (potentially)
     /// higher-ranked trait reference is more complicated, because of
     /// the possibility of late-bound regions appearing in the
@@ -62,9 +67,13 @@ pub trait AstConv<'gcx, 'tcx> {
     /// signatures for that reason. In a function body, we can always
     /// handle it because we can use inference variables to remove the
     /// late-bound regions.
+    ///
+    /// `item_segment` is the path segment naming the associated type,
+    /// which supplies the arguments for its own generic parameters.
     fn projected_ty_from_poly_trait_ref(&self,
                                         span: Span,
                                         item_def_id: DefId,
+                                        item_segment: &hir::PathSegment,
                                         poly_trait_ref: ty::PolyTraitRef<'tcx>)
                                         -> Ty<'tcx>;
 
@@ -202,6 +211,17 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
             check_type_argument_count(tcx, span, num_types_provided, ty_param_defs);
         }
 
+        // Const parameters have no defaults, so all of them must be supplied,
+        // unless they can be inferred.
+        let num_consts_provided = parameters.consts.len();
+        let expected_num_const_params = decl_generics.consts.len();
+        if (!parameters.infer_types || num_consts_provided > 0) &&
+           num_consts_provided != expected_num_const_params {
+            report_const_argument_number_error(tcx, span,
+                                               num_consts_provided,
+                                               expected_num_const_params);
+        }
+
         let is_object = self_ty.map_or(false, |ty| ty.sty == TRAIT_OBJECT_DUMMY_SELF);
         let default_needs_object_self = |p: &ty::TypeParameterDef| {
             if is_object && p.has_default {
@@ -271,6 +291,19 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                 // We've already errored above about the mismatch.
                 tcx.types.err
             }
+        }, |def, _| {
+            let i = def.index as usize - decl_generics.parent_count() -
+                decl_generics.regions.len() - decl_generics.types.len();
+            let ty = tcx.at(span).type_of(def.def_id);
+            if let Some(arg) = parameters.consts.get(i) {
+                // A provided const parameter.
+                self.ast_const_to_const(arg, ty)
+            } else if parameters.infer_types {
+                self.ct_infer_for_def(def, ty, span)
+            } else {
+                // We've already errored above about the mismatch.
+                tcx.mk_const_err()
+            }
         });
 
         let assoc_bindings = parameters.bindings.iter().map(|binding| {
@@ -287,6 +320,150 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         (substs, assoc_bindings)
     }
 
+    /// Given the substitutions `parent_substs` for the trait or impl that
+    /// contains the associated item `item_def_id`, returns the complete set
+    /// of substitutions for a reference to that item, taking the arguments
+    /// for its own generic parameters from `item_segment`, e.g. the `'a` in
+    /// `T::Item<'a>`.
+    pub fn create_substs_for_associated_item(&self,
+        span: Span,
+        item_def_id: DefId,
+        item_segment: &hir::PathSegment,
+        parent_substs: &Substs<'tcx>)
+        -> &'tcx Substs<'tcx>
+    {
+        let tcx = self.tcx();
+        let parameters = &item_segment.parameters;
+        let decl_generics = tcx.generics_of(item_def_id);
+        let parent_count = decl_generics.parent_count();
+
+        debug!("create_substs_for_associated_item(item_def_id={:?}, parent_substs={:?}, \
+               parameters={:?})",
+               item_def_id, parent_substs, parameters);
+
+        if parameters.lifetimes.len() != decl_generics.regions.len() {
+            report_lifetime_number_error(tcx, span,
+                                         parameters.lifetimes.len(),
+                                         decl_generics.regions.len());
+        }
+        let num_types_provided = parameters.types.len();
+        if !parameters.infer_types || num_types_provided > decl_generics.types.len() {
+            check_type_argument_count(tcx, span, num_types_provided, &decl_generics.types);
+        }
+        let num_consts_provided = parameters.consts.len();
+        if (!parameters.infer_types || num_consts_provided > 0) &&
+           num_consts_provided != decl_generics.consts.len() {
+            report_const_argument_number_error(tcx, span,
+                                               num_consts_provided,
+                                               decl_generics.consts.len());
+        }
+        if let Some(binding) = parameters.bindings.first() {
+            self.prohibit_projection(binding.span);
+        }
+
+        Substs::for_item(tcx, item_def_id, |def, _| {
+            let i = def.index as usize;
+            if i < parent_count {
+                return parent_substs.region_at(i);
+            }
+            match parameters.lifetimes.get(i - parent_count) {
+                Some(lifetime) => self.ast_region_to_region(lifetime, Some(def)),
+                None => tcx.types.re_static,
+            }
+        }, |def, substs| {
+            let i = def.index as usize;
+            if i < parent_count {
+                return parent_substs.type_at(i);
+            }
+            let i = i - parent_count - decl_generics.regions.len();
+            if let Some(ty) = parameters.types.get(i) {
+                self.ast_ty_to_ty(ty)
+            } else if parameters.infer_types {
+                self.ty_infer_for_def(def, substs, span)
+            } else {
+                // We've already errored above about the mismatch.
+                tcx.types.err
+            }
+        }, |def, _| {
+            let i = def.index as usize;
+            if i < parent_count {
+                return parent_substs.const_at(i);
+            }
+            let i = i - parent_count - decl_generics.regions.len() - decl_generics.types.len();
+            let ty = tcx.at(span).type_of(def.def_id);
+            if let Some(arg) = parameters.consts.get(i) {
+                self.ast_const_to_const(arg, ty)
+            } else if parameters.infer_types {
+                self.ct_infer_for_def(def, ty, span)
+            } else {
+                // We've already errored above about the mismatch.
+                tcx.mk_const_err()
+            }
+        })
+    }
+
+    /// Converts a const generic argument, like the `3` in `Buf<3>`, into a
+    /// constant of type `ty`.
+    pub fn ast_const_to_const(&self, arg: &hir::ConstArg, ty: Ty<'tcx>)
+                              -> &'tcx ty::Const<'tcx> {
+        match *arg {
+            hir::ConstArg::Param(_, ref path) => self.const_param_path_to_const(path),
+            hir::ConstArg::Expr(body) => self.anon_const_to_const(body, ty),
+        }
+    }
+
+    /// Converts the body of an anonymous constant (a const argument or an
+    /// array length). A body that just names a const parameter, possibly
+    /// within braces as in `{ N }`, refers to that parameter directly.
+    pub fn anon_const_to_const(&self, body: hir::BodyId, ty: Ty<'tcx>)
+                               -> &'tcx ty::Const<'tcx> {
+        let tcx = self.tcx();
+
+        let mut expr = &tcx.hir.body(body).value;
+        loop {
+            match expr.node {
+                hir::ExprBlock(ref block) if block.stmts.is_empty() => {
+                    match block.expr {
+                        Some(ref inner) => expr = inner,
+                        None => break,
+                    }
+                }
+                hir::ExprPath(hir::QPath::Resolved(None, ref path)) => {
+                    if let Def::ConstParam(_) = path.def {
+                        return self.const_param_path_to_const(path);
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        let def_id = tcx.hir.body_owner_def_id(body);
+        let substs = Substs::identity_for_item(tcx, def_id);
+        tcx.mk_const(ty::Const {
+            val: ConstVal::Unevaluated(def_id, substs),
+            ty,
+        })
+    }
+
+    fn const_param_path_to_const(&self, path: &hir::Path) -> &'tcx ty::Const<'tcx> {
+        let tcx = self.tcx();
+        let did = match path.def {
+            Def::ConstParam(did) => did,
+            Def::Err => return tcx.mk_const_err(),
+            def => span_bug!(path.span, "expected a const parameter, found {:?}", def),
+        };
+
+        let node_id = tcx.hir.as_local_node_id(did).unwrap();
+        let item_id = tcx.hir.get_parent_node(node_id);
+        let item_def_id = tcx.hir.local_def_id(item_id);
+        let generics = tcx.generics_of(item_def_id);
+        let def = generics.consts.iter().find(|def| def.def_id == did).unwrap_or_else(|| {
+            span_bug!(path.span, "const parameter `{}` not found in {:?}", path, item_def_id)
+        });
+        tcx.mk_const_param_from_def(def)
+    }
+
     /// Instantiates the path for the given trait reference, assuming that it's
     /// bound to a valid trait type. Returns the def_id for the defining trait.
     /// Fails if the type is a type other than a trait type.
@@ -310,7 +487,8 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
     fn trait_def_id(&self, trait_ref: &hir::TraitRef) -> DefId {
         let path = &trait_ref.path;
         match path.def {
-            Def::Trait(trait_def_id) => trait_def_id,
+            Def::Trait(trait_def_id) |
+            Def::TraitAlias(trait_def_id) => trait_def_id,
             Def::Err => {
                 self.tcx().sess.fatal("cannot continue compilation due to previous error");
             }
@@ -468,29 +646,36 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         }
 
         // Simple case: X is defined in the current trait.
-        if self.trait_defines_associated_type_named(trait_ref.def_id(), binding.item_name) {
-            return Ok(trait_ref.map_bound(|trait_ref| {
-                ty::ProjectionPredicate {
-                    projection_ty: ty::ProjectionTy::from_ref_and_name(
-                        tcx,
-                        trait_ref,
-                        binding.item_name,
-                    ),
-                    ty: binding.ty,
-                }
-            }));
-        }
-
-        // Otherwise, we have to walk through the supertraits to find
-        // those that do.
-        let candidates =
-            traits::supertraits(tcx, trait_ref.clone())
-            .filter(|r| self.trait_defines_associated_type_named(r.def_id(), binding.item_name));
+        let candidate = if self.trait_defines_associated_type_named(trait_ref.def_id(),
+                                                                   binding.item_name) {
+            trait_ref
+        } else {
+            // Otherwise, we have to walk through the supertraits to find
+            // those that do.
+            let candidates =
+                traits::supertraits(tcx, trait_ref.clone())
+                .filter(|r| self.trait_defines_associated_type_named(r.def_id(),
+                                                                     binding.item_name));
+
+            self.one_bound_for_assoc_type(candidates,
+                                          &trait_ref.to_string(),
+                                          &binding.item_name.as_str(),
+                                          binding.span)?
+        };
 
-        let candidate = self.one_bound_for_assoc_type(candidates,
-                                                      &trait_ref.to_string(),
-                                                      &binding.item_name.as_str(),
-                                                      binding.span)?;
+        // A binding has nowhere to put the arguments of a generic
+        // associated type, so it cannot name one.
+        let item = tcx.associated_items(candidate.def_id())
+            .find(|i| i.kind == ty::AssociatedKind::Type && i.name == binding.item_name)
+            .expect("missing associated type");
+        if tcx.generics_of(item.def_id).own_count() != 0 {
+            struct_span_err!(tcx.sess, binding.span, E0635,
+                             "cannot constrain the generic associated type `{}` in a binding",
+                             binding.item_name)
+                .span_label(binding.span, "associated type with generic parameters")
+                .emit();
+            return Err(ErrorReported);
+        }
 
         Ok(candidate.map_bound(|trait_ref| {
             ty::ProjectionPredicate {
@@ -541,18 +726,22 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
         let mut projection_bounds = vec![];
         let dummy_self = tcx.mk_ty(TRAIT_OBJECT_DUMMY_SELF);
-        let principal = self.instantiate_poly_trait_ref(&trait_bounds[0],
-                                                        dummy_self,
-                                                        &mut projection_bounds);
+        let mut principal = self.instantiate_poly_trait_ref(&trait_bounds[0],
+                                                            dummy_self,
+                                                            &mut projection_bounds);
 
+        let mut alias_bounds = vec![];
         for trait_bound in trait_bounds[1..].iter() {
             // Sanity check for non-principal trait bounds
-            self.instantiate_poly_trait_ref(trait_bound,
-                                            dummy_self,
-                                            &mut vec![]);
+            let trait_ref = self.instantiate_poly_trait_ref(trait_bound,
+                                                            dummy_self,
+                                                            &mut vec![]);
+            if tcx.is_trait_alias(trait_ref.def_id()) {
+                alias_bounds.push(trait_ref);
+            }
         }
 
-        let (auto_traits, trait_bounds) = split_auto_traits(tcx, &trait_bounds[1..]);
+        let (mut auto_traits, trait_bounds) = split_auto_traits(tcx, &trait_bounds[1..]);
 
         if !trait_bounds.is_empty() {
             let b = &trait_bounds[0];
@@ -563,6 +752,40 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                 .emit();
         }
 
+        // Replace trait aliases by the traits they stand for. The first
+        // trait of the expansion becomes the principal, the others must be
+        // auto traits.
+        if tcx.is_trait_alias(principal.def_id()) || !alias_bounds.is_empty() {
+            let expansion =
+                traits::expand_trait_aliases(tcx, iter::once(principal).chain(alias_bounds));
+            projection_bounds.extend(expansion.projections);
+
+            let mut expanded = expansion.trait_refs.into_iter();
+            principal = match expanded.next() {
+                Some(trait_ref) => trait_ref,
+                None => {
+                    span_err!(tcx.sess, span, E0224,
+                              "at least one non-builtin trait is required for an object type");
+                    return tcx.types.err;
+                }
+            };
+            for trait_ref in expanded {
+                let def_id = trait_ref.def_id();
+                if Some(def_id) == tcx.lang_items().send_trait() ||
+                   Some(def_id) == tcx.lang_items().sync_trait() {
+                    auto_traits.push(def_id);
+                } else {
+                    struct_span_err!(tcx.sess, span, E0225,
+                        "only Send/Sync traits can be used as additional traits in a trait object")
+                        .span_label(span, format!("non-Send/Sync additional trait `{}`",
+                                                  tcx.item_path_str(def_id)))
+                        .emit();
+                }
+            }
+            auto_traits.sort();
+            auto_traits.dedup();
+        }
+
         // Erase the dummy_self (TRAIT_OBJECT_DUMMY_SELF) used above.
         let existential_principal = principal.map_bound(|trait_ref| {
             self.trait_ref_to_existential(trait_ref)
@@ -590,10 +813,13 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
             return tcx.types.err;
         }
 
+        // The anonymous associated types of `impl Trait` return types
+        // cannot be named, and make the trait not object safe anyway.
         let mut associated_types = FxHashSet::default();
         for tr in traits::supertraits(tcx, principal) {
             associated_types.extend(tcx.associated_items(tr.def_id())
                 .filter(|item| item.kind == ty::AssociatedKind::Type)
+                .filter(|item| !tcx.is_impl_trait_assoc(item.def_id))
                 .map(|item| item.def_id));
         }
 
@@ -766,8 +992,6 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
         debug!("associated_path_def_to_ty: {:?}::{}", ty, assoc_name);
 
-        self.prohibit_type_params(slice::ref_slice(item_segment));
-
         // Find the type of the associated item, and the trait where the associated
         // item is declared.
         let bound = match (&ty.sty, ty_path_def) {
@@ -818,7 +1042,7 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         let item = tcx.associated_items(trait_did).find(|i| i.name == assoc_name)
                                                   .expect("missing associated type");
 
-        let ty = self.projected_ty_from_poly_trait_ref(span, item.def_id, bound);
+        let ty = self.projected_ty_from_poly_trait_ref(span, item.def_id, item_segment, bound);
         let ty = self.normalize_ty(span, ty);
 
         let def = Def::AssociatedTy(item.def_id);
@@ -843,8 +1067,6 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         let tcx = self.tcx();
         let trait_def_id = tcx.parent_def_id(item_def_id).unwrap();
 
-        self.prohibit_type_params(slice::ref_slice(item_segment));
-
         let self_ty = if let Some(ty) = opt_self_ty {
             ty
         } else {
@@ -865,7 +1087,11 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
         debug!("qpath_to_ty: trait_ref={:?}", trait_ref);
 
-        self.normalize_ty(span, tcx.mk_projection(item_def_id, trait_ref.substs))
+        let substs = self.create_substs_for_associated_item(span,
+                                                            item_def_id,
+                                                            item_segment,
+                                                            trait_ref.substs);
+        self.normalize_ty(span, tcx.mk_projection(item_def_id, substs))
     }
 
     pub fn prohibit_type_params(&self, segments: &[hir::PathSegment]) {
@@ -911,7 +1137,8 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
         let span = path.span;
         match path.def {
-            Def::Enum(did) | Def::TyAlias(did) | Def::Struct(did) | Def::Union(did) => {
+            Def::Enum(did) | Def::TyAlias(did) | Def::Struct(did) |
+            Def::Union(did) | Def::TyForeign(did) => {
                 assert_eq!(opt_self_ty, None);
                 self.prohibit_type_params(path.segments.split_last().unwrap().1);
                 self.ast_path_to_ty(span, did, path.segments.last().unwrap())
@@ -970,6 +1197,14 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                     hir::TyStr => tcx.mk_str()
                 }
             }
+            Def::ConstParam(_) => {
+                // Const parameters are only valid as types in generic argument
+                // position, where lowering turns them into const arguments.
+                tcx.sess.span_err(span, &format!("expected type, found const parameter `{}`",
+                                                 path));
+                self.set_tainted_by_errors();
+                tcx.types.err
+            }
             Def::Err => {
                 self.set_tainted_by_errors();
                 return self.tcx().types.err;
@@ -1015,7 +1250,23 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
             hir::TyTraitObject(ref bounds, ref lifetime) => {
                 self.conv_object_ty_poly_trait_ref(ast_ty.span, bounds, lifetime)
             }
-            hir::TyImplTrait(_) => {
+            hir::TyImplTraitUniversal(fn_def_id, _) => {
+                // An anonymous type parameter of the function.
+                let def_id = tcx.hir.local_def_id(ast_ty.id);
+                let generics = tcx.generics_of(fn_def_id);
+                let index = generics.type_param_to_index[&def_id.index];
+                let param = generics.types.iter().find(|p| p.index == index).unwrap();
+                tcx.mk_param_from_def(param)
+            }
+            hir::TyImplTraitExistential(_) => {
+                let def_id = tcx.hir.local_def_id(ast_ty.id);
+
+                // The return type of a trait method, or of its implementation,
+                // is an anonymous associated type.
+                if tcx.is_impl_trait_assoc(def_id) {
+                    return self.impl_trait_assoc_to_ty(ast_ty.span, def_id);
+                }
+
                 // Figure out if we can allow an `impl Trait` here, by walking up
                 // to a `fn` or inherent `impl` method, going only through `Ty`
                 // or `TraitRef` nodes (as nothing else should be in types) and
@@ -1054,7 +1305,6 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
                 // Create the anonymized type.
                 if allow {
-                    let def_id = tcx.hir.local_def_id(ast_ty.id);
                     tcx.mk_anon(def_id, Substs::identity_for_item(tcx, def_id))
                 } else {
                     span_err!(tcx.sess, ast_ty.span, E0562,
@@ -1082,12 +1332,7 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                 self.associated_path_def_to_ty(ast_ty.id, ast_ty.span, ty, def, segment).0
             }
             hir::TyArray(ref ty, length) => {
-                let length_def_id = tcx.hir.body_owner_def_id(length);
-                let substs = Substs::identity_for_item(tcx, length_def_id);
-                let length = tcx.mk_const(ty::Const {
-                    val: ConstVal::Unevaluated(length_def_id, substs),
-                    ty: tcx.types.usize
-                });
+                let length = self.anon_const_to_const(length, tcx.types.usize);
                 let array_ty = tcx.mk_ty(ty::TyArray(self.ast_ty_to_ty(&ty), length));
                 self.normalize_ty(ast_ty.span, array_ty)
             }
@@ -1114,6 +1359,39 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         result_ty
     }
 
+    /// Converts the `impl Trait` return type `def_id` of a trait method, or
+    /// of a method implementing one, to the anonymous associated type it
+    /// stands for: a projection in the trait, and in the impl the
+    /// anonymized type the method body chooses.
+    fn impl_trait_assoc_to_ty(&self, span: Span, def_id: DefId) -> Ty<'tcx> {
+        let tcx = self.tcx();
+
+        if !tcx.sess.features.borrow().impl_trait_in_traits {
+            emit_feature_err(&tcx.sess.parse_sess, "impl_trait_in_traits", span,
+                             GateIssue::Language,
+                             "`impl Trait` in trait method return types is experimental");
+        }
+
+        let substs = Substs::identity_for_item(tcx, def_id);
+        match tcx.associated_item(def_id).container {
+            ty::TraitContainer(_) => {
+                let node_id = tcx.hir.as_local_node_id(def_id).unwrap();
+                let method_id = tcx.hir.get_parent_node(node_id);
+                if let hir::TraitItemKind::Method(_, hir::TraitMethod::Provided(_)) =
+                        tcx.hir.expect_trait_item(method_id).node {
+                    struct_span_err!(tcx.sess, span, E0638,
+                                     "trait methods returning `impl Trait` cannot have a \
+                                      default body")
+                        .span_label(span, "the type each impl returns is chosen by that impl")
+                        .emit();
+                    return tcx.types.err;
+                }
+                tcx.mk_projection(def_id, substs)
+            }
+            ty::ImplContainer(_) => tcx.mk_anon(def_id, substs),
+        }
+    }
+
     pub fn ty_of_arg(&self,
                      ty: &hir::Ty,
                      expected_ty: Option<Ty<'tcx>>)
@@ -1286,7 +1564,7 @@ fn split_auto_traits<'a, 'b, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
                                          trait_bounds: &'b [hir::PolyTraitRef])
     -> (Vec<DefId>, Vec<&'b hir::PolyTraitRef>)
 {
-    let (auto_traits, trait_bounds): (Vec<_>, _) = trait_bounds.iter().partition(|bound| {
+    let (auto_traits, trait_bounds): (Vec<_>, Vec<_>) = trait_bounds.iter().partition(|bound| {
         match bound.trait_ref.path.def {
             Def::Trait(trait_did) => {
                 // Checks whether `trait_did` refers to one of the builtin
@@ -1320,6 +1598,14 @@ fn split_auto_traits<'a, 'b, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
         }
     }).collect::<Vec<_>>();
 
+    // Trait aliases are expanded by the caller.
+    let trait_bounds = trait_bounds.into_iter().filter(|bound| {
+        match bound.trait_ref.path.def {
+            Def::TraitAlias(..) => false,
+            _ => true
+        }
+    }).collect();
+
     (auto_traits, trait_bounds)
 }
 
@@ -1365,6 +1651,15 @@ fn check_type_argument_count(tcx: TyCtxt, span: Span, supplied: usize,
     }
 }
 
+fn report_const_argument_number_error(tcx: TyCtxt, span: Span, number: usize, expected: usize) {
+    let plural = if expected == 1 { "" } else { "s" };
+    struct_span_err!(tcx.sess, span, E0633,
+                     "wrong number of const arguments: expected {}, found {}",
+                     expected, number)
+        .span_label(span, format!("expected {} const argument{}", expected, plural))
+        .emit();
+}
+
 fn report_lifetime_number_error(tcx: TyCtxt, span: Span, number: usize, expected: usize) {
     let label = if number < expected {
         if expected == 1 {
@@ -0,0 +1,202 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Validation of the operands of checked inline assembly against the
+//! registers of the target, see `rustc_back::asm`.
+
+use check::FnCtxt;
+use rustc::hir;
+use rustc::ty::{self, Ty};
+use rustc_back::asm::{self, AsmArch, AsmRegClass, AsmValueKind};
+use syntax::ast::{InlineAsmOperandReg, InlineAsmRegOrRegClass};
+use syntax_pos::Span;
+
+use std::collections::HashMap;
+
+impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
+    /// Checks that the registers of checked inline assembly exist on the
+    /// target, can hold the types of their operands and don't conflict with
+    /// each other.
+    pub fn check_asm_regs(&self,
+                          span: Span,
+                          ia: &hir::InlineAsm,
+                          output_tys: &[Ty<'tcx>],
+                          input_tys: &[Ty<'tcx>]) {
+        let regs = match ia.regs {
+            Some(ref regs) => regs,
+            None => return,
+        };
+
+        let target_arch = &self.tcx.sess.target.target.arch;
+        let arch = match asm::arch(target_arch) {
+            Some(arch) => arch,
+            None => {
+                self.tcx.sess.span_err(span, &format!("checked inline assembly is not \
+                                                       supported on `{}`", target_arch));
+                return;
+            }
+        };
+
+        // Explicit registers in use, by canonical name. Outputs which aren't
+        // late may not share their register with an input.
+        let mut inputs = HashMap::new();
+        let mut outputs = HashMap::new();
+
+        for (reg, &ty) in regs.inputs.iter().zip(input_tys) {
+            if let Some(name) = self.check_asm_operand(arch, reg, ty) {
+                if let Some(prev) = inputs.insert(name, reg.span) {
+                    self.report_asm_reg_conflict(reg.span, prev, name);
+                }
+            }
+        }
+
+        for (reg, &ty) in regs.outputs.iter().zip(output_tys) {
+            if let Some(name) = self.check_asm_operand(arch, reg, ty) {
+                self.add_asm_output_reg(&mut outputs, &inputs, name, reg.span, reg.late);
+            }
+        }
+
+        for &(clobber, span) in &regs.clobbers {
+            if let Some((_, name)) = self.check_asm_reg(arch, &clobber.as_str(), span) {
+                self.add_asm_output_reg(&mut outputs, &inputs, name, span, true);
+            }
+        }
+    }
+
+    /// Validates a single operand, returning the canonical name of its
+    /// register if it was given explicitly.
+    fn check_asm_operand(&self,
+                         arch: &AsmArch,
+                         reg: &InlineAsmOperandReg,
+                         ty: Ty<'tcx>)
+                         -> Option<&'static str> {
+        let (class, name) = match reg.reg {
+            InlineAsmRegOrRegClass::Reg(name) => {
+                match self.check_asm_reg(arch, &name.as_str(), reg.span) {
+                    Some((class, name)) => (class, Some(name)),
+                    None => return None,
+                }
+            }
+            InlineAsmRegOrRegClass::RegClass(name) => {
+                match arch.class(&name.as_str()) {
+                    Some(class) => (class, None),
+                    None => {
+                        self.tcx.sess.span_err(reg.span,
+                                               &format!("invalid register class `{}`", name));
+                        return None;
+                    }
+                }
+            }
+        };
+
+        self.check_asm_operand_ty(class, ty, reg.span);
+        name
+    }
+
+    fn check_asm_reg(&self,
+                     arch: &AsmArch,
+                     name: &str,
+                     span: Span)
+                     -> Option<(&'static AsmRegClass, &'static str)> {
+        if let Some(reason) = arch.reserved_reason(name) {
+            self.tcx.sess.span_err(span, &format!("invalid register `{}`: {}", name, reason));
+            return None;
+        }
+        let reg = arch.reg(name);
+        if reg.is_none() {
+            self.tcx.sess.span_err(span, &format!("invalid register `{}`", name));
+        }
+        reg
+    }
+
+    fn check_asm_operand_ty(&self, class: &AsmRegClass, ty: Ty<'tcx>, span: Span) {
+        let ty = self.resolve_type_vars_with_obligations(ty);
+        let pointer_bits = self.tcx.data_layout.pointer_size.bits();
+
+        let (kind, bits) = match self.asm_value_kind(ty, pointer_bits) {
+            Some(kind) => kind,
+            None => {
+                self.tcx.sess.span_err(span, &format!("cannot use value of type `{}` for \
+                                                       inline assembly", ty));
+                return;
+            }
+        };
+
+        let max_bits = class.max_bits.unwrap_or(pointer_bits);
+        if !class.kinds.contains(&kind) || bits > max_bits {
+            self.tcx.sess.span_err(span, &format!("type `{}` cannot be used with this \
+                                                   register class", ty));
+        }
+    }
+
+    /// The kind and size in bits of a value of type `ty` as seen by the
+    /// register allocator.
+    fn asm_value_kind(&self, ty: Ty<'tcx>, pointer_bits: u64) -> Option<(AsmValueKind, u64)> {
+        match ty.sty {
+            ty::TyInt(ast_ty) => {
+                Some((AsmValueKind::Int, ast_ty.bit_width().map_or(pointer_bits, |w| w as u64)))
+            }
+            ty::TyUint(ast_ty) => {
+                Some((AsmValueKind::Int, ast_ty.bit_width().map_or(pointer_bits, |w| w as u64)))
+            }
+            // Unconstrained integer and float literals default to `i32` and
+            // `f64` later on.
+            ty::TyInfer(ty::IntVar(_)) => Some((AsmValueKind::Int, 32)),
+            ty::TyFloat(ast_ty) => Some((AsmValueKind::Float, ast_ty.bit_width() as u64)),
+            ty::TyInfer(ty::FloatVar(_)) => Some((AsmValueKind::Float, 64)),
+            ty::TyRawPtr(ty::TypeAndMut { ty: pointee, .. }) |
+            ty::TyRef(_, ty::TypeAndMut { ty: pointee, .. }) => {
+                // Fat pointers don't fit in a single register.
+                match self.resolve_type_vars_with_obligations(pointee).sty {
+                    ty::TyStr | ty::TySlice(_) | ty::TyDynamic(..) => None,
+                    _ => Some((AsmValueKind::Int, pointer_bits)),
+                }
+            }
+            ty::TyFnPtr(_) => Some((AsmValueKind::Int, pointer_bits)),
+            ty::TyAdt(..) if ty.is_simd() => {
+                let elem = ty.simd_type(self.tcx);
+                let elem_bits = match self.asm_value_kind(elem, pointer_bits) {
+                    Some((AsmValueKind::Int, bits)) |
+                    Some((AsmValueKind::Float, bits)) => bits,
+                    _ => return None,
+                };
+                Some((AsmValueKind::Vector, elem_bits * ty.simd_size(self.tcx) as u64))
+            }
+            _ => None,
+        }
+    }
+
+    fn add_asm_output_reg(&self,
+                          outputs: &mut HashMap<&'static str, Span>,
+                          inputs: &HashMap<&'static str, Span>,
+                          name: &'static str,
+                          span: Span,
+                          late: bool) {
+        if let Some(prev) = outputs.insert(name, span) {
+            self.report_asm_reg_conflict(span, prev, name);
+        } else if !late {
+            if let Some(&input) = inputs.get(name) {
+                let mut err = self.tcx.sess.struct_span_err(
+                    span, &format!("register `{}` conflicts with an input", name));
+                err.span_label(input, "input uses the same register");
+                err.help("use `lateout` or `inlateout` if the output is only written \
+                          after all inputs have been read");
+                err.emit();
+            }
+        }
+    }
+
+    fn report_asm_reg_conflict(&self, span: Span, prev: Span, name: &str) {
+        let mut err = self.tcx.sess.struct_span_err(
+            span, &format!("register `{}` is used more than once", name));
+        err.span_label(prev, "first use of the register");
+        err.emit();
+    }
+}
@@ -97,6 +97,15 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             _ => None,
         }
     }
+
+    /// Returns true if the unsized tail of `t` is an extern type; pointers
+    /// to such types are thin even though the pointee is unsized.
+    fn has_foreign_tail(&self, t: Ty<'tcx>) -> bool {
+        match self.tcx.struct_tail(t).sty {
+            ty::TyForeign(..) => true,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -446,11 +455,18 @@ impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
         debug!("check_ptr_ptr_cast m_expr={:?} m_cast={:?}", m_expr, m_cast);
         // ptr-ptr cast. vtables must match.
 
-        // Cast to sized is OK
-        if fcx.type_is_known_to_be_sized(m_cast.ty, self.span) {
+        // Cast to sized or to a thin extern type is OK
+        if fcx.type_is_known_to_be_sized(m_cast.ty, self.span) ||
+           fcx.has_foreign_tail(m_cast.ty) {
             return Ok(CastKind::PtrPtrCast);
         }
 
+        // Pointers to extern types are thin, so they can be cast to anything sized,
+        // but not to other unsized types.
+        if fcx.has_foreign_tail(m_expr.ty) {
+            return Err(CastError::SizedUnsizedCast);
+        }
+
         // sized -> unsized? report invalid cast (don't complain about vtable kinds)
         if fcx.type_is_known_to_be_sized(m_expr.ty, self.span) {
             return Err(CastError::SizedUnsizedCast);
@@ -467,9 +483,10 @@ impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
                            fcx: &FnCtxt<'a, 'gcx, 'tcx>,
                            m_cast: &'tcx ty::TypeAndMut<'tcx>)
                            -> Result<CastKind, CastError> {
-        // fptr-ptr cast. must be to sized ptr
+        // fptr-ptr cast. must be to thin ptr
 
-        if fcx.type_is_known_to_be_sized(m_cast.ty, self.span) {
+        if fcx.type_is_known_to_be_sized(m_cast.ty, self.span) ||
+           fcx.has_foreign_tail(m_cast.ty) {
             Ok(CastKind::FnPtrPtrCast)
         } else {
             Err(CastError::IllegalCast)
@@ -480,9 +497,10 @@ impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
                            fcx: &FnCtxt<'a, 'gcx, 'tcx>,
                            m_expr: &'tcx ty::TypeAndMut<'tcx>)
                            -> Result<CastKind, CastError> {
-        // ptr-addr cast. must be from sized ptr
+        // ptr-addr cast. must be from thin ptr
 
-        if fcx.type_is_known_to_be_sized(m_expr.ty, self.span) {
+        if fcx.type_is_known_to_be_sized(m_expr.ty, self.span) ||
+           fcx.has_foreign_tail(m_expr.ty) {
             Ok(CastKind::PtrAddrCast)
         } else {
             Err(CastError::NeedViaThinPtr)
@@ -519,7 +537,8 @@ impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
                            m_cast: &'tcx ty::TypeAndMut<'tcx>)
                            -> Result<CastKind, CastError> {
         // ptr-addr cast. pointer must be thin.
-        if fcx.type_is_known_to_be_sized(m_cast.ty, self.span) {
+        if fcx.type_is_known_to_be_sized(m_cast.ty, self.span) ||
+           fcx.has_foreign_tail(m_cast.ty) {
             Ok(CastKind::AddrPtrCast)
         } else {
             Err(CastError::IllegalCast)
@@ -67,6 +67,22 @@ pub fn compare_impl_method<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         return;
     }
 
+    if let Err(ErrorReported) = compare_synthetic_generics(tcx,
+                                                           impl_m,
+                                                           impl_m_span,
+                                                           trait_m,
+                                                           trait_item_span) {
+        return;
+    }
+
+    if let Err(ErrorReported) = compare_impl_trait_return(tcx,
+                                                          impl_m,
+                                                          impl_m_span,
+                                                          trait_m,
+                                                          trait_item_span) {
+        return;
+    }
+
     if let Err(ErrorReported) = compare_predicate_entailment(tcx,
                                                              impl_m,
                                                              impl_m_span,
@@ -325,6 +341,22 @@ fn compare_predicate_entailment<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             return Err(ErrorReported);
         }
 
+        // If the trait method returns `impl Trait`, the anonymous type
+        // returned by the impl method must satisfy the same bounds.
+        if let Some(trait_assoc) = tcx.impl_trait_assoc_of_method(trait_m.def_id) {
+            let bounds = tcx.predicates_of(trait_assoc.def_id)
+                            .instantiate(tcx, trait_to_skol_substs);
+            for predicate in bounds.predicates {
+                let traits::Normalized { value: predicate, obligations } =
+                    traits::normalize(&mut selcx, param_env, normalize_cause.clone(), &predicate);
+
+                inh.register_predicates(obligations);
+                inh.register_predicate(traits::Obligation::new(cause.clone(),
+                                                               param_env,
+                                                               predicate));
+            }
+        }
+
         // Check that all obligations are satisfied by the implementation's
         // version.
         if let Err(ref errors) = inh.fulfillment_cx.borrow_mut().select_all_or_error(&infcx) {
@@ -556,6 +588,72 @@ fn compare_self_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     Ok(())
 }
 
+/// Checks that `impl Trait` in argument position is used for the same type
+/// parameters in the trait method and in its implementation.
+fn compare_synthetic_generics<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                        impl_m: &ty::AssociatedItem,
+                                        impl_m_span: Span,
+                                        trait_m: &ty::AssociatedItem,
+                                        trait_item_span: Option<Span>)
+                                        -> Result<(), ErrorReported> {
+    let impl_m_generics = tcx.generics_of(impl_m.def_id);
+    let trait_m_generics = tcx.generics_of(trait_m.def_id);
+    let params = impl_m_generics.types.iter().zip(trait_m_generics.types.iter());
+    for (impl_param, trait_param) in params {
+        if impl_param.synthetic == trait_param.synthetic {
+            continue;
+        }
+
+        let mut err = struct_span_err!(tcx.sess,
+                                       impl_m_span,
+                                       E0637,
+                                       "method `{}` has an incompatible signature for trait",
+                                       trait_m.name);
+        if let Some(span) = trait_item_span {
+            err.span_label(span, "declaration in trait here");
+        }
+        err.span_label(impl_m_span, if impl_param.synthetic.is_some() {
+            "expected generic parameter, found `impl Trait`"
+        } else {
+            "expected `impl Trait`, found generic parameter"
+        });
+        err.emit();
+        return Err(ErrorReported);
+    }
+    Ok(())
+}
+
+/// Checks that the implementation of a method returns `impl Trait`
+/// exactly when the trait method does.
+fn compare_impl_trait_return<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                       impl_m: &ty::AssociatedItem,
+                                       impl_m_span: Span,
+                                       trait_m: &ty::AssociatedItem,
+                                       trait_item_span: Option<Span>)
+                                       -> Result<(), ErrorReported> {
+    let trait_returns_impl_trait = tcx.impl_trait_assoc_of_method(trait_m.def_id).is_some();
+    let impl_returns_impl_trait = tcx.impl_trait_assoc_of_method(impl_m.def_id).is_some();
+    if trait_returns_impl_trait == impl_returns_impl_trait {
+        return Ok(());
+    }
+
+    let mut err = struct_span_err!(tcx.sess,
+                                   impl_m_span,
+                                   E0637,
+                                   "method `{}` has an incompatible signature for trait",
+                                   trait_m.name);
+    if let Some(span) = trait_item_span {
+        err.span_label(span, "declaration in trait here");
+    }
+    err.span_label(impl_m_span, if trait_returns_impl_trait {
+        "expected `impl Trait` return type"
+    } else {
+        "found `impl Trait` return type"
+    });
+    err.emit();
+    Err(ErrorReported)
+}
+
 fn compare_number_of_generics<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                         impl_m: &ty::AssociatedItem,
                                         impl_m_span: Span,
@@ -622,6 +720,29 @@ fn compare_number_of_generics<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         return Err(ErrorReported);
     }
 
+    let num_impl_m_const_params = impl_m_generics.consts.len();
+    let num_trait_m_const_params = trait_m_generics.consts.len();
+    if num_impl_m_const_params != num_trait_m_const_params {
+        let mut err = struct_span_err!(tcx.sess,
+                                       impl_m_span,
+                                       E0049,
+                                       "method `{}` has {} const parameter{} but its trait \
+                                        declaration has {} const parameter{}",
+                                       trait_m.name,
+                                       num_impl_m_const_params,
+                                       if num_impl_m_const_params == 1 { "" } else { "s" },
+                                       num_trait_m_const_params,
+                                       if num_trait_m_const_params == 1 { "" } else { "s" });
+        if let Some(span) = trait_item_span {
+            err.span_label(span, format!("expected {} const parameter{}",
+                                         num_trait_m_const_params,
+                                         if num_trait_m_const_params == 1 { "" } else { "s" }));
+        }
+        err.emit();
+
+        return Err(ErrorReported);
+    }
+
     Ok(())
 }
 
@@ -803,3 +924,149 @@ pub fn compare_const_impl<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         fcx.regionck_item(impl_c_node_id, impl_c_span, &[]);
     });
 }
+
+/// Checks that an associated type from an impl conforms to the declaration
+/// of the same associated type in the trait: it must have the same number
+/// of generic parameters, and its where-clauses must not be stricter than
+/// those of the trait.
+pub fn compare_ty_impl<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                 impl_ty: &ty::AssociatedItem,
+                                 impl_ty_span: Span,
+                                 trait_ty: &ty::AssociatedItem,
+                                 impl_trait_ref: ty::TraitRef<'tcx>,
+                                 trait_item_span: Option<Span>) {
+    debug!("compare_ty_impl(impl_trait_ref={:?})", impl_trait_ref);
+
+    if let Err(ErrorReported) = compare_number_of_ty_generics(tcx,
+                                                              impl_ty,
+                                                              impl_ty_span,
+                                                              trait_ty,
+                                                              trait_item_span) {
+        return;
+    }
+
+    // Nothing more to check for associated types without parameters or
+    // where-clauses of their own.
+    let impl_ty_predicates = tcx.predicates_of(impl_ty.def_id);
+    let trait_ty_predicates = tcx.predicates_of(trait_ty.def_id);
+    if impl_ty_predicates.predicates.is_empty() {
+        return;
+    }
+
+    // This follows `compare_predicate_entailment`: we assume the bounds of
+    // the impl and the where-clauses of the trait's associated type, and
+    // check that those of the impl's associated type follow from them.
+    let impl_ty_node_id = tcx.hir.as_local_node_id(impl_ty.def_id).unwrap();
+    let impl_to_skol_substs = Substs::identity_for_item(tcx, impl_ty.def_id);
+    let trait_to_skol_substs = impl_to_skol_substs.rebase_onto(tcx,
+                                                               impl_ty.container.id(),
+                                                               impl_trait_ref.substs);
+
+    let impl_predicates = tcx.predicates_of(impl_ty_predicates.parent.unwrap());
+    let mut hybrid_preds = impl_predicates.instantiate_identity(tcx);
+    hybrid_preds.predicates
+                .extend(trait_ty_predicates.instantiate_own(tcx, trait_to_skol_substs).predicates);
+
+    let normalize_cause = traits::ObligationCause::misc(impl_ty_span, impl_ty_node_id);
+    let param_env = ty::ParamEnv::new(tcx.intern_predicates(&hybrid_preds.predicates),
+                                      Reveal::UserFacing);
+    let param_env = traits::normalize_param_env_or_error(tcx,
+                                                         impl_ty.def_id,
+                                                         param_env,
+                                                         normalize_cause.clone());
+
+    let cause = ObligationCause {
+        span: impl_ty_span,
+        body_id: impl_ty_node_id,
+        code: ObligationCauseCode::CompareImplMethodObligation {
+            item_name: impl_ty.name,
+            impl_item_def_id: impl_ty.def_id,
+            trait_item_def_id: trait_ty.def_id,
+            lint_id: None,
+        },
+    };
+
+    tcx.infer_ctxt().enter(|infcx| {
+        let inh = Inherited::new(infcx, impl_ty.def_id);
+        let infcx = &inh.infcx;
+        let mut selcx = traits::SelectionContext::new(&infcx);
+
+        let impl_ty_own_bounds = impl_ty_predicates.instantiate_own(tcx, impl_to_skol_substs);
+        for predicate in impl_ty_own_bounds.predicates {
+            // Where-clauses repeated from the trait hold trivially.
+            if hybrid_preds.predicates.contains(&predicate) {
+                continue;
+            }
+
+            let traits::Normalized { value: predicate, obligations } =
+                traits::normalize(&mut selcx, param_env, normalize_cause.clone(), &predicate);
+
+            inh.register_predicates(obligations);
+            inh.register_predicate(traits::Obligation::new(cause.clone(), param_env, predicate));
+        }
+
+        if let Err(ref errors) = inh.fulfillment_cx.borrow_mut().select_all_or_error(&infcx) {
+            infcx.report_fulfillment_errors(errors, None);
+            return;
+        }
+
+        let fcx = FnCtxt::new(&inh, param_env, impl_ty_node_id);
+        fcx.regionck_item(impl_ty_node_id, impl_ty_span, &[]);
+    })
+}
+
+fn compare_number_of_ty_generics<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                           impl_ty: &ty::AssociatedItem,
+                                           impl_ty_span: Span,
+                                           trait_ty: &ty::AssociatedItem,
+                                           trait_item_span: Option<Span>)
+                                           -> Result<(), ErrorReported> {
+    let impl_generics = tcx.generics_of(impl_ty.def_id);
+    let trait_generics = tcx.generics_of(trait_ty.def_id);
+
+    if impl_generics.regions.len() != trait_generics.regions.len() {
+        let mut err = struct_span_err!(tcx.sess,
+                                       impl_ty_span,
+                                       E0195,
+                                       "lifetime parameters on associated type `{}` do not \
+                                        match the trait declaration",
+                                       impl_ty.name);
+        err.span_label(impl_ty_span, "lifetimes do not match trait");
+        if let Some(span) = trait_item_span {
+            err.span_label(span, "lifetimes in trait");
+        }
+        err.emit();
+        return Err(ErrorReported);
+    }
+
+    let counts = [("type", impl_generics.types.len(), trait_generics.types.len()),
+                  ("const", impl_generics.consts.len(), trait_generics.consts.len())];
+    for &(kind, num_impl_params, num_trait_params) in &counts {
+        if num_impl_params == num_trait_params {
+            continue;
+        }
+
+        let mut err = struct_span_err!(tcx.sess,
+                                       impl_ty_span,
+                                       E0049,
+                                       "associated type `{}` has {} {} parameter{} but its \
+                                        trait declaration has {} {} parameter{}",
+                                       trait_ty.name,
+                                       num_impl_params,
+                                       kind,
+                                       if num_impl_params == 1 { "" } else { "s" },
+                                       num_trait_params,
+                                       kind,
+                                       if num_trait_params == 1 { "" } else { "s" });
+        if let Some(span) = trait_item_span {
+            err.span_label(span, format!("expected {} {} parameter{}",
+                                         num_trait_params,
+                                         kind,
+                                         if num_trait_params == 1 { "" } else { "s" }));
+        }
+        err.emit();
+        return Err(ErrorReported);
+    }
+
+    Ok(())
+}
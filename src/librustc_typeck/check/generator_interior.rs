@@ -17,7 +17,7 @@ use rustc::hir::def_id::DefId;
 use rustc::hir::intravisit::{self, Visitor, NestedVisitorMap};
 use rustc::hir::{self, Body, Pat, PatKind, Expr};
 use rustc::middle::region;
-use rustc::ty::Ty;
+use rustc::ty::{self, Ty};
 use std::rc::Rc;
 use super::FnCtxt;
 use util::nodemap::FxHashMap;
@@ -54,7 +54,7 @@ impl<'a, 'gcx, 'tcx> InteriorVisitor<'a, 'gcx, 'tcx> {
 pub fn resolve_interior<'a, 'gcx, 'tcx>(fcx: &'a FnCtxt<'a, 'gcx, 'tcx>,
                                         def_id: DefId,
                                         body_id: hir::BodyId,
-                                        witness: Ty<'tcx>) {
+                                        interior: ty::GeneratorInterior<'tcx>) {
     let body = fcx.tcx.hir.body(body_id);
     let mut visitor = InteriorVisitor {
         fcx,
@@ -71,12 +71,21 @@ pub fn resolve_interior<'a, 'gcx, 'tcx>(fcx: &'a FnCtxt<'a, 'gcx, 'tcx>,
     // Extract type components
     let types: Vec<_> = types.into_iter().map(|t| t.0).collect();
 
-    let tuple = fcx.tcx.intern_tup(&types, false);
+    let mut tuple = fcx.tcx.intern_tup(&types, false);
+
+    // Borrows which live across a yield end up in the witness, which forces
+    // them to outlive the generator itself. That is what keeps a movable
+    // generator from borrowing its own locals. A static generator never
+    // moves once it has been resumed, so such borrows are fine there and
+    // the regions in its witness are not tied to anything.
+    if !interior.movable {
+        tuple = fcx.tcx.fold_regions(&tuple, &mut false, |_, _| fcx.tcx.types.re_static);
+    }
 
     debug!("Types in generator {:?}, span = {:?}", tuple, body.value.span);
 
     // Unify the tuple with the witness
-    match fcx.at(&fcx.misc(body.value.span), fcx.param_env).eq(witness, tuple) {
+    match fcx.at(&fcx.misc(body.value.span), fcx.param_env).eq(interior.witness, tuple) {
         Ok(ok) => fcx.register_infer_ok_obligations(ok),
         _ => bug!(),
    }
@@ -13,7 +13,7 @@ use super::{probe, MethodCallee};
 use astconv::AstConv;
 use check::{FnCtxt, LvalueOp, callee};
 use hir::def_id::DefId;
-use rustc::ty::subst::Substs;
+use rustc::ty::subst::{Subst, Substs};
 use rustc::traits;
 use rustc::ty::{self, LvaluePreference, NoPreference, PreferMutLvalue, Ty};
 use rustc::ty::adjustment::{Adjustment, Adjust, AutoBorrow, OverloadedDeref};
@@ -295,6 +295,9 @@ impl<'a, 'gcx, 'tcx> ConfirmContext<'a, 'gcx, 'tcx> {
         // parameters from the type and those from the method.
         assert_eq!(method_generics.parent_count(), parent_substs.len());
         let provided = &segment.parameters;
+        // `check_path_parameter_count` drops the segment when the provided
+        // types must not be used, e.g. for methods with `impl Trait` arguments.
+        let provided_types = if fn_segment.is_some() { &provided.types[..] } else { &[] };
         Substs::for_item(self.tcx, pick.item.def_id, |def, _| {
             let i = def.index as usize;
             if i < parent_substs.len() {
@@ -310,11 +313,23 @@ impl<'a, 'gcx, 'tcx> ConfirmContext<'a, 'gcx, 'tcx> {
             if i < parent_substs.len() {
                 parent_substs.type_at(i)
             } else if let Some(ast_ty)
-                    = provided.types.get(i - parent_substs.len() - method_generics.regions.len()) {
+                    = provided_types.get(i - parent_substs.len() - method_generics.regions.len()) {
                 self.to_ty(ast_ty)
             } else {
                 self.type_var_for_def(self.span, def, cur_substs)
             }
+        }, |def, cur_substs| {
+            let i = def.index as usize;
+            if i < parent_substs.len() {
+                parent_substs.const_at(i)
+            } else if let Some(arg) = provided.consts.get(i - parent_substs.len() -
+                                                          method_generics.regions.len() -
+                                                          method_generics.types.len()) {
+                let ty = self.tcx.type_of(def.def_id).subst(self.tcx, cur_substs);
+                AstConv::ast_const_to_const(self.fcx, arg, ty)
+            } else {
+                self.const_var_for_def(self.span, def, cur_substs)
+            }
         })
     }
 
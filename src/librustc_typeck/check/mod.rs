@@ -80,7 +80,7 @@ pub use self::Expectation::*;
 use self::autoderef::Autoderef;
 use self::callee::DeferredCallResolution;
 use self::coercion::{CoerceMany, DynamicCoerceMany};
-pub use self::compare_method::{compare_impl_method, compare_const_impl};
+pub use self::compare_method::{compare_impl_method, compare_const_impl, compare_ty_impl};
 use self::method::MethodCallee;
 use self::TupleArgumentsFlag::*;
 
@@ -90,18 +90,19 @@ use hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
 use rustc_back::slice::ref_slice;
 use rustc::infer::{self, InferCtxt, InferOk, RegionVariableOrigin};
 use rustc::infer::type_variable::{TypeVariableOrigin};
+use rustc::middle::const_val::ConstVal;
 use rustc::middle::region;
 use rustc::ty::subst::{Kind, Subst, Substs};
 use rustc::traits::{self, FulfillmentContext, ObligationCause, ObligationCauseCode};
 use rustc::ty::{ParamTy, LvaluePreference, NoPreference, PreferMutLvalue};
-use rustc::ty::{self, Ty, TyCtxt, Visibility};
+use rustc::ty::{self, Ty, TyCtxt, ToPredicate, Visibility};
 use rustc::ty::adjustment::{Adjust, Adjustment, AutoBorrow};
 use rustc::ty::fold::{BottomUpFolder, TypeFoldable};
 use rustc::ty::maps::Providers;
 use rustc::ty::util::{Representability, IntTypeExt};
 use errors::DiagnosticBuilder;
 use require_c_abi_if_variadic;
-use session::{CompileIncomplete, Session};
+use session::{config, CompileIncomplete, Session};
 use TypeAndSubsts;
 use lint;
 use util::common::{ErrorReported, indenter};
@@ -111,6 +112,7 @@ use std::cell::{Cell, RefCell, Ref, RefMut};
 use std::collections::hash_map::Entry;
 use std::cmp;
 use std::fmt::Display;
+use std::iter;
 use std::mem::replace;
 use std::ops::{self, Deref};
 use syntax::abi::Abi;
@@ -130,6 +132,7 @@ use rustc::middle::lang_items;
 use rustc_back::slice;
 use rustc_const_math::ConstInt;
 
+mod asm;
 mod autoderef;
 pub mod dropck;
 pub mod _match;
@@ -204,7 +207,7 @@ pub struct Inherited<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
 
     deferred_cast_checks: RefCell<Vec<cast::CastCheck<'tcx>>>,
 
-    deferred_generator_interiors: RefCell<Vec<(hir::BodyId, Ty<'tcx>)>>,
+    deferred_generator_interiors: RefCell<Vec<(hir::BodyId, ty::GeneratorInterior<'tcx>)>>,
 
     // Anonymized types found in explicit return types and their
     // associated fresh inference variable. Writeback resolves these
@@ -879,7 +882,7 @@ fn typeck_tables_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                                   param_env,
                                                   &fn_sig);
 
-            check_fn(&inh, param_env, fn_sig, decl, id, body, false).0
+            check_fn(&inh, param_env, fn_sig, decl, id, body, None).0
         } else {
             let fcx = FnCtxt::new(&inh, param_env, body.value.id);
             let expected_type = tcx.type_of(def_id);
@@ -1002,7 +1005,7 @@ fn check_fn<'a, 'gcx, 'tcx>(inherited: &'a Inherited<'a, 'gcx, 'tcx>,
                             decl: &'gcx hir::FnDecl,
                             fn_id: ast::NodeId,
                             body: &'gcx hir::Body,
-                            can_be_generator: bool)
+                            can_be_generator: Option<hir::GeneratorMovability>)
                             -> (FnCtxt<'a, 'gcx, 'tcx>, Option<ty::GeneratorInterior<'tcx>>)
 {
     let mut fn_sig = fn_sig.clone();
@@ -1028,7 +1031,7 @@ fn check_fn<'a, 'gcx, 'tcx>(inherited: &'a Inherited<'a, 'gcx, 'tcx>,
 
     let span = body.value.span;
 
-    if body.is_generator && can_be_generator {
+    if body.is_generator && can_be_generator.is_some() {
         fcx.yield_ty = Some(fcx.next_ty_var(TypeVariableOrigin::TypeInference(span)));
     }
 
@@ -1051,7 +1054,7 @@ fn check_fn<'a, 'gcx, 'tcx>(inherited: &'a Inherited<'a, 'gcx, 'tcx>,
     }
 
     let fn_hir_id = fcx.tcx.hir.node_to_hir_id(fn_id);
-    let gen_ty = if can_be_generator && body.is_generator {
+    let gen_ty = if can_be_generator.is_some() && body.is_generator {
         let gen_sig = ty::GenSig {
             yield_ty: fcx.yield_ty.unwrap(),
             return_ty: ret_ty,
@@ -1059,8 +1062,9 @@ fn check_fn<'a, 'gcx, 'tcx>(inherited: &'a Inherited<'a, 'gcx, 'tcx>,
         inherited.tables.borrow_mut().generator_sigs_mut().insert(fn_hir_id, Some(gen_sig));
 
         let witness = fcx.next_ty_var(TypeVariableOrigin::MiscVariable(span));
-        fcx.deferred_generator_interiors.borrow_mut().push((body.id(), witness));
-        let interior = ty::GeneratorInterior::new(witness);
+        let movable = can_be_generator == Some(hir::GeneratorMovability::Movable);
+        let interior = ty::GeneratorInterior::new(witness, movable);
+        fcx.deferred_generator_interiors.borrow_mut().push((body.id(), interior));
 
         inherited.tables.borrow_mut().generator_interiors_mut().insert(fn_hir_id, interior);
 
@@ -1104,6 +1108,23 @@ fn check_fn<'a, 'gcx, 'tcx>(inherited: &'a Inherited<'a, 'gcx, 'tcx>,
     }
     fcx.demand_suptype(span, ret_ty, actual_return_ty);
 
+    // With the `termination_trait` feature, `main` may return any type
+    // implementing `Termination`, which maps it to an exit code.
+    if fcx.tcx.sess.features.borrow().termination_trait {
+        if let Some(term_id) = fcx.tcx.lang_items().termination() {
+            if let Some((id, _)) = *fcx.tcx.sess.entry_fn.borrow() {
+                if id == fn_id && fcx.tcx.sess.entry_type.get() == Some(config::EntryMain) {
+                    let substs = fcx.tcx.mk_substs(iter::once(Kind::from(ret_ty)));
+                    let trait_ref = ty::TraitRef::new(term_id, substs);
+                    let cause = traits::ObligationCause::new(
+                        decl.output.span(), fn_id, ObligationCauseCode::MainFunctionType);
+                    inherited.register_predicate(
+                        traits::Obligation::new(cause, param_env, trait_ref.to_predicate()));
+                }
+            }
+        }
+    }
+
     (fcx, gen_ty)
 }
 
@@ -1256,7 +1277,7 @@ fn check_specialization_validity<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     let kind = match impl_item.node {
         hir::ImplItemKind::Const(..) => ty::AssociatedKind::Const,
         hir::ImplItemKind::Method(..) => ty::AssociatedKind::Method,
-        hir::ImplItemKind::Type(_) => ty::AssociatedKind::Type
+        hir::ImplItemKind::Type(..) => ty::AssociatedKind::Type
     };
     let parent = ancestors.defs(tcx, impl_item.name, kind).skip(1).next()
         .map(|node_item| node_item.map(|parent| parent.defaultness));
@@ -1352,8 +1373,14 @@ fn check_impl_items_against_trait<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                          err.emit()
                     }
                 }
-                hir::ImplItemKind::Type(_) => {
+                hir::ImplItemKind::Type(..) => {
                     if ty_trait_item.kind == ty::AssociatedKind::Type {
+                        compare_ty_impl(tcx,
+                                        &ty_impl_item,
+                                        impl_item.span,
+                                        &ty_trait_item,
+                                        impl_trait_ref,
+                                        tcx.hir.span_if_local(ty_trait_item.def_id));
                         if ty_trait_item.defaultness.has_value() {
                             overridden_associated_type = Some(impl_item);
                         }
@@ -1381,6 +1408,12 @@ fn check_impl_items_against_trait<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     let mut invalidated_items = Vec::new();
     let associated_type_overridden = overridden_associated_type.is_some();
     for trait_item in tcx.associated_items(impl_trait_ref.def_id) {
+        // The types standing for `impl Trait` return types are implemented
+        // together with their method; `compare_impl_method` checks them.
+        if tcx.is_impl_trait_assoc(trait_item.def_id) {
+            continue;
+        }
+
         let is_implemented = trait_def.ancestors(tcx, impl_id)
             .defs(tcx, trait_item.name, trait_item.kind)
             .next()
@@ -1639,9 +1672,17 @@ impl<'a, 'gcx, 'tcx> AstConv<'gcx, 'tcx> for FnCtxt<'a, 'gcx, 'tcx> {
         self.type_var_for_def(span, ty_param_def, substs)
     }
 
+    fn ct_infer_for_def(&self,
+                        _def: &ty::ConstParameterDef,
+                        ty: Ty<'tcx>,
+                        _span: Span) -> &'tcx ty::Const<'tcx> {
+        self.next_const_var(ty)
+    }
+
     fn projected_ty_from_poly_trait_ref(&self,
                                         span: Span,
                                         item_def_id: DefId,
+                                        item_segment: &hir::PathSegment,
                                         poly_trait_ref: ty::PolyTraitRef<'tcx>)
                                         -> Ty<'tcx>
     {
@@ -1651,7 +1692,12 @@ impl<'a, 'gcx, 'tcx> AstConv<'gcx, 'tcx> for FnCtxt<'a, 'gcx, 'tcx> {
                 infer::LateBoundRegionConversionTime::AssocTypeProjection(item_def_id),
                 &poly_trait_ref);
 
-        self.tcx().mk_projection(item_def_id, trait_ref.substs)
+        let substs = AstConv::create_substs_for_associated_item(self,
+                                                                span,
+                                                                item_def_id,
+                                                                item_segment,
+                                                                trait_ref.substs);
+        self.tcx().mk_projection(item_def_id, substs)
     }
 
     fn normalize_ty(&self, span: Span, ty: Ty<'tcx>) -> Ty<'tcx> {
@@ -2091,8 +2137,8 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
 
     fn resolve_generator_interiors(&self, def_id: DefId) {
         let mut deferred_generator_interiors = self.deferred_generator_interiors.borrow_mut();
-        for (body_id, witness) in deferred_generator_interiors.drain(..) {
-            generator_interior::resolve_interior(self, def_id, body_id, witness);
+        for (body_id, interior) in deferred_generator_interiors.drain(..) {
+            generator_interior::resolve_interior(self, def_id, body_id, interior);
         }
     }
 
@@ -3407,6 +3453,15 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             hir::QPath::TypeRelative(ref qself, _) => qself.span
         };
 
+        // Prohibit struct expressions when non exhaustive flag is set.
+        if let ty::TyAdt(adt, _) = struct_ty.sty {
+            if !adt.did.is_local() && adt.is_struct() && adt.is_non_exhaustive() {
+                span_err!(self.tcx.sess, expr.span, E0639,
+                          "cannot create non-exhaustive {} using struct expression",
+                          adt.variant_descr());
+            }
+        }
+
         self.check_expr_struct_fields(struct_ty, expected, expr.id, path_span, variant, fields,
                                       base_expr.is_none());
         if let &Some(ref base_expr) = base_expr {
@@ -3634,13 +3689,14 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
 
               ty
           }
-          hir::ExprInlineAsm(_, ref outputs, ref inputs) => {
-              for output in outputs {
-                  self.check_expr(output);
-              }
-              for input in inputs {
-                  self.check_expr(input);
-              }
+          hir::ExprInlineAsm(ref ia, ref outputs, ref inputs) => {
+              let output_tys = outputs.iter().map(|output| {
+                  self.check_expr(output)
+              }).collect::<Vec<_>>();
+              let input_tys = inputs.iter().map(|input| {
+                  self.check_expr(input)
+              }).collect::<Vec<_>>();
+              self.check_asm_regs(expr.span, ia, &output_tys, &input_tys);
               tcx.mk_nil()
           }
           hir::ExprBreak(destination, ref expr_opt) => {
@@ -3829,8 +3885,8 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
           hir::ExprMatch(ref discrim, ref arms, match_src) => {
             self.check_match(expr, &discrim, arms, expected, match_src)
           }
-          hir::ExprClosure(capture, ref decl, body_id, _, _) => {
-              self.check_expr_closure(expr, capture, &decl, body_id, expected)
+          hir::ExprClosure(capture, ref decl, body_id, _, gen) => {
+              self.check_expr_closure(expr, capture, &decl, body_id, gen, expected)
           }
           hir::ExprBlock(ref body) => {
             self.check_block_with_expected(&body, expected)
@@ -3898,9 +3954,16 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
           }
           hir::ExprRepeat(ref element, count) => {
             let count_def_id = tcx.hir.body_owner_def_id(count);
-            let param_env = ty::ParamEnv::empty(traits::Reveal::UserFacing);
-            let substs = Substs::identity_for_item(tcx.global_tcx(), count_def_id);
-            let count = tcx.const_eval(param_env.and((count_def_id, substs)));
+            let count = match AstConv::anon_const_to_const(self, count, tcx.types.usize) {
+                // A const parameter, as in `[x; N]`, is only known after
+                // monomorphization.
+                count @ &ty::Const { val: ConstVal::Param(_), .. } => Ok(count),
+                _ => {
+                    let param_env = ty::ParamEnv::empty(traits::Reveal::UserFacing);
+                    let substs = Substs::identity_for_item(tcx.global_tcx(), count_def_id);
+                    tcx.const_eval(param_env.and((count_def_id, substs)))
+                }
+            };
 
             if let Err(ref err) = count {
                err.report(tcx, tcx.def_span(count_def_id), "constant expression");
@@ -4610,8 +4673,8 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                 fn_segment = Some((segments.last().unwrap(), generics));
             }
 
-            // Case 4. Local variable, no generics.
-            Def::Local(..) | Def::Upvar(..) => {}
+            // Case 4. Local variable or const parameter, no generics.
+            Def::Local(..) | Def::Upvar(..) | Def::ConstParam(..) => {}
 
             _ => bug!("unexpected definition: {:?}", def),
         }
@@ -4633,6 +4696,11 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                 self.write_ty(self.tcx.hir.node_to_hir_id(node_id), ty);
                 return ty;
             }
+            Def::ConstParam(did) => {
+                let ty = self.tcx.type_of(did);
+                self.write_ty(self.tcx.hir.node_to_hir_id(node_id), ty);
+                return ty;
+            }
             _ => {}
         }
 
@@ -4713,6 +4781,30 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                 // TyError to let type inference recover somewhat.
                 self.type_var_for_def(span, def, substs)
             }
+        }, |def, substs| {
+            let mut i = def.index as usize;
+
+            let segment = if i < fn_start {
+                type_segment
+            } else {
+                i -= fn_start;
+                fn_segment
+            };
+            let consts = segment.map_or(&[][..], |(s, _)| &s.parameters.consts[..]);
+
+            // Skip over the lifetimes and types in the same segment.
+            if let Some((_, generics)) = segment {
+                i -= generics.regions.len() + generics.types.len();
+            }
+
+            let ty = self.tcx.type_of(def.def_id).subst_spanned(self.tcx, substs, Some(span));
+            if let Some(arg) = consts.get(i) {
+                // A provided const parameter.
+                AstConv::ast_const_to_const(self, arg, ty)
+            } else {
+                // No const parameters were provided, we can infer all.
+                self.next_const_var(ty)
+            }
         });
 
         // The things we are substituting into the type should not contain
@@ -4784,6 +4876,17 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                 &generics.types
             }
         });
+        // Type parameters introduced by `impl Trait` in argument position
+        // have no name, so none of the parameters may be given explicitly.
+        if !types.is_empty() && type_defs.iter().any(|d| d.synthetic.is_some()) {
+            struct_span_err!(self.tcx.sess, span, E0636,
+                             "cannot provide explicit type parameters when `impl Trait` is \
+                              used in argument position")
+                .emit();
+            *segment = None;
+            return;
+        }
+
         let required_len = type_defs.iter().take_while(|d| !d.has_default).count();
         if types.len() > type_defs.len() {
             let span = types[type_defs.len()].span;
@@ -4811,6 +4914,20 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                 .emit();
         }
 
+        // Check provided const parameters. They have no defaults, so either
+        // all of them are given or all of them are inferred.
+        let num_consts = segment.map_or(0, |(s, _)| s.parameters.consts.len());
+        let num_const_defs = segment.map_or(0, |(_, generics)| generics.consts.len());
+        if num_consts != 0 && num_consts != num_const_defs {
+            let plural = if num_const_defs == 1 { "" } else { "s" };
+            struct_span_err!(self.tcx.sess, span, E0633,
+                             "wrong number of const arguments: expected {}, found {}",
+                             num_const_defs, num_consts)
+                .span_label(span, format!("expected {} const argument{}", num_const_defs, plural))
+                .emit();
+            *segment = None;
+        }
+
         if !bindings.is_empty() {
             AstConv::prohibit_projection(self, bindings[0].span);
         }
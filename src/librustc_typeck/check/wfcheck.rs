@@ -10,14 +10,17 @@
 
 use astconv::ExplicitSelf;
 use check::{Inherited, FnCtxt};
+use collect;
 use constrained_type_params::{identify_constrained_type_params, Parameter};
 
 use hir::def_id::DefId;
 use rustc::traits::{self, ObligationCauseCode};
 use rustc::ty::{self, Ty, TyCtxt};
+use rustc::ty::subst::{Subst, Substs};
 use rustc::util::nodemap::{FxHashSet, FxHashMap};
 use rustc::middle::lang_items;
 
+use lint;
 use syntax::ast;
 use syntax_pos::Span;
 use errors::DiagnosticBuilder;
@@ -155,6 +158,35 @@ impl<'a, 'gcx> CheckTypeWellFormedVisitor<'a, 'gcx> {
         }
     }
 
+    /// With `#![feature(implied_bounds)]`, reports the bounds a fn or impl
+    /// only gets from the where-clauses of types defined in other crates:
+    /// those crates removing the where-clause would break the item.
+    fn check_foreign_implied_bounds(&mut self, id: ast::NodeId, span: Span) {
+        let tcx = self.tcx;
+        if !tcx.sess.features.borrow().implied_bounds {
+            return;
+        }
+
+        let def_id = tcx.hir.local_def_id(id);
+        let explicit = tcx.explicit_predicates_of(def_id).predicates;
+        for (predicate, adt_def_id) in collect::implied_predicates(tcx, def_id, id, &explicit) {
+            if adt_def_id.is_local() {
+                continue;
+            }
+
+            tcx.lint_node_note(
+                lint::builtin::FOREIGN_IMPLIED_BOUNDS,
+                id,
+                span,
+                &format!("`{}` is implied by the where-clauses of `{}`, \
+                          which is defined in another crate",
+                         predicate,
+                         tcx.item_path_str(adt_def_id)),
+                "removing the where-clause from that type would break this item; \
+                 consider writing the bound explicitly");
+        }
+    }
+
     fn check_associated_item(&mut self,
                              item_id: ast::NodeId,
                              span: Span,
@@ -188,11 +220,21 @@ impl<'a, 'gcx> CheckTypeWellFormedVisitor<'a, 'gcx> {
                     this.check_method_receiver(fcx, sig_if_method, &item, self_ty);
                 }
                 ty::AssociatedKind::Type => {
+                    reject_shadowing_type_parameters(fcx.tcx, item.def_id);
+                    let predicates = fcx.tcx.predicates_of(item.def_id)
+                        .instantiate_own(fcx.tcx, Substs::identity_for_item(fcx.tcx,
+                                                                            item.def_id));
+                    let predicates = fcx.normalize_associated_types_in(span, &predicates);
+                    this.check_where_clauses(fcx, span, &predicates);
+
                     if item.defaultness.has_value() {
                         let ty = fcx.tcx.type_of(item.def_id);
                         let ty = fcx.normalize_associated_types_in(span, &ty);
                         fcx.register_wf_obligation(ty, span, code.clone());
                     }
+                    if let ty::ImplContainer(impl_def_id) = item.container {
+                        this.check_generic_assoc_ty_bounds(fcx, span, &item, impl_def_id);
+                    }
                 }
             }
 
@@ -421,6 +463,69 @@ impl<'a, 'gcx> CheckTypeWellFormedVisitor<'a, 'gcx> {
         }
     }
 
+    /// Checks that the type given to a generic associated type in an impl
+    /// satisfies the bounds declared on the associated type in the trait.
+    /// The bounds of other associated types are predicates of the trait,
+    /// and are checked along with the impl itself.
+    fn check_generic_assoc_ty_bounds<'fcx, 'tcx>(&mut self,
+                                                 fcx: &FnCtxt<'fcx, 'gcx, 'tcx>,
+                                                 span: Span,
+                                                 item: &ty::AssociatedItem,
+                                                 impl_def_id: DefId)
+    {
+        let tcx = fcx.tcx;
+        let trait_ref = match tcx.impl_trait_ref(impl_def_id) {
+            Some(trait_ref) => trait_ref,
+            None => return,
+        };
+        let trait_item = tcx.associated_items(trait_ref.def_id)
+            .find(|i| i.kind == ty::AssociatedKind::Type && i.name == item.name);
+        let trait_item = match trait_item {
+            Some(trait_item) => trait_item,
+            None => return,
+        };
+        let trait_item_generics = tcx.generics_of(trait_item.def_id);
+        if trait_item_generics.own_count() == 0 {
+            return;
+        }
+
+        // A mismatch in the number of parameters is reported by
+        // `compare_ty_impl`.
+        let substs = Substs::identity_for_item(tcx, item.def_id)
+            .rebase_onto(tcx, impl_def_id, trait_ref.substs);
+        if substs.len() != trait_item_generics.count() {
+            return;
+        }
+
+        // The bounds are the predicates of the associated type that
+        // constrain `<Self as Trait>::Item<..>` itself.
+        let assoc_ty = tcx.mk_projection(trait_item.def_id,
+                                         Substs::identity_for_item(tcx, trait_item.def_id));
+        let bounds: Vec<_> = tcx.predicates_of(trait_item.def_id).predicates.into_iter()
+            .filter(|predicate| {
+                let bounded_ty = match *predicate {
+                    ty::Predicate::Trait(ref data) => data.skip_binder().self_ty(),
+                    ty::Predicate::Projection(ref data) => {
+                        data.skip_binder().projection_ty.self_ty()
+                    }
+                    ty::Predicate::TypeOutlives(ref data) => data.skip_binder().0,
+                    _ => return false,
+                };
+                bounded_ty == assoc_ty
+            })
+            .map(|predicate| predicate.subst(tcx, substs))
+            .collect();
+        let bounds = fcx.normalize_associated_types_in(span, &bounds);
+
+        for bound in bounds {
+            let cause = traits::ObligationCause::new(
+                span,
+                fcx.body_id,
+                ObligationCauseCode::ItemObligation(trait_item.def_id));
+            fcx.register_predicate(traits::Obligation::new(cause, fcx.param_env, bound));
+        }
+    }
+
     fn check_fn_or_method<'fcx, 'tcx>(&mut self,
                                       fcx: &FnCtxt<'fcx, 'gcx, 'tcx>,
                                       span: Span,
@@ -585,6 +690,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for CheckTypeWellFormedVisitor<'a, 'tcx> {
     fn visit_item(&mut self, i: &hir::Item) {
         debug!("visit_item: {:?}", i);
         self.check_item_well_formed(i);
+        self.check_foreign_implied_bounds(i.id, i.span);
         intravisit::walk_item(self, i);
     }
 
@@ -595,6 +701,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for CheckTypeWellFormedVisitor<'a, 'tcx> {
             _ => None
         };
         self.check_associated_item(trait_item.id, trait_item.span, method_sig);
+        self.check_foreign_implied_bounds(trait_item.id, trait_item.span);
         intravisit::walk_trait_item(self, trait_item)
     }
 
@@ -605,6 +712,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for CheckTypeWellFormedVisitor<'a, 'tcx> {
             _ => None
         };
         self.check_associated_item(impl_item.id, impl_item.span, method_sig);
+        self.check_foreign_implied_bounds(impl_item.id, impl_item.span);
         intravisit::walk_impl_item(self, impl_item)
     }
 }
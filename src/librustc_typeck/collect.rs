@@ -62,7 +62,7 @@ use middle::resolve_lifetime as rl;
 use rustc::traits::Reveal;
 use rustc::ty::subst::Substs;
 use rustc::ty::{ToPredicate, ReprOptions};
-use rustc::ty::{self, AdtKind, ToPolyTraitRef, Ty, TyCtxt};
+use rustc::ty::{self, AdtKind, ToPolyTraitRef, Ty, TyCtxt, TypeFoldable};
 use rustc::ty::maps::Providers;
 use rustc::ty::util::IntTypeExt;
 use util::nodemap::FxHashMap;
@@ -73,6 +73,7 @@ use std::collections::BTreeMap;
 
 use syntax::{abi, ast};
 use syntax::codemap::Spanned;
+use syntax::feature_gate::{GateIssue, emit_feature_err};
 use syntax::symbol::{Symbol, keywords};
 use syntax_pos::{Span, DUMMY_SP};
 
@@ -94,6 +95,7 @@ pub fn provide(providers: &mut Providers) {
         type_of,
         generics_of,
         predicates_of,
+        explicit_predicates_of,
         super_predicates_of,
         type_param_predicates,
         trait_def,
@@ -146,6 +148,18 @@ impl<'a, 'tcx> Visitor<'tcx> for CollectItemTypesVisitor<'a, 'tcx> {
                 self.tcx.type_of(def_id);
             }
         }
+        for param in &generics.const_params {
+            let def_id = self.tcx.hir.local_def_id(param.id);
+            let ty = self.tcx.type_of(def_id);
+            match ty.sty {
+                ty::TyInt(_) | ty::TyUint(_) | ty::TyBool | ty::TyChar | ty::TyError => {}
+                _ => {
+                    span_err!(self.tcx.sess, param.ty.span, E0632,
+                              "the type of const parameter `{}` must be an integer, \
+                               `bool` or `char`, found `{}`", param.name, ty);
+                }
+            }
+        }
         intravisit::walk_generics(self, generics);
     }
 
@@ -159,7 +173,7 @@ impl<'a, 'tcx> Visitor<'tcx> for CollectItemTypesVisitor<'a, 'tcx> {
     }
 
     fn visit_ty(&mut self, ty: &'tcx hir::Ty) {
-        if let hir::TyImplTrait(..) = ty.node {
+        if let hir::TyImplTraitExistential(..) = ty.node {
             let def_id = self.tcx.hir.local_def_id(ty.id);
             self.tcx.generics_of(def_id);
             self.tcx.predicates_of(def_id);
@@ -224,14 +238,30 @@ impl<'a, 'tcx> AstConv<'tcx, 'tcx> for ItemCtxt<'a, 'tcx> {
         self.tcx().types.err
     }
 
+    fn ct_infer_for_def(&self,
+                        def: &ty::ConstParameterDef,
+                        _ty: Ty<'tcx>,
+                        span: Span) -> &'tcx ty::Const<'tcx> {
+        span_err!(self.tcx().sess, span, E0633,
+                  "const argument for `{}` must be given explicitly in item signatures",
+                  def.name);
+        self.tcx().mk_const_err()
+    }
+
     fn projected_ty_from_poly_trait_ref(&self,
                                         span: Span,
                                         item_def_id: DefId,
+                                        item_segment: &hir::PathSegment,
                                         poly_trait_ref: ty::PolyTraitRef<'tcx>)
                                         -> Ty<'tcx>
     {
         if let Some(trait_ref) = self.tcx().no_late_bound_regions(&poly_trait_ref) {
-            self.tcx().mk_projection(item_def_id, trait_ref.substs)
+            let substs = AstConv::create_substs_for_associated_item(self,
+                                                                    span,
+                                                                    item_def_id,
+                                                                    item_segment,
+                                                                    trait_ref.substs);
+            self.tcx().mk_projection(item_def_id, substs)
         } else {
             // no late-bound regions, we can just ignore the binder
             span_err!(self.tcx().sess, span, E0212,
@@ -289,6 +319,7 @@ fn type_param_predicates<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeTraitItem(item) => {
             match item.node {
                 TraitItemKind::Method(ref sig, _) => &sig.generics,
+                TraitItemKind::Type(ref generics, ..) => generics,
                 _ => return result
             }
         }
@@ -296,6 +327,7 @@ fn type_param_predicates<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeImplItem(item) => {
             match item.node {
                 ImplItemKind::Method(ref sig, _) => &sig.generics,
+                ImplItemKind::Type(ref generics, _) => generics,
                 _ => return result
             }
         }
@@ -308,7 +340,8 @@ fn type_param_predicates<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 ItemEnum(_, ref generics) |
                 ItemStruct(_, ref generics) |
                 ItemUnion(_, ref generics) => generics,
-                ItemTrait(_, ref generics, ..) => {
+                ItemTrait(_, ref generics, ..) |
+                ItemTraitAlias(ref generics, ..) => {
                     // Implied `Self: Trait` and supertrait bounds.
                     if param_id == item_node_id {
                         result.predicates.push(ty::TraitRef {
@@ -466,10 +499,17 @@ fn convert_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, item_id: ast::NodeId) {
         hir::ItemImpl(..) => {
             tcx.generics_of(def_id);
             tcx.type_of(def_id);
-            tcx.impl_trait_ref(def_id);
+            if let Some(trait_ref) = tcx.impl_trait_ref(def_id) {
+                if tcx.is_trait_alias(trait_ref.def_id) {
+                    span_err!(tcx.sess, it.span, E0643,
+                              "cannot implement trait alias `{}`",
+                              tcx.item_path_str(trait_ref.def_id));
+                }
+            }
             tcx.predicates_of(def_id);
         },
-        hir::ItemTrait(..) => {
+        hir::ItemTrait(..) |
+        hir::ItemTraitAlias(..) => {
             tcx.generics_of(def_id);
             tcx.trait_def(def_id);
             tcx.at(it.span).super_predicates_of(def_id);
@@ -516,7 +556,7 @@ fn convert_trait_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, trait_item_id: ast:
 
     match trait_item.node {
         hir::TraitItemKind::Const(..) |
-        hir::TraitItemKind::Type(_, Some(_)) |
+        hir::TraitItemKind::Type(_, _, Some(_)) |
         hir::TraitItemKind::Method(..) => {
             tcx.type_of(def_id);
             if let hir::TraitItemKind::Method(..) = trait_item.node {
@@ -524,7 +564,7 @@ fn convert_trait_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, trait_item_id: ast:
             }
         }
 
-        hir::TraitItemKind::Type(_, None) => {}
+        hir::TraitItemKind::Type(_, _, None) => {}
     };
 
     tcx.predicates_of(def_id);
@@ -687,7 +727,8 @@ fn adt_def<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         }
         _ => bug!()
     };
-    tcx.alloc_adt_def(def_id, kind, variants, repr)
+    let non_exhaustive = tcx.has_attr(def_id, "non_exhaustive");
+    tcx.alloc_adt_def(def_id, kind, variants, repr, non_exhaustive)
 }
 
 /// Ensures that the super-predicates of the trait with def-id
@@ -706,6 +747,7 @@ fn super_predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
     let (generics, bounds) = match item.node {
         hir::ItemTrait(_, ref generics, ref supertraits, _) => (generics, supertraits),
+        hir::ItemTraitAlias(ref generics, ref bounds) => (generics, bounds),
         _ => span_bug!(item.span,
                        "super_predicates invoked on non-trait"),
     };
@@ -747,8 +789,9 @@ fn trait_def<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     let node_id = tcx.hir.as_local_node_id(def_id).unwrap();
     let item = tcx.hir.expect_item(node_id);
 
-    let unsafety = match item.node {
-        hir::ItemTrait(unsafety, ..) => unsafety,
+    let (unsafety, is_alias) = match item.node {
+        hir::ItemTrait(unsafety, ..) => (unsafety, false),
+        hir::ItemTraitAlias(..) => (hir::Unsafety::Normal, true),
         _ => span_bug!(item.span, "trait_def_of_item invoked on non-trait"),
     };
 
@@ -770,6 +813,7 @@ fn trait_def<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                 unsafety,
                                 paren_sugar,
                                 has_default_impl,
+                                is_alias,
                                 def_path_hash);
     tcx.alloc_trait_def(def)
 }
@@ -886,7 +930,7 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeExpr(&hir::Expr { node: hir::ExprClosure(..), .. }) => {
             Some(tcx.closure_base_def_id(def_id))
         }
-        NodeTy(&hir::Ty { node: hir::TyImplTrait(..), .. }) => {
+        NodeTy(&hir::Ty { node: hir::TyImplTraitExistential(..), .. }) => {
             let mut parent_id = node_id;
             loop {
                 match tcx.hir.get(parent_id) {
@@ -909,6 +953,7 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeTraitItem(item) => {
             match item.node {
                 TraitItemKind::Method(ref sig, _) => &sig.generics,
+                TraitItemKind::Type(ref generics, ..) => generics,
                 _ => &no_generics
             }
         }
@@ -916,6 +961,7 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeImplItem(item) => {
             match item.node {
                 ImplItemKind::Method(ref sig, _) => &sig.generics,
+                ImplItemKind::Type(ref generics, _) => generics,
                 _ => &no_generics
             }
         }
@@ -933,7 +979,8 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                     generics
                 }
 
-                ItemTrait(_, ref generics, ..) => {
+                ItemTrait(_, ref generics, ..) |
+                ItemTraitAlias(ref generics, ..) => {
                     // Add in the self type parameter.
                     //
                     // Something of a hack: use the node id for the trait, also as
@@ -947,6 +994,7 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                         has_default: false,
                         object_lifetime_default: rl::Set1::Empty,
                         pure_wrt_drop: false,
+                        synthetic: None,
                     });
 
                     allow_defaults = true;
@@ -960,7 +1008,8 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeForeignItem(item) => {
             match item.node {
                 ForeignItemStatic(..) => &no_generics,
-                ForeignItemFn(_, _, ref generics) => generics
+                ForeignItemFn(_, _, ref generics) => generics,
+                ForeignItemType => &no_generics,
             }
         }
 
@@ -970,14 +1019,16 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     let has_self = opt_self.is_some();
     let mut parent_has_self = false;
     let mut own_start = has_self as u32;
-    let (parent_regions, parent_types) = parent_def_id.map_or((0, 0), |def_id| {
-        let generics = tcx.generics_of(def_id);
-        assert_eq!(has_self, false);
-        parent_has_self = generics.has_self;
-        own_start = generics.count() as u32;
-        (generics.parent_regions + generics.regions.len() as u32,
-            generics.parent_types + generics.types.len() as u32)
-    });
+    let (parent_regions, parent_types, parent_consts) =
+        parent_def_id.map_or((0, 0, 0), |def_id| {
+            let generics = tcx.generics_of(def_id);
+            assert_eq!(has_self, false);
+            parent_has_self = generics.has_self;
+            own_start = generics.count() as u32;
+            (generics.parent_regions + generics.regions.len() as u32,
+                generics.parent_types + generics.types.len() as u32,
+                generics.parent_consts + generics.consts.len() as u32)
+        });
 
     let early_lifetimes = early_bound_lifetimes_from_generics(tcx, ast_generics);
     let regions = early_lifetimes.enumerate().map(|(i, l)| {
@@ -1018,10 +1069,32 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             object_lifetime_default:
                 object_lifetime_defaults.as_ref().map_or(rl::Set1::Empty, |o| o[i]),
             pure_wrt_drop: p.pure_wrt_drop,
+            synthetic: None,
         }
     });
     let mut types: Vec<_> = opt_self.into_iter().chain(types).collect();
 
+    // Each `impl Trait` argument is an anonymous type parameter, placed
+    // after the declared ones.
+    let synthetic_start = type_start + ast_generics.ty_params.len() as u32;
+    for (i, ty) in impl_trait_args(node).into_iter().enumerate() {
+        if !tcx.sess.features.borrow().universal_impl_trait {
+            emit_feature_err(&tcx.sess.parse_sess, "universal_impl_trait", ty.span,
+                             GateIssue::Language,
+                             "`impl Trait` in argument position is experimental");
+        }
+
+        types.push(ty::TypeParameterDef {
+            index: synthetic_start + i as u32,
+            name: impl_trait_arg_name(tcx, ty),
+            def_id: tcx.hir.local_def_id(ty.id),
+            has_default: false,
+            object_lifetime_default: rl::Set1::Empty,
+            pure_wrt_drop: false,
+            synthetic: Some(hir::SyntheticTyParamKind::ImplTrait),
+        });
+    }
+
     // provide junk type parameter defs - the only place that
     // cares about anything but the length is instantiation,
     // and we don't do that for closures.
@@ -1034,10 +1107,22 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 has_default: false,
                 object_lifetime_default: rl::Set1::Empty,
                 pure_wrt_drop: false,
+                synthetic: None,
             }));
         });
     }
 
+    // Const parameters come last, after all of the type parameters
+    // (including `Self` and any closure upvar types).
+    let const_start = type_start + types.len() as u32 - has_self as u32;
+    let consts = ast_generics.const_params.iter().enumerate().map(|(i, p)| {
+        ty::ConstParameterDef {
+            index: const_start + i as u32,
+            name: p.name,
+            def_id: tcx.hir.local_def_id(p.id),
+        }
+    }).collect();
+
     let mut type_param_to_index = BTreeMap::new();
     for param in &types {
         type_param_to_index.insert(param.def_id.index, param.index);
@@ -1047,14 +1132,60 @@ fn generics_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         parent: parent_def_id,
         parent_regions,
         parent_types,
+        parent_consts,
         regions,
         types,
+        consts,
         type_param_to_index,
         has_self: has_self || parent_has_self,
         has_late_bound_regions: has_late_bound_regions(tcx, node),
     })
 }
 
+/// Collects the `impl Trait` types in the arguments of the function `node`,
+/// each of which is an anonymous type parameter of that function.
+fn impl_trait_args<'hir>(node: hir_map::Node<'hir>) -> Vec<&'hir hir::Ty> {
+    struct ImplTraitArgs<'hir> {
+        tys: Vec<&'hir hir::Ty>,
+    }
+
+    impl<'hir> Visitor<'hir> for ImplTraitArgs<'hir> {
+        fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'hir> {
+            NestedVisitorMap::None
+        }
+
+        fn visit_ty(&mut self, ty: &'hir hir::Ty) {
+            if let hir::TyImplTraitUniversal(..) = ty.node {
+                self.tys.push(ty);
+            }
+            intravisit::walk_ty(self, ty);
+        }
+    }
+
+    let decl = match node {
+        hir_map::NodeItem(&hir::Item { node: hir::ItemFn(ref decl, ..), .. }) => decl,
+        hir_map::NodeTraitItem(&hir::TraitItem {
+            node: hir::TraitItemKind::Method(ref sig, _), ..
+        }) |
+        hir_map::NodeImplItem(&hir::ImplItem {
+            node: hir::ImplItemKind::Method(ref sig, _), ..
+        }) => &sig.decl,
+        _ => return vec![],
+    };
+
+    let mut visitor = ImplTraitArgs { tys: vec![] };
+    for input in &decl.inputs {
+        visitor.visit_ty(input);
+    }
+    visitor.tys
+}
+
+/// The name of the anonymous type parameter for the `impl Trait` argument
+/// `ty`, which is how the argument was written.
+fn impl_trait_arg_name(tcx: TyCtxt, ty: &hir::Ty) -> ast::Name {
+    Symbol::intern(&tcx.hir.node_to_pretty_string(ty.id))
+}
+
 fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                      def_id: DefId)
                      -> Ty<'tcx> {
@@ -1073,8 +1204,8 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                     tcx.mk_fn_def(def_id, substs)
                 }
                 TraitItemKind::Const(ref ty, _) |
-                TraitItemKind::Type(_, Some(ref ty)) => icx.to_ty(ty),
-                TraitItemKind::Type(_, None) => {
+                TraitItemKind::Type(_, _, Some(ref ty)) => icx.to_ty(ty),
+                TraitItemKind::Type(_, _, None) => {
                     span_bug!(item.span, "associated type missing default");
                 }
             }
@@ -1087,7 +1218,7 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                     tcx.mk_fn_def(def_id, substs)
                 }
                 ImplItemKind::Const(ref ty, _) => icx.to_ty(ty),
-                ImplItemKind::Type(ref ty) => {
+                ImplItemKind::Type(_, ref ty) => {
                     if tcx.impl_trait_ref(tcx.hir.get_parent_did(node_id)).is_none() {
                         span_err!(tcx.sess, item.span, E0202,
                                   "associated types are not allowed in inherent impls");
@@ -1117,6 +1248,7 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 }
                 ItemDefaultImpl(..) |
                 ItemTrait(..) |
+                ItemTraitAlias(..) |
                 ItemMod(..) |
                 ItemForeignMod(..) |
                 ItemGlobalAsm(..) |
@@ -1136,7 +1268,8 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                     let substs = Substs::identity_for_item(tcx, def_id);
                     tcx.mk_fn_def(def_id, substs)
                 }
-                ForeignItemStatic(ref t, _) => icx.to_ty(t)
+                ForeignItemStatic(ref t, _) => icx.to_ty(t),
+                ForeignItemType => tcx.mk_foreign(def_id),
             }
         }
 
@@ -1155,8 +1288,8 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
         NodeField(field) => icx.to_ty(&field.ty),
 
-        NodeExpr(&hir::Expr { node: hir::ExprClosure(.., is_generator), .. }) => {
-            if is_generator {
+        NodeExpr(&hir::Expr { node: hir::ExprClosure(.., gen), .. }) => {
+            if gen.is_some() {
                 let hir_id = tcx.hir.node_to_hir_id(node_id);
                 return tcx.typeck_tables_of(def_id).node_id_to_type(hir_id);
             }
@@ -1167,7 +1300,8 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                     let region = def.to_early_bound_region_data();
                     tcx.mk_region(ty::ReEarlyBound(region))
                 },
-                |def, _| tcx.mk_param_from_def(def)
+                |def, _| tcx.mk_param_from_def(def),
+                |def, _| tcx.mk_const_param_from_def(def)
             ))
         }
 
@@ -1184,7 +1318,10 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 }
 
             x => {
-                bug!("unexpected expr parent in type_of_def_id(): {:?}", x);
+                match const_arg_param_ty(tcx, node_id) {
+                    Some(ty) => ty,
+                    None => bug!("unexpected expr parent in type_of_def_id(): {:?}", x),
+                }
             }
         },
 
@@ -1192,7 +1329,11 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             icx.to_ty(ty)
         }
 
-        NodeTy(&hir::Ty { node: TyImplTrait(..), .. }) => {
+        NodeConstParam(param) => {
+            icx.to_ty(&param.ty)
+        }
+
+        NodeTy(&hir::Ty { node: TyImplTraitExistential(..), .. }) => {
             let owner = tcx.hir.get_parent_did(node_id);
             let hir_id = tcx.hir.node_to_hir_id(node_id);
             tcx.typeck_tables_of(owner).node_id_to_type(hir_id)
@@ -1204,6 +1345,41 @@ fn type_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     }
 }
 
+/// Returns the type of the const parameter that the anonymous constant
+/// `node_id` is passed for, e.g. `usize` for `{ N + 1 }` in `Buf<{ N + 1 }>`.
+fn const_arg_param_ty<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                node_id: ast::NodeId)
+                                -> Option<Ty<'tcx>> {
+    use rustc::hir::map::*;
+
+    let path = match tcx.hir.get(tcx.hir.get_parent_node(node_id)) {
+        NodeTy(&hir::Ty { node: hir::TyPath(hir::QPath::Resolved(_, ref path)), .. }) |
+        NodeExpr(&hir::Expr { node: hir::ExprPath(hir::QPath::Resolved(_, ref path)), .. }) |
+        NodeTraitRef(&hir::TraitRef { ref path, .. }) => path,
+        _ => return None,
+    };
+
+    let index = match path.segments.last() {
+        Some(segment) => segment.parameters.consts.iter().position(|arg| match *arg {
+            hir::ConstArg::Expr(body) => body.node_id == node_id,
+            hir::ConstArg::Param(..) => false,
+        }),
+        None => None,
+    };
+    let index = match index {
+        Some(index) => index,
+        None => return None,
+    };
+
+    let def_id = match path.def {
+        Def::StructCtor(def_id, _) |
+        Def::VariantCtor(def_id, _) => tcx.parent_def_id(def_id).unwrap(),
+        def => def.def_id(),
+    };
+    let generics = tcx.generics_of(def_id);
+    generics.consts.get(index).map(|param| tcx.type_of(param.def_id))
+}
+
 fn fn_sig<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                     def_id: DefId)
                     -> ty::PolyFnSig<'tcx> {
@@ -1354,6 +1530,43 @@ fn early_bound_lifetimes_from_generics<'a, 'tcx>(
 fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                            def_id: DefId)
                            -> ty::GenericPredicates<'tcx> {
+    let mut result = tcx.explicit_predicates_of(def_id);
+
+    // With `#![feature(implied_bounds)]`, the where-clauses of the types
+    // in the inputs of a fn or the header of an impl are predicates of the
+    // fn or impl too: callers have to prove them, and the body can assume
+    // them without repeating them.
+    if tcx.sess.features.borrow().implied_bounds {
+        let node_id = tcx.hir.as_local_node_id(def_id).unwrap();
+        let implied = implied_predicates(tcx, def_id, node_id, &result.predicates);
+        if !implied.is_empty() {
+            result.predicates.extend(implied.into_iter().map(|(predicate, _)| predicate));
+
+            // Keep the ordering `explicit_predicates_of` established for
+            // impls, see `setup_constraining_predicates`.
+            if let hir_map::NodeItem(&hir::Item { node: hir::ItemImpl(..), .. }) =
+                tcx.hir.get(node_id)
+            {
+                let self_ty = tcx.type_of(def_id);
+                let trait_ref = tcx.impl_trait_ref(def_id);
+                ctp::setup_constraining_predicates(tcx,
+                                                   &mut result.predicates,
+                                                   trait_ref,
+                                                   &mut ctp::parameters_for_impl(self_ty,
+                                                                                 trait_ref));
+            }
+        }
+    }
+
+    result
+}
+
+/// The predicates of `def_id` as written in the source, i.e. its generics,
+/// where-clauses and associated type bounds, but none of the bounds
+/// `#![feature(implied_bounds)]` adds from its input types.
+fn explicit_predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                    def_id: DefId)
+                                    -> ty::GenericPredicates<'tcx> {
     use rustc::hir::map::*;
     use rustc::hir::*;
 
@@ -1361,6 +1574,7 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     let node = tcx.hir.get(node_id);
 
     let mut is_trait = None;
+    let mut is_trait_alias = false;
 
     let icx = ItemCtxt::new(tcx, def_id);
     let no_generics = hir::Generics::empty();
@@ -1368,6 +1582,7 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeTraitItem(item) => {
             match item.node {
                 TraitItemKind::Method(ref sig, _) => &sig.generics,
+                TraitItemKind::Type(ref generics, ..) => generics,
                 _ => &no_generics
             }
         }
@@ -1375,6 +1590,7 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeImplItem(item) => {
             match item.node {
                 ImplItemKind::Method(ref sig, _) => &sig.generics,
+                ImplItemKind::Type(ref generics, _) => generics,
                 _ => &no_generics
             }
         }
@@ -1398,6 +1614,11 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                     generics
                 }
 
+                ItemTraitAlias(ref generics, _) => {
+                    is_trait_alias = true;
+                    generics
+                }
+
                 _ => &no_generics
             }
         }
@@ -1405,13 +1626,18 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         NodeForeignItem(item) => {
             match item.node {
                 ForeignItemStatic(..) => &no_generics,
-                ForeignItemFn(_, _, ref generics) => generics
+                ForeignItemFn(_, _, ref generics) => generics,
+                ForeignItemType => &no_generics,
             }
         }
 
-        NodeTy(&Ty { node: TyImplTrait(ref bounds), span, .. }) => {
+        NodeTy(&Ty { node: TyImplTraitExistential(ref bounds), span, .. }) => {
             let substs = Substs::identity_for_item(tcx, def_id);
-            let anon_ty = tcx.mk_anon(def_id, substs);
+            // In a trait, the return type of a method is an associated type.
+            let anon_ty = match tcx.opt_associated_item(def_id).map(|item| item.container) {
+                Some(ty::TraitContainer(_)) => tcx.mk_projection(def_id, substs),
+                _ => tcx.mk_anon(def_id, substs),
+            };
 
             // Collect the bounds, i.e. the `A+B+'c` in `impl A+B+'c`.
             let bounds = compute_bounds(&icx, anon_ty, bounds,
@@ -1444,6 +1670,12 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         predicates.push(trait_ref.to_poly_trait_ref().to_predicate());
     }
 
+    // A trait alias holds for any type satisfying its bounds, so those are
+    // its predicates; unlike a trait, it has no `Self: Alias` predicate.
+    if is_trait_alias {
+        predicates = tcx.super_predicates_of(def_id).predicates;
+    }
+
     // Collect the region predicates that were declared inline as
     // well. In the case of parameters declared on a fn or method, we
     // have to be careful to only iterate over early-bound regions.
@@ -1477,6 +1709,21 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         predicates.extend(bounds.predicates(tcx, param_ty));
     }
 
+    // Likewise for the anonymous type parameters of `impl Trait` arguments.
+    for ty in impl_trait_args(node) {
+        if let TyImplTraitUniversal(_, ref bounds) = ty.node {
+            let param_ty = ty::ParamTy::new(index, impl_trait_arg_name(tcx, ty)).to_ty(tcx);
+            index += 1;
+
+            let bounds = compute_bounds(&icx,
+                                        param_ty,
+                                        bounds,
+                                        SizedByDefault::Yes,
+                                        ty.span);
+            predicates.extend(bounds.predicates(tcx, param_ty));
+        }
+    }
+
     // Add in the bounds that appear in the where-clause
     let where_clause = &ast_generics.where_clause;
     for predicate in &where_clause.predicates {
@@ -1528,12 +1775,30 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         }
     }
 
+    // The bounds of a generic associated type may mention its own
+    // parameters, so they are predicates of the associated type itself
+    // rather than of the trait.
+    if let NodeTraitItem(item) = node {
+        if let TraitItemKind::Type(ref generics, ref bounds, _) = item.node {
+            if generics.is_parameterized() {
+                let assoc_ty = tcx.mk_projection(def_id, Substs::identity_for_item(tcx, def_id));
+                let bounds = compute_bounds(&icx,
+                                            assoc_ty,
+                                            bounds,
+                                            SizedByDefault::Yes,
+                                            item.span);
+                predicates.extend(bounds.predicates(tcx, assoc_ty));
+            }
+        }
+    }
+
     // Add predicates from associated type bounds.
     if let Some((self_trait_ref, trait_items)) = is_trait {
         predicates.extend(trait_items.iter().flat_map(|trait_item_ref| {
             let trait_item = tcx.hir.trait_item(trait_item_ref.id);
             let bounds = match trait_item.node {
-                hir::TraitItemKind::Type(ref bounds, _) => bounds,
+                hir::TraitItemKind::Type(ref generics, ref bounds, _)
+                    if !generics.is_parameterized() => bounds,
                 _ => {
                     return vec![].into_iter();
                 }
@@ -1574,6 +1839,71 @@ fn predicates_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     }
 }
 
+/// Returns the predicates implied by the well-formedness of the input
+/// types of `def_id` -- the arguments of a fn, or the self type and trait
+/// reference of an impl -- that are not already in `explicit`, each paired
+/// with the ADT whose where-clauses it comes from.
+///
+/// Only where-clauses written on the ADT are considered; the implicit
+/// `Sized` bounds on its type parameters are not, since every type
+/// argument is required to be `Sized` anyway.
+pub fn implied_predicates<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                    def_id: DefId,
+                                    node_id: ast::NodeId,
+                                    explicit: &[ty::Predicate<'tcx>])
+                                    -> Vec<(ty::Predicate<'tcx>, DefId)> {
+    let input_tys: Vec<Ty<'tcx>> = match tcx.hir.get(node_id) {
+        hir_map::NodeItem(&hir::Item { node: hir::ItemFn(..), .. }) |
+        hir_map::NodeTraitItem(&hir::TraitItem {
+            node: hir::TraitItemKind::Method(..), ..
+        }) |
+        hir_map::NodeImplItem(&hir::ImplItem { node: hir::ImplItemKind::Method(..), .. }) => {
+            tcx.fn_sig(def_id).skip_binder().inputs().to_vec()
+        }
+        hir_map::NodeItem(&hir::Item { node: hir::ItemImpl(..), .. }) => {
+            match tcx.impl_trait_ref(def_id) {
+                Some(trait_ref) => trait_ref.substs.types().collect(),
+                None => vec![tcx.type_of(def_id)],
+            }
+        }
+        _ => return vec![],
+    };
+
+    let mut explicit = explicit.to_vec();
+    if let Some(parent) = tcx.generics_of(def_id).parent {
+        explicit.extend(tcx.predicates_of(parent).predicates);
+    }
+
+    let sized_trait = tcx.lang_items().sized_trait();
+    let mut implied: Vec<(ty::Predicate<'tcx>, DefId)> = vec![];
+    for ty in input_tys.iter().flat_map(|ty| ty.walk()) {
+        let (adt_def, substs) = match ty.sty {
+            ty::TyAdt(adt_def, substs) => (adt_def, substs),
+            _ => continue,
+        };
+
+        for predicate in tcx.predicates_of(adt_def.did).instantiate(tcx, substs).predicates {
+            // Bounds that do not mention any generic parameter tell us
+            // nothing, and bounds on late-bound regions cannot be
+            // expressed as where-clauses of the item.
+            if let ty::Predicate::Trait(ref data) = predicate {
+                if Some(data.def_id()) == sized_trait {
+                    continue;
+                }
+            }
+
+            if predicate.is_global() || predicate.has_escaping_regions() ||
+                explicit.contains(&predicate) ||
+                implied.iter().any(|&(ref p, _)| *p == predicate) {
+                continue;
+            }
+
+            implied.push((predicate, adt_def.did));
+        }
+    }
+    implied
+}
+
 pub enum SizedByDefault { Yes, No, }
 
 /// Translate the AST's notion of ty param bounds (which are an enum consisting of a newtyped Ty or
@@ -4677,4 +4677,14 @@ register_diagnostics! {
     E0592, // duplicate definitions with name `{}`
 //  E0613, // Removed (merged with E0609)
     E0627, // yield statement outside of generator literal
+    E0632, // the type of a const parameter must be an integer, `bool` or `char`
+    E0633, // wrong number of const arguments
+    E0634, // cannot pattern-match on an array without a fixed length
+    E0635, // cannot constrain a generic associated type in a binding
+    E0636, // explicit type parameters given for a fn with `impl Trait` arguments
+    E0637, // `impl Trait` used differently in trait method and implementation
+    E0638, // trait method returning `impl Trait` cannot have a default body
+    E0639, // cannot create non-exhaustive struct using struct expression
+    E0640, // `..` required in patterns of a non-exhaustive struct
+    E0643, // cannot implement a trait alias
 }
@@ -112,6 +112,14 @@ fn enforce_impl_params_are_constrained<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         }
     }
 
+    // Disallow ANY unconstrained const parameters.
+    for (const_param, param) in impl_generics.consts.iter().zip(&impl_hir_generics.const_params) {
+        let param_const = ty::ParamConst::for_def(const_param);
+        if !input_parameters.contains(&ctp::Parameter::from(param_const)) {
+            report_unused_parameter(tcx, param.span, "const", &param_const.to_string());
+        }
+    }
+
     // Disallow unconstrained lifetimes, but only if they appear in assoc types.
     let lifetimes_in_associated_types: FxHashSet<_> = impl_item_refs.iter()
         .map(|item_ref|  tcx.hir.local_def_id(item_ref.id.node_id))
@@ -179,7 +187,7 @@ fn enforce_impl_items_are_distinct<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     for impl_item_ref in impl_item_refs {
         let impl_item = tcx.hir.impl_item(impl_item_ref.id);
         let seen_items = match impl_item.node {
-            hir::ImplItemKind::Type(_) => &mut seen_type_items,
+            hir::ImplItemKind::Type(..) => &mut seen_type_items,
             _                    => &mut seen_value_items,
         };
         match seen_items.entry(impl_item.name) {
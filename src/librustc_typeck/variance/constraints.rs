@@ -289,7 +289,7 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
 
         match ty.sty {
             ty::TyBool | ty::TyChar | ty::TyInt(_) | ty::TyUint(_) | ty::TyFloat(_) |
-            ty::TyStr | ty::TyNever => {
+            ty::TyStr | ty::TyNever | ty::TyForeign(..) => {
                 // leaf type -- noop
             }
 
@@ -417,9 +417,11 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
                 self.add_constraints_from_ty(current, ty, variance_i);
             } else if let Some(r) = k.as_region() {
                 self.add_constraints_from_region(current, r, variance_i);
-            } else {
+            } else if k.as_const().is_none() {
                 bug!();
             }
+            // Const arguments are always related invariantly, so they don't
+            // constrain the variance of any parameters.
         }
     }
 
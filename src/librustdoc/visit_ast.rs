@@ -500,6 +500,9 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                 };
                 om.traits.push(t);
             },
+            hir::ItemTraitAlias(..) => {
+                // FIXME: trait aliases are not documented yet.
+            },
 
             hir::ItemImpl(unsafety,
                           polarity,
@@ -0,0 +1,653 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for capturing a stack backtrace of an OS thread
+//!
+//! This module contains the support necessary to capture a stack backtrace of
+//! a running OS thread from the OS thread itself. The [`Backtrace`] type
+//! supports capturing a stack trace via the [`Backtrace::capture`] and
+//! [`Backtrace::force_capture`] functions.
+//!
+//! A backtrace is typically quite handy to attach to errors (e.g. types
+//! implementing [`Error`]) to get a causal chain of where an error was
+//! generated.
+//!
+//! ## Accuracy
+//!
+//! Backtraces are attempted to be as accurate as possible, but no guarantees
+//! are provided about the exact accuracy of a backtrace. Instruction
+//! pointers, symbol names, filenames, line numbers, etc, may all be incorrect
+//! when reported. Accuracy is attempted on a best-effort basis, however, and
+//! bugs are always welcome to indicate areas of improvement!
+//!
+//! For most platforms a backtrace with a filename/line number requires that
+//! programs be compiled with debug information. Without debug information
+//! filenames/line numbers will not be reported.
+//!
+//! ## Platform support
+//!
+//! Not all platforms that libstd compiles for support capturing backtraces.
+//! Some platforms simply do nothing when capturing a backtrace. To check
+//! whether the platform supports capturing backtraces you can consult the
+//! [`BacktraceStatus`] enum as a result of [`Backtrace::status`].
+//!
+//! Like above with accuracy platform support is done on a best effort basis.
+//!
+//! ## Environment Variables
+//!
+//! The [`Backtrace::capture`] function may not actually capture a backtrace
+//! by default. Its behavior is governed by two environment variables:
+//!
+//! * `RUST_LIB_BACKTRACE` - if this is set to `0` then
+//!   `Backtrace::capture` will never capture a backtrace. Any other value
+//!   set will enable `Backtrace::capture`.
+//!
+//! * `RUST_BACKTRACE` - if `RUST_LIB_BACKTRACE` is not set, then this
+//!   variable is consulted with the same rules of `RUST_LIB_BACKTRACE`.
+//!
+//! * If neither of the above env vars are set, then `Backtrace::capture`
+//!   will be disabled.
+//!
+//! Capturing a backtrace can be a quite expensive runtime operation, so the
+//! environment variables allow either forcibly disabling this runtime
+//! performance hit or allow selectively enabling it in some programs.
+//!
+//! Note that the [`Backtrace::force_capture`] function can be used to ignore
+//! these environment variables. Also note that the state of environment
+//! variables is cached once the first backtrace is created, so altering
+//! `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE` at runtime may not actually
+//! change how backtraces are captured.
+//!
+//! [`Backtrace`]: struct.Backtrace.html
+//! [`Backtrace::capture`]: struct.Backtrace.html#method.capture
+//! [`Backtrace::force_capture`]: struct.Backtrace.html#method.force_capture
+//! [`Backtrace::status`]: struct.Backtrace.html#method.status
+//! [`BacktraceStatus`]: enum.BacktraceStatus.html
+//! [`Error`]: ../error/trait.Error.html
+
+#![unstable(feature = "backtrace", issue = "0")]
+
+// Capturing only records the raw instruction pointers of each frame. Symbols
+// are resolved, with a fresh symbolication context, the first time
+// the backtrace is displayed or its frames are inspected, since resolution is
+// by far the most expensive part and most captured backtraces are never
+// looked at.
+
+use cell::UnsafeCell;
+use env;
+use fmt;
+use path::Path;
+use str;
+use sync::Mutex;
+use sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[cfg(feature = "backtrace")]
+use sys_common::backtrace::{self as backtrace_rs, Frame, PrintFormat};
+
+/// A captured OS thread stack backtrace.
+///
+/// This type represents a stack backtrace for an OS thread captured at a
+/// previous point in time. In some instances the `Backtrace` type may
+/// internally be empty due to configuration. For more information see
+/// [`Backtrace::capture`].
+///
+/// Symbols are resolved the first time the backtrace is formatted or its
+/// frames are requested. Formatting with `{}` omits the frames of the runtime
+/// below `main` or the thread's entry point; `{:#}` prints every frame along
+/// with its address.
+///
+/// [`Backtrace::capture`]: struct.Backtrace.html#method.capture
+pub struct Backtrace {
+    inner: Inner,
+}
+
+/// The current status of a backtrace, indicating whether it was captured or
+/// whether it is empty for some other reason.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BacktraceStatus {
+    /// Capturing a backtrace is not supported, likely because it's not
+    /// implemented for the current platform.
+    Unsupported,
+    /// Capturing a backtrace has been disabled through either the
+    /// `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE` environment variables.
+    Disabled,
+    /// A backtrace has been captured and the `Backtrace` should print
+    /// reasonable information when rendered.
+    Captured,
+}
+
+enum Inner {
+    Unsupported,
+    Disabled,
+    Captured(LazilyResolvedCapture),
+}
+
+struct Capture {
+    actual_start: usize,
+    frames: Vec<BacktraceFrame>,
+}
+
+/// A single frame of a backtrace.
+pub struct BacktraceFrame {
+    ip: usize,
+    symbol_addr: usize,
+    symbols: Vec<BacktraceSymbol>,
+}
+
+/// A symbol that a [`BacktraceFrame`] resolved to.
+///
+/// A frame can resolve to several symbols when functions were inlined into
+/// it, starting with the innermost one.
+///
+/// [`BacktraceFrame`]: struct.BacktraceFrame.html
+pub struct BacktraceSymbol {
+    name: Option<Vec<u8>>,
+    filename: Option<Vec<u8>>,
+    lineno: Option<u32>,
+}
+
+/// A `Capture` whose frames are resolved on first access.
+struct LazilyResolvedCapture {
+    resolved: AtomicBool,
+    lock: Mutex<()>,
+    capture: UnsafeCell<Capture>,
+}
+
+// The capture is only mutated once, by `force`, while holding `lock` and
+// before any shared reference to it has been handed out.
+unsafe impl Send for LazilyResolvedCapture {}
+unsafe impl Sync for LazilyResolvedCapture {}
+
+impl LazilyResolvedCapture {
+    fn new(capture: Capture) -> LazilyResolvedCapture {
+        LazilyResolvedCapture {
+            resolved: AtomicBool::new(false),
+            lock: Mutex::new(()),
+            capture: UnsafeCell::new(capture),
+        }
+    }
+
+    fn force(&self) -> &Capture {
+        if !self.resolved.load(Ordering::Acquire) {
+            let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+            if !self.resolved.load(Ordering::Relaxed) {
+                unsafe {
+                    (*self.capture.get()).resolve();
+                }
+                self.resolved.store(true, Ordering::Release);
+            }
+        }
+        unsafe { &*self.capture.get() }
+    }
+}
+
+impl fmt::Debug for Backtrace {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let capture = match self.inner {
+            Inner::Unsupported => return fmt.write_str("<unsupported>"),
+            Inner::Disabled => return fmt.write_str("<disabled>"),
+            Inner::Captured(ref c) => c.force(),
+        };
+
+        let frames = &capture.frames[capture.actual_start..];
+
+        write!(fmt, "Backtrace ")?;
+
+        let mut dbg = fmt.debug_list();
+
+        for frame in frames {
+            if frame.ip == 0 {
+                continue;
+            }
+
+            dbg.entries(&frame.symbols);
+        }
+
+        dbg.finish()
+    }
+}
+
+impl fmt::Debug for BacktraceFrame {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut dbg = fmt.debug_list();
+        dbg.entries(&self.symbols);
+        dbg.finish()
+    }
+}
+
+impl fmt::Debug for BacktraceSymbol {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{{ ")?;
+
+        if let Some(ref name) = self.name {
+            write!(fmt, "fn: \"")?;
+            fmt_symbol_name(fmt, name, false)?;
+            write!(fmt, "\"")?;
+        } else {
+            write!(fmt, "fn: <unknown>")?;
+        }
+
+        if let Some(ref filename) = self.filename {
+            write!(fmt, ", file: {:?}", str::from_utf8(filename).unwrap_or("<unknown>"))?;
+        }
+
+        if let Some(line) = self.lineno {
+            write!(fmt, ", line: {}", line)?;
+        }
+
+        write!(fmt, " }}")
+    }
+}
+
+impl Backtrace {
+    /// Returns whether backtrace captures are enabled through environment
+    /// variables.
+    fn enabled() -> bool {
+        // Cache the result of reading the environment variables to make
+        // backtrace captures speedy, because otherwise reading environment
+        // variables every time can be somewhat slow.
+        static ENABLED: AtomicUsize = AtomicUsize::new(0);
+        match ENABLED.load(Ordering::SeqCst) {
+            0 => {}
+            1 => return false,
+            _ => return true,
+        }
+        let enabled = match env::var("RUST_LIB_BACKTRACE") {
+            Ok(s) => s != "0",
+            Err(_) => match env::var("RUST_BACKTRACE") {
+                Ok(s) => s != "0",
+                Err(_) => false,
+            },
+        };
+        ENABLED.store(enabled as usize + 1, Ordering::SeqCst);
+        enabled
+    }
+
+    /// Capture a stack backtrace of the current thread.
+    ///
+    /// This function will capture a stack backtrace of the current OS thread
+    /// of execution, returning a `Backtrace` type which can be later used to
+    /// print the entire stack trace or render it to a string.
+    ///
+    /// This function will be a noop if the `RUST_BACKTRACE` or
+    /// `RUST_LIB_BACKTRACE` backtrace variables are both not set. If either
+    /// environment variable is set and enabled then this function will
+    /// actually capture a backtrace. Capturing a backtrace can be both memory
+    /// intensive and slow, so these environment variables allow liberally
+    /// using `Backtrace::capture` and only incurring a slowdown when the
+    /// environment variables are set.
+    ///
+    /// To forcibly capture a backtrace regardless of environment variables,
+    /// use the [`Backtrace::force_capture`] function.
+    ///
+    /// [`Backtrace::force_capture`]: struct.Backtrace.html#method.force_capture
+    #[inline(never)] // want to make sure there's a frame here to remove
+    pub fn capture() -> Backtrace {
+        if !Backtrace::enabled() {
+            return Backtrace { inner: Inner::Disabled };
+        }
+        Backtrace::create(Backtrace::capture as usize)
+    }
+
+    /// Forcibly captures a full backtrace, regardless of environment variable
+    /// configuration.
+    ///
+    /// This function behaves the same as [`Backtrace::capture`] except that
+    /// it ignores the values of the `RUST_BACKTRACE` and
+    /// `RUST_LIB_BACKTRACE` environment variables, always capturing a
+    /// backtrace.
+    ///
+    /// Note that capturing a backtrace can be an expensive operation on some
+    /// platforms, so this should be used with caution in performance-
+    /// sensitive parts of code.
+    ///
+    /// [`Backtrace::capture`]: struct.Backtrace.html#method.capture
+    #[inline(never)] // want to make sure there's a frame here to remove
+    pub fn force_capture() -> Backtrace {
+        Backtrace::create(Backtrace::force_capture as usize)
+    }
+
+    // Capture a backtrace which start just before the function addressed by
+    // `ip`
+    #[cfg(feature = "backtrace")]
+    fn create(ip: usize) -> Backtrace {
+        let mut raw = [Frame {
+            exact_position: 0 as *const _,
+            symbol_addr: 0 as *const _,
+        }; backtrace_rs::MAX_NB_FRAMES];
+
+        let nb_frames = {
+            let _lock = backtrace_rs::lock();
+            // The platform context is dropped right away; symbolication will
+            // set up a fresh one once the backtrace is actually looked at.
+            match backtrace_rs::unwind_backtrace(&mut raw) {
+                Ok((nb_frames, _context)) => nb_frames,
+                Err(_) => return Backtrace { inner: Inner::Unsupported },
+            }
+        };
+
+        let mut frames = Vec::with_capacity(nb_frames);
+        let mut actual_start = None;
+        for frame in raw[..nb_frames].iter() {
+            frames.push(BacktraceFrame {
+                ip: frame.exact_position as usize,
+                symbol_addr: frame.symbol_addr as usize,
+                symbols: Vec::new(),
+            });
+            if frame.symbol_addr as usize == ip && actual_start.is_none() {
+                actual_start = Some(frames.len());
+            }
+        }
+
+        // If no frames came out assume that this is an unsupported platform
+        // since the unwinder isn't able to walk the stack.
+        let inner = if frames.is_empty() {
+            Inner::Unsupported
+        } else {
+            Inner::Captured(LazilyResolvedCapture::new(Capture {
+                actual_start: actual_start.unwrap_or(0),
+                frames,
+            }))
+        };
+
+        Backtrace { inner }
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    fn create(_ip: usize) -> Backtrace {
+        Backtrace { inner: Inner::Unsupported }
+    }
+
+    /// Returns the status of this backtrace, indicating whether this
+    /// backtrace request was unsupported, disabled, or a stack trace was
+    /// actually captured.
+    pub fn status(&self) -> BacktraceStatus {
+        match self.inner {
+            Inner::Unsupported => BacktraceStatus::Unsupported,
+            Inner::Disabled => BacktraceStatus::Disabled,
+            Inner::Captured(_) => BacktraceStatus::Captured,
+        }
+    }
+
+    /// Returns the frames of this backtrace, starting at the caller of
+    /// [`Backtrace::capture`] or [`Backtrace::force_capture`].
+    ///
+    /// This resolves the symbols of every frame if that hasn't been done
+    /// yet. Unsupported and disabled backtraces have no frames.
+    ///
+    /// [`Backtrace::capture`]: struct.Backtrace.html#method.capture
+    /// [`Backtrace::force_capture`]: struct.Backtrace.html#method.force_capture
+    pub fn frames(&self) -> &[BacktraceFrame] {
+        match self.inner {
+            Inner::Captured(ref c) => {
+                let capture = c.force();
+                &capture.frames[capture.actual_start..]
+            }
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for Backtrace {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let capture = match self.inner {
+            Inner::Unsupported => return fmt.write_str("unsupported backtrace"),
+            Inner::Disabled => return fmt.write_str("disabled backtrace"),
+            Inner::Captured(ref c) => c.force(),
+        };
+
+        let full = fmt.alternate();
+        let frames = if full {
+            &capture.frames[..]
+        } else {
+            &capture.frames[capture.actual_start..]
+        };
+
+        writeln!(fmt, "stack backtrace:")?;
+        let mut idx = 0;
+        for frame in frames {
+            if frame.ip == 0 && !full {
+                continue;
+            }
+            // In the short format stop at the frame marking the start of
+            // `main` or of a spawned thread, as panic backtraces do.
+            if !full && frame.is_short_backtrace_marker() {
+                break;
+            }
+
+            if frame.symbols.is_empty() {
+                write!(fmt, "  {:2}: ", idx)?;
+                if full {
+                    write!(fmt, "{:#x} - ", frame.ip)?;
+                }
+                writeln!(fmt, "<unknown>")?;
+            }
+            for (i, symbol) in frame.symbols.iter().enumerate() {
+                if i == 0 {
+                    write!(fmt, "  {:2}: ", idx)?;
+                    if full {
+                        write!(fmt, "{:#x} - ", frame.ip)?;
+                    }
+                } else {
+                    write!(fmt, "      ")?;
+                    if full {
+                        write!(fmt, "{:1$}   ", "", HEX_WIDTH)?;
+                    }
+                }
+                match symbol.name {
+                    Some(ref name) => fmt_symbol_name(fmt, name, full)?,
+                    None => fmt.write_str("<unknown>")?,
+                }
+                fmt.write_str("\n")?;
+                if let (&Some(ref file), Some(line)) = (&symbol.filename, symbol.lineno) {
+                    let file = str::from_utf8(file).unwrap_or("<unknown>");
+                    writeln!(fmt, "             at {}:{}", file, line)?;
+                }
+            }
+            idx += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+const HEX_WIDTH: usize = 18;
+
+#[cfg(target_pointer_width = "32")]
+const HEX_WIDTH: usize = 10;
+
+/// Writes a possibly mangled symbol name, demangling it if it looks like a
+/// Rust symbol.
+#[cfg(feature = "backtrace")]
+fn fmt_symbol_name(fmt: &mut fmt::Formatter, name: &[u8], full: bool) -> fmt::Result {
+    let name = match str::from_utf8(name) {
+        Ok(name) => name,
+        Err(_) => return fmt.write_str("<unknown>"),
+    };
+    let format = if full { PrintFormat::Full } else { PrintFormat::Short };
+    let mut demangled = Vec::new();
+    if backtrace_rs::demangle(&mut demangled, name, format).is_err() {
+        return fmt.write_str(name);
+    }
+    fmt.write_str(str::from_utf8(&demangled).unwrap_or(name))
+}
+
+#[cfg(not(feature = "backtrace"))]
+fn fmt_symbol_name(fmt: &mut fmt::Formatter, name: &[u8], _full: bool) -> fmt::Result {
+    fmt.write_str(str::from_utf8(name).unwrap_or("<unknown>"))
+}
+
+impl BacktraceFrame {
+    /// Returns the instruction pointer of this frame.
+    ///
+    /// This is normally the address of the next instruction to execute in
+    /// the frame, i.e. the return address for every frame but the first.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Returns the starting address of the function this frame is in, or 0
+    /// if it isn't known.
+    pub fn symbol_address(&self) -> usize {
+        self.symbol_addr
+    }
+
+    /// Returns the symbols this frame resolved to.
+    ///
+    /// This is empty if symbolication failed or isn't supported on the
+    /// current platform.
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        &self.symbols
+    }
+
+    fn is_short_backtrace_marker(&self) -> bool {
+        self.symbols.iter().any(|symbol| {
+            match symbol.name {
+                Some(ref name) => {
+                    str::from_utf8(name)
+                        .map(|name| name.contains("__rust_begin_short_backtrace"))
+                        .unwrap_or(false)
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+impl BacktraceSymbol {
+    /// Returns the demangled name of this symbol, if it is known.
+    pub fn name(&self) -> Option<String> {
+        self.name.as_ref().map(|name| SymbolName(name).to_string())
+    }
+
+    /// Returns the raw, possibly mangled, name of this symbol, if it is
+    /// known.
+    pub fn name_bytes(&self) -> Option<&[u8]> {
+        self.name.as_ref().map(|name| &name[..])
+    }
+
+    /// Returns the path of the source file this symbol is defined in, if it
+    /// is known.
+    pub fn filename(&self) -> Option<&Path> {
+        self.filename.as_ref().and_then(|file| bytes_to_path(file))
+    }
+
+    /// Returns the line number in [`filename`] this symbol refers to, if it
+    /// is known.
+    ///
+    /// [`filename`]: #method.filename
+    pub fn lineno(&self) -> Option<u32> {
+        self.lineno
+    }
+}
+
+/// Displays a symbol name the way `{}` does, i.e. demangled and without the
+/// trailing hash of Rust symbols.
+struct SymbolName<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for SymbolName<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt_symbol_name(fmt, self.0, false)
+    }
+}
+
+#[cfg(any(unix, target_os = "redox"))]
+fn bytes_to_path(bytes: &[u8]) -> Option<&Path> {
+    use ffi::OsStr;
+    use sys::ext::ffi::OsStrExt;
+    Some(Path::new(OsStr::from_bytes(bytes)))
+}
+
+#[cfg(not(any(unix, target_os = "redox")))]
+fn bytes_to_path(bytes: &[u8]) -> Option<&Path> {
+    str::from_utf8(bytes).ok().map(Path::new)
+}
+
+impl Capture {
+    // Only ever called once per capture, by `LazilyResolvedCapture::force`.
+    #[cfg(feature = "backtrace")]
+    fn resolve(&mut self) {
+        // Use the global backtrace lock to synchronize this as it's a
+        // requirement of the platform symbolication APIs.
+        let _lock = backtrace_rs::lock();
+        let context = match backtrace_rs::symbolication_context() {
+            Ok(context) => context,
+            Err(_) => return,
+        };
+        for frame in self.frames.iter_mut() {
+            let raw = Frame {
+                exact_position: frame.ip as *const _,
+                symbol_addr: frame.symbol_addr as *const _,
+            };
+            let symbols = &mut frame.symbols;
+            let _ = backtrace_rs::resolve_symname(raw, |name| {
+                symbols.push(BacktraceSymbol {
+                    name: name.map(|name| name.as_bytes().to_vec()),
+                    filename: None,
+                    lineno: None,
+                });
+                Ok(())
+            }, &context);
+            let _ = backtrace_rs::foreach_symbol_fileline(raw, |file, line| {
+                // Inlined frames report several locations for one
+                // address; the outermost name belongs to the first one.
+                let has_location = symbols.last().map_or(true, |s| s.filename.is_some());
+                if has_location {
+                    symbols.push(BacktraceSymbol {
+                        name: None,
+                        filename: None,
+                        lineno: None,
+                    });
+                }
+                let symbol = symbols.last_mut().unwrap();
+                symbol.filename = Some(file.to_vec());
+                symbol.lineno = Some(line as u32);
+                Ok(())
+            }, &context);
+        }
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    fn resolve(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_and_unsupported() {
+        let bt = Backtrace { inner: Inner::Disabled };
+        assert_eq!(bt.status(), BacktraceStatus::Disabled);
+        assert!(bt.frames().is_empty());
+        assert_eq!(format!("{}", bt), "disabled backtrace");
+        assert_eq!(format!("{:?}", bt), "<disabled>");
+
+        let bt = Backtrace { inner: Inner::Unsupported };
+        assert_eq!(bt.status(), BacktraceStatus::Unsupported);
+        assert_eq!(format!("{}", bt), "unsupported backtrace");
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "backtrace"), ignore)]
+    fn force_capture() {
+        let bt = Backtrace::force_capture();
+        assert_eq!(bt.status(), BacktraceStatus::Captured);
+
+        // Formatting and `frames` share one resolution of the symbols.
+        assert!(format!("{:#}", bt).starts_with("stack backtrace:\n"));
+        let frames = bt.frames();
+        assert!(!frames.is_empty());
+        assert!(frames.iter().any(|frame| {
+            frame.symbols().iter().any(|symbol| symbol.name().is_some())
+        }));
+    }
+}
@@ -126,3 +126,68 @@ fn get_remove_insert(b: &mut Bencher) {
         k += 1;
     })
 }
+
+#[bench]
+fn find_existing_string(b: &mut Bencher) {
+    use super::map::HashMap;
+
+    let keys: Vec<String> = (1..1001).map(|i| format!("key{}", i)).collect();
+    let mut m = HashMap::new();
+
+    for key in &keys {
+        m.insert(key.clone(), 0);
+    }
+
+    b.iter(|| {
+        for key in &keys {
+            m.contains_key(key);
+        }
+    });
+}
+
+#[bench]
+fn find_nonexisting_string(b: &mut Bencher) {
+    use super::map::HashMap;
+
+    let mut m = HashMap::new();
+
+    for i in 1..1001 {
+        m.insert(format!("key{}", i), 0);
+    }
+
+    let misses: Vec<String> = (1001..2001).map(|i| format!("key{}", i)).collect();
+
+    b.iter(|| {
+        for key in &misses {
+            m.contains_key(key);
+        }
+    });
+}
+
+#[bench]
+fn insert_reserved(b: &mut Bencher) {
+    use super::map::HashMap;
+
+    b.iter(|| {
+        let mut m = HashMap::with_capacity(1000);
+        for i in 1..1001 {
+            m.insert(i, i);
+        }
+        m
+    });
+}
+
+#[bench]
+fn iterate(b: &mut Bencher) {
+    use super::map::HashMap;
+
+    let mut m = HashMap::new();
+
+    for i in 1..1001 {
+        m.insert(i, i);
+    }
+
+    b.iter(|| {
+        m.iter().map(|(_, &v)| v).sum::<i32>()
+    });
+}
@@ -0,0 +1,302 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Groups of control bytes, scanned in parallel.
+//!
+//! A `RawTable` keeps one control byte per bucket, and looks at
+//! `GROUP_WIDTH` of them at a time. With SSE2 a group is a 16-byte vector
+//! compared with `pcmpeqb` and reduced with `pmovmskb`; everywhere else a
+//! group is a `u64` and the same queries are answered with a few bitwise
+//! tricks. Either way a query produces a `BitMask` with one set bit per
+//! matching byte.
+
+pub use self::imp::{Group, BitMaskWord, BITMASK_STRIDE, BITMASK_MASK, GROUP_WIDTH};
+
+/// Control byte value for an empty bucket.
+pub const EMPTY: u8 = 0b1111_1111;
+
+/// Control byte value for a deleted bucket.
+pub const DELETED: u8 = 0b1000_0000;
+
+/// Checks whether a control byte represents a full bucket (top bit is clear).
+#[inline]
+pub fn is_full(ctrl: u8) -> bool {
+    ctrl & 0x80 == 0
+}
+
+/// Checks whether a special control value is EMPTY (just check 1 bit).
+#[inline]
+pub fn special_is_empty(ctrl: u8) -> bool {
+    debug_assert!(!is_full(ctrl));
+    ctrl & 0x01 != 0
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+mod imp {
+    use ptr;
+    use super::{BitMask, EMPTY};
+
+    pub type BitMaskWord = u16;
+    pub const BITMASK_STRIDE: usize = 1;
+    pub const BITMASK_MASK: BitMaskWord = 0xffff;
+
+    /// Number of control bytes in a group.
+    pub const GROUP_WIDTH: usize = 16;
+
+    #[repr(simd)]
+    #[derive(Copy, Clone)]
+    #[allow(non_camel_case_types)]
+    struct i8x16(i8, i8, i8, i8, i8, i8, i8, i8,
+                 i8, i8, i8, i8, i8, i8, i8, i8);
+
+    extern "platform-intrinsic" {
+        fn simd_eq<T, U>(x: T, y: T) -> U;
+        fn x86_mm_movemask_epi8(x: i8x16) -> i32;
+    }
+
+    /// A group of 16 control bytes held in an SSE2 register.
+    #[derive(Copy, Clone)]
+    pub struct Group(i8x16);
+
+    impl Group {
+        /// Loads a group of bytes starting at the given address, which need
+        /// not be aligned.
+        #[inline]
+        pub unsafe fn load(ptr: *const u8) -> Group {
+            Group(ptr::read_unaligned(ptr as *const i8x16))
+        }
+
+        /// Returns a `BitMask` indicating all bytes in the group which have
+        /// the given value.
+        #[inline]
+        pub fn match_byte(self, byte: u8) -> BitMask {
+            let b = byte as i8;
+            let splat = i8x16(b, b, b, b, b, b, b, b, b, b, b, b, b, b, b, b);
+            unsafe {
+                let cmp: i8x16 = simd_eq(self.0, splat);
+                BitMask(x86_mm_movemask_epi8(cmp) as BitMaskWord)
+            }
+        }
+
+        /// Returns a `BitMask` indicating all bytes in the group which are
+        /// `EMPTY`.
+        #[inline]
+        pub fn match_empty(self) -> BitMask {
+            self.match_byte(EMPTY)
+        }
+
+        /// Returns a `BitMask` indicating all bytes in the group which are
+        /// `EMPTY` or `DELETED`.
+        #[inline]
+        pub fn match_empty_or_deleted(self) -> BitMask {
+            // A byte is EMPTY or DELETED iff its high bit is set, which is
+            // exactly what `pmovmskb` collects.
+            unsafe { BitMask(x86_mm_movemask_epi8(self.0) as BitMaskWord) }
+        }
+
+        /// Returns a `BitMask` indicating all bytes in the group which are
+        /// full.
+        #[inline]
+        pub fn match_full(self) -> BitMask {
+            self.match_empty_or_deleted().invert()
+        }
+    }
+}
+
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
+mod imp {
+    use ptr;
+    use super::BitMask;
+
+    type GroupWord = u64;
+
+    pub type BitMaskWord = u64;
+    pub const BITMASK_STRIDE: usize = 8;
+    pub const BITMASK_MASK: BitMaskWord = 0x8080_8080_8080_8080;
+
+    /// Number of control bytes in a group.
+    pub const GROUP_WIDTH: usize = 8;
+
+    /// Helper function to replicate a byte across a `GroupWord`.
+    #[inline]
+    fn repeat(byte: u8) -> GroupWord {
+        (byte as GroupWord) * 0x0101_0101_0101_0101
+    }
+
+    /// A group of 8 control bytes held in a general purpose register.
+    ///
+    /// The word is always kept in little-endian order so that the first
+    /// control byte of the group maps to the lowest bits of the `BitMask`.
+    #[derive(Copy, Clone)]
+    pub struct Group(GroupWord);
+
+    impl Group {
+        /// Loads a group of bytes starting at the given address, which need
+        /// not be aligned.
+        #[inline]
+        pub unsafe fn load(ptr: *const u8) -> Group {
+            Group(GroupWord::from_le(ptr::read_unaligned(ptr as *const GroupWord)))
+        }
+
+        /// Returns a `BitMask` indicating all bytes in the group which *may*
+        /// have the given value.
+        ///
+        /// This may return a false positive for a byte equal to
+        /// `byte ^ 1` that directly follows a real match. That byte is
+        /// always a full bucket whose key gets compared anyway, so the
+        /// false positive is harmless.
+        #[inline]
+        pub fn match_byte(self, byte: u8) -> BitMask {
+            // Bytes that match are zero in `cmp`; find them with the
+            // classic "has zero byte" trick.
+            let cmp = self.0 ^ repeat(byte);
+            BitMask(cmp.wrapping_sub(repeat(0x01)) & !cmp & repeat(0x80))
+        }
+
+        /// Returns a `BitMask` indicating all bytes in the group which are
+        /// `EMPTY`.
+        #[inline]
+        pub fn match_empty(self) -> BitMask {
+            // EMPTY is the only control value with both of its top two bits
+            // set.
+            BitMask(self.0 & (self.0 << 1) & repeat(0x80))
+        }
+
+        /// Returns a `BitMask` indicating all bytes in the group which are
+        /// `EMPTY` or `DELETED`.
+        #[inline]
+        pub fn match_empty_or_deleted(self) -> BitMask {
+            BitMask(self.0 & repeat(0x80))
+        }
+
+        /// Returns a `BitMask` indicating all bytes in the group which are
+        /// full.
+        #[inline]
+        pub fn match_full(self) -> BitMask {
+            self.match_empty_or_deleted().invert()
+        }
+    }
+}
+
+/// A bit mask with one bit per control byte of a `Group`.
+///
+/// Bit `i * BITMASK_STRIDE` is set if the `i`th byte of the group matched
+/// the query.
+#[derive(Copy, Clone)]
+pub struct BitMask(pub BitMaskWord);
+
+impl BitMask {
+    /// Returns a new `BitMask` with all bits inverted.
+    #[inline]
+    pub fn invert(self) -> BitMask {
+        BitMask(self.0 ^ BITMASK_MASK)
+    }
+
+    /// Returns a new `BitMask` with the lowest bit removed.
+    #[inline]
+    pub fn remove_lowest_bit(self) -> BitMask {
+        BitMask(self.0 & (self.0 - 1))
+    }
+
+    /// Returns whether the `BitMask` has at least one set bit.
+    #[inline]
+    pub fn any_bit_set(self) -> bool {
+        self.0 != 0
+    }
+
+    /// Returns the index of the first matching byte, if any.
+    #[inline]
+    pub fn lowest_set_bit(self) -> Option<usize> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.trailing_zeros())
+        }
+    }
+
+    /// Returns the number of non-matching bytes at the start of the group.
+    #[inline]
+    pub fn trailing_zeros(self) -> usize {
+        self.0.trailing_zeros() as usize / BITMASK_STRIDE
+    }
+
+    /// Returns the number of non-matching bytes at the end of the group.
+    #[inline]
+    pub fn leading_zeros(self) -> usize {
+        self.0.leading_zeros() as usize / BITMASK_STRIDE
+    }
+}
+
+impl IntoIterator for BitMask {
+    type Item = usize;
+    type IntoIter = BitMaskIter;
+
+    #[inline]
+    fn into_iter(self) -> BitMaskIter {
+        BitMaskIter(self)
+    }
+}
+
+/// Iterator over the indices of the matching bytes in a `BitMask`.
+pub struct BitMaskIter(BitMask);
+
+impl Iterator for BitMaskIter {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let bit = self.0.lowest_set_bit();
+        if bit.is_some() {
+            self.0 = self.0.remove_lowest_bit();
+        }
+        bit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Group, BitMask, EMPTY, DELETED, GROUP_WIDTH};
+
+    fn indices(mask: BitMask) -> Vec<usize> {
+        mask.into_iter().collect()
+    }
+
+    #[test]
+    fn test_match() {
+        let mut bytes = [EMPTY; GROUP_WIDTH];
+        bytes[1] = 0x12;
+        bytes[2] = DELETED;
+        bytes[4] = 0x12;
+        bytes[5] = 0x34;
+        let group = unsafe { Group::load(bytes.as_ptr()) };
+
+        assert_eq!(indices(group.match_byte(0x12)), vec![1, 4]);
+        assert_eq!(indices(group.match_byte(0x56)), vec![]);
+        assert_eq!(indices(group.match_full()), vec![1, 4, 5]);
+
+        let empty = indices(group.match_empty());
+        assert_eq!(empty.len(), GROUP_WIDTH - 4);
+        assert!(!empty.contains(&2));
+
+        let empty_or_deleted = indices(group.match_empty_or_deleted());
+        assert_eq!(empty_or_deleted.len(), GROUP_WIDTH - 3);
+        assert!(empty_or_deleted.contains(&2));
+    }
+
+    #[test]
+    fn test_leading_trailing_zeros() {
+        let mut bytes = [0; GROUP_WIDTH];
+        bytes[2] = EMPTY;
+        let group = unsafe { Group::load(bytes.as_ptr()) };
+        assert_eq!(group.match_empty().trailing_zeros(), 2);
+        assert_eq!(group.match_empty().leading_zeros(), GROUP_WIDTH - 3);
+        assert_eq!(group.match_byte(0x7f).trailing_zeros(), GROUP_WIDTH);
+    }
+}
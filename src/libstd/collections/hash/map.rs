@@ -9,213 +9,39 @@
 // except according to those terms.
 
 use self::Entry::*;
-use self::VacantEntryState::*;
 
 use cell::Cell;
 use borrow::Borrow;
-use cmp::max;
 use fmt::{self, Debug};
 #[allow(deprecated)]
 use hash::{Hash, Hasher, BuildHasher, SipHasher13};
 use iter::{FromIterator, FusedIterator};
-use mem::{self, replace};
-use ops::{Deref, Index, InPlace, Place, Placer};
+use mem;
+use ops::{Index, InPlace, Place, Placer};
 use rand::{self, Rng};
 use ptr;
 
-use super::table::{self, Bucket, EmptyBucket, FullBucket, FullBucketMut, RawTable, SafeHash};
-use super::table::BucketState::{Empty, Full};
-
-const MIN_NONZERO_RAW_CAPACITY: usize = 32;     // must be a power of two
-
-/// The default behavior of HashMap implements a maximum load factor of 90.9%.
-#[derive(Clone)]
-struct DefaultResizePolicy;
-
-impl DefaultResizePolicy {
-    fn new() -> DefaultResizePolicy {
-        DefaultResizePolicy
-    }
-
-    /// A hash map's "capacity" is the number of elements it can hold without
-    /// being resized. Its "raw capacity" is the number of slots required to
-    /// provide that capacity, accounting for maximum loading. The raw capacity
-    /// is always zero or a power of two.
-    #[inline]
-    fn raw_capacity(&self, len: usize) -> usize {
-        if len == 0 {
-            0
-        } else {
-            // 1. Account for loading: `raw_capacity >= len * 1.1`.
-            // 2. Ensure it is a power of two.
-            // 3. Ensure it is at least the minimum size.
-            let mut raw_cap = len * 11 / 10;
-            assert!(raw_cap >= len, "raw_cap overflow");
-            raw_cap = raw_cap.checked_next_power_of_two().expect("raw_capacity overflow");
-            raw_cap = max(MIN_NONZERO_RAW_CAPACITY, raw_cap);
-            raw_cap
-        }
-    }
-
-    /// The capacity of the given raw capacity.
-    #[inline]
-    fn capacity(&self, raw_cap: usize) -> usize {
-        // This doesn't have to be checked for overflow since allocation size
-        // in bytes will overflow earlier than multiplication by 10.
-        //
-        // As per https://github.com/rust-lang/rust/pull/30991 this is updated
-        // to be: (raw_cap * den + den - 1) / num
-        (raw_cap * 10 + 10 - 1) / 11
-    }
-}
-
-// The main performance trick in this hashmap is called Robin Hood Hashing.
-// It gains its excellent performance from one essential operation:
-//
-//    If an insertion collides with an existing element, and that element's
-//    "probe distance" (how far away the element is from its ideal location)
-//    is higher than how far we've already probed, swap the elements.
-//
-// This massively lowers variance in probe distance, and allows us to get very
-// high load factors with good performance. The 90% load factor I use is rather
-// conservative.
-//
-// > Why a load factor of approximately 90%?
-//
-// In general, all the distances to initial buckets will converge on the mean.
-// At a load factor of α, the odds of finding the target bucket after k
-// probes is approximately 1-α^k. If we set this equal to 50% (since we converge
-// on the mean) and set k=8 (64-byte cache line / 8-byte hash), α=0.92. I round
-// this down to make the math easier on the CPU and avoid its FPU.
-// Since on average we start the probing in the middle of a cache line, this
-// strategy pulls in two cache lines of hashes on every lookup. I think that's
-// pretty good, but if you want to trade off some space, it could go down to one
-// cache line on average with an α of 0.84.
-//
-// > Wait, what? Where did you get 1-α^k from?
-//
-// On the first probe, your odds of a collision with an existing element is α.
-// The odds of doing this twice in a row is approximately α^2. For three times,
-// α^3, etc. Therefore, the odds of colliding k times is α^k. The odds of NOT
-// colliding after k tries is 1-α^k.
-//
-// The paper from 1986 cited below mentions an implementation which keeps track
-// of the distance-to-initial-bucket histogram. This approach is not suitable
-// for modern architectures because it requires maintaining an internal data
-// structure. This allows very good first guesses, but we are most concerned
-// with guessing entire cache lines, not individual indexes. Furthermore, array
-// accesses are no longer linear and in one direction, as we have now. There
-// is also memory and cache pressure that this would entail that would be very
-// difficult to properly see in a microbenchmark.
-//
-// ## Future Improvements (FIXME!)
-//
-// Allow the load factor to be changed dynamically and/or at initialization.
-//
-// Also, would it be possible for us to reuse storage when growing the
-// underlying table? This is exactly the use case for 'realloc', and may
-// be worth exploring.
-//
-// ## Future Optimizations (FIXME!)
+use super::table::{self, Bucket, RawTable};
+use super::table::Fallibility::{self, Fallible, Infallible};
+use alloc::allocator::CollectionAllocErr;
+
+// The hashtable itself lives in `table.rs`. It is an open-addressing table in
+// the style of Google's SwissTable: every bucket has a control byte holding 7
+// bits of its element's hash, and lookups compare a whole group of control
+// bytes against the key's hash at once (16 of them with SSE2, 8 with the
+// portable fallback), only reading the keys whose control byte matched.
+// Deleted buckets are marked with tombstones, which are cleaned up when the
+// table is rehashed.
 //
-// Another possible design choice that I made without any real reason is
-// parameterizing the raw table over keys and values. Technically, all we need
-// is the size and alignment of keys and values, and the code should be just as
-// efficient (well, we might need one for power-of-two size and one for not...).
-// This has the potential to reduce code bloat in rust executables, without
-// really losing anything except 4 words (key size, key alignment, val size,
-// val alignment) which can be passed in to every call of a `RawTable` function.
-// This would definitely be an avenue worth exploring if people start complaining
-// about the size of rust executables.
+// This module wraps the table into a map. It hashes every key with the map's
+// `BuildHasher` before handing it to the table, and grows the table before
+// any insertion that could fill it beyond its maximum load factor of 87.5%.
 //
-// Annotate exceedingly likely branches in `table::make_hash`
-// and `search_hashed` to reduce instruction cache pressure
-// and mispredictions once it becomes possible (blocked on issue #11092).
-//
-// Shrinking the table could simply reallocate in place after moving buckets
-// to the first half.
-//
-// The growth algorithm (fragment of the Proof of Correctness)
-// --------------------
-//
-// The growth algorithm is basically a fast path of the naive reinsertion-
-// during-resize algorithm. Other paths should never be taken.
-//
-// Consider growing a robin hood hashtable of capacity n. Normally, we do this
-// by allocating a new table of capacity `2n`, and then individually reinsert
-// each element in the old table into the new one. This guarantees that the
-// new table is a valid robin hood hashtable with all the desired statistical
-// properties. Remark that the order we reinsert the elements in should not
-// matter. For simplicity and efficiency, we will consider only linear
-// reinsertions, which consist of reinserting all elements in the old table
-// into the new one by increasing order of index. However we will not be
-// starting our reinsertions from index 0 in general. If we start from index
-// i, for the purpose of reinsertion we will consider all elements with real
-// index j < i to have virtual index n + j.
-//
-// Our hash generation scheme consists of generating a 64-bit hash and
-// truncating the most significant bits. When moving to the new table, we
-// simply introduce a new bit to the front of the hash. Therefore, if an
-// elements has ideal index i in the old table, it can have one of two ideal
-// locations in the new table. If the new bit is 0, then the new ideal index
-// is i. If the new bit is 1, then the new ideal index is n + i. Intuitively,
-// we are producing two independent tables of size n, and for each element we
-// independently choose which table to insert it into with equal probability.
-// However the rather than wrapping around themselves on overflowing their
-// indexes, the first table overflows into the first, and the first into the
-// second. Visually, our new table will look something like:
-//
-// [yy_xxx_xxxx_xxx|xx_yyy_yyyy_yyy]
-//
-// Where x's are elements inserted into the first table, y's are elements
-// inserted into the second, and _'s are empty sections. We now define a few
-// key concepts that we will use later. Note that this is a very abstract
-// perspective of the table. A real resized table would be at least half
-// empty.
-//
-// Theorem: A linear robin hood reinsertion from the first ideal element
-// produces identical results to a linear naive reinsertion from the same
-// element.
-//
-// FIXME(Gankro, pczarn): review the proof and put it all in a separate README.md
-//
-// Adaptive early resizing
-// ----------------------
-// To protect against degenerate performance scenarios (including DOS attacks),
-// the implementation includes an adaptive behavior that can resize the map
-// early (before its capacity is exceeded) when suspiciously long probe sequences
-// are encountered.
-//
-// With this algorithm in place it would be possible to turn a CPU attack into
-// a memory attack due to the aggressive resizing. To prevent that the
-// adaptive behavior only triggers when the map is at least half full.
-// This reduces the effectiveness of the algorithm but also makes it completely safe.
-//
-// The previous safety measure also prevents degenerate interactions with
-// really bad quality hash algorithms that can make normal inputs look like a
-// DOS attack.
-//
-const DISPLACEMENT_THRESHOLD: usize = 128;
-//
-// The threshold of 128 is chosen to minimize the chance of exceeding it.
-// In particular, we want that chance to be less than 10^-8 with a load of 90%.
-// For displacement, the smallest constant that fits our needs is 90,
-// so we round that up to 128.
-//
-// At a load factor of α, the odds of finding the target bucket after exactly n
-// unsuccessful probes[1] are
-//
-// Pr_α{displacement = n} =
-// (1 - α) / α * ∑_{k≥1} e^(-kα) * (kα)^(k+n) / (k + n)! * (1 - kα / (k + n + 1))
-//
-// We use this formula to find the probability of triggering the adaptive behavior
-//
-// Pr_0.909{displacement > 128} = 1.601 * 10^-11
-//
-// 1. Alfredo Viola (2005). Distributional analysis of Robin Hood linear probing
-//    hashing with buckets.
+// Protection against degenerate inputs (including HashDoS attacks) comes from
+// the hasher: the default `RandomState` seeds SipHash 1-3 with random keys, so
+// an attacker cannot predict which keys will collide.
 
-/// A hash map implemented with linear probing and Robin Hood bucket stealing.
+/// A hash map implemented with quadratic probing and SIMD lookup.
 ///
 /// By default, `HashMap` uses a hashing algorithm selected to provide
 /// resistance against HashDoS attacks. The algorithm is randomly seeded, and a
@@ -253,13 +79,10 @@ const DISPLACEMENT_THRESHOLD: usize = 128;
 /// the [`Eq`] trait, changes while it is in the map. This is normally only
 /// possible through [`Cell`], [`RefCell`], global state, I/O, or unsafe code.
 ///
-/// Relevant papers/articles:
-///
-/// 1. Pedro Celis. ["Robin Hood Hashing"](https://cs.uwaterloo.ca/research/tr/1986/CS-86-14.pdf)
-/// 2. Emmanuel Goossaert. ["Robin Hood
-///    hashing"](http://codecapsule.com/2013/11/11/robin-hood-hashing/)
-/// 3. Emmanuel Goossaert. ["Robin Hood hashing: backward shift
-///    deletion"](http://codecapsule.com/2013/11/17/robin-hood-hashing-backward-shift-deletion/)
+/// The hash table implementation follows the design of Google's SwissTable,
+/// which is described in Matt Kulukundis' CppCon 2017 talk ["Designing a Fast,
+/// Efficient, Cache-friendly Hash Table, Step by
+/// Step"](https://www.youtube.com/watch?v=ncHmEUmJZf4).
 ///
 /// # Examples
 ///
@@ -393,195 +216,26 @@ pub struct HashMap<K, V, S = RandomState> {
     hash_builder: S,
 
     table: RawTable<K, V>,
-
-    resize_policy: DefaultResizePolicy,
-}
-
-/// Search for a pre-hashed key.
-#[inline]
-fn search_hashed<K, V, M, F>(table: M, hash: SafeHash, mut is_match: F) -> InternalEntry<K, V, M>
-    where M: Deref<Target = RawTable<K, V>>,
-          F: FnMut(&K) -> bool
-{
-    // This is the only function where capacity can be zero. To avoid
-    // undefined behavior when Bucket::new gets the raw bucket in this
-    // case, immediately return the appropriate search result.
-    if table.capacity() == 0 {
-        return InternalEntry::TableIsEmpty;
-    }
-
-    let size = table.size();
-    let mut probe = Bucket::new(table, hash);
-    let mut displacement = 0;
-
-    loop {
-        let full = match probe.peek() {
-            Empty(bucket) => {
-                // Found a hole!
-                return InternalEntry::Vacant {
-                    hash,
-                    elem: NoElem(bucket, displacement),
-                };
-            }
-            Full(bucket) => bucket,
-        };
-
-        let probe_displacement = full.displacement();
-
-        if probe_displacement < displacement {
-            // Found a luckier bucket than me.
-            // We can finish the search early if we hit any bucket
-            // with a lower distance to initial bucket than we've probed.
-            return InternalEntry::Vacant {
-                hash,
-                elem: NeqElem(full, probe_displacement),
-            };
-        }
-
-        // If the hash doesn't match, it can't be this one..
-        if hash == full.hash() {
-            // If the key doesn't match, it can't be this one..
-            if is_match(full.read().0) {
-                return InternalEntry::Occupied { elem: full };
-            }
-        }
-        displacement += 1;
-        probe = full.next();
-        debug_assert!(displacement <= size);
-    }
-}
-
-fn pop_internal<K, V>(starting_bucket: FullBucketMut<K, V>)
-    -> (K, V, &mut RawTable<K, V>)
-{
-    let (empty, retkey, retval) = starting_bucket.take();
-    let mut gap = match empty.gap_peek() {
-        Ok(b) => b,
-        Err(b) => return (retkey, retval, b.into_table()),
-    };
-
-    while gap.full().displacement() != 0 {
-        gap = match gap.shift() {
-            Ok(b) => b,
-            Err(b) => {
-                return (retkey, retval, b.into_table());
-            },
-        };
-    }
-
-    // Now we've done all our shifting. Return the value we grabbed earlier.
-    (retkey, retval, gap.into_table())
-}
-
-/// Perform robin hood bucket stealing at the given `bucket`. You must
-/// also pass that bucket's displacement so we don't have to recalculate it.
-///
-/// `hash`, `key`, and `val` are the elements to "robin hood" into the hashtable.
-fn robin_hood<'a, K: 'a, V: 'a>(bucket: FullBucketMut<'a, K, V>,
-                                mut displacement: usize,
-                                mut hash: SafeHash,
-                                mut key: K,
-                                mut val: V)
-                                -> FullBucketMut<'a, K, V> {
-    let size = bucket.table().size();
-    let raw_capacity = bucket.table().capacity();
-    // There can be at most `size - dib` buckets to displace, because
-    // in the worst case, there are `size` elements and we already are
-    // `displacement` buckets away from the initial one.
-    let idx_end = (bucket.index() + size - bucket.displacement()) % raw_capacity;
-    // Save the *starting point*.
-    let mut bucket = bucket.stash();
-
-    loop {
-        let (old_hash, old_key, old_val) = bucket.replace(hash, key, val);
-        hash = old_hash;
-        key = old_key;
-        val = old_val;
-
-        loop {
-            displacement += 1;
-            let probe = bucket.next();
-            debug_assert!(probe.index() != idx_end);
-
-            let full_bucket = match probe.peek() {
-                Empty(bucket) => {
-                    // Found a hole!
-                    let bucket = bucket.put(hash, key, val);
-                    // Now that it's stolen, just read the value's pointer
-                    // right out of the table! Go back to the *starting point*.
-                    //
-                    // This use of `into_table` is misleading. It turns the
-                    // bucket, which is a FullBucket on top of a
-                    // FullBucketMut, into just one FullBucketMut. The "table"
-                    // refers to the inner FullBucketMut in this context.
-                    return bucket.into_table();
-                }
-                Full(bucket) => bucket,
-            };
-
-            let probe_displacement = full_bucket.displacement();
-
-            bucket = full_bucket;
-
-            // Robin hood! Steal the spot.
-            if probe_displacement < displacement {
-                displacement = probe_displacement;
-                break;
-            }
-        }
-    }
 }
 
 impl<K, V, S> HashMap<K, V, S>
     where K: Eq + Hash,
           S: BuildHasher
 {
-    fn make_hash<X: ?Sized>(&self, x: &X) -> SafeHash
+    fn make_hash<X: ?Sized>(&self, x: &X) -> u64
         where X: Hash
     {
         table::make_hash(&self.hash_builder, x)
     }
 
-    /// Search for a key, yielding the index if it's found in the hashtable.
-    /// If you already have the hash for the key lying around, use
-    /// search_hashed.
-    #[inline]
-    fn search<'a, Q: ?Sized>(&'a self, q: &Q) -> InternalEntry<K, V, &'a RawTable<K, V>>
-        where K: Borrow<Q>,
-              Q: Eq + Hash
-    {
-        let hash = self.make_hash(q);
-        search_hashed(&self.table, hash, |k| q.eq(k.borrow()))
-    }
-
+    /// Search for a key, yielding its bucket if it's found in the hashtable.
     #[inline]
-    fn search_mut<'a, Q: ?Sized>(&'a mut self, q: &Q) -> InternalEntry<K, V, &'a mut RawTable<K, V>>
+    fn search<Q: ?Sized>(&self, q: &Q) -> Option<Bucket<K, V>>
         where K: Borrow<Q>,
               Q: Eq + Hash
     {
         let hash = self.make_hash(q);
-        search_hashed(&mut self.table, hash, |k| q.eq(k.borrow()))
-    }
-
-    // The caller should ensure that invariants by Robin Hood Hashing hold
-    // and that there's space in the underlying table.
-    fn insert_hashed_ordered(&mut self, hash: SafeHash, k: K, v: V) {
-        let mut buckets = Bucket::new(&mut self.table, hash);
-        let start_index = buckets.index();
-
-        loop {
-            // We don't need to compare hashes for value swap.
-            // Not even DIBs for Robin Hood.
-            buckets = match buckets.peek() {
-                Empty(empty) => {
-                    empty.put(hash, k, v);
-                    return;
-                }
-                Full(b) => b.into_bucket(),
-            };
-            buckets.next();
-            debug_assert!(buckets.index() != start_index);
-        }
+        self.table.find(hash, |k| q.eq(k.borrow()))
     }
 }
 
@@ -650,8 +304,7 @@ impl<K, V, S> HashMap<K, V, S>
     pub fn with_hasher(hash_builder: S) -> HashMap<K, V, S> {
         HashMap {
             hash_builder,
-            resize_policy: DefaultResizePolicy::new(),
-            table: RawTable::new(0),
+            table: RawTable::new(),
         }
     }
 
@@ -679,12 +332,9 @@ impl<K, V, S> HashMap<K, V, S>
     #[inline]
     #[stable(feature = "hashmap_build_hasher", since = "1.7.0")]
     pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> HashMap<K, V, S> {
-        let resize_policy = DefaultResizePolicy::new();
-        let raw_cap = resize_policy.raw_capacity(capacity);
         HashMap {
             hash_builder,
-            resize_policy,
-            table: RawTable::new(raw_cap),
+            table: RawTable::with_capacity(capacity),
         }
     }
 
@@ -711,12 +361,6 @@ impl<K, V, S> HashMap<K, V, S>
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn capacity(&self) -> usize {
-        self.resize_policy.capacity(self.raw_capacity())
-    }
-
-    /// Returns the hash map's raw capacity.
-    #[inline]
-    fn raw_capacity(&self) -> usize {
         self.table.capacity()
     }
 
@@ -739,69 +383,40 @@ impl<K, V, S> HashMap<K, V, S>
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn reserve(&mut self, additional: usize) {
-        let remaining = self.capacity() - self.len(); // this can't overflow
-        if remaining < additional {
-            let min_cap = self.len().checked_add(additional).expect("reserve overflow");
-            let raw_cap = self.resize_policy.raw_capacity(min_cap);
-            self.resize(raw_cap);
-        } else if self.table.tag() && remaining <= self.len() {
-            // Probe sequence is too long and table is half full,
-            // resize early to reduce probing length.
-            let new_capacity = self.table.capacity() * 2;
-            self.resize(new_capacity);
+        match self.reserve_internal(additional, Infallible) {
+            Err(CollectionAllocErr::CapacityOverflow) => panic!("capacity overflow"),
+            Err(CollectionAllocErr::AllocErr(_)) => unreachable!(),
+            Ok(()) => { /* yay */ }
         }
     }
 
-    /// Resizes the internal vectors to a new capacity. It's your
-    /// responsibility to:
-    ///   1) Ensure `new_raw_cap` is enough for all the elements, accounting
-    ///      for the load factor.
-    ///   2) Ensure `new_raw_cap` is a power of two or zero.
-    #[inline(never)]
-    #[cold]
-    fn resize(&mut self, new_raw_cap: usize) {
-        assert!(self.table.size() <= new_raw_cap);
-        assert!(new_raw_cap.is_power_of_two() || new_raw_cap == 0);
-
-        let mut old_table = replace(&mut self.table, RawTable::new(new_raw_cap));
-        let old_size = old_table.size();
-
-        if old_table.size() == 0 {
-            return;
-        }
-
-        let mut bucket = Bucket::head_bucket(&mut old_table);
+    /// Tries to reserve capacity for at least `additional` more elements to be inserted
+    /// in the given `HashMap<K,V>`. The collection may reserve more space to avoid
+    /// frequent reallocations.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashMap;
+    /// let mut map: HashMap<&str, isize> = HashMap::new();
+    /// map.try_reserve(10).expect("why is the test harness OOMing on 10 bytes?");
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), CollectionAllocErr> {
+        self.reserve_internal(additional, Fallible)
+    }
 
-        // This is how the buckets might be laid out in memory:
-        // ($ marks an initialized bucket)
-        //  ________________
-        // |$$$_$$$$$$_$$$$$|
-        //
-        // But we've skipped the entire initial cluster of buckets
-        // and will continue iteration in this order:
-        //  ________________
-        //     |$$$$$$_$$$$$
-        //                  ^ wrap around once end is reached
-        //  ________________
-        //  $$$_____________|
-        //    ^ exit once table.size == 0
-        loop {
-            bucket = match bucket.peek() {
-                Full(bucket) => {
-                    let h = bucket.hash();
-                    let (b, k, v) = bucket.take();
-                    self.insert_hashed_ordered(h, k, v);
-                    if b.table().size() == 0 {
-                        break;
-                    }
-                    b.into_bucket()
-                }
-                Empty(b) => b.into_bucket(),
-            };
-            bucket.next();
-        }
+    fn reserve_internal(&mut self, additional: usize, fallibility: Fallibility)
+        -> Result<(), CollectionAllocErr> {
 
-        assert_eq!(self.table.size(), old_size);
+        let hash_builder = &self.hash_builder;
+        self.table.reserve(additional, |k| table::make_hash(hash_builder, k), fallibility)
     }
 
     /// Shrinks the capacity of the map as much as possible. It will drop
@@ -822,36 +437,8 @@ impl<K, V, S> HashMap<K, V, S>
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn shrink_to_fit(&mut self) {
-        let new_raw_cap = self.resize_policy.raw_capacity(self.len());
-        if self.raw_capacity() != new_raw_cap {
-            let old_table = replace(&mut self.table, RawTable::new(new_raw_cap));
-            let old_size = old_table.size();
-
-            // Shrink the table. Naive algorithm for resizing:
-            for (h, k, v) in old_table.into_iter() {
-                self.insert_hashed_nocheck(h, k, v);
-            }
-
-            debug_assert_eq!(self.table.size(), old_size);
-        }
-    }
-
-    /// Insert a pre-hashed key-value pair, without first checking
-    /// that there's enough room in the buckets. Returns a reference to the
-    /// newly insert value.
-    ///
-    /// If the key already exists, the hashtable will be returned untouched
-    /// and a reference to the existing element will be returned.
-    fn insert_hashed_nocheck(&mut self, hash: SafeHash, k: K, v: V) -> Option<V> {
-        let entry = search_hashed(&mut self.table, hash, |key| *key == k).into_entry(k);
-        match entry {
-            Some(Occupied(mut elem)) => Some(elem.insert(v)),
-            Some(Vacant(elem)) => {
-                elem.insert(v);
-                None
-            }
-            None => unreachable!(),
-        }
+        let hash_builder = &self.hash_builder;
+        self.table.shrink_to_fit(|k| table::make_hash(hash_builder, k));
     }
 
     /// An iterator visiting all keys in arbitrary order.
@@ -999,8 +586,138 @@ impl<K, V, S> HashMap<K, V, S>
         // Gotta resize now.
         self.reserve(1);
         let hash = self.make_hash(&key);
-        search_hashed(&mut self.table, hash, |q| q.eq(&key))
-            .into_entry(key).expect("unreachable")
+        let elem = self.table.find(hash, |q| q.eq(&key));
+        match elem {
+            Some(elem) => {
+                Occupied(OccupiedEntry {
+                    key: Some(key),
+                    elem,
+                    table: &mut self.table,
+                })
+            }
+            None => {
+                Vacant(VacantEntry {
+                    hash,
+                    key,
+                    table: &mut self.table,
+                })
+            }
+        }
+    }
+
+    /// Creates a raw entry builder for the `HashMap`.
+    ///
+    /// Raw entries provide the lowest level of control for searching and
+    /// manipulating a map. They must be manually initialized with a hash and
+    /// then manually searched. After this, insertions into a vacant entry
+    /// still require an owned key to be provided.
+    ///
+    /// Raw entries are useful for such exotic situations as:
+    ///
+    /// * Hash memoization
+    /// * Deferring the creation of an owned key until it is known to be required
+    /// * Using a search key that doesn't work with the [`Borrow`] trait
+    /// * Using custom comparison logic without newtype wrappers
+    ///
+    /// Because raw entries provide much more low-level control, it's much
+    /// easier to put the `HashMap` into an inconsistent state which, while
+    /// memory-safe, will cause the map to produce seemingly random results.
+    /// Higher-level and more foolproof APIs like [`entry`] should be preferred
+    /// when possible.
+    ///
+    /// In particular, the hash used to initialize the raw entry must still be
+    /// consistent with the hash of the key that is ultimately stored in the
+    /// entry. This is because the map may need to recompute hashes when
+    /// resizing, at which point only the keys are available.
+    ///
+    /// Raw entries give mutable access to the keys. This must not be used to
+    /// modify how the key would compare or hash, as the map will not
+    /// re-evaluate where the key should go, meaning the keys may become "lost"
+    /// if their location does not reflect their state.
+    ///
+    /// [`Borrow`]: ../../std/borrow/trait.Borrow.html
+    /// [`entry`]: #method.entry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_raw_entry)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::RawEntryMut;
+    /// use std::hash::{BuildHasher, Hash, Hasher};
+    ///
+    /// // Give every distinct word an id, hashing each word only once and
+    /// // only allocating a `String` for the words we haven't seen yet.
+    /// let mut ids: HashMap<String, usize> = HashMap::new();
+    ///
+    /// for word in "the quick brown fox jumps over the lazy dog".split(' ') {
+    ///     let mut hasher = ids.hasher().build_hasher();
+    ///     word.hash(&mut hasher);
+    ///     let hash = hasher.finish();
+    ///
+    ///     let next_id = ids.len();
+    ///     match ids.raw_entry_mut().from_hash(hash, |k| k == word) {
+    ///         RawEntryMut::Occupied(_) => {}
+    ///         RawEntryMut::Vacant(entry) => {
+    ///             entry.insert_hashed_nocheck(hash, word.to_string(), next_id);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(ids.len(), 8);
+    /// assert_eq!(ids["the"], 0);
+    /// assert_eq!(ids["dog"], 7);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<K, V, S> {
+        self.reserve(1);
+        RawEntryBuilderMut { map: self }
+    }
+
+    /// Creates a raw immutable entry builder for the `HashMap`.
+    ///
+    /// Raw entries provide the lowest level of control for searching and
+    /// manipulating a map. They must be manually initialized with a hash and
+    /// then manually searched.
+    ///
+    /// This is useful for
+    ///
+    /// * Hash memoization
+    /// * Using a search key that doesn't work with the [`Borrow`] trait
+    /// * Using custom comparison logic without newtype wrappers
+    ///
+    /// Unless you are in such a situation, higher-level and more foolproof
+    /// APIs like [`get`] should be preferred.
+    ///
+    /// Immutable raw entries have very limited use; you might instead want
+    /// [`raw_entry_mut`].
+    ///
+    /// [`Borrow`]: ../../std/borrow/trait.Borrow.html
+    /// [`get`]: #method.get
+    /// [`raw_entry_mut`]: #method.raw_entry_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_raw_entry)]
+    /// use std::collections::HashMap;
+    /// use std::hash::{BuildHasher, Hash, Hasher};
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert("a".to_string(), 1);
+    ///
+    /// let mut hasher = map.hasher().build_hasher();
+    /// "a".hash(&mut hasher);
+    /// let hash = hasher.finish();
+    ///
+    /// assert_eq!(map.raw_entry().from_hash(hash, |k| k == "a"), Some((&"a".to_string(), &1)));
+    /// assert_eq!(map.raw_entry().from_key("b"), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn raw_entry(&self) -> RawEntryBuilder<K, V, S> {
+        RawEntryBuilder { map: self }
     }
 
     /// Returns the number of elements in the map.
@@ -1106,7 +823,7 @@ impl<K, V, S> HashMap<K, V, S>
         where K: Borrow<Q>,
               Q: Hash + Eq
     {
-        self.search(k).into_occupied_bucket().map(|bucket| bucket.into_refs().1)
+        self.search(k).map(|bucket| unsafe { &bucket.as_ref().1 })
     }
 
     /// Returns true if the map contains a value for the specified key.
@@ -1133,7 +850,7 @@ impl<K, V, S> HashMap<K, V, S>
         where K: Borrow<Q>,
               Q: Hash + Eq
     {
-        self.search(k).into_occupied_bucket().is_some()
+        self.search(k).is_some()
     }
 
     /// Returns a mutable reference to the value corresponding to the key.
@@ -1162,7 +879,7 @@ impl<K, V, S> HashMap<K, V, S>
         where K: Borrow<Q>,
               Q: Hash + Eq
     {
-        self.search_mut(k).into_occupied_bucket().map(|bucket| bucket.into_mut_refs().1)
+        self.search(k).map(|bucket| unsafe { &mut bucket.as_mut().1 })
     }
 
     /// Inserts a key-value pair into the map.
@@ -1193,8 +910,15 @@ impl<K, V, S> HashMap<K, V, S>
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(&mut self, k: K, v: V) -> Option<V> {
         let hash = self.make_hash(&k);
-        self.reserve(1);
-        self.insert_hashed_nocheck(hash, k, v)
+        let elem = self.table.find(hash, |q| q.eq(&k));
+        match elem {
+            Some(bucket) => unsafe { Some(mem::replace(&mut bucket.as_mut().1, v)) },
+            None => {
+                self.reserve(1);
+                self.table.insert_no_grow(hash, k, v);
+                None
+            }
+        }
     }
 
     /// Removes a key from the map, returning the value at the key if the key
@@ -1222,11 +946,7 @@ impl<K, V, S> HashMap<K, V, S>
         where K: Borrow<Q>,
               Q: Hash + Eq
     {
-        if self.table.size() == 0 {
-            return None;
-        }
-
-        self.search_mut(k).into_occupied_bucket().map(|bucket| pop_internal(bucket).1)
+        self.search(k).map(|bucket| unsafe { self.table.remove(bucket).1 })
     }
 
     /// Retains only the elements specified by the predicate.
@@ -1246,35 +966,15 @@ impl<K, V, S> HashMap<K, V, S>
     pub fn retain<F>(&mut self, mut f: F)
         where F: FnMut(&K, &mut V) -> bool
     {
-        if self.table.size() == 0 {
-            return;
-        }
-        let mut elems_left = self.table.size();
-        let mut bucket = Bucket::head_bucket(&mut self.table);
-        bucket.prev();
-        let start_index = bucket.index();
-        while elems_left != 0 {
-            bucket = match bucket.peek() {
-                Full(mut full) => {
-                    elems_left -= 1;
-                    let should_remove = {
-                        let (k, v) = full.read_mut();
-                        !f(k, v)
-                    };
-                    if should_remove {
-                        let prev_raw = full.raw();
-                        let (_, _, t) = pop_internal(full);
-                        Bucket::new_from(prev_raw, t)
-                    } else {
-                        full.into_bucket()
-                    }
-                },
-                Empty(b) => {
-                    b.into_bucket()
+        // Erasing a bucket leaves every other bucket where it is, so the
+        // iteration can carry on after removing an element.
+        unsafe {
+            for bucket in self.table.raw_iter() {
+                let &mut (ref key, ref mut value) = bucket.as_mut();
+                if !f(key, value) {
+                    self.table.remove(bucket);
                 }
-            };
-            bucket.prev();  // reverse iteration
-            debug_assert!(elems_left == 0 || bucket.index() != start_index);
+            }
         }
     }
 }
@@ -1473,47 +1173,424 @@ pub struct ValuesMut<'a, K: 'a, V: 'a> {
     inner: IterMut<'a, K, V>,
 }
 
-enum InternalEntry<K, V, M> {
-    Occupied { elem: FullBucket<K, V, M> },
-    Vacant {
-        hash: SafeHash,
-        elem: VacantEntryState<K, V, M>,
-    },
-    TableIsEmpty,
+/// A builder for computing where in a `HashMap` a key-value pair would be
+/// stored.
+///
+/// See the [`HashMap::raw_entry_mut`] docs for usage examples.
+///
+/// [`HashMap::raw_entry_mut`]: struct.HashMap.html#method.raw_entry_mut
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+pub struct RawEntryBuilderMut<'a, K: 'a, V: 'a, S: 'a> {
+    map: &'a mut HashMap<K, V, S>,
+}
+
+/// A view into a single entry in a map, which may either be vacant or
+/// occupied.
+///
+/// This is a lower-level version of [`Entry`].
+///
+/// This `enum` is constructed through the [`raw_entry_mut`] method on
+/// [`HashMap`], then calling one of the methods of that
+/// [`RawEntryBuilderMut`].
+///
+/// [`HashMap`]: struct.HashMap.html
+/// [`Entry`]: enum.Entry.html
+/// [`raw_entry_mut`]: struct.HashMap.html#method.raw_entry_mut
+/// [`RawEntryBuilderMut`]: struct.RawEntryBuilderMut.html
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+pub enum RawEntryMut<'a, K: 'a, V: 'a, S: 'a> {
+    /// An occupied entry.
+    Occupied(RawOccupiedEntryMut<'a, K, V>),
+    /// A vacant entry.
+    Vacant(RawVacantEntryMut<'a, K, V, S>),
+}
+
+/// A view into an occupied entry in a `HashMap`.
+/// It is part of the [`RawEntryMut`] enum.
+///
+/// [`RawEntryMut`]: enum.RawEntryMut.html
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+pub struct RawOccupiedEntryMut<'a, K: 'a, V: 'a> {
+    elem: Bucket<K, V>,
+    table: &'a mut RawTable<K, V>,
+}
+
+/// A view into a vacant entry in a `HashMap`.
+/// It is part of the [`RawEntryMut`] enum.
+///
+/// [`RawEntryMut`]: enum.RawEntryMut.html
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+pub struct RawVacantEntryMut<'a, K: 'a, V: 'a, S: 'a> {
+    table: &'a mut RawTable<K, V>,
+    hash_builder: &'a S,
 }
 
-impl<K, V, M> InternalEntry<K, V, M> {
+/// A builder for computing where in a `HashMap` a key-value pair would be
+/// stored.
+///
+/// See the [`HashMap::raw_entry`] docs for usage examples.
+///
+/// [`HashMap::raw_entry`]: struct.HashMap.html#method.raw_entry
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+pub struct RawEntryBuilder<'a, K: 'a, V: 'a, S: 'a> {
+    map: &'a HashMap<K, V, S>,
+}
+
+impl<'a, K, V, S> RawEntryBuilderMut<'a, K, V, S> {
+    /// Creates a `RawEntryMut` from the given key.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn from_key<Q: ?Sized>(self, k: &Q) -> RawEntryMut<'a, K, V, S>
+        where S: BuildHasher,
+              K: Borrow<Q>,
+              Q: Hash + Eq
+    {
+        let hash = table::make_hash(&self.map.hash_builder, k);
+        self.from_key_hashed_nocheck(hash, k)
+    }
+
+    /// Creates a `RawEntryMut` from the given key and its hash.
     #[inline]
-    fn into_occupied_bucket(self) -> Option<FullBucket<K, V, M>> {
-        match self {
-            InternalEntry::Occupied { elem } => Some(elem),
-            _ => None,
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn from_key_hashed_nocheck<Q: ?Sized>(self, hash: u64, k: &Q) -> RawEntryMut<'a, K, V, S>
+        where K: Borrow<Q>,
+              Q: Eq
+    {
+        self.from_hash(hash, |q| k.eq(q.borrow()))
+    }
+
+    /// Creates a `RawEntryMut` from the given hash, calling `is_match` on the
+    /// keys stored under that hash to find the entry.
+    #[inline]
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn from_hash<F>(self, hash: u64, is_match: F) -> RawEntryMut<'a, K, V, S>
+        where F: FnMut(&K) -> bool
+    {
+        let map = self.map;
+        match map.table.find(hash, is_match) {
+            Some(elem) => {
+                RawEntryMut::Occupied(RawOccupiedEntryMut {
+                    elem,
+                    table: &mut map.table,
+                })
+            }
+            None => {
+                RawEntryMut::Vacant(RawVacantEntryMut {
+                    table: &mut map.table,
+                    hash_builder: &map.hash_builder,
+                })
+            }
         }
     }
 }
 
-impl<'a, K, V> InternalEntry<K, V, &'a mut RawTable<K, V>> {
+impl<'a, K, V, S> RawEntryBuilder<'a, K, V, S> {
+    /// Access an entry by key.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn from_key<Q: ?Sized>(self, k: &Q) -> Option<(&'a K, &'a V)>
+        where S: BuildHasher,
+              K: Borrow<Q>,
+              Q: Hash + Eq
+    {
+        let hash = table::make_hash(&self.map.hash_builder, k);
+        self.from_key_hashed_nocheck(hash, k)
+    }
+
+    /// Access an entry by a key and its hash.
     #[inline]
-    fn into_entry(self, key: K) -> Option<Entry<'a, K, V>> {
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn from_key_hashed_nocheck<Q: ?Sized>(self, hash: u64, k: &Q) -> Option<(&'a K, &'a V)>
+        where K: Borrow<Q>,
+              Q: Eq
+    {
+        self.from_hash(hash, |q| k.eq(q.borrow()))
+    }
+
+    /// Access an entry by hash, calling `is_match` on the keys stored under
+    /// that hash to find it.
+    #[inline]
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn from_hash<F>(self, hash: u64, is_match: F) -> Option<(&'a K, &'a V)>
+        where F: FnMut(&K) -> bool
+    {
+        self.map.table.find(hash, is_match).map(|bucket| unsafe {
+            let &(ref key, ref value) = bucket.as_ref();
+            (key, value)
+        })
+    }
+}
+
+impl<'a, K, V, S> RawEntryMut<'a, K, V, S> {
+    /// Ensures a value is in the entry by inserting the default if empty, and
+    /// returns mutable references to the key and value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_raw_entry)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<&str, u32> = HashMap::new();
+    ///
+    /// map.raw_entry_mut().from_key("poneyland").or_insert("poneyland", 3);
+    /// assert_eq!(map["poneyland"], 3);
+    ///
+    /// *map.raw_entry_mut().from_key("poneyland").or_insert("poneyland", 10).1 *= 2;
+    /// assert_eq!(map["poneyland"], 6);
+    /// ```
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn or_insert(self, default_key: K, default_val: V) -> (&'a mut K, &'a mut V)
+        where K: Hash,
+              S: BuildHasher
+    {
         match self {
-            InternalEntry::Occupied { elem } => {
-                Some(Occupied(OccupiedEntry {
-                    key: Some(key),
-                    elem,
-                }))
+            RawEntryMut::Occupied(entry) => entry.into_key_value(),
+            RawEntryMut::Vacant(entry) => entry.insert(default_key, default_val),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns mutable references to the key and value
+    /// in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_raw_entry)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<&str, String> = HashMap::new();
+    ///
+    /// map.raw_entry_mut().from_key("poneyland").or_insert_with(|| {
+    ///     ("poneyland", "hoho".to_string())
+    /// });
+    ///
+    /// assert_eq!(map["poneyland"], "hoho".to_string());
+    /// ```
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn or_insert_with<F>(self, default: F) -> (&'a mut K, &'a mut V)
+        where F: FnOnce() -> (K, V),
+              K: Hash,
+              S: BuildHasher
+    {
+        match self {
+            RawEntryMut::Occupied(entry) => entry.into_key_value(),
+            RawEntryMut::Vacant(entry) => {
+                let (k, v) = default();
+                entry.insert(k, v)
             }
-            InternalEntry::Vacant { hash, elem } => {
-                Some(Vacant(VacantEntry {
-                    hash,
-                    key,
-                    elem,
-                }))
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_raw_entry)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<&str, u32> = HashMap::new();
+    ///
+    /// map.raw_entry_mut()
+    ///    .from_key("poneyland")
+    ///    .and_modify(|_k, v| { *v += 1 })
+    ///    .or_insert("poneyland", 42);
+    /// assert_eq!(map["poneyland"], 42);
+    ///
+    /// map.raw_entry_mut()
+    ///    .from_key("poneyland")
+    ///    .and_modify(|_k, v| { *v += 1 })
+    ///    .or_insert("poneyland", 0);
+    /// assert_eq!(map["poneyland"], 43);
+    /// ```
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn and_modify<F>(self, f: F) -> Self
+        where F: FnOnce(&mut K, &mut V)
+    {
+        match self {
+            RawEntryMut::Occupied(mut entry) => {
+                {
+                    let (k, v) = entry.get_key_value_mut();
+                    f(k, v);
+                }
+                RawEntryMut::Occupied(entry)
+            }
+            RawEntryMut::Vacant(entry) => RawEntryMut::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K, V> RawOccupiedEntryMut<'a, K, V> {
+    /// Gets a reference to the key in the entry.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn key(&self) -> &K {
+        unsafe { &self.elem.as_ref().0 }
+    }
+
+    /// Gets a mutable reference to the key in the entry.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn key_mut(&mut self) -> &mut K {
+        unsafe { &mut self.elem.as_mut().0 }
+    }
+
+    /// Converts the entry into a mutable reference to the key in the entry
+    /// with a lifetime bound to the map itself.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn into_key(self) -> &'a mut K {
+        unsafe { &mut self.elem.as_mut().0 }
+    }
+
+    /// Gets a reference to the value in the entry.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn get(&self) -> &V {
+        unsafe { &self.elem.as_ref().1 }
+    }
+
+    /// Converts the entry into a mutable reference to the value in the entry
+    /// with a lifetime bound to the map itself.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { &mut self.elem.as_mut().1 }
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut self.elem.as_mut().1 }
+    }
+
+    /// Gets a reference to the key and value in the entry.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn get_key_value(&self) -> (&K, &V) {
+        unsafe {
+            let &(ref key, ref value) = self.elem.as_ref();
+            (key, value)
+        }
+    }
+
+    /// Gets a mutable reference to the key and value in the entry.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn get_key_value_mut(&mut self) -> (&mut K, &mut V) {
+        unsafe {
+            let &mut (ref mut key, ref mut value) = self.elem.as_mut();
+            (key, value)
+        }
+    }
+
+    /// Converts the `RawOccupiedEntryMut` into mutable references to the key
+    /// and value in the entry with a lifetime bound to the map itself.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn into_key_value(self) -> (&'a mut K, &'a mut V) {
+        unsafe {
+            let &mut (ref mut key, ref mut value) = self.elem.as_mut();
+            (key, value)
+        }
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Sets the key of the entry, and returns the entry's old key.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn insert_key(&mut self, key: K) -> K {
+        mem::replace(self.key_mut(), key)
+    }
+
+    /// Takes the value out of the entry, and returns it.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Takes the key and value out of the entry, and returns them.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn remove_entry(self) -> (K, V) {
+        unsafe { self.table.remove(self.elem) }
+    }
+}
+
+impl<'a, K, V, S> RawVacantEntryMut<'a, K, V, S> {
+    /// Sets the value of the entry with the `RawVacantEntryMut`'s key,
+    /// and returns mutable references to the key and value in the entry.
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn insert(self, key: K, value: V) -> (&'a mut K, &'a mut V)
+        where K: Hash,
+              S: BuildHasher
+    {
+        let hash = table::make_hash(self.hash_builder, &key);
+        self.insert_hashed_nocheck(hash, key, value)
+    }
+
+    /// Sets the value of the entry with the `RawVacantEntryMut`'s key, using
+    /// the hash that was already computed for it, and returns mutable
+    /// references to the key and value in the entry.
+    #[inline]
+    #[unstable(feature = "hash_raw_entry", issue = "0")]
+    pub fn insert_hashed_nocheck(self, hash: u64, key: K, value: V) -> (&'a mut K, &'a mut V) {
+        let bucket = self.table.insert_no_grow(hash, key, value);
+        unsafe {
+            let &mut (ref mut key, ref mut value) = bucket.as_mut();
+            (key, value)
+        }
+    }
+}
+
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+impl<'a, K, V, S> Debug for RawEntryBuilderMut<'a, K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RawEntryBuilder")
+            .finish()
+    }
+}
+
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+impl<'a, K: Debug, V: Debug, S> Debug for RawEntryMut<'a, K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RawEntryMut::Vacant(ref v) => {
+                f.debug_tuple("RawEntry")
+                    .field(v)
+                    .finish()
+            }
+            RawEntryMut::Occupied(ref o) => {
+                f.debug_tuple("RawEntry")
+                    .field(o)
+                    .finish()
             }
-            InternalEntry::TableIsEmpty => None,
         }
     }
 }
 
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+impl<'a, K: Debug, V: Debug> Debug for RawOccupiedEntryMut<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RawOccupiedEntryMut")
+            .field("key", self.key())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+impl<'a, K, V, S> Debug for RawVacantEntryMut<'a, K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RawVacantEntryMut")
+            .finish()
+    }
+}
+
+#[unstable(feature = "hash_raw_entry", issue = "0")]
+impl<'a, K, V, S> Debug for RawEntryBuilder<'a, K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RawEntryBuilder")
+            .finish()
+    }
+}
+
 /// A view into a single entry in a map, which may either be vacant or occupied.
 ///
 /// This `enum` is constructed from the [`entry`] method on [`HashMap`].
@@ -1558,7 +1635,8 @@ impl<'a, K: 'a + Debug, V: 'a + Debug> Debug for Entry<'a, K, V> {
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
     key: Option<K>,
-    elem: FullBucket<K, V, &'a mut RawTable<K, V>>,
+    elem: Bucket<K, V>,
+    table: &'a mut RawTable<K, V>,
 }
 
 #[stable(feature= "debug_hash_map", since = "1.12.0")]
@@ -1577,9 +1655,9 @@ impl<'a, K: 'a + Debug, V: 'a + Debug> Debug for OccupiedEntry<'a, K, V> {
 /// [`Entry`]: enum.Entry.html
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct VacantEntry<'a, K: 'a, V: 'a> {
-    hash: SafeHash,
+    hash: u64,
     key: K,
-    elem: VacantEntryState<K, V, &'a mut RawTable<K, V>>,
+    table: &'a mut RawTable<K, V>,
 }
 
 #[stable(feature= "debug_hash_map", since = "1.12.0")]
@@ -1591,15 +1669,6 @@ impl<'a, K: 'a + Debug, V: 'a> Debug for VacantEntry<'a, K, V> {
     }
 }
 
-/// Possible states of a VacantEntry.
-enum VacantEntryState<K, V, M> {
-    /// The index is occupied, but the key to insert has precedence,
-    /// and will kick the current one out on insertion.
-    NeqElem(FullBucket<K, V, M>, usize),
-    /// The index is genuinely vacant.
-    NoElem(EmptyBucket<K, V, M>, usize),
-}
-
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S>
     where K: Eq + Hash,
@@ -1721,7 +1790,7 @@ impl<K, V> Iterator for IntoIter<K, V> {
 
     #[inline]
     fn next(&mut self) -> Option<(K, V)> {
-        self.inner.next().map(|(_, k, v)| (k, v))
+        self.inner.next()
     }
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -1834,7 +1903,7 @@ impl<'a, K, V> Iterator for Drain<'a, K, V> {
 
     #[inline]
     fn next(&mut self) -> Option<(K, V)> {
-        self.inner.next().map(|(_, k, v)| (k, v))
+        self.inner.next()
     }
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -1871,7 +1940,8 @@ impl<'a, K, V> fmt::Debug for Drain<'a, K, V>
            reason = "struct name and placement protocol is subject to change",
            issue = "30172")]
 pub struct EntryPlace<'a, K: 'a, V: 'a> {
-    bucket: FullBucketMut<'a, K, V>,
+    table: &'a mut RawTable<K, V>,
+    bucket: Bucket<K, V>,
 }
 
 #[unstable(feature = "collection_placement",
@@ -1879,9 +1949,10 @@ pub struct EntryPlace<'a, K: 'a, V: 'a> {
            issue = "30172")]
 impl<'a, K: 'a + Debug, V: 'a + Debug> Debug for EntryPlace<'a, K, V> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let &(ref key, ref value) = unsafe { self.bucket.as_ref() };
         f.debug_struct("EntryPlace")
-            .field("key", self.bucket.read().0)
-            .field("value", self.bucket.read().1)
+            .field("key", key)
+            .field("value", value)
             .finish()
     }
 }
@@ -1893,7 +1964,10 @@ impl<'a, K, V> Drop for EntryPlace<'a, K, V> {
     fn drop(&mut self) {
         // Inplacement insertion failed. Only key need to drop.
         // The value is failed to insert into map.
-        unsafe { self.bucket.remove_key() };
+        unsafe {
+            self.table.erase_no_drop(&self.bucket);
+            ptr::drop_in_place(&mut self.bucket.as_mut().0);
+        }
     }
 }
 
@@ -1904,16 +1978,16 @@ impl<'a, K, V> Placer<V> for Entry<'a, K, V> {
     type Place = EntryPlace<'a, K, V>;
 
     fn make_place(self) -> EntryPlace<'a, K, V> {
-        let b = match self {
-            Occupied(mut o) => {
-                unsafe { ptr::drop_in_place(o.elem.read_mut().1); }
-                o.elem
+        let (table, bucket) = match self {
+            Occupied(o) => {
+                unsafe { ptr::drop_in_place(&mut o.elem.as_mut().1); }
+                (o.table, o.elem)
             }
             Vacant(v) => {
                 unsafe { v.insert_key() }
             }
         };
-        EntryPlace { bucket: b }
+        EntryPlace { table, bucket }
     }
 }
 
@@ -1922,7 +1996,7 @@ impl<'a, K, V> Placer<V> for Entry<'a, K, V> {
            issue = "30172")]
 impl<'a, K, V> Place<V> for EntryPlace<'a, K, V> {
     fn pointer(&mut self) -> *mut V {
-        self.bucket.read_mut().1
+        unsafe { &mut self.bucket.as_mut().1 }
     }
 }
 
@@ -2045,7 +2119,7 @@ impl<'a, K, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[stable(feature = "map_entry_keys", since = "1.10.0")]
     pub fn key(&self) -> &K {
-        self.elem.read().0
+        unsafe { &self.elem.as_ref().0 }
     }
 
     /// Take the ownership of the key and value from the map.
@@ -2068,8 +2142,7 @@ impl<'a, K, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[stable(feature = "map_entry_recover_keys2", since = "1.12.0")]
     pub fn remove_entry(self) -> (K, V) {
-        let (k, v, _) = pop_internal(self.elem);
-        (k, v)
+        unsafe { self.table.remove(self.elem) }
     }
 
     /// Gets a reference to the value in the entry.
@@ -2089,7 +2162,7 @@ impl<'a, K, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn get(&self) -> &V {
-        self.elem.read().1
+        unsafe { &self.elem.as_ref().1 }
     }
 
     /// Gets a mutable reference to the value in the entry.
@@ -2112,7 +2185,7 @@ impl<'a, K, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn get_mut(&mut self) -> &mut V {
-        self.elem.read_mut().1
+        unsafe { &mut self.elem.as_mut().1 }
     }
 
     /// Converts the OccupiedEntry into a mutable reference to the value in the entry
@@ -2136,7 +2209,7 @@ impl<'a, K, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn into_mut(self) -> &'a mut V {
-        self.elem.into_mut_refs().1
+        unsafe { &mut self.elem.as_mut().1 }
     }
 
     /// Sets the value of the entry, and returns the entry's old value.
@@ -2182,7 +2255,7 @@ impl<'a, K, V> OccupiedEntry<'a, K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn remove(self) -> V {
-        pop_internal(self.elem).1
+        self.remove_entry().1
     }
 
     /// Returns a key that was used for search.
@@ -2247,41 +2320,16 @@ impl<'a, K: 'a, V: 'a> VacantEntry<'a, K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(self, value: V) -> &'a mut V {
-        let b = match self.elem {
-            NeqElem(mut bucket, disp) => {
-                if disp >= DISPLACEMENT_THRESHOLD {
-                    bucket.table_mut().set_tag(true);
-                }
-                robin_hood(bucket, disp, self.hash, self.key, value)
-            },
-            NoElem(mut bucket, disp) => {
-                if disp >= DISPLACEMENT_THRESHOLD {
-                    bucket.table_mut().set_tag(true);
-                }
-                bucket.put(self.hash, self.key, value)
-            },
-        };
-        b.into_mut_refs().1
+        let bucket = self.table.insert_no_grow(self.hash, self.key, value);
+        unsafe { &mut bucket.as_mut().1 }
     }
 
     // Only used for InPlacement insert. Avoid unnecessary value copy.
     // The value remains uninitialized.
-    unsafe fn insert_key(self) -> FullBucketMut<'a, K, V> {
-        match self.elem {
-            NeqElem(mut bucket, disp) => {
-                if disp >= DISPLACEMENT_THRESHOLD {
-                    bucket.table_mut().set_tag(true);
-                }
-                let uninit = mem::uninitialized();
-                robin_hood(bucket, disp, self.hash, self.key, uninit)
-            },
-            NoElem(mut bucket, disp) => {
-                if disp >= DISPLACEMENT_THRESHOLD {
-                    bucket.table_mut().set_tag(true);
-                }
-                bucket.put_key(self.hash, self.key)
-            },
-        }
+    unsafe fn insert_key(self) -> (&'a mut RawTable<K, V>, Bucket<K, V>) {
+        let uninit = mem::uninitialized();
+        let bucket = self.table.insert_no_grow(self.hash, self.key, uninit);
+        (self.table, bucket)
     }
 }
 
@@ -2479,15 +2527,11 @@ impl<K, S, Q: ?Sized> super::Recover<Q> for HashMap<K, (), S>
     type Key = K;
 
     fn get(&self, key: &Q) -> Option<&K> {
-        self.search(key).into_occupied_bucket().map(|bucket| bucket.into_refs().0)
+        self.search(key).map(|bucket| unsafe { &bucket.as_ref().0 })
     }
 
     fn take(&mut self, key: &Q) -> Option<K> {
-        if self.table.size() == 0 {
-            return None;
-        }
-
-        self.search_mut(key).into_occupied_bucket().map(|bucket| pop_internal(bucket).0)
+        self.search(key).map(|bucket| unsafe { self.table.remove(bucket).0 })
     }
 
     fn replace(&mut self, key: K) -> Option<K> {
@@ -2496,7 +2540,7 @@ impl<K, S, Q: ?Sized> super::Recover<Q> for HashMap<K, (), S>
         match self.entry(key) {
             Occupied(mut occupied) => {
                 let key = occupied.take_key().unwrap();
-                Some(mem::replace(occupied.elem.read_mut().0, key))
+                Some(mem::replace(unsafe { &mut occupied.elem.as_mut().0 }, key))
             }
             Vacant(vacant) => {
                 vacant.insert(());
@@ -3043,8 +3087,8 @@ mod test_map {
         assert!(m.is_empty());
 
         let mut i = 0;
-        let old_raw_cap = m.raw_capacity();
-        while old_raw_cap == m.raw_capacity() {
+        let old_buckets = m.table.buckets();
+        while old_buckets == m.table.buckets() {
             m.insert(i, i);
             i += 1;
         }
@@ -3058,47 +3102,50 @@ mod test_map {
         let mut m = HashMap::new();
 
         assert_eq!(m.len(), 0);
-        assert_eq!(m.raw_capacity(), 0);
+        assert_eq!(m.capacity(), 0);
         assert!(m.is_empty());
 
         m.insert(0, 0);
         m.remove(&0);
         assert!(m.is_empty());
-        let initial_raw_cap = m.raw_capacity();
-        m.reserve(initial_raw_cap);
-        let raw_cap = m.raw_capacity();
+        let initial_buckets = m.table.buckets();
+        let initial_cap = m.capacity();
+        m.reserve(initial_cap + 1);
+        let buckets = m.table.buckets();
+
+        assert_eq!(buckets, initial_buckets * 2);
 
-        assert_eq!(raw_cap, initial_raw_cap * 2);
+        // at most 7/8 of the buckets can be filled
+        let cap = m.capacity();
+        assert!(cap <= buckets * 7 / 8);
 
         let mut i = 0;
-        for _ in 0..raw_cap * 3 / 4 {
+        for _ in 0..cap {
             m.insert(i, i);
             i += 1;
         }
-        // three quarters full
+        // full
 
         assert_eq!(m.len(), i);
-        assert_eq!(m.raw_capacity(), raw_cap);
+        assert_eq!(m.table.buckets(), buckets);
 
-        for _ in 0..raw_cap / 4 {
-            m.insert(i, i);
-            i += 1;
-        }
+        m.insert(i, i);
+        i += 1;
         // half full
 
-        let new_raw_cap = m.raw_capacity();
-        assert_eq!(new_raw_cap, raw_cap * 2);
+        let new_buckets = m.table.buckets();
+        assert_eq!(new_buckets, buckets * 2);
 
-        for _ in 0..raw_cap / 2 - 1 {
+        while i > buckets / 2 {
             i -= 1;
             m.remove(&i);
-            assert_eq!(m.raw_capacity(), new_raw_cap);
+            assert_eq!(m.table.buckets(), new_buckets);
         }
-        // A little more than one quarter full.
+        // A quarter full.
         m.shrink_to_fit();
-        assert_eq!(m.raw_capacity(), raw_cap);
-        // again, a little more than half full
-        for _ in 0..raw_cap / 2 - 1 {
+        assert_eq!(m.table.buckets(), buckets);
+        // again, only a single element left
+        while i > 1 {
             i -= 1;
             m.remove(&i);
         }
@@ -3106,7 +3153,117 @@ mod test_map {
 
         assert_eq!(m.len(), i);
         assert!(!m.is_empty());
-        assert_eq!(m.raw_capacity(), initial_raw_cap);
+        assert_eq!(m.table.buckets(), initial_buckets);
+    }
+
+    #[test]
+    fn test_raw_entry() {
+        use super::RawEntryMut::{Occupied, Vacant};
+        use hash::{BuildHasher, Hash, Hasher};
+
+        let xs = [(1i32, 10i32), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)];
+
+        let mut map: HashMap<_, _> = xs.iter().cloned().collect();
+
+        fn compute_hash<S: BuildHasher>(hash_builder: &S, k: i32) -> u64 {
+            let mut hasher = hash_builder.build_hasher();
+            k.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        // Existing key (insert)
+        match map.raw_entry_mut().from_key(&1) {
+            Vacant(_) => unreachable!(),
+            Occupied(mut view) => {
+                assert_eq!(view.get(), &10);
+                assert_eq!(view.insert(100), 10);
+            }
+        }
+        let hash1 = compute_hash(map.hasher(), 1);
+        assert_eq!(map.raw_entry().from_key(&1).unwrap(), (&1, &100));
+        assert_eq!(map.raw_entry().from_hash(hash1, |k| *k == 1).unwrap(), (&1, &100));
+        assert_eq!(map.raw_entry().from_key_hashed_nocheck(hash1, &1).unwrap(), (&1, &100));
+        assert_eq!(map.len(), 6);
+
+        // Existing key (update)
+        match map.raw_entry_mut().from_key(&2) {
+            Vacant(_) => unreachable!(),
+            Occupied(mut view) => {
+                let v = view.get_mut();
+                let new_v = (*v) * 10;
+                *v = new_v;
+            }
+        }
+        assert_eq!(map.raw_entry().from_key(&2).unwrap(), (&2, &200));
+        assert_eq!(map.len(), 6);
+
+        // Existing key (take)
+        let hash3 = compute_hash(map.hasher(), 3);
+        match map.raw_entry_mut().from_key_hashed_nocheck(hash3, &3) {
+            Vacant(_) => unreachable!(),
+            Occupied(view) => {
+                assert_eq!(view.remove_entry(), (3, 30));
+            }
+        }
+        assert_eq!(map.raw_entry().from_key(&3), None);
+        assert_eq!(map.raw_entry().from_hash(hash3, |k| *k == 3), None);
+        assert_eq!(map.len(), 5);
+
+        // Nonexistent key (insert)
+        let hash10 = compute_hash(map.hasher(), 10);
+        match map.raw_entry_mut().from_hash(hash10, |k| *k == 10) {
+            Occupied(_) => unreachable!(),
+            Vacant(view) => {
+                assert_eq!(view.insert_hashed_nocheck(hash10, 10, 1000), (&mut 10, &mut 1000));
+            }
+        }
+        assert_eq!(map.raw_entry().from_key(&10).unwrap(), (&10, &1000));
+        assert_eq!(map.len(), 6);
+
+        // Ensure all lookup methods produce equivalent results.
+        for k in 0..12 {
+            let hash = compute_hash(map.hasher(), k);
+            let v = map.get(&k).cloned();
+            let kv = v.as_ref().map(|v| (&k, v));
+
+            assert_eq!(map.raw_entry().from_key(&k), kv);
+            assert_eq!(map.raw_entry().from_hash(hash, |q| *q == k), kv);
+            assert_eq!(map.raw_entry().from_key_hashed_nocheck(hash, &k), kv);
+
+            match map.raw_entry_mut().from_key(&k) {
+                Occupied(o) => assert_eq!(Some(o.get_key_value()), kv),
+                Vacant(_) => assert_eq!(v, None),
+            }
+            match map.raw_entry_mut().from_hash(hash, |q| *q == k) {
+                Occupied(o) => assert_eq!(Some(o.get_key_value()), kv),
+                Vacant(_) => assert_eq!(v, None),
+            }
+        }
+
+        // or_insert only inserts missing keys, and and_modify only touches
+        // present ones
+        map.raw_entry_mut().from_key(&1).and_modify(|_, v| *v += 1).or_insert(1, 0);
+        map.raw_entry_mut().from_key(&11).and_modify(|_, v| *v += 1).or_insert(11, 0);
+        assert_eq!(map[&1], 101);
+        assert_eq!(map[&11], 0);
+        assert_eq!(map.len(), 7);
+    }
+
+    #[test]
+    fn test_try_reserve() {
+        use collections::CollectionAllocErr::*;
+        use usize;
+
+        let mut empty: HashMap<usize, usize> = HashMap::new();
+        assert!(empty.try_reserve(10).is_ok());
+        assert!(empty.capacity() >= 10);
+
+        let mut m: HashMap<usize, usize> = (0..10).map(|i| (i, i)).collect();
+        if let Err(CapacityOverflow) = m.try_reserve(usize::MAX) {
+        } else { panic!("usize::MAX should trigger an overflow!") }
+        if let Err(CapacityOverflow) = m.try_reserve(usize::MAX / 8) {
+        } else { panic!("usize::MAX / 8 should trigger an overflow!") }
+        assert_eq!(m.len(), 10);
     }
 
     #[test]
@@ -3407,7 +3564,7 @@ mod test_map {
     }
 
     #[test]
-    fn test_adaptive() {
+    fn test_insert_in_iteration_order() {
         const TEST_LEN: usize = 5000;
         // by cloning we get maps with the same hasher seed
         let mut first = HashMap::new();
@@ -3415,15 +3572,16 @@ mod test_map {
         first.extend((0..TEST_LEN).map(|i| (i, i)));
         second.extend((TEST_LEN..TEST_LEN * 2).map(|i| (i, i)));
 
+        // Inserting in the iteration order of a map with the same hasher
+        // clusters the keys, which must not make the map grow before it is
+        // full.
         for (&k, &v) in &second {
             let prev_cap = first.capacity();
             let expect_grow = first.len() == prev_cap;
             first.insert(k, v);
-            if !expect_grow && first.capacity() != prev_cap {
-                return;
-            }
+            assert!(expect_grow || first.capacity() == prev_cap);
         }
-        panic!("Adaptive early resize failed");
+        assert_eq!(first.len(), TEST_LEN * 2);
     }
 
     #[test]
@@ -1,4 +1,4 @@
-// Copyright 2014-2015 The Rust Project Developers. See the COPYRIGHT
+// Copyright 2014-2018 The Rust Project Developers. See the COPYRIGHT
 // file at the top-level directory of this distribution and at
 // http://rust-lang.org/COPYRIGHT.
 //
@@ -9,968 +9,762 @@
 // except according to those terms.
 
 use alloc::heap::{Heap, Alloc, Layout};
+use alloc::allocator::CollectionAllocErr;
 
 use cmp;
 use hash::{BuildHasher, Hash, Hasher};
 use marker;
-use mem::{align_of, size_of, needs_drop};
-use mem;
-use ops::{Deref, DerefMut};
+use mem::{self, size_of, needs_drop, ManuallyDrop};
 use ptr::{self, Unique, Shared};
 
-use self::BucketState::*;
+use super::group::{Group, BitMask, EMPTY, DELETED, GROUP_WIDTH, is_full, special_is_empty};
 
-/// Integer type used for stored hash values.
-///
-/// No more than bit_width(usize) bits are needed to select a bucket.
-///
-/// The most significant bit is ours to use for tagging `SafeHash`.
-///
-/// (Even if we could have usize::MAX bytes allocated for buckets,
-/// each bucket stores at least a `HashUint`, so there can be no more than
-/// usize::MAX / size_of(usize) buckets.)
-type HashUint = usize;
-
-const EMPTY_BUCKET: HashUint = 0;
-const EMPTY: usize = 1;
-
-/// Special `Unique<HashUint>` that uses the lower bit of the pointer
-/// to expose a boolean tag.
-/// Note: when the pointer is initialized to EMPTY `.ptr()` will return
-/// null and the tag functions shouldn't be used.
-struct TaggedHashUintPtr(Unique<HashUint>);
+use self::Fallibility::*;
+use self::CollectionAllocErr::*;
 
-impl TaggedHashUintPtr {
-    #[inline]
-    unsafe fn new(ptr: *mut HashUint) -> Self {
-        debug_assert!(ptr as usize & 1 == 0 || ptr as usize == EMPTY as usize);
-        TaggedHashUintPtr(Unique::new_unchecked(ptr))
-    }
-
-    #[inline]
-    fn set_tag(&mut self, value: bool) {
-        let mut usize_ptr = self.0.as_ptr() as usize;
-        unsafe {
-            if value {
-                usize_ptr |= 1;
-            } else {
-                usize_ptr &= !1;
-            }
-            self.0 = Unique::new_unchecked(usize_ptr as *mut HashUint)
-        }
-    }
-
-    #[inline]
-    fn tag(&self) -> bool {
-        (self.0.as_ptr() as usize) & 1 == 1
-    }
-
-    #[inline]
-    fn ptr(&self) -> *mut HashUint {
-        (self.0.as_ptr() as usize & !1) as *mut HashUint
-    }
-}
-
-/// The raw hashtable, providing safe-ish access to the unzipped and highly
-/// optimized arrays of hashes, and key-value pairs.
-///
-/// This design is a lot faster than the naive
-/// `Vec<Option<(u64, K, V)>>`, because we don't pay for the overhead of an
-/// option on every element, and we get a generally more cache-aware design.
+/// Control bytes of the empty singleton table.
 ///
-/// Essential invariants of this structure:
+/// Tables that have not allocated yet point their control bytes here, so that
+/// a lookup in an empty table needs no special case: it loads one group of
+/// `EMPTY` bytes and stops. Nothing ever writes through this pointer.
+static EMPTY_SINGLETON: [u8; GROUP_WIDTH] = [EMPTY; GROUP_WIDTH];
+
+/// The raw hashtable, an open-addressing table in the style of Google's
+/// SwissTable.
 ///
-///   - if t.hashes[i] == EMPTY_BUCKET, then `Bucket::at_index(&t, i).raw`
-///     points to 'undefined' contents. Don't read from it. This invariant is
-///     enforced outside this module with the `EmptyBucket`, `FullBucket`,
-///     and `SafeHash` types.
+/// Every bucket has a one-byte control value next to it, and all of the
+/// control bytes are stored together in front of the key-value pairs:
 ///
-///   - An `EmptyBucket` is only constructed at an index with
-///     a hash of EMPTY_BUCKET.
+///   - `EMPTY` (`0b1111_1111`) marks a bucket that has never been used since
+///     the last rehash,
 ///
-///   - A `FullBucket` is only constructed at an index with a
-///     non-EMPTY_BUCKET hash.
+///   - `DELETED` (`0b1000_0000`) marks a tombstone, a bucket whose element
+///     has been erased but which may still lie on another element's probe
+///     sequence,
 ///
-///   - A `SafeHash` is only constructed for non-`EMPTY_BUCKET` hash. We get
-///     around hashes of zero by changing them to 0x8000_0000_0000_0000,
-///     which will likely map to the same bucket, while not being confused
-///     with "empty".
+///   - a full bucket stores `h2`, the top 7 bits of its element's hash, with
+///     the high bit clear.
 ///
-///   - Both "arrays represented by pointers" are the same length:
-///     `capacity`. This is set at creation and never changes. The arrays
-///     are unzipped and are more cache aware (scanning through 8 hashes
-///     brings in at most 2 cache lines, since they're all right beside each
-///     other). This layout may waste space in padding such as in a map from
-///     u64 to u8, but is a more cache conscious layout as the key-value pairs
-///     are only very shortly probed and the desired value will be in the same
-///     or next cache line.
+/// The remaining bits of the hash, `h1`, pick the position at which probing
+/// starts. Probing looks at a whole `Group` of control bytes at once (16
+/// with SSE2, 8 in the portable fallback), compares all of them against the
+/// `h2` of the key being looked up, and only reads the keys whose control
+/// byte matched. The probe ends at the first group containing an `EMPTY`
+/// byte, so a miss usually costs a single group load and no key comparisons
+/// at all.
 ///
-/// You can kind of think of this module/data structure as a safe wrapper
-/// around just the "table" part of the hashtable. It enforces some
-/// invariants at the type level and employs some performance trickery,
-/// but in general is just a tricked out `Vec<Option<(u64, K, V)>>`.
+/// Groups are loaded unaligned at arbitrary bucket indexes. To keep those
+/// loads within the allocation there are `GROUP_WIDTH` extra control bytes
+/// after the last bucket, which mirror the first `GROUP_WIDTH` control bytes
+/// of the table.
 ///
-/// The hashtable also exposes a special boolean tag. The tag defaults to false
-/// when the RawTable is created and is accessible with the `tag` and `set_tag`
-/// functions.
+/// The table never fills more than 7/8 of its buckets (see
+/// `bucket_mask_to_capacity`), which guarantees that every probe sequence
+/// eventually meets an `EMPTY` byte.
 pub struct RawTable<K, V> {
-    capacity_mask: usize,
-    size: usize,
-    hashes: TaggedHashUintPtr,
+    // Mask to get an index from a hash value. The value is one less than the
+    // number of buckets in the table.
+    bucket_mask: usize,
+
+    // Pointer to the array of control bytes.
+    ctrl: Unique<u8>,
 
-    // Because K/V do not appear directly in any of the types in the struct,
-    // inform rustc that in fact instances of K and V are reachable from here.
+    // Pointer to the array of buckets.
+    data: Unique<(K, V)>,
+
+    // Number of elements that can be inserted before we need to grow the
+    // table.
+    growth_left: usize,
+
+    // Number of elements in the table.
+    items: usize,
+
+    // Inform rustc that instances of K and V are reachable from here.
     marker: marker::PhantomData<(K, V)>,
 }
 
 unsafe impl<K: Send, V: Send> Send for RawTable<K, V> {}
 unsafe impl<K: Sync, V: Sync> Sync for RawTable<K, V> {}
 
-// An unsafe view of a RawTable bucket
-// Valid indexes are within [0..table_capacity)
-pub struct RawBucket<K, V> {
-    hash_start: *mut HashUint,
-    // We use *const to ensure covariance with respect to K and V
-    pair_start: *const (K, V),
-    idx: usize,
-    _marker: marker::PhantomData<(K, V)>,
-}
-
-impl<K, V> Copy for RawBucket<K, V> {}
-impl<K, V> Clone for RawBucket<K, V> {
-    fn clone(&self) -> RawBucket<K, V> {
-        *self
-    }
-}
-
-pub struct Bucket<K, V, M> {
-    raw: RawBucket<K, V>,
-    table: M,
+/// A handle to a full bucket of a `RawTable`.
+///
+/// This is little more than a pointer: it does not borrow the table, so it is
+/// up to the caller not to use it after the bucket has been erased or the
+/// table has been resized.
+pub struct Bucket<K, V> {
+    index: usize,
+    ptr: *mut (K, V),
 }
 
-impl<K, V, M: Copy> Copy for Bucket<K, V, M> {}
-impl<K, V, M: Copy> Clone for Bucket<K, V, M> {
-    fn clone(&self) -> Bucket<K, V, M> {
+impl<K, V> Copy for Bucket<K, V> {}
+impl<K, V> Clone for Bucket<K, V> {
+    fn clone(&self) -> Bucket<K, V> {
         *self
     }
 }
 
-pub struct EmptyBucket<K, V, M> {
-    raw: RawBucket<K, V>,
-    table: M,
-}
-
-pub struct FullBucket<K, V, M> {
-    raw: RawBucket<K, V>,
-    table: M,
-}
-
-pub type FullBucketMut<'table, K, V> = FullBucket<K, V, &'table mut RawTable<K, V>>;
-
-pub enum BucketState<K, V, M> {
-    Empty(EmptyBucket<K, V, M>),
-    Full(FullBucket<K, V, M>),
-}
-
-// A GapThenFull encapsulates the state of two consecutive buckets at once.
-// The first bucket, called the gap, is known to be empty.
-// The second bucket is full.
-pub struct GapThenFull<K, V, M> {
-    gap: EmptyBucket<K, V, ()>,
-    full: FullBucket<K, V, M>,
-}
-
-/// A hash that is not zero, since we use a hash of zero to represent empty
-/// buckets.
-#[derive(PartialEq, Copy, Clone)]
-pub struct SafeHash {
-    hash: HashUint,
-}
-
-impl SafeHash {
-    /// Peek at the hash value, which is guaranteed to be non-zero.
-    #[inline(always)]
-    pub fn inspect(&self) -> HashUint {
-        self.hash
+impl<K, V> Bucket<K, V> {
+    #[inline]
+    pub unsafe fn as_ref<'a>(&self) -> &'a (K, V) {
+        &*self.ptr
     }
 
-    #[inline(always)]
-    pub fn new(hash: u64) -> Self {
-        // We need to avoid 0 in order to prevent collisions with
-        // EMPTY_HASH. We can maintain our precious uniform distribution
-        // of initial indexes by unconditionally setting the MSB,
-        // effectively reducing the hashes by one bit.
-        //
-        // Truncate hash to fit in `HashUint`.
-        let hash_bits = size_of::<HashUint>() * 8;
-        SafeHash { hash: (1 << (hash_bits - 1)) | (hash as HashUint) }
+    #[inline]
+    pub unsafe fn as_mut<'a>(&self) -> &'a mut (K, V) {
+        &mut *self.ptr
     }
 }
 
-/// We need to remove hashes of 0. That's reserved for empty buckets.
-/// This function wraps up `hash_keyed` to be the only way outside this
-/// module to generate a SafeHash.
-pub fn make_hash<T: ?Sized, S>(hash_state: &S, t: &T) -> SafeHash
+/// Hashes `t` with a fresh hasher from `hash_state`. This is the only way the
+/// map produces the hashes that are handed to a `RawTable`.
+pub fn make_hash<T: ?Sized, S>(hash_state: &S, t: &T) -> u64
     where T: Hash,
           S: BuildHasher
 {
     let mut state = hash_state.build_hasher();
     t.hash(&mut state);
-    SafeHash::new(state.finish())
+    state.finish()
 }
 
-// `replace` casts a `*HashUint` to a `*SafeHash`. Since we statically
-// ensure that a `FullBucket` points to an index with a non-zero hash,
-// and a `SafeHash` is just a `HashUint` with a different name, this is
-// safe.
-//
-// This test ensures that a `SafeHash` really IS the same size as a
-// `HashUint`. If you need to change the size of `SafeHash` (and
-// consequently made this test fail), `replace` needs to be
-// modified to no longer assume this.
-#[test]
-fn can_alias_safehash_as_hash() {
-    assert_eq!(size_of::<SafeHash>(), size_of::<HashUint>())
+/// Primary hash function, used to select the initial bucket to probe from.
+#[inline]
+fn h1(hash: u64) -> usize {
+    hash as usize
 }
 
-// RawBucket methods are unsafe as it's possible to
-// make a RawBucket point to invalid memory using safe code.
-impl<K, V> RawBucket<K, V> {
-    unsafe fn hash(&self) -> *mut HashUint {
-        self.hash_start.offset(self.idx as isize)
-    }
-    unsafe fn pair(&self) -> *mut (K, V) {
-        self.pair_start.offset(self.idx as isize) as *mut (K, V)
-    }
-    unsafe fn hash_pair(&self) -> (*mut HashUint, *mut (K, V)) {
-        (self.hash(), self.pair())
-    }
+/// Secondary hash function, saved in the low 7 bits of the control byte.
+#[inline]
+fn h2(hash: u64) -> u8 {
+    // Grab the top 7 bits of the hash. Some hashers only produce a
+    // `usize`-sized result, in which case the top 32 bits of the `u64` are
+    // zero on 32-bit platforms, so take them from the top of the `usize`
+    // instead.
+    let hash_len = cmp::min(size_of::<usize>(), size_of::<u64>());
+    let top7 = hash >> (hash_len * 8 - 7);
+    (top7 & 0x7f) as u8
 }
 
-// Buckets hold references to the table.
-impl<K, V, M> FullBucket<K, V, M> {
-    /// Borrow a reference to the table.
-    pub fn table(&self) -> &M {
-        &self.table
-    }
-    /// Borrow a mutable reference to the table.
-    pub fn table_mut(&mut self) -> &mut M {
-        &mut self.table
-    }
-    /// Move out the reference to the table.
-    pub fn into_table(self) -> M {
-        self.table
-    }
-    /// Get the raw index.
-    pub fn index(&self) -> usize {
-        self.raw.idx
-    }
-    /// Get the raw bucket.
-    pub fn raw(&self) -> RawBucket<K, V> {
-        self.raw
-    }
+/// Probe sequence based on triangular numbers, which is guaranteed (since our
+/// table size is a power of two) to visit every group of elements exactly
+/// once.
+struct ProbeSeq {
+    bucket_mask: usize,
+    pos: usize,
+    stride: usize,
 }
 
-impl<K, V, M> EmptyBucket<K, V, M> {
-    /// Borrow a reference to the table.
-    pub fn table(&self) -> &M {
-        &self.table
-    }
-    /// Borrow a mutable reference to the table.
-    pub fn table_mut(&mut self) -> &mut M {
-        &mut self.table
-    }
-}
+impl Iterator for ProbeSeq {
+    type Item = usize;
 
-impl<K, V, M> Bucket<K, V, M> {
-    /// Get the raw index.
-    pub fn index(&self) -> usize {
-        self.raw.idx
-    }
-    /// get the table.
-    pub fn into_table(self) -> M {
-        self.table
-    }
-}
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        // We should have found an empty bucket by now and ended the probe.
+        debug_assert!(self.stride <= self.bucket_mask, "went past end of probe sequence");
 
-impl<K, V, M> Deref for FullBucket<K, V, M>
-    where M: Deref<Target = RawTable<K, V>>
-{
-    type Target = RawTable<K, V>;
-    fn deref(&self) -> &RawTable<K, V> {
-        &self.table
+        let result = self.pos;
+        self.stride += GROUP_WIDTH;
+        self.pos += self.stride;
+        self.pos &= self.bucket_mask;
+        Some(result)
     }
 }
 
-/// `Put` is implemented for types which provide access to a table and cannot be invalidated
-///  by filling a bucket. A similar implementation for `Take` is possible.
-pub trait Put<K, V> {
-    unsafe fn borrow_table_mut(&mut self) -> &mut RawTable<K, V>;
-}
-
+/// Returns the number of buckets needed to hold the given number of items,
+/// taking the maximum load factor into account.
+///
+/// Returns `None` if an overflow occurs.
+#[inline]
+fn capacity_to_buckets(cap: usize) -> Option<usize> {
+    debug_assert_ne!(cap, 0);
 
-impl<'t, K, V> Put<K, V> for &'t mut RawTable<K, V> {
-    unsafe fn borrow_table_mut(&mut self) -> &mut RawTable<K, V> {
-        *self
+    // For small tables we require at least 1 empty bucket so that lookups
+    // are guaranteed to terminate if an element doesn't exist in the table.
+    if cap < 8 {
+        // We don't bother with a table size of 2 buckets since that can
+        // only hold a single element. Instead we skip directly to a 4
+        // bucket table which can hold 3 elements.
+        return Some(if cap < 4 { 4 } else { 8 });
     }
-}
 
-impl<K, V, M> Put<K, V> for Bucket<K, V, M>
-    where M: Put<K, V>
-{
-    unsafe fn borrow_table_mut(&mut self) -> &mut RawTable<K, V> {
-        self.table.borrow_table_mut()
-    }
+    // Otherwise require 1/8 buckets to be empty (87.5% load).
+    //
+    // Be careful when modifying this, `capacity_to_buckets` must stay the
+    // inverse of `bucket_mask_to_capacity`.
+    let adjusted_cap = match cap.checked_mul(8) {
+        Some(cap) => cap / 7,
+        None => return None,
+    };
+    adjusted_cap.checked_next_power_of_two()
 }
 
-impl<K, V, M> Put<K, V> for FullBucket<K, V, M>
-    where M: Put<K, V>
-{
-    unsafe fn borrow_table_mut(&mut self) -> &mut RawTable<K, V> {
-        self.table.borrow_table_mut()
+/// Returns the maximum effective capacity for the given bucket mask, taking
+/// the maximum load factor into account.
+#[inline]
+fn bucket_mask_to_capacity(bucket_mask: usize) -> usize {
+    if bucket_mask < 8 {
+        // For tables with 1/2/4/8 buckets, we always reserve one empty
+        // bucket. This is the empty singleton's capacity of 0, too.
+        bucket_mask
+    } else {
+        // For larger tables we reserve 12.5% of the buckets as empty.
+        ((bucket_mask + 1) / 8) * 7
     }
 }
 
-impl<K, V, M: Deref<Target = RawTable<K, V>>> Bucket<K, V, M> {
-    pub fn new(table: M, hash: SafeHash) -> Bucket<K, V, M> {
-        Bucket::at_index(table, hash.inspect() as usize)
-    }
-
-    pub fn new_from(r: RawBucket<K, V>, t: M)
-        -> Bucket<K, V, M>
-    {
-        Bucket {
-            raw: r,
-            table: t,
-        }
-    }
+/// Returns the layout of a single allocation holding the control bytes for
+/// `buckets` buckets followed by the buckets themselves, and the offset of
+/// the buckets within it. Returns `None` if an overflow occurs.
+#[inline]
+fn calculate_layout<K, V>(buckets: usize) -> Option<(Layout, usize)> {
+    debug_assert!(buckets.is_power_of_two());
 
-    pub fn at_index(table: M, ib_index: usize) -> Bucket<K, V, M> {
-        // if capacity is 0, then the RawBucket will be populated with bogus pointers.
-        // This is an uncommon case though, so avoid it in release builds.
-        debug_assert!(table.capacity() > 0,
-                      "Table should have capacity at this point");
-        let ib_index = ib_index & table.capacity_mask;
-        Bucket {
-            raw: table.raw_bucket_at(ib_index),
-            table,
-        }
+    let ctrl = match buckets.checked_add(GROUP_WIDTH) {
+        Some(num_ctrl_bytes) => Layout::array::<u8>(num_ctrl_bytes),
+        None => return None,
+    };
+    let data = Layout::array::<(K, V)>(buckets);
+    match (ctrl, data) {
+        (Some(ctrl), Some(data)) => ctrl.extend(data),
+        _ => None,
     }
+}
 
-    pub fn first(table: M) -> Bucket<K, V, M> {
-        Bucket {
-            raw: table.raw_bucket_at(0),
-            table,
-        }
-    }
+/// Whether an allocation failure should be returned to the caller or
+/// handled by `oom`.
+pub(crate) enum Fallibility {
+    Fallible,
+    Infallible,
+}
 
-    // "So a few of the first shall be last: for many be called,
-    // but few chosen."
-    //
-    // We'll most likely encounter a few buckets at the beginning that
-    // have their initial buckets near the end of the table. They were
-    // placed at the beginning as the probe wrapped around the table
-    // during insertion. We must skip forward to a bucket that won't
-    // get reinserted too early and won't unfairly steal others spot.
-    // This eliminates the need for robin hood.
-    pub fn head_bucket(table: M) -> Bucket<K, V, M> {
-        let mut bucket = Bucket::first(table);
-
-        loop {
-            bucket = match bucket.peek() {
-                Full(full) => {
-                    if full.displacement() == 0 {
-                        // This bucket occupies its ideal spot.
-                        // It indicates the start of another "cluster".
-                        bucket = full.into_bucket();
-                        break;
-                    }
-                    // Leaving this bucket in the last cluster for later.
-                    full.into_bucket()
-                }
-                Empty(b) => {
-                    // Encountered a hole between clusters.
-                    b.into_bucket()
-                }
-            };
-            bucket.next();
-        }
-        bucket
-    }
-
-    /// Reads a bucket at a given index, returning an enum indicating whether
-    /// it's initialized or not. You need to match on this enum to get
-    /// the appropriate types to call most of the other functions in
-    /// this module.
-    pub fn peek(self) -> BucketState<K, V, M> {
-        match unsafe { *self.raw.hash() } {
-            EMPTY_BUCKET => {
-                Empty(EmptyBucket {
-                    raw: self.raw,
-                    table: self.table,
-                })
-            }
-            _ => {
-                Full(FullBucket {
-                    raw: self.raw,
-                    table: self.table,
-                })
-            }
-        }
+/// Unwraps the result of an operation that was performed with `Infallible`.
+fn infallible<T>(result: Result<T, CollectionAllocErr>) -> T {
+    match result {
+        Err(CapacityOverflow) => panic!("capacity overflow"),
+        Err(AllocErr(_)) => unreachable!(),
+        Ok(t) => t,
     }
+}
 
-    /// Modifies the bucket in place to make it point to the next slot.
-    pub fn next(&mut self) {
-        self.raw.idx = self.raw.idx.wrapping_add(1) & self.table.capacity_mask;
-    }
+/// Frees the allocation of a table without dropping its elements, which have
+/// been moved elsewhere. Used during resizing, where it also cleans up the
+/// new table if the hasher panics halfway through.
+struct ResizeGuard<K, V>(ManuallyDrop<RawTable<K, V>>);
 
-    /// Modifies the bucket in place to make it point to the previous slot.
-    pub fn prev(&mut self) {
-        self.raw.idx = self.raw.idx.wrapping_sub(1) & self.table.capacity_mask;
+impl<K, V> Drop for ResizeGuard<K, V> {
+    fn drop(&mut self) {
+        unsafe {
+            self.0.free_buckets();
+        }
     }
 }
 
-impl<K, V, M: Deref<Target = RawTable<K, V>>> EmptyBucket<K, V, M> {
-    #[inline]
-    pub fn next(self) -> Bucket<K, V, M> {
-        let mut bucket = self.into_bucket();
-        bucket.next();
-        bucket
-    }
-
+impl<K, V> RawTable<K, V> {
+    /// Creates a new empty table without allocating any memory.
     #[inline]
-    pub fn into_bucket(self) -> Bucket<K, V, M> {
-        Bucket {
-            raw: self.raw,
-            table: self.table,
+    pub fn new() -> RawTable<K, V> {
+        RawTable {
+            bucket_mask: 0,
+            ctrl: unsafe { Unique::new_unchecked(EMPTY_SINGLETON.as_ptr() as *mut u8) },
+            data: Unique::empty(),
+            growth_left: 0,
+            items: 0,
+            marker: marker::PhantomData,
         }
     }
 
-    pub fn gap_peek(self) -> Result<GapThenFull<K, V, M>, Bucket<K, V, M>> {
-        let gap = EmptyBucket {
-            raw: self.raw,
-            table: (),
+    /// Allocates a table with `buckets` buckets, which must be a power of
+    /// two. Does not initialize the control bytes; the caller should set
+    /// every one of them to `EMPTY`, or copy them from another table.
+    unsafe fn new_uninitialized_internal(buckets: usize,
+                                         fallibility: Fallibility)
+                                         -> Result<RawTable<K, V>, CollectionAllocErr> {
+        let (layout, data_offset) = calculate_layout::<K, V>(buckets).ok_or(CapacityOverflow)?;
+        let ctrl = match (Heap.alloc(layout), fallibility) {
+            (Ok(ctrl), _) => ctrl,
+            (Err(e), Infallible) => Heap.oom(e),
+            (Err(e), Fallible) => return Err(AllocErr(e)),
         };
-
-        match self.next().peek() {
-            Full(bucket) => {
-                Ok(GapThenFull {
-                    gap,
-                    full: bucket,
-                })
-            }
-            Empty(e) => Err(e.into_bucket()),
-        }
+        let data = ctrl.offset(data_offset as isize) as *mut (K, V);
+
+        Ok(RawTable {
+            bucket_mask: buckets - 1,
+            ctrl: Unique::new_unchecked(ctrl),
+            data: Unique::new_unchecked(data),
+            growth_left: bucket_mask_to_capacity(buckets - 1),
+            items: 0,
+            marker: marker::PhantomData,
+        })
     }
-}
 
-impl<K, V, M> EmptyBucket<K, V, M>
-    where M: Put<K, V>
-{
-    /// Puts given key and value pair, along with the key's hash,
-    /// into this bucket in the hashtable. Note how `self` is 'moved' into
-    /// this function, because this slot will no longer be empty when
-    /// we return! A `FullBucket` is returned for later use, pointing to
-    /// the newly-filled slot in the hashtable.
-    ///
-    /// Use `make_hash` to construct a `SafeHash` to pass to this function.
-    pub fn put(mut self, hash: SafeHash, key: K, value: V) -> FullBucket<K, V, M> {
-        unsafe {
-            *self.raw.hash() = hash.inspect();
-            ptr::write(self.raw.pair(), (key, value));
+    /// Like `new_uninitialized_internal`, but panics if the capacity
+    /// overflows and aborts if the allocation fails.
+    unsafe fn new_uninitialized(buckets: usize) -> RawTable<K, V> {
+        infallible(Self::new_uninitialized_internal(buckets, Infallible))
+    }
 
-            self.table.borrow_table_mut().size += 1;
+    /// Tries to create a new raw table that can hold at least `capacity`
+    /// elements without reallocating. If it cannot allocate, it returns with
+    /// AllocErr.
+    pub(crate) fn new_internal(capacity: usize,
+                               fallibility: Fallibility)
+                               -> Result<RawTable<K, V>, CollectionAllocErr> {
+        if capacity == 0 {
+            return Ok(RawTable::new());
         }
 
-        FullBucket {
-            raw: self.raw,
-            table: self.table,
+        unsafe {
+            let buckets = capacity_to_buckets(capacity).ok_or(CapacityOverflow)?;
+            let ret = RawTable::new_uninitialized_internal(buckets, fallibility)?;
+            ptr::write_bytes(ret.ctrl(0), EMPTY, ret.num_ctrl_bytes());
+            Ok(ret)
         }
     }
 
-    /// Puts given key, remain value uninitialized.
-    /// It is only used for inplacement insertion.
-    pub unsafe fn put_key(mut self, hash: SafeHash, key: K) -> FullBucket<K, V, M> {
-        *self.raw.hash() = hash.inspect();
-        let pair_ptr = self.raw.pair();
-        ptr::write(&mut (*pair_ptr).0, key);
-
-        self.table.borrow_table_mut().size += 1;
+    /// Creates a new raw table that can hold at least `capacity` elements
+    /// without reallocating. All buckets are initially empty.
+    pub fn with_capacity(capacity: usize) -> RawTable<K, V> {
+        infallible(Self::new_internal(capacity, Infallible))
+    }
 
-        FullBucket {
-            raw: self.raw,
-            table: self.table,
+    /// Deallocates the table without dropping any entries.
+    unsafe fn free_buckets(&mut self) {
+        if self.is_empty_singleton() {
+            return;
         }
+
+        let (layout, _) = calculate_layout::<K, V>(self.buckets())
+            .expect("should be impossible");
+        Heap.dealloc(self.ctrl.as_ptr(), layout);
     }
-}
 
-impl<K, V, M: Deref<Target = RawTable<K, V>>> FullBucket<K, V, M> {
+    /// Returns a pointer to a control byte.
     #[inline]
-    pub fn next(self) -> Bucket<K, V, M> {
-        let mut bucket = self.into_bucket();
-        bucket.next();
-        bucket
+    unsafe fn ctrl(&self, index: usize) -> *mut u8 {
+        debug_assert!(index < self.num_ctrl_bytes());
+        self.ctrl.as_ptr().offset(index as isize)
     }
 
+    /// Returns a handle to the bucket at the given index.
     #[inline]
-    pub fn into_bucket(self) -> Bucket<K, V, M> {
+    unsafe fn bucket(&self, index: usize) -> Bucket<K, V> {
+        debug_assert!(!self.is_empty_singleton());
+        debug_assert!(index < self.buckets());
         Bucket {
-            raw: self.raw,
-            table: self.table,
+            index,
+            ptr: self.data.as_ptr().offset(index as isize),
         }
     }
 
-    /// Duplicates the current position. This can be useful for operations
-    /// on two or more buckets.
-    pub fn stash(self) -> FullBucket<K, V, Self> {
-        FullBucket {
-            raw: self.raw,
-            table: self,
-        }
-    }
-
-    /// Get the distance between this bucket and the 'ideal' location
-    /// as determined by the key's hash stored in it.
-    ///
-    /// In the cited blog posts above, this is called the "distance to
-    /// initial bucket", or DIB. Also known as "probe count".
-    pub fn displacement(&self) -> usize {
-        // Calculates the distance one has to travel when going from
-        // `hash mod capacity` onwards to `idx mod capacity`, wrapping around
-        // if the destination is not reached before the end of the table.
-        (self.raw.idx.wrapping_sub(self.hash().inspect() as usize)) & self.table.capacity_mask
-    }
-
+    /// Sets a control byte, and possibly also the replicated control byte at
+    /// the end of the array.
     #[inline]
-    pub fn hash(&self) -> SafeHash {
-        unsafe { SafeHash { hash: *self.raw.hash() } }
-    }
+    unsafe fn set_ctrl(&self, index: usize, ctrl: u8) {
+        // Replicate the first GROUP_WIDTH control bytes at the end of the
+        // array without using a branch:
+        // - If index >= GROUP_WIDTH then index == index2.
+        // - Otherwise index2 == self.bucket_mask + 1 + index.
+        //
+        // The very last replicated control byte is never actually read
+        // because we mask the initial index for unaligned loads, but we
+        // need an additional byte past the end of the table to avoid having
+        // to handle a table smaller than the group width specially.
+        let index2 = ((index.wrapping_sub(GROUP_WIDTH)) & self.bucket_mask) + GROUP_WIDTH;
 
-    /// Gets references to the key and value at a given index.
-    pub fn read(&self) -> (&K, &V) {
-        unsafe {
-            let pair_ptr = self.raw.pair();
-            (&(*pair_ptr).0, &(*pair_ptr).1)
-        }
+        *self.ctrl(index) = ctrl;
+        *self.ctrl(index2) = ctrl;
     }
-}
 
-// We take a mutable reference to the table instead of accepting anything that
-// implements `DerefMut` to prevent fn `take` from being called on `stash`ed
-// buckets.
-impl<'t, K, V> FullBucket<K, V, &'t mut RawTable<K, V>> {
-    /// Removes this bucket's key and value from the hashtable.
+    /// Returns an iterator for a probe sequence on the table.
     ///
-    /// This works similarly to `put`, building an `EmptyBucket` out of the
-    /// taken bucket.
-    pub fn take(self) -> (EmptyBucket<K, V, &'t mut RawTable<K, V>>, K, V) {
-        self.table.size -= 1;
-
-        unsafe {
-            *self.raw.hash() = EMPTY_BUCKET;
-            let (k, v) = ptr::read(self.raw.pair());
-            (EmptyBucket {
-                 raw: self.raw,
-                 table: self.table,
-             },
-            k,
-            v)
+    /// This iterator never terminates, but is guaranteed to visit each
+    /// bucket group exactly once.
+    #[inline]
+    fn probe_seq(&self, hash: u64) -> ProbeSeq {
+        ProbeSeq {
+            bucket_mask: self.bucket_mask,
+            pos: h1(hash) & self.bucket_mask,
+            stride: 0,
         }
     }
 
-    /// Remove this bucket's `key` from the hashtable.
-    /// Only used for inplacement insertion.
-    /// NOTE: `Value` is uninitialized when this function is called, don't try to drop the `Value`.
-    pub unsafe fn remove_key(&mut self) {
-        self.table.size -= 1;
-
-        *self.raw.hash() = EMPTY_BUCKET;
-        let pair_ptr = self.raw.pair();
-        ptr::drop_in_place(&mut (*pair_ptr).0); // only drop key
-    }
-}
-
-// This use of `Put` is misleading and restrictive, but safe and sufficient for our use cases
-// where `M` is a full bucket or table reference type with mutable access to the table.
-impl<K, V, M> FullBucket<K, V, M>
-    where M: Put<K, V>
-{
-    pub fn replace(&mut self, h: SafeHash, k: K, v: V) -> (SafeHash, K, V) {
-        unsafe {
-            let old_hash = ptr::replace(self.raw.hash() as *mut SafeHash, h);
-            let (old_key, old_val) = ptr::replace(self.raw.pair(), (k, v));
-
-            (old_hash, old_key, old_val)
+    /// Searches for an empty or deleted bucket which is suitable for
+    /// inserting a new element.
+    ///
+    /// There must be at least 1 empty bucket in the table.
+    fn find_insert_slot(&self, hash: u64) -> usize {
+        for pos in self.probe_seq(hash) {
+            unsafe {
+                let group = Group::load(self.ctrl(pos));
+                if let Some(bit) = group.match_empty_or_deleted().lowest_set_bit() {
+                    let result = (pos + bit) & self.bucket_mask;
+
+                    // In tables smaller than the group width, trailing
+                    // control bytes outside the range of the table are
+                    // filled with EMPTY entries. These will unfortunately
+                    // trigger a match, but once masked they may point to a
+                    // full bucket that is already occupied. We detect this
+                    // situation here and perform a second scan starting at
+                    // the beginning of the table. This second scan is
+                    // guaranteed to find an empty slot (due to the load
+                    // factor) before hitting the trailing control bytes
+                    // (containing EMPTY).
+                    if is_full(*self.ctrl(result)) {
+                        debug_assert!(self.bucket_mask < GROUP_WIDTH);
+                        debug_assert_ne!(pos, 0);
+                        return Group::load(self.ctrl(0))
+                            .match_empty_or_deleted()
+                            .lowest_set_bit()
+                            .unwrap();
+                    }
+                    return result;
+                }
+            }
         }
-    }
-}
 
-impl<K, V, M> FullBucket<K, V, M>
-    where M: Deref<Target = RawTable<K, V>> + DerefMut
-{
-    /// Gets mutable references to the key and value at a given index.
-    pub fn read_mut(&mut self) -> (&mut K, &mut V) {
-        unsafe {
-            let pair_ptr = self.raw.pair();
-            (&mut (*pair_ptr).0, &mut (*pair_ptr).1)
-        }
+        // probe_seq never returns.
+        unreachable!();
     }
-}
 
-impl<'t, K, V, M> FullBucket<K, V, M>
-    where M: Deref<Target = RawTable<K, V>> + 't
-{
-    /// Exchange a bucket state for immutable references into the table.
-    /// Because the underlying reference to the table is also consumed,
-    /// no further changes to the structure of the table are possible;
-    /// in exchange for this, the returned references have a longer lifetime
-    /// than the references returned by `read()`.
-    pub fn into_refs(self) -> (&'t K, &'t V) {
+    /// Searches for an element in the table. `eq` is only called on keys
+    /// whose hash very likely matches `hash`.
+    #[inline]
+    pub fn find<F>(&self, hash: u64, mut eq: F) -> Option<Bucket<K, V>>
+        where F: FnMut(&K) -> bool
+    {
         unsafe {
-            let pair_ptr = self.raw.pair();
-            (&(*pair_ptr).0, &(*pair_ptr).1)
+            let h2_hash = h2(hash);
+            for pos in self.probe_seq(hash) {
+                let group = Group::load(self.ctrl(pos));
+                for bit in group.match_byte(h2_hash) {
+                    let index = (pos + bit) & self.bucket_mask;
+                    let bucket = self.bucket(index);
+                    if eq(&bucket.as_ref().0) {
+                        return Some(bucket);
+                    }
+                }
+                if group.match_empty().any_bit_set() {
+                    return None;
+                }
+            }
         }
-    }
-}
 
-impl<'t, K, V, M> FullBucket<K, V, M>
-    where M: Deref<Target = RawTable<K, V>> + DerefMut + 't
-{
-    /// This works similarly to `into_refs`, exchanging a bucket state
-    /// for mutable references into the table.
-    pub fn into_mut_refs(self) -> (&'t mut K, &'t mut V) {
-        unsafe {
-            let pair_ptr = self.raw.pair();
-            (&mut (*pair_ptr).0, &mut (*pair_ptr).1)
-        }
+        // probe_seq never returns.
+        unreachable!();
     }
-}
 
-impl<K, V, M> GapThenFull<K, V, M>
-    where M: Deref<Target = RawTable<K, V>>
-{
+    /// Inserts a new element into the table, without growing the table.
+    ///
+    /// The caller must have made room for it first, with `reserve`, and
+    /// must have checked that the key is not already present.
     #[inline]
-    pub fn full(&self) -> &FullBucket<K, V, M> {
-        &self.full
+    pub fn insert_no_grow(&mut self, hash: u64, key: K, value: V) -> Bucket<K, V> {
+        unsafe {
+            let index = self.find_insert_slot(hash);
+            let old_ctrl = *self.ctrl(index);
+
+            // If we are replacing a DELETED entry then we don't need to
+            // update the load counter.
+            debug_assert!(self.growth_left != 0 || !special_is_empty(old_ctrl));
+            self.growth_left -= special_is_empty(old_ctrl) as usize;
+
+            self.set_ctrl(index, h2(hash));
+            let bucket = self.bucket(index);
+            ptr::write(bucket.ptr, (key, value));
+            self.items += 1;
+            bucket
+        }
+    }
+
+    /// Erases an element from the table without dropping it.
+    pub unsafe fn erase_no_drop(&mut self, bucket: &Bucket<K, V>) {
+        let index = bucket.index;
+        debug_assert!(is_full(*self.ctrl(index)));
+        let index_before = index.wrapping_sub(GROUP_WIDTH) & self.bucket_mask;
+        let empty_before = Group::load(self.ctrl(index_before)).match_empty();
+        let empty_after = Group::load(self.ctrl(index)).match_empty();
+
+        // If we are inside a continuous block of GROUP_WIDTH full or deleted
+        // buckets then a probe window may have seen a full block when
+        // trying to insert. We therefore need to keep that block non-empty
+        // so that lookups will continue searching to the next probe window.
+        let ctrl = if empty_before.leading_zeros() + empty_after.trailing_zeros() >= GROUP_WIDTH {
+            DELETED
+        } else {
+            self.growth_left += 1;
+            EMPTY
+        };
+        self.set_ctrl(index, ctrl);
+        self.items -= 1;
     }
 
-    pub fn into_table(self) -> M {
-        self.full.into_table()
+    /// Removes an element from the table, returning it.
+    pub unsafe fn remove(&mut self, bucket: Bucket<K, V>) -> (K, V) {
+        self.erase_no_drop(&bucket);
+        ptr::read(bucket.ptr)
     }
 
-    pub fn shift(mut self) -> Result<GapThenFull<K, V, M>, Bucket<K, V, M>> {
-        unsafe {
-            let (gap_hash, gap_pair) = self.gap.raw.hash_pair();
-            let (full_hash, full_pair) = self.full.raw.hash_pair();
-            *gap_hash = mem::replace(&mut *full_hash, EMPTY_BUCKET);
-            ptr::copy_nonoverlapping(full_pair, gap_pair, 1);
-        }
-
-        let FullBucket { raw: prev_raw, .. } = self.full;
-
-        match self.full.next().peek() {
-            Full(bucket) => {
-                self.gap.raw = prev_raw;
-
-                self.full = bucket;
-
-                Ok(self)
+    /// Marks all buckets as empty without dropping their contents.
+    fn clear_no_drop(&mut self) {
+        if !self.is_empty_singleton() {
+            unsafe {
+                ptr::write_bytes(self.ctrl(0), EMPTY, self.num_ctrl_bytes());
             }
-            Empty(b) => Err(b.into_bucket()),
         }
+        self.items = 0;
+        self.growth_left = bucket_mask_to_capacity(self.bucket_mask);
     }
-}
-
-
-/// Rounds up to a multiple of a power of two. Returns the closest multiple
-/// of `target_alignment` that is higher or equal to `unrounded`.
-///
-/// # Panics
-///
-/// Panics if `target_alignment` is not a power of two.
-#[inline]
-fn round_up_to_next(unrounded: usize, target_alignment: usize) -> usize {
-    assert!(target_alignment.is_power_of_two());
-    (unrounded + target_alignment - 1) & !(target_alignment - 1)
-}
-
-#[test]
-fn test_rounding() {
-    assert_eq!(round_up_to_next(0, 4), 0);
-    assert_eq!(round_up_to_next(1, 4), 4);
-    assert_eq!(round_up_to_next(2, 4), 4);
-    assert_eq!(round_up_to_next(3, 4), 4);
-    assert_eq!(round_up_to_next(4, 4), 4);
-    assert_eq!(round_up_to_next(5, 4), 8);
-}
-
-// Returns a tuple of (pairs_offset, end_of_pairs_offset),
-// from the start of a mallocated array.
-#[inline]
-fn calculate_offsets(hashes_size: usize,
-                     pairs_size: usize,
-                     pairs_align: usize)
-                     -> (usize, usize, bool) {
-    let pairs_offset = round_up_to_next(hashes_size, pairs_align);
-    let (end_of_pairs, oflo) = pairs_offset.overflowing_add(pairs_size);
-
-    (pairs_offset, end_of_pairs, oflo)
-}
 
-// Returns a tuple of (minimum required malloc alignment, hash_offset,
-// array_size), from the start of a mallocated array.
-fn calculate_allocation(hash_size: usize,
-                        hash_align: usize,
-                        pairs_size: usize,
-                        pairs_align: usize)
-                        -> (usize, usize, usize, bool) {
-    let hash_offset = 0;
-    let (_, end_of_pairs, oflo) = calculate_offsets(hash_size, pairs_size, pairs_align);
-
-    let align = cmp::max(hash_align, pairs_align);
+    /// Ensures that at least `additional` items can be inserted into the
+    /// table without reallocation.
+    ///
+    /// `hasher` must return the same hash the element was inserted with.
+    pub(crate) fn reserve<H>(&mut self,
+                             additional: usize,
+                             hasher: H,
+                             fallibility: Fallibility)
+                             -> Result<(), CollectionAllocErr>
+        where H: Fn(&K) -> u64
+    {
+        if additional <= self.growth_left {
+            return Ok(());
+        }
 
-    (align, hash_offset, end_of_pairs, oflo)
-}
+        let new_items = self.items.checked_add(additional).ok_or(CapacityOverflow)?;
+        let full_capacity = bucket_mask_to_capacity(self.bucket_mask);
 
-#[test]
-fn test_offset_calculation() {
-    assert_eq!(calculate_allocation(128, 8, 16, 8), (8, 0, 144, false));
-    assert_eq!(calculate_allocation(3, 1, 2, 1), (1, 0, 5, false));
-    assert_eq!(calculate_allocation(6, 2, 12, 4), (4, 0, 20, false));
-    assert_eq!(calculate_offsets(128, 15, 4), (128, 143, false));
-    assert_eq!(calculate_offsets(3, 2, 4), (4, 6, false));
-    assert_eq!(calculate_offsets(6, 12, 4), (8, 20, false));
-}
+        // If at least half of the capacity is being wasted on tombstones,
+        // rehash into a table of the same size to reclaim them instead of
+        // growing. Otherwise at least double the size of the table, to keep
+        // insertion amortized O(1).
+        let capacity = if new_items <= full_capacity / 2 {
+            full_capacity
+        } else {
+            cmp::max(new_items, full_capacity + 1)
+        };
+        self.resize(capacity, hasher, fallibility)
+    }
 
-impl<K, V> RawTable<K, V> {
-    /// Does not initialize the buckets. The caller should ensure they,
-    /// at the very least, set every hash to EMPTY_BUCKET.
-    unsafe fn new_uninitialized(capacity: usize) -> RawTable<K, V> {
-        if capacity == 0 {
-            return RawTable {
-                size: 0,
-                capacity_mask: capacity.wrapping_sub(1),
-                hashes: TaggedHashUintPtr::new(EMPTY as *mut HashUint),
-                marker: marker::PhantomData,
-            };
+    /// Shrinks the table to fit its current elements.
+    pub fn shrink_to_fit<H>(&mut self, hasher: H)
+        where H: Fn(&K) -> u64
+    {
+        if self.items == 0 {
+            *self = RawTable::new();
+            return;
         }
 
-        // No need for `checked_mul` before a more restrictive check performed
-        // later in this method.
-        let hashes_size = capacity.wrapping_mul(size_of::<HashUint>());
-        let pairs_size = capacity.wrapping_mul(size_of::<(K, V)>());
-
-        // Allocating hashmaps is a little tricky. We need to allocate two
-        // arrays, but since we know their sizes and alignments up front,
-        // we just allocate a single array, and then have the subarrays
-        // point into it.
-        //
-        // This is great in theory, but in practice getting the alignment
-        // right is a little subtle. Therefore, calculating offsets has been
-        // factored out into a different function.
-        let (alignment, hash_offset, size, oflo) = calculate_allocation(hashes_size,
-                                                                        align_of::<HashUint>(),
-                                                                        pairs_size,
-                                                                        align_of::<(K, V)>());
-        assert!(!oflo, "capacity overflow");
-
-        // One check for overflow that covers calculation and rounding of size.
-        let size_of_bucket = size_of::<HashUint>().checked_add(size_of::<(K, V)>()).unwrap();
-        assert!(size >=
-                capacity.checked_mul(size_of_bucket)
-                    .expect("capacity overflow"),
-                "capacity overflow");
-
-        let buffer = Heap.alloc(Layout::from_size_align(size, alignment).unwrap())
-            .unwrap_or_else(|e| Heap.oom(e));
-
-        let hashes = buffer.offset(hash_offset as isize) as *mut HashUint;
-
-        RawTable {
-            capacity_mask: capacity.wrapping_sub(1),
-            size: 0,
-            hashes: TaggedHashUintPtr::new(hashes),
-            marker: marker::PhantomData,
+        let min_buckets = capacity_to_buckets(self.items).expect("should be impossible");
+        if min_buckets < self.buckets() {
+            let items = self.items;
+            infallible(self.resize(items, hasher, Infallible));
         }
     }
 
-    fn raw_bucket_at(&self, index: usize) -> RawBucket<K, V> {
-        let hashes_size = self.capacity() * size_of::<HashUint>();
-        let pairs_size = self.capacity() * size_of::<(K, V)>();
-
-        let (pairs_offset, _, oflo) =
-            calculate_offsets(hashes_size, pairs_size, align_of::<(K, V)>());
-        debug_assert!(!oflo, "capacity overflow");
+    /// Moves all elements into a new table with room for at least
+    /// `capacity` elements. Tombstones are not carried over.
+    fn resize<H>(&mut self,
+                 capacity: usize,
+                 hasher: H,
+                 fallibility: Fallibility)
+                 -> Result<(), CollectionAllocErr>
+        where H: Fn(&K) -> u64
+    {
+        debug_assert!(self.items <= capacity);
 
-        let buffer = self.hashes.ptr() as *mut u8;
         unsafe {
-            RawBucket {
-                hash_start: buffer as *mut HashUint,
-                pair_start: buffer.offset(pairs_offset as isize) as *const (K, V),
-                idx: index,
-                _marker: marker::PhantomData,
+            let new_table = RawTable::new_internal(capacity, fallibility)?;
+
+            // If the hasher panics, the elements are still owned by `self`
+            // and the guard only frees the new allocation.
+            let mut guard = ResizeGuard(ManuallyDrop::new(new_table));
+            guard.0.growth_left -= self.items;
+            guard.0.items = self.items;
+
+            // No elements can be equal, and the new table has no tombstones,
+            // so each element simply goes into the first free bucket of its
+            // probe sequence.
+            for bucket in self.raw_iter() {
+                let hash = hasher(&bucket.as_ref().0);
+                let index = guard.0.find_insert_slot(hash);
+                guard.0.set_ctrl(index, h2(hash));
+                ptr::copy_nonoverlapping(bucket.ptr, guard.0.bucket(index).ptr, 1);
             }
-        }
-    }
 
-    /// Creates a new raw table from a given capacity. All buckets are
-    /// initially empty.
-    pub fn new(capacity: usize) -> RawTable<K, V> {
-        unsafe {
-            let ret = RawTable::new_uninitialized(capacity);
-            ptr::write_bytes(ret.hashes.ptr(), 0, capacity);
-            ret
+            // Every element has moved to the new table. Swap it in, and let
+            // the guard free the old allocation without dropping anything.
+            mem::swap(self, &mut *guard.0);
+            Ok(())
         }
     }
 
-    /// The hashtable's capacity, similar to a vector's.
+    /// Returns the number of elements the table can hold without
+    /// reallocating.
+    #[inline]
     pub fn capacity(&self) -> usize {
-        self.capacity_mask.wrapping_add(1)
+        self.items + self.growth_left
     }
 
-    /// The number of elements ever `put` in the hashtable, minus the number
-    /// of elements ever `take`n.
+    /// Returns the number of elements in the table.
+    #[inline]
     pub fn size(&self) -> usize {
-        self.size
+        self.items
     }
 
-    fn raw_buckets(&self) -> RawBuckets<K, V> {
-        RawBuckets {
-            raw: self.raw_bucket_at(0),
-            elems_left: self.size,
-            marker: marker::PhantomData,
+    /// Returns the number of buckets in the table.
+    #[inline]
+    pub fn buckets(&self) -> usize {
+        self.bucket_mask + 1
+    }
+
+    /// Returns the number of control bytes in the table.
+    #[inline]
+    fn num_ctrl_bytes(&self) -> usize {
+        self.bucket_mask + 1 + GROUP_WIDTH
+    }
+
+    /// Returns whether this table points to the empty singleton with a
+    /// capacity of 0.
+    #[inline]
+    fn is_empty_singleton(&self) -> bool {
+        self.bucket_mask == 0
+    }
+
+    /// Returns an iterator over every element in the table. It is up to the
+    /// caller to ensure that the table outlives the iterator.
+    ///
+    /// The iterator does not borrow the table, so the elements it has
+    /// already yielded may be erased while iterating.
+    #[inline]
+    pub unsafe fn raw_iter(&self) -> RawIter<K, V> {
+        RawIter {
+            current_group: Group::load(self.ctrl.as_ptr()).match_full(),
+            group_base: 0,
+            ctrl: self.ctrl.as_ptr(),
+            data: self.data.as_ptr(),
+            items: self.items,
         }
     }
 
     pub fn iter(&self) -> Iter<K, V> {
         Iter {
-            iter: self.raw_buckets(),
+            iter: unsafe { self.raw_iter() },
+            marker: marker::PhantomData,
         }
     }
 
     pub fn iter_mut(&mut self) -> IterMut<K, V> {
         IterMut {
-            iter: self.raw_buckets(),
-            _marker: marker::PhantomData,
+            iter: unsafe { self.raw_iter() },
+            marker: marker::PhantomData,
         }
     }
 
-    pub fn into_iter(self) -> IntoIter<K, V> {
-        let RawBuckets { raw, elems_left, .. } = self.raw_buckets();
-        // Replace the marker regardless of lifetime bounds on parameters.
+    pub fn into_iter(mut self) -> IntoIter<K, V> {
+        let iter = unsafe { self.raw_iter() };
+        // The iterator owns the elements from now on; the table only keeps
+        // the allocation alive.
+        self.items = 0;
         IntoIter {
-            iter: RawBuckets {
-                raw,
-                elems_left,
-                marker: marker::PhantomData,
-            },
             table: self,
+            iter,
         }
     }
 
     pub fn drain(&mut self) -> Drain<K, V> {
-        let RawBuckets { raw, elems_left, .. } = self.raw_buckets();
-        // Replace the marker regardless of lifetime bounds on parameters.
+        let iter = unsafe { self.raw_iter() };
+        // Take the table out of `self` while draining, so that forgetting
+        // the `Drain` only leaks the elements instead of leaving them
+        // behind to be dropped twice.
+        let mut table = mem::replace(self, RawTable::new());
+        table.items = 0;
         Drain {
-            iter: RawBuckets {
-                raw,
-                elems_left,
-                marker: marker::PhantomData,
-            },
-            table: Shared::from(self),
+            table,
+            orig_table: Shared::from(self),
+            iter,
             marker: marker::PhantomData,
         }
     }
 
-    /// Drops buckets in reverse order. It leaves the table in an inconsistent
-    /// state and should only be used for dropping the table's remaining
-    /// entries. It's used in the implementation of Drop.
-    unsafe fn rev_drop_buckets(&mut self) {
-        // initialize the raw bucket past the end of the table
-        let mut raw = self.raw_bucket_at(self.capacity());
-        let mut elems_left = self.size;
-
-        while elems_left != 0 {
-            raw.idx -= 1;
-
-            if *raw.hash() != EMPTY_BUCKET {
-                elems_left -= 1;
-                ptr::drop_in_place(raw.pair());
-            }
+    /// Drops all elements in the table without resetting the control bytes.
+    unsafe fn drop_elements(&mut self) {
+        for bucket in self.raw_iter() {
+            ptr::drop_in_place(bucket.ptr);
         }
     }
-
-    /// Set the table tag
-    pub fn set_tag(&mut self, value: bool) {
-        self.hashes.set_tag(value)
-    }
-
-    /// Get the table tag
-    pub fn tag(&self) -> bool {
-        self.hashes.tag()
-    }
 }
 
-/// A raw iterator. The basis for some other iterators in this module. Although
-/// this interface is safe, it's not used outside this module.
-struct RawBuckets<'a, K, V> {
-    raw: RawBucket<K, V>,
-    elems_left: usize,
-
-    // Strictly speaking, this should be &'a (K,V), but that would
-    // require that K:'a, and we often use RawBuckets<'static...> for
-    // move iterations, so that messes up a lot of other things. So
-    // just use `&'a (K,V)` as this is not a publicly exposed type
-    // anyway.
-    marker: marker::PhantomData<&'a ()>,
+/// A raw iterator over the full buckets of a table. The basis for the other
+/// iterators in this module.
+///
+/// The iterator walks the control bytes a group at a time, keeping a
+/// `BitMask` of the full buckets in the current group.
+pub struct RawIter<K, V> {
+    current_group: BitMask,
+    group_base: usize,
+    ctrl: *const u8,
+    // We use *const to ensure covariance with respect to K and V
+    data: *const (K, V),
+    items: usize,
 }
 
 // FIXME(#19839) Remove in favor of `#[derive(Clone)]`
-impl<'a, K, V> Clone for RawBuckets<'a, K, V> {
-    fn clone(&self) -> RawBuckets<'a, K, V> {
-        RawBuckets {
-            raw: self.raw,
-            elems_left: self.elems_left,
-            marker: marker::PhantomData,
+impl<K, V> Clone for RawIter<K, V> {
+    fn clone(&self) -> RawIter<K, V> {
+        RawIter {
+            current_group: self.current_group,
+            group_base: self.group_base,
+            ctrl: self.ctrl,
+            data: self.data,
+            items: self.items,
         }
     }
 }
 
+impl<K, V> Iterator for RawIter<K, V> {
+    type Item = Bucket<K, V>;
 
-impl<'a, K, V> Iterator for RawBuckets<'a, K, V> {
-    type Item = RawBucket<K, V>;
-
-    fn next(&mut self) -> Option<RawBucket<K, V>> {
-        if self.elems_left == 0 {
+    #[inline]
+    fn next(&mut self) -> Option<Bucket<K, V>> {
+        if self.items == 0 {
             return None;
         }
 
-        loop {
-            unsafe {
-                let item = self.raw;
-                self.raw.idx += 1;
-                if *item.hash() != EMPTY_BUCKET {
-                    self.elems_left -= 1;
-                    return Some(item);
+        unsafe {
+            loop {
+                if let Some(bit) = self.current_group.lowest_set_bit() {
+                    self.current_group = self.current_group.remove_lowest_bit();
+                    self.items -= 1;
+                    let index = self.group_base + bit;
+                    return Some(Bucket {
+                        index,
+                        ptr: self.data.offset(index as isize) as *mut (K, V),
+                    });
                 }
+
+                // There are items left, so they must be in a later group.
+                self.group_base += GROUP_WIDTH;
+                let ctrl = self.ctrl.offset(self.group_base as isize);
+                self.current_group = Group::load(ctrl).match_full();
             }
         }
     }
 
+    #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.elems_left, Some(self.elems_left))
+        (self.items, Some(self.items))
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for RawBuckets<'a, K, V> {
+impl<K, V> ExactSizeIterator for RawIter<K, V> {
     fn len(&self) -> usize {
-        self.elems_left
+        self.items
     }
 }
 
 /// Iterator over shared references to entries in a table.
 pub struct Iter<'a, K: 'a, V: 'a> {
-    iter: RawBuckets<'a, K, V>,
+    iter: RawIter<K, V>,
+    marker: marker::PhantomData<&'a (K, V)>,
 }
 
 unsafe impl<'a, K: Sync, V: Sync> Sync for Iter<'a, K, V> {}
@@ -981,15 +775,16 @@ impl<'a, K, V> Clone for Iter<'a, K, V> {
     fn clone(&self) -> Iter<'a, K, V> {
         Iter {
             iter: self.iter.clone(),
+            marker: marker::PhantomData,
         }
     }
 }
 
 /// Iterator over mutable references to entries in a table.
 pub struct IterMut<'a, K: 'a, V: 'a> {
-    iter: RawBuckets<'a, K, V>,
+    iter: RawIter<K, V>,
     // To ensure invariance with respect to V
-    _marker: marker::PhantomData<&'a mut V>,
+    marker: marker::PhantomData<(&'a K, &'a mut V)>,
 }
 
 unsafe impl<'a, K: Sync, V: Sync> Sync for IterMut<'a, K, V> {}
@@ -1001,6 +796,7 @@ impl<'a, K: 'a, V: 'a> IterMut<'a, K, V> {
     pub fn iter(&self) -> Iter<K, V> {
         Iter {
             iter: self.iter.clone(),
+            marker: marker::PhantomData,
         }
     }
 }
@@ -1008,7 +804,7 @@ impl<'a, K: 'a, V: 'a> IterMut<'a, K, V> {
 /// Iterator over the entries in a table, consuming the table.
 pub struct IntoIter<K, V> {
     table: RawTable<K, V>,
-    iter: RawBuckets<'static, K, V>,
+    iter: RawIter<K, V>,
 }
 
 unsafe impl<K: Sync, V: Sync> Sync for IntoIter<K, V> {}
@@ -1018,14 +814,16 @@ impl<K, V> IntoIter<K, V> {
     pub fn iter(&self) -> Iter<K, V> {
         Iter {
             iter: self.iter.clone(),
+            marker: marker::PhantomData,
         }
     }
 }
 
 /// Iterator over the entries in a table, clearing the table.
 pub struct Drain<'a, K: 'a, V: 'a> {
-    table: Shared<RawTable<K, V>>,
-    iter: RawBuckets<'static, K, V>,
+    table: RawTable<K, V>,
+    orig_table: Shared<RawTable<K, V>>,
+    iter: RawIter<K, V>,
     marker: marker::PhantomData<&'a RawTable<K, V>>,
 }
 
@@ -1036,6 +834,7 @@ impl<'a, K, V> Drain<'a, K, V> {
     pub fn iter(&self) -> Iter<K, V> {
         Iter {
             iter: self.iter.clone(),
+            marker: marker::PhantomData,
         }
     }
 }
@@ -1044,9 +843,9 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<(&'a K, &'a V)> {
-        self.iter.next().map(|raw| unsafe {
-            let pair_ptr = raw.pair();
-            (&(*pair_ptr).0, &(*pair_ptr).1)
+        self.iter.next().map(|bucket| unsafe {
+            let pair = bucket.as_ref();
+            (&pair.0, &pair.1)
         })
     }
 
@@ -1065,9 +864,9 @@ impl<'a, K, V> Iterator for IterMut<'a, K, V> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
-        self.iter.next().map(|raw| unsafe {
-            let pair_ptr = raw.pair();
-            (&(*pair_ptr).0, &mut (*pair_ptr).1)
+        self.iter.next().map(|bucket| unsafe {
+            let pair = bucket.as_mut();
+            (&pair.0, &mut pair.1)
         })
     }
 
@@ -1083,16 +882,10 @@ impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {
 }
 
 impl<K, V> Iterator for IntoIter<K, V> {
-    type Item = (SafeHash, K, V);
+    type Item = (K, V);
 
-    fn next(&mut self) -> Option<(SafeHash, K, V)> {
-        self.iter.next().map(|raw| {
-            self.table.size -= 1;
-            unsafe {
-                let (k, v) = ptr::read(raw.pair());
-                (SafeHash { hash: *raw.hash() }, k, v)
-            }
-        })
+    fn next(&mut self) -> Option<(K, V)> {
+        self.iter.next().map(|bucket| unsafe { ptr::read(bucket.ptr) })
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -1102,22 +895,26 @@ impl<K, V> Iterator for IntoIter<K, V> {
 
 impl<K, V> ExactSizeIterator for IntoIter<K, V> {
     fn len(&self) -> usize {
-        self.iter().len()
+        self.iter.len()
+    }
+}
+
+unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for IntoIter<K, V> {
+    fn drop(&mut self) {
+        // Drop the elements that were not yielded; the table itself only
+        // frees its allocation.
+        if needs_drop::<(K, V)>() {
+            for _ in self {}
+        }
     }
 }
 
 impl<'a, K, V> Iterator for Drain<'a, K, V> {
-    type Item = (SafeHash, K, V);
+    type Item = (K, V);
 
     #[inline]
-    fn next(&mut self) -> Option<(SafeHash, K, V)> {
-        self.iter.next().map(|raw| {
-            unsafe {
-                self.table.as_mut().size -= 1;
-                let (k, v) = ptr::read(raw.pair());
-                (SafeHash { hash: ptr::replace(&mut *raw.hash(), EMPTY_BUCKET) }, k, v)
-            }
-        })
+    fn next(&mut self) -> Option<(K, V)> {
+        self.iter.next().map(|bucket| unsafe { ptr::read(bucket.ptr) })
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -1133,68 +930,64 @@ impl<'a, K, V> ExactSizeIterator for Drain<'a, K, V> {
 
 impl<'a, K: 'a, V: 'a> Drop for Drain<'a, K, V> {
     fn drop(&mut self) {
-        for _ in self {}
+        for _ in &mut *self {}
+
+        // Hand the now empty table, and its allocation, back to the map.
+        self.table.clear_no_drop();
+        unsafe {
+            mem::swap(self.orig_table.as_mut(), &mut self.table);
+        }
     }
 }
 
 impl<K: Clone, V: Clone> Clone for RawTable<K, V> {
     fn clone(&self) -> RawTable<K, V> {
+        if self.is_empty_singleton() {
+            return RawTable::new();
+        }
+
         unsafe {
-            let cap = self.capacity();
-            let mut new_ht = RawTable::new_uninitialized(cap);
-
-            let mut new_buckets = new_ht.raw_bucket_at(0);
-            let mut buckets = self.raw_bucket_at(0);
-            while buckets.idx < cap {
-                *new_buckets.hash() = *buckets.hash();
-                if *new_buckets.hash() != EMPTY_BUCKET {
-                    let pair_ptr = buckets.pair();
-                    let kv = ((*pair_ptr).0.clone(), (*pair_ptr).1.clone());
-                    ptr::write(new_buckets.pair(), kv);
-                }
-                buckets.idx += 1;
-                new_buckets.idx += 1;
+            let mut new_table = RawTable::new_uninitialized(self.buckets());
+            ptr::write_bytes(new_table.ctrl(0), EMPTY, new_table.num_ctrl_bytes());
+
+            // Clone every element into the same bucket. A control byte is
+            // only copied once its bucket has been written, so if a `clone`
+            // panics, `new_table` drops exactly the elements cloned so far.
+            for bucket in self.raw_iter() {
+                let (ref k, ref v) = *bucket.as_ref();
+                ptr::write(new_table.bucket(bucket.index).ptr, (k.clone(), v.clone()));
+                new_table.set_ctrl(bucket.index, *self.ctrl(bucket.index));
+                new_table.items += 1;
             }
 
-            new_ht.size = self.size();
-
-            new_ht
+            // Tombstones are not copied, so the clone may have more room to
+            // grow than the original.
+            new_table.growth_left = bucket_mask_to_capacity(self.bucket_mask) - self.items;
+            new_table
         }
     }
 }
 
 unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for RawTable<K, V> {
     fn drop(&mut self) {
-        if self.capacity() == 0 {
-            return;
-        }
-
-        // This is done in reverse because we've likely partially taken
-        // some elements out with `.into_iter()` from the front.
-        // Check if the size is 0, so we don't do a useless scan when
-        // dropping empty tables such as on resize.
-        // Also avoid double drop of elements that have been already moved out.
         unsafe {
             if needs_drop::<(K, V)>() {
                 // avoid linear runtime for types that don't need drop
-                self.rev_drop_buckets();
+                self.drop_elements();
             }
-        }
-
-        let hashes_size = self.capacity() * size_of::<HashUint>();
-        let pairs_size = self.capacity() * size_of::<(K, V)>();
-        let (align, _, size, oflo) = calculate_allocation(hashes_size,
-                                                          align_of::<HashUint>(),
-                                                          pairs_size,
-                                                          align_of::<(K, V)>());
-
-        debug_assert!(!oflo, "should be impossible");
-
-        unsafe {
-            Heap.dealloc(self.hashes.ptr() as *mut u8,
-                         Layout::from_size_align(size, align).unwrap());
-            // Remember how everything was allocated out of one buffer
-            // during initialization? We only need one call to free here.
+            self.free_buckets();
         }
     }
 }
+
+#[test]
+fn test_capacity_to_buckets() {
+    for &cap in &[1, 3, 4, 7, 8, 14, 15, 28, 29, 1000] {
+        let buckets = capacity_to_buckets(cap).unwrap();
+        assert!(buckets.is_power_of_two());
+        assert!(bucket_mask_to_capacity(buckets - 1) >= cap);
+        // The table is as small as it can be for that capacity.
+        assert!(buckets == 4 || bucket_mask_to_capacity(buckets / 2 - 1) < cap);
+    }
+    assert_eq!(capacity_to_buckets(usize::max_value() / 4), None);
+}
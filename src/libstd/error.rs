@@ -53,6 +53,7 @@
 
 use alloc::allocator;
 use any::TypeId;
+use backtrace::Backtrace;
 use cell;
 use char;
 use fmt::{self, Debug, Display};
@@ -150,6 +151,62 @@ pub trait Error: Debug + Display {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn cause(&self) -> Option<&Error> { None }
 
+    /// Returns a stack backtrace, if available, of where this error occurred.
+    ///
+    /// This function allows inspecting the location, in code, of where an
+    /// error happened. The returned `Backtrace` contains information about
+    /// the stack trace of the OS thread of execution of where the error
+    /// originated from.
+    ///
+    /// Note that not all errors contain a `Backtrace`. Also note that a
+    /// `Backtrace` may actually be empty. For more information consult the
+    /// [`Backtrace`] type itself.
+    ///
+    /// [`Backtrace`]: ../backtrace/struct.Backtrace.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace)]
+    /// use std::backtrace::Backtrace;
+    /// use std::error::Error;
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError {
+    ///     backtrace: Backtrace,
+    /// }
+    ///
+    /// impl MyError {
+    ///     fn new() -> MyError {
+    ///         MyError { backtrace: Backtrace::capture() }
+    ///     }
+    /// }
+    ///
+    /// impl fmt::Display for MyError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "my error")
+    ///     }
+    /// }
+    ///
+    /// impl Error for MyError {
+    ///     fn description(&self) -> &str {
+    ///         "my error"
+    ///     }
+    ///
+    ///     fn backtrace(&self) -> Option<&Backtrace> {
+    ///         Some(&self.backtrace)
+    ///     }
+    /// }
+    ///
+    /// let err = MyError::new();
+    /// assert!(err.backtrace().is_some());
+    /// ```
+    #[unstable(feature = "backtrace", issue = "0")]
+    fn backtrace(&self) -> Option<&Backtrace> {
+        None
+    }
+
     /// Get the `TypeId` of `self`
     #[doc(hidden)]
     #[unstable(feature = "error_type_id",
@@ -240,6 +297,13 @@ impl Error for allocator::CannotReallocInPlace {
     }
 }
 
+#[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+impl Error for allocator::CollectionAllocErr {
+    fn description(&self) -> &str {
+        allocator::CollectionAllocErr::description(self)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Error for str::ParseBoolError {
     fn description(&self) -> &str { "failed to parse bool" }
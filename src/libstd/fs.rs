@@ -19,7 +19,7 @@
 
 use fmt;
 use ffi::OsString;
-use io::{self, SeekFrom, Seek, Read, Initializer, Write};
+use io::{self, SeekFrom, Seek, Read, Initializer, Write, IoSlice, IoSliceMut};
 use path::{Path, PathBuf};
 use sys::fs as fs_imp;
 use sys_common::{AsInnerMut, FromInner, AsInner, IntoInner};
@@ -449,6 +449,10 @@ impl Read for File {
         self.inner.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -459,6 +463,9 @@ impl Write for File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -473,6 +480,10 @@ impl<'a> Read for &'a File {
         self.inner.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -483,6 +494,9 @@ impl<'a> Write for &'a File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -1874,7 +1888,7 @@ mod tests {
     use io::prelude::*;
 
     use fs::{self, File, OpenOptions};
-    use io::{ErrorKind, SeekFrom};
+    use io::{self, ErrorKind, SeekFrom};
     use path::Path;
     use rand::{StdRng, Rng};
     use str;
@@ -2510,6 +2524,45 @@ mod tests {
                    check!(out.metadata()).permissions());
     }
 
+    #[test]
+    fn io_copy_between_files_respects_offsets() {
+        let tmpdir = tmpdir();
+        let input = tmpdir.join("in.bin");
+        let out = tmpdir.join("out.bin");
+
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<u8>>();
+        check!(check!(File::create(&input)).write_all(&data));
+
+        let mut reader = check!(File::open(&input));
+        let mut skipped = [0; 10];
+        check!(reader.read_exact(&mut skipped));
+
+        let mut writer = check!(File::create(&out));
+        check!(writer.write_all(b"ab"));
+        assert_eq!(check!(io::copy(&mut reader, &mut writer)), 99_990);
+        check!(writer.write_all(b"yz"));
+        drop(writer);
+
+        let mut expected = b"ab".to_vec();
+        expected.extend_from_slice(&data[10..]);
+        expected.extend_from_slice(b"yz");
+        let mut v = Vec::new();
+        check!(check!(File::open(&out)).read_to_end(&mut v));
+        assert_eq!(v, expected);
+
+        // Appending writers can't be targeted by every kernel copy, make sure
+        // the fallback produces the same result.
+        let mut reader = check!(File::open(&input));
+        let mut writer = check!(OpenOptions::new().append(true).open(&out));
+        assert_eq!(check!(io::copy(&mut reader, &mut writer)), 100_000);
+        drop(writer);
+
+        expected.extend_from_slice(&data);
+        let mut v = Vec::new();
+        check!(check!(File::open(&out)).read_to_end(&mut v));
+        assert_eq!(v, expected);
+    }
+
     #[test]
     fn copy_file_dst_dir() {
         let tmpdir = tmpdir();
@@ -0,0 +1,127 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Asynchronous values.
+
+#![unstable(feature = "futures_api",
+            reason = "futures in libcore are unstable",
+            issue = "0")]
+
+use core::cell::Cell;
+use core::marker::Unpin;
+use core::mem::Pin;
+use core::ops::{Generator, GeneratorState};
+use core::ptr;
+
+#[doc(inline)]
+pub use core::future::*;
+
+use task::{Context, Poll};
+
+/// Wrap a generator in a future.
+///
+/// This is the lowering target of `async fn`: the body of the function is
+/// turned into an immovable generator which yields `()` whenever it is waiting
+/// on another future. The generator is only ever resumed through a `Pin`, so
+/// it may hold borrows across its suspension points.
+#[doc(hidden)]
+#[unstable(feature = "gen_future", issue = "0")]
+pub fn from_generator<T: Generator<Yield = ()>>(x: T) -> impl Future<Output = T::Return> {
+    GenFuture(x)
+}
+
+/// A wrapper around generators used to implement `Future` for `async fn`s.
+#[doc(hidden)]
+#[unstable(feature = "gen_future", issue = "0")]
+struct GenFuture<T: Generator<Yield = ()>>(T);
+
+// `async fn` bodies are `static` generators and thus never `Unpin`, but
+// `poll` below relies on the generator staying in place for any `T`.
+#[unstable(feature = "gen_future", issue = "0")]
+impl<T: Generator<Yield = ()>> !Unpin for GenFuture<T> {}
+
+#[unstable(feature = "gen_future", issue = "0")]
+impl<T: Generator<Yield = ()>> Future for GenFuture<T> {
+    type Output = T::Return;
+
+    fn poll(this: Pin<Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Safe because the generator is only ever accessed through the `Pin`
+        // and hence never moved again.
+        let gen = unsafe { &mut Pin::get_mut_unchecked(this).0 };
+        set_task_context(cx, || match unsafe { gen.resume() } {
+            GeneratorState::Yielded(()) => Poll::Pending,
+            GeneratorState::Complete(x) => Poll::Ready(x),
+        })
+    }
+}
+
+thread_local! {
+    static TLS_CX: Cell<*mut Context<'static>> = Cell::new(ptr::null_mut())
+}
+
+struct SetOnDrop(*mut Context<'static>);
+
+impl Drop for SetOnDrop {
+    fn drop(&mut self) {
+        TLS_CX.with(|tls_cx| {
+            tls_cx.set(self.0);
+        });
+    }
+}
+
+/// Sets the thread-local task context used by `await!`, for the duration of
+/// the call to `f`.
+#[doc(hidden)]
+#[unstable(feature = "gen_future", issue = "0")]
+pub fn set_task_context<F, R>(cx: &mut Context, f: F) -> R
+    where F: FnOnce() -> R
+{
+    let old_cx = TLS_CX.with(|tls_cx| {
+        tls_cx.replace(cx as *mut Context as *mut () as *mut Context<'static>)
+    });
+    let _reset_cx = SetOnDrop(old_cx);
+    f()
+}
+
+/// Retrieves the thread-local task context used by `await!`.
+///
+/// # Panics
+///
+/// Panics if no task context is set. The context is only set while the
+/// future of an `async fn` is being polled through `Future::poll`, so this is
+/// a caller error: `await!` was used outside of an `async fn`, or the
+/// generator was resumed outside of `poll`.
+#[doc(hidden)]
+#[unstable(feature = "gen_future", issue = "0")]
+pub fn get_task_context<F, R>(f: F) -> R
+    where F: FnOnce(&mut Context) -> R
+{
+    let cx_ptr = TLS_CX.with(|tls_cx| {
+        // Clear the entry so that nested `get_task_context` calls
+        // will fail or set their own value.
+        tls_cx.replace(ptr::null_mut())
+    });
+    let _reset_cx = SetOnDrop(cx_ptr);
+
+    let cx = unsafe { cx_ptr.as_mut() }.expect(
+        "no task context set: `await!` can only be used in an `async fn` \
+         whose future is being polled through `Future::poll`");
+
+    f(cx)
+}
+
+/// Polls a future in the current thread-local task context.
+#[doc(hidden)]
+#[unstable(feature = "gen_future", issue = "0")]
+pub fn poll_in_task_cx<F>(f: Pin<F>) -> Poll<F::Output>
+    where F: Future + ?Sized
+{
+    get_task_context(|cx| F::poll(f, cx))
+}
@@ -12,7 +12,7 @@ use io::prelude::*;
 
 use core::convert::TryInto;
 use cmp;
-use io::{self, Initializer, SeekFrom, Error, ErrorKind};
+use io::{self, Initializer, SeekFrom, Error, ErrorKind, IoSlice, IoSliceMut};
 
 /// A `Cursor` wraps another type and provides it with a
 /// [`Seek`] implementation.
@@ -230,6 +230,18 @@ impl<T> Read for Cursor<T> where T: AsRef<[u8]> {
         Ok(n)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        let mut nread = 0;
+        for buf in bufs {
+            let n = self.read(buf)?;
+            nread += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(nread)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -254,6 +266,17 @@ impl<'a> Write for Cursor<&'a mut [u8]> {
         self.pos += amt as u64;
         Ok(amt)
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let mut nwritten = 0;
+        for buf in bufs {
+            let n = self.write(buf)?;
+            nwritten += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(nwritten)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
@@ -284,6 +307,17 @@ impl Write for Cursor<Vec<u8>> {
         self.set_position((pos + buf.len()) as u64);
         Ok(buf.len())
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let mut nwritten = 0;
+        for buf in bufs {
+            let n = self.write(buf)?;
+            nwritten += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(nwritten)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
@@ -296,13 +330,24 @@ impl Write for Cursor<Box<[u8]>> {
         self.pos += amt as u64;
         Ok(amt)
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let mut nwritten = 0;
+        for buf in bufs {
+            let n = self.write(buf)?;
+            nwritten += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(nwritten)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
 #[cfg(test)]
 mod tests {
     use io::prelude::*;
-    use io::{Cursor, SeekFrom};
+    use io::{Cursor, SeekFrom, IoSlice, IoSliceMut};
 
     #[test]
     fn test_vec_writer() {
@@ -314,6 +359,28 @@ mod tests {
         assert_eq!(writer, b);
     }
 
+    #[test]
+    fn test_vec_writer_vectored() {
+        let mut writer = Vec::new();
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[])]).unwrap(), 0);
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[0]),
+                                           IoSlice::new(&[1, 2, 3]),
+                                           IoSlice::new(&[4, 5, 6, 7])]).unwrap(), 8);
+        let b: &[_] = &[0, 1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(writer, b);
+    }
+
+    #[test]
+    fn test_box_slice_writer_vectored() {
+        let mut writer = Cursor::new(vec![0u8; 5].into_boxed_slice());
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[0, 1]),
+                                           IoSlice::new(&[2, 3, 4, 5])]).unwrap(), 5);
+        assert_eq!(writer.position(), 5);
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[6])]).unwrap(), 0);
+        let b: &[_] = &[0, 1, 2, 3, 4];
+        assert_eq!(&**writer.get_ref(), b);
+    }
+
     #[test]
     fn test_mem_writer() {
         let mut writer = Cursor::new(Vec::new());
@@ -423,6 +490,27 @@ mod tests {
         assert_eq!(reader.read(&mut buf).unwrap(), 0);
     }
 
+    #[test]
+    fn test_mem_reader_vectored() {
+        let mut reader = Cursor::new(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        let mut buf = [0];
+        let mut buf2 = [0; 4];
+        assert_eq!(reader.read_vectored(&mut [IoSliceMut::new(&mut buf),
+                                              IoSliceMut::new(&mut buf2)]).unwrap(), 5);
+        assert_eq!(reader.position(), 5);
+        let b: &[_] = &[0];
+        assert_eq!(buf, b);
+        let b: &[_] = &[1, 2, 3, 4];
+        assert_eq!(buf2, b);
+        assert_eq!(reader.read_vectored(&mut [IoSliceMut::new(&mut buf),
+                                              IoSliceMut::new(&mut buf2)]).unwrap(), 3);
+        let b: &[_] = &[5];
+        assert_eq!(buf, b);
+        let b: &[_] = &[6, 7];
+        assert_eq!(&buf2[..2], b);
+        assert_eq!(reader.read_vectored(&mut [IoSliceMut::new(&mut buf)]).unwrap(), 0);
+    }
+
     #[test]
     fn test_boxed_slice_reader() {
         let mut reader = Cursor::new(vec![0, 1, 2, 3, 4, 5, 6, 7].into_boxed_slice());
@@ -29,6 +29,14 @@ use mem;
 /// `write` returns an error. All instances of `ErrorKind::Interrupted` are
 /// handled by this function and the underlying operation is retried.
 ///
+/// # Platform-specific behavior
+///
+/// On Linux, when both `reader` and `writer` are backed by file descriptors
+/// (such as `File`, `TcpStream`, `UnixStream` or the pipes of a child
+/// process) this function may copy the data in the kernel using
+/// `copy_file_range`, `sendfile` or `splice` instead of going through a
+/// user-space buffer. This is an implementation detail and may change.
+///
 /// # Examples
 ///
 /// ```
@@ -47,6 +55,19 @@ use mem;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub fn copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
     where R: Read, W: Write
+{
+    #[cfg(target_os = "linux")]
+    return ::sys::kernel_copy::copy_spec(reader, writer);
+
+    #[cfg(not(target_os = "linux"))]
+    return generic_copy(reader, writer);
+}
+
+/// The user-space copy loop behind `copy`, always going through a
+/// `DEFAULT_BUF_SIZE` buffer on the stack.
+pub(crate) fn generic_copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W)
+                                                 -> io::Result<u64>
+    where R: Read, W: Write
 {
     let mut buf = unsafe {
         let mut buf: [u8; super::DEFAULT_BUF_SIZE] = mem::uninitialized();
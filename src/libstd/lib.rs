@@ -244,6 +244,7 @@
 #![feature(allow_internal_unstable)]
 #![feature(asm)]
 #![feature(box_syntax)]
+#![feature(cfg_target_feature)]
 #![feature(cfg_target_has_atomic)]
 #![feature(cfg_target_thread_local)]
 #![feature(cfg_target_vendor)]
@@ -251,6 +252,7 @@
 #![feature(char_internals)]
 #![feature(collections_range)]
 #![feature(compiler_builtins_lib)]
+#![feature(conservative_impl_trait)]
 #![feature(const_fn)]
 #![feature(core_float)]
 #![feature(core_intrinsics)]
@@ -260,9 +262,13 @@
 #![feature(fn_traits)]
 #![feature(fnbox)]
 #![feature(fused)]
+#![feature(futures_api)]
+#![feature(gen_future)]
+#![feature(generator_trait)]
 #![feature(generic_param_attrs)]
 #![feature(hashmap_hasher)]
 #![feature(heap_api)]
+#![feature(hint_core_should_pause)]
 #![feature(i128)]
 #![feature(i128_type)]
 #![feature(inclusive_range)]
@@ -286,7 +292,9 @@
 #![feature(panic_unwind)]
 #![feature(peek)]
 #![feature(placement_in_syntax)]
+#![feature(pin)]
 #![feature(placement_new_protocol)]
+#![feature(platform_intrinsics)]
 #![feature(prelude_import)]
 #![feature(rand)]
 #![feature(raw)]
@@ -298,6 +306,7 @@
 #![feature(slice_bytes)]
 #![feature(slice_concat_ext)]
 #![feature(slice_patterns)]
+#![feature(specialization)]
 #![feature(staged_api)]
 #![feature(stmt_expr_attributes)]
 #![feature(str_char)]
@@ -307,6 +316,7 @@
 #![feature(thread_local)]
 #![feature(toowned_clone_into)]
 #![feature(try_from)]
+#![feature(try_reserve)]
 #![feature(unboxed_closures)]
 #![feature(unicode)]
 #![feature(unique)]
@@ -321,6 +331,7 @@
 #![cfg_attr(not(stage0), feature(const_atomic_isize_new))]
 #![cfg_attr(not(stage0), feature(const_atomic_usize_new))]
 #![cfg_attr(all(not(stage0), windows), feature(const_atomic_ptr_new))]
+#![cfg_attr(all(not(stage0), target_os = "linux"), feature(const_atomic_u32_new))]
 #![cfg_attr(not(stage0), feature(const_unsafe_cell_new))]
 #![cfg_attr(not(stage0), feature(const_cell_new))]
 #![cfg_attr(not(stage0), feature(const_once_new))]
@@ -471,11 +482,13 @@ pub mod f64;
 #[macro_use]
 pub mod thread;
 pub mod ascii;
+pub mod backtrace;
 pub mod collections;
 pub mod env;
 pub mod error;
 pub mod ffi;
 pub mod fs;
+pub mod future;
 pub mod io;
 pub mod net;
 pub mod num;
@@ -484,6 +497,7 @@ pub mod panic;
 pub mod path;
 pub mod process;
 pub mod sync;
+pub mod task;
 pub mod time;
 pub mod heap;
 
@@ -501,6 +515,12 @@ mod memchr;
 // compiler
 pub mod rt;
 
+// The trait mapping the return value of `main` to an exit code
+mod termination;
+
+#[unstable(feature = "termination_trait_lib", issue = "0")]
+pub use self::termination::Termination;
+
 // Some external utilities of the standard library rely on randomness (aka
 // rustc_back::TempDir and tests) and need a way to get at the OS rng we've got
 // here. This module is not at all intended for stabilization as-is, however,
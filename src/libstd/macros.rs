@@ -263,6 +263,47 @@ macro_rules! select {
     })
 }
 
+/// Wait for a future to complete inside of an `async fn`.
+///
+/// `await!` polls the given future in the context of the task running the
+/// enclosing `async fn`, suspending the function until the future is ready
+/// and then evaluating to its output. It may only be used inside of the body
+/// of an `async fn`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_await, futures_api)]
+///
+/// async fn answer() -> u32 {
+///     42
+/// }
+///
+/// async fn twice() -> u32 {
+///     await!(answer()) + await!(answer())
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+#[unstable(feature = "async_await", issue = "0")]
+#[allow_internal_unstable]
+#[allow_internal_unsafe]
+macro_rules! await {
+    ($e:expr) => { {
+        let mut pinned = $e;
+        loop {
+            if let $crate::task::Poll::Ready(x) =
+                $crate::future::poll_in_task_cx(unsafe {
+                    $crate::mem::Pin::new_unchecked(&mut pinned)
+                })
+            {
+                break x;
+            }
+            yield;
+        }
+    } }
+}
+
 #[cfg(test)]
 macro_rules! assert_approx_eq {
     ($a:expr, $b:expr) => ({
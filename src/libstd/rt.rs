@@ -28,19 +28,17 @@
 pub use panicking::{begin_panic, begin_panic_fmt, update_panic_count};
 
 #[cfg(not(test))]
-#[lang = "start"]
-fn lang_start(main: fn(), argc: isize, argv: *const *const u8) -> isize {
+fn lang_start_internal(main: &(Fn() -> i32 + Sync + ::panic::RefUnwindSafe),
+                       argc: isize, argv: *const *const u8) -> isize {
     use panic;
     use sys;
     use sys_common;
     use sys_common::thread_info;
     use thread::Thread;
-    #[cfg(not(feature = "backtrace"))]
-    use mem;
 
     sys::init();
 
-    let failed = unsafe {
+    unsafe {
         let main_guard = sys::thread::guard::init();
         sys::stack_overflow::init();
 
@@ -56,18 +54,27 @@ fn lang_start(main: fn(), argc: isize, argv: *const *const u8) -> isize {
 
         // Let's run some code!
         #[cfg(feature = "backtrace")]
-        let res = panic::catch_unwind(|| {
-            ::sys_common::backtrace::__rust_begin_short_backtrace(main)
+        let exit_code = panic::catch_unwind(|| {
+            ::sys_common::backtrace::__rust_begin_short_backtrace(move || main())
         });
         #[cfg(not(feature = "backtrace"))]
-        let res = panic::catch_unwind(mem::transmute::<_, fn()>(main));
-        sys_common::cleanup();
-        res.is_err()
-    };
+        let exit_code = panic::catch_unwind(move || main());
 
-    if failed {
-        101
-    } else {
-        0
+        sys_common::cleanup();
+        exit_code.unwrap_or(101) as isize
     }
 }
+
+#[cfg(not(any(test, stage0)))]
+#[lang = "start"]
+fn lang_start<T: ::termination::Termination + 'static>
+    (main: fn() -> T, argc: isize, argv: *const *const u8) -> isize
+{
+    lang_start_internal(&move || main().report(), argc, argv)
+}
+
+#[cfg(all(not(test), stage0))]
+#[lang = "start"]
+fn lang_start(main: fn(), argc: isize, argv: *const *const u8) -> isize {
+    lang_start_internal(&move || { main(); 0 }, argc, argv)
+}
@@ -87,9 +87,10 @@ impl WaitTimeoutResult {
 /// are bindings to system-provided condition variables where possible. Note
 /// that this module places one additional restriction over the system condition
 /// variables: each condvar can be used with precisely one mutex at runtime. Any
-/// attempt to use multiple mutexes on the same condition variable will result
-/// in a runtime panic. If this is not desired, then the unsafe primitives in
-/// `sys` do not have this restriction but may result in undefined behavior.
+/// attempt to use multiple mutexes on the same condition variable may result
+/// in a runtime panic. Whether it does is platform-specific: on platforms
+/// where mixing mutexes would be undefined behavior the condvar checks for it
+/// and panics, elsewhere (such as on Linux) the check is skipped.
 ///
 /// # Examples
 ///
@@ -118,7 +119,7 @@ impl WaitTimeoutResult {
 /// ```
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct Condvar {
-    inner: Box<sys::Condvar>,
+    inner: sys::MovableCondvar,
     mutex: AtomicUsize,
 }
 
@@ -135,14 +136,10 @@ impl Condvar {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> Condvar {
-        let mut c = Condvar {
-            inner: box sys::Condvar::new(),
+        Condvar {
+            inner: sys::MovableCondvar::new(),
             mutex: AtomicUsize::new(0),
-        };
-        unsafe {
-            c.inner.init();
         }
-        c
     }
 
     /// Blocks the current thread until this condition variable receives a
@@ -167,10 +164,11 @@ impl Condvar {
     ///
     /// # Panics
     ///
-    /// This function will [`panic!`] if it is used with more than one mutex
-    /// over time. Each condition variable is dynamically bound to exactly one
-    /// mutex to ensure defined behavior across platforms. If this functionality
-    /// is not desired, then unsafe primitives in `sys` are provided.
+    /// This function may [`panic!`] if it is used with more than one mutex
+    /// over time. On platforms where that would be undefined behavior each
+    /// condition variable is dynamically bound to exactly one mutex, and using
+    /// it with another one panics. Where the underlying primitive has no such
+    /// requirement, such as on Linux, no check is performed.
     ///
     /// [`notify_one`]: #method.notify_one
     /// [`notify_all`]: #method.notify_all
@@ -435,8 +433,12 @@ impl Condvar {
         unsafe { self.inner.notify_all() }
     }
 
-    fn verify(&self, mutex: &sys_mutex::Mutex) {
-        let addr = mutex as *const _ as usize;
+    fn verify(&self, mutex: &sys_mutex::MovableMutex) {
+        if !sys::REQUIRES_SAME_MUTEX {
+            return;
+        }
+
+        let addr = mutex.raw() as *const _ as usize;
         match self.mutex.compare_and_swap(0, addr, Ordering::SeqCst) {
             // If we got out 0, then we have successfully bound the mutex to
             // this cvar.
@@ -602,8 +604,22 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn wait_after_moving_mutex() {
+        // The futex-based mutex is stored inline, so moving the `Mutex`
+        // changes its address; that must not trip the same-mutex check.
+        let c = Condvar::new();
+        let m = Mutex::new(());
+        drop(c.wait_timeout(m.lock().unwrap(), Duration::from_millis(1)).unwrap());
+
+        let m = Box::new(m);
+        drop(c.wait_timeout(m.lock().unwrap(), Duration::from_millis(1)).unwrap());
+    }
+
     #[test]
     #[should_panic]
+    #[cfg(not(target_os = "linux"))]
     #[cfg_attr(target_os = "emscripten", ignore)]
     fn two_mutexes() {
         let m = Arc::new(Mutex::new(()));
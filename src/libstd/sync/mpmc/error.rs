@@ -0,0 +1,129 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use error;
+use fmt;
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+pub use sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+
+/// An error returned from the [`send_timeout`] method.
+///
+/// The error contains the message being sent so it can be recovered.
+///
+/// [`send_timeout`]: struct.Sender.html#method.send_timeout
+#[unstable(feature = "mpmc_channel", issue = "0")]
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum SendTimeoutError<T> {
+    /// The message could not be sent because the channel is full and the
+    /// operation timed out.
+    ///
+    /// If this is a zero-capacity channel, then the error indicates that there
+    /// was no receiver available to receive the message and the operation
+    /// timed out.
+    Timeout(T),
+
+    /// The message could not be sent because the channel is disconnected.
+    Disconnected(T),
+}
+
+/// An error returned from the [`try_select`] method.
+///
+/// Failed because none of the channel operations were ready.
+///
+/// [`try_select`]: struct.Select.html#method.try_select
+#[unstable(feature = "mpmc_channel", issue = "0")]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct TrySelectError;
+
+/// An error returned from the [`select_timeout`] method.
+///
+/// Failed because none of the channel operations became ready before the
+/// timeout.
+///
+/// [`select_timeout`]: struct.Select.html#method.select_timeout
+#[unstable(feature = "mpmc_channel", issue = "0")]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SelectTimeoutError;
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> fmt::Debug for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SendTimeoutError::Timeout(..) => "Timeout(..)".fmt(f),
+            SendTimeoutError::Disconnected(..) => "Disconnected(..)".fmt(f),
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> fmt::Display for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SendTimeoutError::Timeout(..) => {
+                "timed out waiting on send operation".fmt(f)
+            }
+            SendTimeoutError::Disconnected(..) => {
+                "sending on a closed channel".fmt(f)
+            }
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T: Send> error::Error for SendTimeoutError<T> {
+    fn description(&self) -> &str {
+        match *self {
+            SendTimeoutError::Timeout(..) => {
+                "timed out waiting on send operation"
+            }
+            SendTimeoutError::Disconnected(..) => {
+                "sending on a closed channel"
+            }
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> From<SendError<T>> for SendTimeoutError<T> {
+    fn from(err: SendError<T>) -> SendTimeoutError<T> {
+        match err {
+            SendError(e) => SendTimeoutError::Disconnected(e),
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl fmt::Display for TrySelectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "all operations in select would block".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl error::Error for TrySelectError {
+    fn description(&self) -> &str {
+        "all operations in select would block"
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl fmt::Display for SelectTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "timed out waiting on select".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl error::Error for SelectTimeoutError {
+    fn description(&self) -> &str {
+        "timed out waiting on select"
+    }
+}
@@ -0,0 +1,1046 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Multi-producer, multi-consumer FIFO queue communication primitives.
+//!
+//! This module provides message-based communication over channels, concretely
+//! defined by two types:
+//!
+//! * [`Sender`]
+//! * [`Receiver`]
+//!
+//! Unlike the channels in [`mpsc`], both halves of these channels can be
+//! cloned: several threads can send into and receive from the same channel,
+//! and each message is received by exactly one of the receivers. A channel is
+//! disconnected once all of its senders or all of its receivers have been
+//! dropped.
+//!
+//! Channels come in three flavors:
+//!
+//! 1. An unbounded channel, created by [`channel`], has infinite buffer
+//!    capacity and sends never block.
+//!
+//! 2. A bounded channel, created by [`sync_channel`] with a positive
+//!    capacity, is backed by a preallocated buffer. Sends block while the
+//!    buffer is full.
+//!
+//! 3. A zero-capacity channel, created by `sync_channel(0)`, is a rendezvous
+//!    channel: every send blocks until it is paired with a receive.
+//!
+//! Blocking operations come in timeout and deadline variants, and [`Select`]
+//! can wait on several send and receive operations at once.
+//!
+//! [`Sender`]: struct.Sender.html
+//! [`Receiver`]: struct.Receiver.html
+//! [`mpsc`]: ../mpsc/index.html
+//! [`channel`]: fn.channel.html
+//! [`sync_channel`]: fn.sync_channel.html
+//! [`Select`]: struct.Select.html
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(mpmc_channel)]
+//! use std::sync::mpmc::channel;
+//! use std::thread;
+//!
+//! let (tx, rx) = channel();
+//!
+//! let consumers: Vec<_> = (0..4).map(|_| {
+//!     let rx = rx.clone();
+//!     thread::spawn(move || rx.iter().count())
+//! }).collect();
+//!
+//! for i in 0..100 {
+//!     tx.send(i).unwrap();
+//! }
+//! drop(tx);
+//!
+//! let total: usize = consumers.into_iter().map(|h| h.join().unwrap()).sum();
+//! assert_eq!(total, 100);
+//! ```
+
+#![unstable(feature = "mpmc_channel", issue = "0")]
+
+// This module is a port of the `crossbeam-channel` crate. The counter, the
+// waker queues and the per-thread context are shared by all three flavors;
+// each flavor exposes `Sender`/`Receiver` handles implementing
+// `SelectHandle`, which is all `Select` needs to know about a channel.
+
+mod array;
+mod context;
+mod counter;
+mod error;
+mod list;
+mod select;
+mod utils;
+mod waker;
+mod zero;
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+pub use self::error::*;
+#[unstable(feature = "mpmc_channel", issue = "0")]
+pub use self::select::{Select, SelectedOperation};
+
+use fmt;
+use time::{Duration, Instant};
+
+use self::context::Context;
+use self::select::{Operation, SelectHandle, Token};
+
+/// Creates a new asynchronous channel of unbounded capacity, returning the
+/// sender/receiver halves.
+///
+/// All data sent on the [`Sender`] becomes available on the [`Receiver`] in
+/// the same order as it was sent, and no [`send`] will block the calling
+/// thread. Both halves can be cloned.
+///
+/// [`Sender`]: struct.Sender.html
+/// [`Receiver`]: struct.Receiver.html
+/// [`send`]: struct.Sender.html#method.send
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpmc::channel;
+/// use std::thread;
+///
+/// let (sender, receiver) = channel();
+///
+/// // Spawn off an expensive computation
+/// thread::spawn(move|| {
+///     sender.send(53).unwrap();
+/// });
+///
+/// assert_eq!(receiver.recv().unwrap(), 53);
+/// ```
+#[unstable(feature = "mpmc_channel", issue = "0")]
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (s, r) = counter::new(list::Channel::new());
+    let s = Sender { flavor: SenderFlavor::List(s) };
+    let r = Receiver { flavor: ReceiverFlavor::List(r) };
+    (s, r)
+}
+
+/// Creates a new synchronous, bounded channel.
+///
+/// Messages sent on the [`Sender`] are buffered until the buffer holds `cap`
+/// messages, after which [`send`] blocks until a receiver makes room.
+///
+/// If `cap` is zero the channel has no buffer at all: each send blocks until
+/// it is paired up with a receive, and vice versa.
+///
+/// [`Sender`]: struct.Sender.html
+/// [`send`]: struct.Sender.html#method.send
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpmc::sync_channel;
+/// use std::thread;
+///
+/// let (sender, receiver) = sync_channel(1);
+///
+/// // this returns immediately
+/// sender.send(1).unwrap();
+///
+/// thread::spawn(move|| {
+///     // this will block until the previous message has been received
+///     sender.send(2).unwrap();
+/// });
+///
+/// assert_eq!(receiver.recv().unwrap(), 1);
+/// assert_eq!(receiver.recv().unwrap(), 2);
+/// ```
+#[unstable(feature = "mpmc_channel", issue = "0")]
+pub fn sync_channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    if cap == 0 {
+        let (s, r) = counter::new(zero::Channel::new());
+        let s = Sender { flavor: SenderFlavor::Zero(s) };
+        let r = Receiver { flavor: ReceiverFlavor::Zero(r) };
+        (s, r)
+    } else {
+        let (s, r) = counter::new(array::Channel::with_capacity(cap));
+        let s = Sender { flavor: SenderFlavor::Array(s) };
+        let r = Receiver { flavor: ReceiverFlavor::Array(r) };
+        (s, r)
+    }
+}
+
+/// The sending side of a channel.
+///
+/// Senders can be cloned and shared among threads. The channel is
+/// disconnected once the last sender is dropped.
+#[unstable(feature = "mpmc_channel", issue = "0")]
+pub struct Sender<T> {
+    flavor: SenderFlavor<T>,
+}
+
+/// Sender flavors.
+enum SenderFlavor<T> {
+    /// Bounded channel based on a preallocated array.
+    Array(counter::Sender<array::Channel<T>>),
+
+    /// Unbounded channel implemented as a linked list.
+    List(counter::Sender<list::Channel<T>>),
+
+    /// Zero-capacity channel.
+    Zero(counter::Sender<zero::Channel<T>>),
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+unsafe impl<T: Send> Send for Sender<T> {}
+#[unstable(feature = "mpmc_channel", issue = "0")]
+unsafe impl<T: Send> Sync for Sender<T> {}
+
+impl<T> Sender<T> {
+    /// Attempts to send a message into the channel without blocking.
+    ///
+    /// This method will either send a message into the channel immediately
+    /// or return an error if the channel is full or disconnected. The
+    /// returned error contains the original message.
+    ///
+    /// If called on a zero-capacity channel, this method will send the
+    /// message only if there happens to be a receive operation on the other
+    /// side of the channel at the same time.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.try_send(msg),
+            SenderFlavor::List(ref chan) => chan.try_send(msg),
+            SenderFlavor::Zero(ref chan) => chan.try_send(msg),
+        }
+    }
+
+    /// Blocks the current thread until a message is sent or the channel is
+    /// disconnected.
+    ///
+    /// If the channel is full and not disconnected, this call will block
+    /// until the send operation can proceed. If the channel becomes
+    /// disconnected, this call will wake up and return an error. The returned
+    /// error contains the original message.
+    ///
+    /// If called on a zero-capacity channel, this method will wait for a
+    /// receive operation to appear on the other side of the channel.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        match self.send_inner(msg, None) {
+            Ok(()) => Ok(()),
+            Err(SendTimeoutError::Disconnected(msg)) => Err(SendError(msg)),
+            Err(SendTimeoutError::Timeout(_)) => unreachable!(),
+        }
+    }
+
+    /// Waits for a message to be sent into the channel, but only for a
+    /// limited time.
+    ///
+    /// If the channel is full and not disconnected, this call will block
+    /// until the send operation can proceed or the operation times out. If
+    /// the channel becomes disconnected, this call will wake up and return
+    /// an error. The returned error contains the original message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpmc::{sync_channel, SendTimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let (tx, rx) = sync_channel(1);
+    /// tx.send(1).unwrap();
+    ///
+    /// assert_eq!(tx.send_timeout(2, Duration::from_millis(10)),
+    ///            Err(SendTimeoutError::Timeout(2)));
+    /// drop(rx);
+    /// assert_eq!(tx.send_timeout(3, Duration::from_millis(10)),
+    ///            Err(SendTimeoutError::Disconnected(3)));
+    /// ```
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn send_timeout(&self, msg: T, timeout: Duration) -> Result<(), SendTimeoutError<T>> {
+        self.send_deadline(msg, Instant::now() + timeout)
+    }
+
+    /// Waits for a message to be sent into the channel, but only until a
+    /// given deadline.
+    ///
+    /// If the channel is full and not disconnected, this call will block
+    /// until the send operation can proceed or the deadline is reached. If
+    /// the channel becomes disconnected, this call will wake up and return
+    /// an error. The returned error contains the original message.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn send_deadline(&self, msg: T, deadline: Instant) -> Result<(), SendTimeoutError<T>> {
+        self.send_inner(msg, Some(deadline))
+    }
+
+    fn send_inner(&self, msg: T, deadline: Option<Instant>) -> Result<(), SendTimeoutError<T>> {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.send(msg, deadline),
+            SenderFlavor::List(ref chan) => chan.send(msg, deadline),
+            SenderFlavor::Zero(ref chan) => chan.send(msg, deadline),
+        }
+    }
+
+    /// Returns `true` if the channel is empty.
+    ///
+    /// Note: Zero-capacity channels are always empty.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn is_empty(&self) -> bool {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.is_empty(),
+            SenderFlavor::List(ref chan) => chan.is_empty(),
+            SenderFlavor::Zero(ref chan) => chan.is_empty(),
+        }
+    }
+
+    /// Returns `true` if the channel is full.
+    ///
+    /// Note: Zero-capacity channels are always full.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn is_full(&self) -> bool {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.is_full(),
+            SenderFlavor::List(ref chan) => chan.is_full(),
+            SenderFlavor::Zero(ref chan) => chan.is_full(),
+        }
+    }
+
+    /// Returns the number of messages in the channel.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn len(&self) -> usize {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.len(),
+            SenderFlavor::List(ref chan) => chan.len(),
+            SenderFlavor::Zero(ref chan) => chan.len(),
+        }
+    }
+
+    /// If the channel is bounded, returns its capacity.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn capacity(&self) -> Option<usize> {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.capacity(),
+            SenderFlavor::List(ref chan) => chan.capacity(),
+            SenderFlavor::Zero(ref chan) => chan.capacity(),
+        }
+    }
+
+    /// Returns `true` if senders belong to the same channel.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn same_channel(&self, other: &Sender<T>) -> bool {
+        match (&self.flavor, &other.flavor) {
+            (&SenderFlavor::Array(ref a), &SenderFlavor::Array(ref b)) => a == b,
+            (&SenderFlavor::List(ref a), &SenderFlavor::List(ref b)) => a == b,
+            (&SenderFlavor::Zero(ref a), &SenderFlavor::Zero(ref b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Completes a send operation selected by `Select`.
+    unsafe fn write(&self, token: &mut Token, msg: T) -> Result<(), T> {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.write(token, msg),
+            SenderFlavor::List(ref chan) => chan.write(token, msg),
+            SenderFlavor::Zero(ref chan) => chan.write(token, msg),
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        unsafe {
+            match self.flavor {
+                SenderFlavor::Array(ref chan) => chan.release(|c| c.disconnect()),
+                SenderFlavor::List(ref chan) => chan.release(|c| c.disconnect()),
+                SenderFlavor::Zero(ref chan) => chan.release(|c| c.disconnect()),
+            }
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        let flavor = match self.flavor {
+            SenderFlavor::Array(ref chan) => SenderFlavor::Array(chan.acquire()),
+            SenderFlavor::List(ref chan) => SenderFlavor::List(chan.acquire()),
+            SenderFlavor::Zero(ref chan) => SenderFlavor::Zero(chan.acquire()),
+        };
+
+        Sender { flavor }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+impl<T> SelectHandle for Sender<T> {
+    fn try_select(&self, token: &mut Token) -> bool {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.sender().try_select(token),
+            SenderFlavor::List(ref chan) => chan.sender().try_select(token),
+            SenderFlavor::Zero(ref chan) => chan.sender().try_select(token),
+        }
+    }
+
+    fn register(&self, oper: Operation, cx: &Context) -> bool {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.sender().register(oper, cx),
+            SenderFlavor::List(ref chan) => chan.sender().register(oper, cx),
+            SenderFlavor::Zero(ref chan) => chan.sender().register(oper, cx),
+        }
+    }
+
+    fn unregister(&self, oper: Operation) {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.sender().unregister(oper),
+            SenderFlavor::List(ref chan) => chan.sender().unregister(oper),
+            SenderFlavor::Zero(ref chan) => chan.sender().unregister(oper),
+        }
+    }
+
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.sender().accept(token, cx),
+            SenderFlavor::List(ref chan) => chan.sender().accept(token, cx),
+            SenderFlavor::Zero(ref chan) => chan.sender().accept(token, cx),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self.flavor {
+            SenderFlavor::Array(ref chan) => chan.sender().is_ready(),
+            SenderFlavor::List(ref chan) => chan.sender().is_ready(),
+            SenderFlavor::Zero(ref chan) => chan.sender().is_ready(),
+        }
+    }
+}
+
+/// The receiving side of a channel.
+///
+/// Receivers can be cloned and shared among threads; each message is
+/// delivered to exactly one of them. The channel is disconnected once the
+/// last receiver is dropped.
+#[unstable(feature = "mpmc_channel", issue = "0")]
+pub struct Receiver<T> {
+    flavor: ReceiverFlavor<T>,
+}
+
+/// Receiver flavors.
+enum ReceiverFlavor<T> {
+    /// Bounded channel based on a preallocated array.
+    Array(counter::Receiver<array::Channel<T>>),
+
+    /// Unbounded channel implemented as a linked list.
+    List(counter::Receiver<list::Channel<T>>),
+
+    /// Zero-capacity channel.
+    Zero(counter::Receiver<zero::Channel<T>>),
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+unsafe impl<T: Send> Send for Receiver<T> {}
+#[unstable(feature = "mpmc_channel", issue = "0")]
+unsafe impl<T: Send> Sync for Receiver<T> {}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive a message from the channel without blocking.
+    ///
+    /// This method will either receive a message from the channel
+    /// immediately or return an error if the channel is empty.
+    ///
+    /// If called on a zero-capacity channel, this method will receive a
+    /// message only if there happens to be a send operation on the other
+    /// side of the channel at the same time.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.try_recv(),
+            ReceiverFlavor::List(ref chan) => chan.try_recv(),
+            ReceiverFlavor::Zero(ref chan) => chan.try_recv(),
+        }
+    }
+
+    /// Blocks the current thread until a message is received or the channel
+    /// is empty and disconnected.
+    ///
+    /// If the channel is empty and not disconnected, this call will block
+    /// until the receive operation can proceed. If the channel is empty and
+    /// becomes disconnected, this call will wake up and return an error.
+    ///
+    /// If called on a zero-capacity channel, this method will wait for a
+    /// send operation to appear on the other side of the channel.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn recv(&self) -> Result<T, RecvError> {
+        match self.recv_inner(None) {
+            Ok(msg) => Ok(msg),
+            Err(RecvTimeoutError::Disconnected) => Err(RecvError),
+            Err(RecvTimeoutError::Timeout) => unreachable!(),
+        }
+    }
+
+    /// Waits for a message to be received from the channel, but only for a
+    /// limited time.
+    ///
+    /// If the channel is empty and not disconnected, this call will block
+    /// until the receive operation can proceed or the operation times out.
+    /// If the channel is empty and becomes disconnected, this call will wake
+    /// up and return an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpmc::{channel, RecvTimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    ///
+    /// assert_eq!(rx.recv_timeout(Duration::from_millis(10)),
+    ///            Err(RecvTimeoutError::Timeout));
+    /// drop(tx);
+    /// assert_eq!(rx.recv_timeout(Duration::from_millis(10)),
+    ///            Err(RecvTimeoutError::Disconnected));
+    /// ```
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.recv_deadline(Instant::now() + timeout)
+    }
+
+    /// Waits for a message to be received from the channel, but only before
+    /// a given deadline.
+    ///
+    /// If the channel is empty and not disconnected, this call will block
+    /// until the receive operation can proceed or the deadline is reached.
+    /// If the channel is empty and becomes disconnected, this call will wake
+    /// up and return an error.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<T, RecvTimeoutError> {
+        self.recv_inner(Some(deadline))
+    }
+
+    fn recv_inner(&self, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.recv(deadline),
+            ReceiverFlavor::List(ref chan) => chan.recv(deadline),
+            ReceiverFlavor::Zero(ref chan) => chan.recv(deadline),
+        }
+    }
+
+    /// Returns an iterator that will block waiting for messages, but never
+    /// panic. It will return `None` when the channel is empty and
+    /// disconnected.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn iter(&self) -> Iter<T> {
+        Iter { rx: self }
+    }
+
+    /// Returns an iterator that will attempt to yield all pending messages
+    /// without blocking.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn try_iter(&self) -> TryIter<T> {
+        TryIter { rx: self }
+    }
+
+    /// Returns `true` if the channel is empty.
+    ///
+    /// Note: Zero-capacity channels are always empty.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn is_empty(&self) -> bool {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.is_empty(),
+            ReceiverFlavor::List(ref chan) => chan.is_empty(),
+            ReceiverFlavor::Zero(ref chan) => chan.is_empty(),
+        }
+    }
+
+    /// Returns `true` if the channel is full.
+    ///
+    /// Note: Zero-capacity channels are always full.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn is_full(&self) -> bool {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.is_full(),
+            ReceiverFlavor::List(ref chan) => chan.is_full(),
+            ReceiverFlavor::Zero(ref chan) => chan.is_full(),
+        }
+    }
+
+    /// Returns the number of messages in the channel.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn len(&self) -> usize {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.len(),
+            ReceiverFlavor::List(ref chan) => chan.len(),
+            ReceiverFlavor::Zero(ref chan) => chan.len(),
+        }
+    }
+
+    /// If the channel is bounded, returns its capacity.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn capacity(&self) -> Option<usize> {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.capacity(),
+            ReceiverFlavor::List(ref chan) => chan.capacity(),
+            ReceiverFlavor::Zero(ref chan) => chan.capacity(),
+        }
+    }
+
+    /// Returns `true` if receivers belong to the same channel.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn same_channel(&self, other: &Receiver<T>) -> bool {
+        match (&self.flavor, &other.flavor) {
+            (&ReceiverFlavor::Array(ref a), &ReceiverFlavor::Array(ref b)) => a == b,
+            (&ReceiverFlavor::List(ref a), &ReceiverFlavor::List(ref b)) => a == b,
+            (&ReceiverFlavor::Zero(ref a), &ReceiverFlavor::Zero(ref b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Completes a receive operation selected by `Select`.
+    unsafe fn read(&self, token: &mut Token) -> Result<T, ()> {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.read(token),
+            ReceiverFlavor::List(ref chan) => chan.read(token),
+            ReceiverFlavor::Zero(ref chan) => chan.read(token),
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        unsafe {
+            match self.flavor {
+                ReceiverFlavor::Array(ref chan) => chan.release(|c| c.disconnect()),
+                ReceiverFlavor::List(ref chan) => chan.release(|c| c.disconnect()),
+                ReceiverFlavor::Zero(ref chan) => chan.release(|c| c.disconnect()),
+            }
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let flavor = match self.flavor {
+            ReceiverFlavor::Array(ref chan) => ReceiverFlavor::Array(chan.acquire()),
+            ReceiverFlavor::List(ref chan) => ReceiverFlavor::List(chan.acquire()),
+            ReceiverFlavor::Zero(ref chan) => ReceiverFlavor::Zero(chan.acquire()),
+        };
+
+        Receiver { flavor }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+impl<T> SelectHandle for Receiver<T> {
+    fn try_select(&self, token: &mut Token) -> bool {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.receiver().try_select(token),
+            ReceiverFlavor::List(ref chan) => chan.receiver().try_select(token),
+            ReceiverFlavor::Zero(ref chan) => chan.receiver().try_select(token),
+        }
+    }
+
+    fn register(&self, oper: Operation, cx: &Context) -> bool {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.receiver().register(oper, cx),
+            ReceiverFlavor::List(ref chan) => chan.receiver().register(oper, cx),
+            ReceiverFlavor::Zero(ref chan) => chan.receiver().register(oper, cx),
+        }
+    }
+
+    fn unregister(&self, oper: Operation) {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.receiver().unregister(oper),
+            ReceiverFlavor::List(ref chan) => chan.receiver().unregister(oper),
+            ReceiverFlavor::Zero(ref chan) => chan.receiver().unregister(oper),
+        }
+    }
+
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.receiver().accept(token, cx),
+            ReceiverFlavor::List(ref chan) => chan.receiver().accept(token, cx),
+            ReceiverFlavor::Zero(ref chan) => chan.receiver().accept(token, cx),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self.flavor {
+            ReceiverFlavor::Array(ref chan) => chan.receiver().is_ready(),
+            ReceiverFlavor::List(ref chan) => chan.receiver().is_ready(),
+            ReceiverFlavor::Zero(ref chan) => chan.receiver().is_ready(),
+        }
+    }
+}
+
+/// An iterator over messages on a [`Receiver`], created by [`iter`].
+///
+/// This iterator will block whenever [`next`] is called, waiting for a new
+/// message, and [`None`] will be returned when the channel is empty and
+/// disconnected.
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`iter`]: struct.Receiver.html#method.iter
+/// [`next`]: ../../../std/iter/trait.Iterator.html#tymethod.next
+/// [`None`]: ../../../std/option/enum.Option.html#variant.None
+#[unstable(feature = "mpmc_channel", issue = "0")]
+#[derive(Debug)]
+pub struct Iter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An iterator that attempts to yield all pending messages for a
+/// [`Receiver`], created by [`try_iter`].
+///
+/// [`None`] will be returned when there are no pending messages remaining
+/// or if the channel is disconnected.
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`try_iter`]: struct.Receiver.html#method.try_iter
+/// [`None`]: ../../../std/option/enum.Option.html#variant.None
+#[unstable(feature = "mpmc_channel", issue = "0")]
+#[derive(Debug)]
+pub struct TryIter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An owning iterator over messages on a [`Receiver`], created by
+/// **Receiver::into_iter**.
+///
+/// [`Receiver`]: struct.Receiver.html
+#[unstable(feature = "mpmc_channel", issue = "0")]
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rx: Receiver<T>,
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<'a, T> Iterator for TryIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.try_recv().ok()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { rx: self }
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests {
+    use super::*;
+    use sync::Arc;
+    use sync::atomic::{AtomicUsize, Ordering};
+    use thread;
+    use time::{Duration, Instant};
+
+    #[test]
+    fn smoke() {
+        let (tx, rx) = channel::<i32>();
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1);
+
+        let (tx, rx) = sync_channel::<i32>(1);
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn smoke_zero() {
+        let (tx, rx) = sync_channel::<i32>(0);
+        assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+        let t = thread::spawn(move || {
+            tx.send(2).unwrap();
+        });
+        assert_eq!(rx.recv().unwrap(), 2);
+        t.join().unwrap();
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn len_and_capacity() {
+        let (tx, rx) = channel::<i32>();
+        assert_eq!(tx.capacity(), None);
+        for i in 0..100 {
+            tx.send(i).unwrap();
+        }
+        assert_eq!(rx.len(), 100);
+        assert!(!rx.is_full());
+
+        let (tx, rx) = sync_channel::<i32>(2);
+        assert_eq!(rx.capacity(), Some(2));
+        assert!(tx.is_empty());
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(tx.len(), 2);
+        assert!(tx.is_full());
+        assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+
+        let (tx, _rx) = sync_channel::<i32>(0);
+        assert_eq!(tx.capacity(), Some(0));
+        assert!(tx.is_empty() && tx.is_full());
+    }
+
+    #[test]
+    fn same_channel() {
+        let (tx1, rx1) = channel::<i32>();
+        let (tx2, _rx2) = channel::<i32>();
+        assert!(tx1.same_channel(&tx1.clone()));
+        assert!(!tx1.same_channel(&tx2));
+        assert!(rx1.same_channel(&rx1.clone()));
+    }
+
+    #[test]
+    fn disconnected() {
+        let (tx, rx) = channel::<i32>();
+        tx.send(1).unwrap();
+        drop(tx);
+        assert_eq!(rx.try_recv(), Ok(1));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+
+        let (tx, rx) = sync_channel::<i32>(1);
+        drop(rx);
+        assert_eq!(tx.send(1), Err(SendError(1)));
+        assert_eq!(tx.try_send(2), Err(TrySendError::Disconnected(2)));
+    }
+
+    #[test]
+    fn recv_timeout() {
+        for &cap in &[None, Some(0), Some(1)] {
+            let (tx, rx) = match cap {
+                None => channel::<i32>(),
+                Some(cap) => sync_channel::<i32>(cap),
+            };
+            let start = Instant::now();
+            assert_eq!(rx.recv_timeout(Duration::from_millis(10)),
+                       Err(RecvTimeoutError::Timeout));
+            assert!(start.elapsed() >= Duration::from_millis(10));
+
+            let t = thread::spawn(move || {
+                tx.send(1).unwrap();
+            });
+            assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(1));
+            t.join().unwrap();
+            assert_eq!(rx.recv_timeout(Duration::from_millis(10)),
+                       Err(RecvTimeoutError::Disconnected));
+        }
+    }
+
+    #[test]
+    fn send_timeout() {
+        let (tx, rx) = sync_channel::<i32>(1);
+        tx.send(1).unwrap();
+        assert_eq!(tx.send_timeout(2, Duration::from_millis(10)),
+                   Err(SendTimeoutError::Timeout(2)));
+
+        let t = thread::spawn(move || {
+            assert_eq!(rx.recv(), Ok(1));
+            assert_eq!(rx.recv(), Ok(3));
+        });
+        tx.send_timeout(3, Duration::from_secs(10)).unwrap();
+        t.join().unwrap();
+        assert_eq!(tx.send_timeout(4, Duration::from_millis(10)),
+                   Err(SendTimeoutError::Disconnected(4)));
+    }
+
+    #[test]
+    fn drops_pending_messages() {
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let (tx, _rx) = channel();
+            for _ in 0..100 {
+                tx.send(DropCounter(drops.clone())).unwrap();
+            }
+            let (tx, rx) = sync_channel(10);
+            for _ in 0..10 {
+                tx.send(DropCounter(drops.clone())).unwrap();
+            }
+            drop(rx.recv().unwrap());
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 110);
+    }
+
+    #[test]
+    fn multiple_consumers() {
+        const THREADS: usize = 4;
+        const COUNT: usize = 10_000;
+
+        for &cap in &[None, Some(0), Some(1), Some(100)] {
+            let (tx, rx) = match cap {
+                None => channel::<usize>(),
+                Some(cap) => sync_channel::<usize>(cap),
+            };
+            let sum = Arc::new(AtomicUsize::new(0));
+
+            let consumers: Vec<_> = (0..THREADS).map(|_| {
+                let rx = rx.clone();
+                let sum = sum.clone();
+                thread::spawn(move || {
+                    for i in rx.iter() {
+                        sum.fetch_add(i, Ordering::SeqCst);
+                    }
+                })
+            }).collect();
+            drop(rx);
+
+            let producers: Vec<_> = (0..THREADS).map(|_| {
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    for i in 0..COUNT {
+                        tx.send(i).unwrap();
+                    }
+                })
+            }).collect();
+            drop(tx);
+
+            for t in producers.into_iter().chain(consumers) {
+                t.join().unwrap();
+            }
+            assert_eq!(sum.load(Ordering::SeqCst), THREADS * COUNT * (COUNT - 1) / 2);
+        }
+    }
+
+    #[test]
+    fn try_iter_does_not_block() {
+        let (tx, rx) = channel::<i32>();
+        assert!(rx.try_iter().next().is_none());
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn select_recv() {
+        let (tx1, rx1) = channel::<i32>();
+        let (tx2, rx2) = sync_channel::<i32>(0);
+
+        let t = thread::spawn(move || {
+            tx2.send(2).unwrap();
+            drop(tx1);
+        });
+
+        let mut sel = Select::new();
+        let oper1 = sel.recv(&rx1);
+        let oper2 = sel.recv(&rx2);
+
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper2);
+        assert_eq!(oper.recv(&rx2), Ok(2));
+
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper1);
+        assert_eq!(oper.recv(&rx1), Err(RecvError));
+        t.join().unwrap();
+    }
+
+    #[test]
+    fn select_send() {
+        let (tx1, rx1) = sync_channel::<i32>(1);
+        let (tx2, _rx2) = sync_channel::<i32>(0);
+        tx1.send(0).unwrap();
+
+        let mut sel = Select::new();
+        sel.send(&tx1);
+        sel.send(&tx2);
+        assert!(sel.try_select().is_err());
+        assert!(sel.select_timeout(Duration::from_millis(10)).is_err());
+
+        assert_eq!(rx1.recv(), Ok(0));
+        let oper = sel.select();
+        assert_eq!(oper.index(), 0);
+        oper.send(&tx1, 1).unwrap();
+        assert_eq!(rx1.recv(), Ok(1));
+    }
+
+    #[test]
+    fn select_remove() {
+        let (tx1, rx1) = channel::<i32>();
+        let (tx2, rx2) = channel::<i32>();
+        drop(tx1);
+        tx2.send(2).unwrap();
+
+        let mut sel = Select::new();
+        let oper1 = sel.recv(&rx1);
+        let oper2 = sel.recv(&rx2);
+        sel.remove(oper1);
+
+        let oper = sel.try_select().unwrap();
+        assert_eq!(oper.index(), oper2);
+        assert_eq!(oper.recv(&rx2), Ok(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn select_drop_without_completing() {
+        let (tx, rx) = channel::<i32>();
+        tx.send(1).unwrap();
+        let mut sel = Select::new();
+        sel.recv(&rx);
+        let _oper = sel.select();
+    }
+}
@@ -0,0 +1,712 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Selection over a set of channel operations.
+
+use fmt;
+use marker::PhantomData;
+use mem;
+use time::{Duration, Instant};
+
+use super::{Receiver, Sender, RecvError, SendError, SelectTimeoutError, TrySelectError};
+use super::array::ArrayToken;
+use super::context::Context;
+use super::list::ListToken;
+use super::utils;
+use super::zero::ZeroToken;
+
+/// Temporary data that gets initialized during select or a blocking
+/// operation, and is consumed by `read` or `write`.
+///
+/// Each field contains data associated with a specific channel flavor.
+#[derive(Debug, Default)]
+pub struct Token {
+    pub array: ArrayToken,
+    pub list: ListToken,
+    pub zero: ZeroToken,
+}
+
+/// Identifier associated with an operation by a specific thread on a
+/// specific channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operation(usize);
+
+impl Operation {
+    /// Creates an operation identifier from a mutable reference.
+    ///
+    /// This function essentially just turns the address of the reference
+    /// into a number. The reference should point to a variable that is
+    /// specific to the thread and the operation, and is alive for the entire
+    /// duration of select or blocking operation.
+    #[inline]
+    pub fn hook<T>(r: &mut T) -> Operation {
+        let val = r as *mut T as usize;
+        // Make sure that the pointer address doesn't equal the numerical
+        // representation of `Selected::{Waiting, Aborted, Disconnected}`.
+        assert!(val > 2);
+        Operation(val)
+    }
+}
+
+/// Current state of a select or a blocking operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selected {
+    /// Still waiting for an operation.
+    Waiting,
+
+    /// The attempt to block the current thread has been aborted.
+    Aborted,
+
+    /// An operation became ready because a channel is disconnected.
+    Disconnected,
+
+    /// An operation became ready because a message can be sent or received.
+    Operation(Operation),
+}
+
+impl From<usize> for Selected {
+    #[inline]
+    fn from(val: usize) -> Selected {
+        match val {
+            0 => Selected::Waiting,
+            1 => Selected::Aborted,
+            2 => Selected::Disconnected,
+            oper => Selected::Operation(Operation(oper)),
+        }
+    }
+}
+
+impl Into<usize> for Selected {
+    #[inline]
+    fn into(self) -> usize {
+        match self {
+            Selected::Waiting => 0,
+            Selected::Aborted => 1,
+            Selected::Disconnected => 2,
+            Selected::Operation(Operation(val)) => val,
+        }
+    }
+}
+
+/// A receiver or a sender that can participate in select.
+///
+/// This is a handle that assists select in executing an operation,
+/// registration, deciding on the appropriate deadline for blocking, etc.
+pub trait SelectHandle {
+    /// Attempts to select an operation and returns `true` on success.
+    fn try_select(&self, token: &mut Token) -> bool;
+
+    /// Registers an operation for execution and returns `true` if it is
+    /// ready.
+    fn register(&self, oper: Operation, cx: &Context) -> bool;
+
+    /// Unregisters an operation for execution.
+    fn unregister(&self, oper: Operation);
+
+    /// Attempts to select an operation the thread got woken up for and
+    /// returns `true` on success.
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool;
+
+    /// Returns `true` if an operation can be executed without blocking.
+    fn is_ready(&self) -> bool;
+}
+
+impl<'a, T: SelectHandle + ?Sized> SelectHandle for &'a T {
+    fn try_select(&self, token: &mut Token) -> bool {
+        (**self).try_select(token)
+    }
+
+    fn register(&self, oper: Operation, cx: &Context) -> bool {
+        (**self).register(oper, cx)
+    }
+
+    fn unregister(&self, oper: Operation) {
+        (**self).unregister(oper);
+    }
+
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool {
+        (**self).accept(token, cx)
+    }
+
+    fn is_ready(&self) -> bool {
+        (**self).is_ready()
+    }
+}
+
+/// Determines when a select operation should time out.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Timeout {
+    /// No blocking.
+    Now,
+
+    /// Block forever.
+    Never,
+
+    /// Time out after the time instant.
+    At(Instant),
+}
+
+/// Runs until one of the operations is selected, potentially blocking the
+/// current thread.
+///
+/// Successful receive operations will have to be followed up by
+/// `Receiver::read()` and successful send operations by `Sender::write()`.
+fn run_select(handles: &mut [(&SelectHandle, usize, *const u8)],
+              timeout: Timeout)
+              -> Option<(Token, usize, *const u8)> {
+    if handles.is_empty() {
+        // Wait until the timeout and return.
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {
+                loop {
+                    ::thread::park();
+                }
+            }
+            Timeout::At(when) => {
+                let now = Instant::now();
+                if now < when {
+                    ::thread::sleep(when - now);
+                }
+                return None;
+            }
+        }
+    }
+
+    // Shuffle the operations for fairness.
+    utils::shuffle(handles);
+
+    // Create a token, which serves as a temporary variable that gets
+    // initialized in this function and is later used by a call to
+    // `channel::read()` or `channel::write()` that completes the selected
+    // operation.
+    let mut token = Token::default();
+
+    // Try selecting one of the operations without blocking.
+    for &(handle, i, ptr) in handles.iter() {
+        if handle.try_select(&mut token) {
+            return Some((token, i, ptr));
+        }
+    }
+
+    loop {
+        // Prepare for blocking.
+        let res = Context::with(|cx| {
+            let mut sel = Selected::Waiting;
+            let mut registered_count = 0;
+
+            // Begin watching all operations.
+            for entry in handles.iter_mut() {
+                registered_count += 1;
+                let handle = entry.0;
+
+                // If registration returns `false`, that means the operation
+                // has just become ready.
+                if handle.register(Operation::hook(entry), cx) {
+                    // Try aborting select.
+                    sel = match cx.try_select(Selected::Aborted) {
+                        Ok(()) => Selected::Aborted,
+                        Err(s) => s,
+                    };
+                    break;
+                }
+
+                // If another thread has already selected one of the
+                // operations, stop registration.
+                sel = cx.selected();
+                if sel != Selected::Waiting {
+                    break;
+                }
+            }
+
+            if sel == Selected::Waiting {
+                // Block the current thread.
+                let deadline = match timeout {
+                    Timeout::Now => Some(Instant::now()),
+                    Timeout::Never => None,
+                    Timeout::At(when) => Some(when),
+                };
+                sel = cx.wait_until(deadline);
+            }
+
+            // Unwatch all operations.
+            for entry in handles.iter_mut().take(registered_count) {
+                let handle = entry.0;
+                handle.unregister(Operation::hook(entry));
+            }
+
+            match sel {
+                Selected::Waiting => unreachable!(),
+                Selected::Aborted => {}
+                Selected::Disconnected => {}
+                Selected::Operation(_) => {
+                    // Find the selected operation.
+                    for entry in handles.iter_mut() {
+                        let (handle, i, ptr) = *entry;
+                        // Is this the selected operation?
+                        if sel == Selected::Operation(Operation::hook(entry)) {
+                            // Try selecting this operation.
+                            if handle.accept(&mut token, cx) {
+                                return Some((i, ptr));
+                            }
+                        }
+                    }
+                }
+            }
+
+            None
+        });
+
+        // Return if an operation was selected.
+        if let Some((i, ptr)) = res {
+            return Some((token, i, ptr));
+        }
+
+        // Try selecting one of the operations without blocking.
+        for &(handle, i, ptr) in handles.iter() {
+            if handle.try_select(&mut token) {
+                return Some((token, i, ptr));
+            }
+        }
+
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {}
+            Timeout::At(when) => {
+                if Instant::now() >= when {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Selects from a set of channel operations.
+///
+/// `Select` allows you to define a set of channel operations, wait until any
+/// one of them becomes ready, and finally execute it. If multiple operations
+/// are ready at the same time, a random one among them is selected.
+///
+/// An operation is considered to be ready if it doesn't have to block. Note
+/// that it is ready even when it will simply return an error because the
+/// channel is disconnected.
+///
+/// The [`select`] method will block until an operation is selected, and
+/// returns a [`SelectedOperation`] which *must* then be completed by calling
+/// its [`send`] or [`recv`] method with the channel it was registered for.
+/// Dropping a `SelectedOperation` without completing it panics.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpmc::{channel, Select};
+/// use std::thread;
+///
+/// let (s1, r1) = channel();
+/// let (s2, r2) = channel();
+///
+/// thread::spawn(move || s1.send(10).unwrap());
+/// thread::spawn(move || s2.send(20).unwrap());
+///
+/// let mut sel = Select::new();
+/// let oper1 = sel.recv(&r1);
+/// let oper2 = sel.recv(&r2);
+///
+/// // Both operations are initially not ready, so this blocks until one of
+/// // the two messages arrives.
+/// let oper = sel.select();
+/// match oper.index() {
+///     i if i == oper1 => assert_eq!(oper.recv(&r1), Ok(10)),
+///     i if i == oper2 => assert_eq!(oper.recv(&r2), Ok(20)),
+///     _ => unreachable!(),
+/// }
+/// ```
+///
+/// [`select`]: struct.Select.html#method.select
+/// [`SelectedOperation`]: struct.SelectedOperation.html
+/// [`send`]: struct.SelectedOperation.html#method.send
+/// [`recv`]: struct.SelectedOperation.html#method.recv
+#[unstable(feature = "mpmc_channel", issue = "0")]
+pub struct Select<'a> {
+    /// A list of senders and receivers participating in selection.
+    handles: Vec<(&'a SelectHandle, usize, *const u8)>,
+
+    /// The next index to assign to an operation.
+    next_index: usize,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty list of channel operations for selection.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn new() -> Select<'a> {
+        Select {
+            handles: Vec::with_capacity(4),
+            next_index: 0,
+        }
+    }
+
+    /// Adds a send operation.
+    ///
+    /// Returns the index of the added operation.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn send<T>(&mut self, s: &'a Sender<T>) -> usize {
+        let i = self.next_index;
+        let ptr = s as *const Sender<_> as *const u8;
+        self.handles.push((s, i, ptr));
+        self.next_index += 1;
+        i
+    }
+
+    /// Adds a receive operation.
+    ///
+    /// Returns the index of the added operation.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn recv<T>(&mut self, r: &'a Receiver<T>) -> usize {
+        let i = self.next_index;
+        let ptr = r as *const Receiver<_> as *const u8;
+        self.handles.push((r, i, ptr));
+        self.next_index += 1;
+        i
+    }
+
+    /// Removes a previously added operation.
+    ///
+    /// This is useful when an operation is selected because the channel got
+    /// disconnected and we want to try again to select a different operation
+    /// instead.
+    ///
+    /// # Panics
+    ///
+    /// An attempt to remove a non-existing or already removed operation will
+    /// panic.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn remove(&mut self, index: usize) {
+        assert!(index < self.next_index,
+                "index out of bounds; {} >= {}",
+                index,
+                self.next_index);
+
+        let i = self.handles
+            .iter()
+            .position(|&(_, i, _)| i == index)
+            .expect("no operation with this index");
+
+        self.handles.swap_remove(i);
+    }
+
+    /// Attempts to select one of the operations without blocking.
+    ///
+    /// If an operation is ready, it is selected and returned. If multiple
+    /// operations are ready at the same time, a random one among them is
+    /// selected. If none of the operations are ready, an error is returned.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn try_select(&mut self) -> Result<SelectedOperation<'a>, TrySelectError> {
+        match run_select(&mut self.handles, Timeout::Now) {
+            None => Err(TrySelectError),
+            Some((token, index, ptr)) => Ok(SelectedOperation::new(token, index, ptr)),
+        }
+    }
+
+    /// Blocks until one of the operations becomes ready and selects it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operations have been added to `Select`.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        if self.handles.is_empty() {
+            panic!("no operations have been added to `Select`");
+        }
+
+        let (token, index, ptr) = run_select(&mut self.handles, Timeout::Never).unwrap();
+        SelectedOperation::new(token, index, ptr)
+    }
+
+    /// Blocks for a limited time until one of the operations becomes ready
+    /// and selects it.
+    ///
+    /// If the timeout elapses before an operation becomes ready, an error
+    /// is returned.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn select_timeout(&mut self, timeout: Duration)
+                          -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        self.select_deadline(Instant::now() + timeout)
+    }
+
+    /// Blocks until a given deadline, or until one of the operations becomes
+    /// ready and selects it.
+    ///
+    /// If the deadline is reached before an operation becomes ready, an
+    /// error is returned.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn select_deadline(&mut self, deadline: Instant)
+                           -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        match run_select(&mut self.handles, Timeout::At(deadline)) {
+            None => Err(SelectTimeoutError),
+            Some((token, index, ptr)) => Ok(SelectedOperation::new(token, index, ptr)),
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+unsafe impl<'a> Send for Select<'a> {}
+#[unstable(feature = "mpmc_channel", issue = "0")]
+unsafe impl<'a> Sync for Select<'a> {}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<'a> Clone for Select<'a> {
+    fn clone(&self) -> Select<'a> {
+        Select {
+            handles: self.handles.clone(),
+            next_index: self.next_index,
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<'a> Default for Select<'a> {
+    fn default() -> Select<'a> {
+        Select::new()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<'a> fmt::Debug for Select<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("Select { .. }")
+    }
+}
+
+/// A selected operation that needs to be completed.
+///
+/// To complete the operation, call [`send`] or [`recv`].
+///
+/// # Panics
+///
+/// Forgetting to complete the operation is an error and might lead to
+/// deadlocks. If a `SelectedOperation` is dropped without completion, a
+/// panic occurs.
+///
+/// [`send`]: struct.SelectedOperation.html#method.send
+/// [`recv`]: struct.SelectedOperation.html#method.recv
+#[unstable(feature = "mpmc_channel", issue = "0")]
+#[must_use]
+pub struct SelectedOperation<'a> {
+    /// Token needed to complete the operation.
+    token: Token,
+
+    /// The index of the selected operation.
+    index: usize,
+
+    /// The address of the selected `Sender` or `Receiver`.
+    ptr: *const u8,
+
+    /// Indicates that `Sender`s and `Receiver`s are borrowed.
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a> SelectedOperation<'a> {
+    fn new(token: Token, index: usize, ptr: *const u8) -> SelectedOperation<'a> {
+        SelectedOperation {
+            token,
+            index,
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the index of the selected operation.
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Completes the send operation.
+    ///
+    /// The passed [`Sender`] reference must be the same one that was used in
+    /// [`Select::send`] when the operation was added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an incorrect [`Sender`] reference is passed.
+    ///
+    /// [`Sender`]: struct.Sender.html
+    /// [`Select::send`]: struct.Select.html#method.send
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn send<T>(mut self, s: &Sender<T>, msg: T) -> Result<(), SendError<T>> {
+        assert!(s as *const Sender<T> as *const u8 == self.ptr,
+                "passed a sender that wasn't selected");
+        let res = unsafe { s.write(&mut self.token, msg) };
+        mem::forget(self);
+        res.map_err(SendError)
+    }
+
+    /// Completes the receive operation.
+    ///
+    /// The passed [`Receiver`] reference must be the same one that was used
+    /// in [`Select::recv`] when the operation was added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an incorrect [`Receiver`] reference is passed.
+    ///
+    /// [`Receiver`]: struct.Receiver.html
+    /// [`Select::recv`]: struct.Select.html#method.recv
+    #[unstable(feature = "mpmc_channel", issue = "0")]
+    pub fn recv<T>(mut self, r: &Receiver<T>) -> Result<T, RecvError> {
+        assert!(r as *const Receiver<T> as *const u8 == self.ptr,
+                "passed a receiver that wasn't selected");
+        let res = unsafe { r.read(&mut self.token) };
+        mem::forget(self);
+        res.map_err(|_| RecvError)
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<'a> fmt::Debug for SelectedOperation<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("SelectedOperation { .. }")
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "0")]
+impl<'a> Drop for SelectedOperation<'a> {
+    fn drop(&mut self) {
+        panic!("dropped `SelectedOperation` without completing the operation");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sync::mpmc::*;
+    use thread;
+    use time::{Duration, Instant};
+
+    #[test]
+    fn try_select_nothing_ready() {
+        let (_tx1, rx1) = channel::<i32>();
+        let (_tx2, rx2) = sync_channel::<i32>(0);
+
+        let mut sel = Select::new();
+        sel.recv(&rx1);
+        sel.recv(&rx2);
+        assert_eq!(sel.try_select().err(), Some(TrySelectError));
+    }
+
+    #[test]
+    fn select_timeout_expires() {
+        let (_tx, rx) = channel::<i32>();
+
+        let mut sel = Select::new();
+        sel.recv(&rx);
+        let start = Instant::now();
+        assert_eq!(sel.select_timeout(Duration::from_millis(50)).err(),
+                   Some(SelectTimeoutError));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn select_picks_ready_operation() {
+        let (tx1, rx1) = sync_channel::<i32>(1);
+        let (_tx2, rx2) = channel::<i32>();
+        let (tx3, _rx3) = sync_channel::<i32>(1);
+        tx1.send(1).unwrap();
+        tx3.send(3).unwrap();
+
+        let mut sel = Select::new();
+        let oper1 = sel.recv(&rx1);
+        sel.recv(&rx2);
+        sel.send(&tx3);
+
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper1);
+        assert_eq!(oper.recv(&rx1), Ok(1));
+    }
+
+    #[test]
+    fn blocked_select_wakes_on_array_send() {
+        let (tx1, rx1) = sync_channel::<i32>(1);
+        let (_tx2, rx2) = channel::<i32>();
+
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            tx1.send(1).unwrap();
+        });
+
+        let mut sel = Select::new();
+        let oper1 = sel.recv(&rx1);
+        sel.recv(&rx2);
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper1);
+        assert_eq!(oper.recv(&rx1), Ok(1));
+        t.join().unwrap();
+    }
+
+    #[test]
+    fn blocked_select_send_rendezvous() {
+        let (tx, rx) = sync_channel::<i32>(0);
+
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            rx.recv()
+        });
+
+        let mut sel = Select::new();
+        let oper1 = sel.send(&tx);
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper1);
+        oper.send(&tx, 5).unwrap();
+        assert_eq!(t.join().unwrap(), Ok(5));
+    }
+
+    #[test]
+    fn disconnect_wakes_blocked_select_recv() {
+        let (tx1, rx1) = channel::<i32>();
+        let (tx2, rx2) = sync_channel::<i32>(0);
+        let (_tx3, rx3) = sync_channel::<i32>(2);
+
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(tx2);
+        });
+
+        let mut sel = Select::new();
+        sel.recv(&rx1);
+        let oper2 = sel.recv(&rx2);
+        sel.recv(&rx3);
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper2);
+        assert_eq!(oper.recv(&rx2), Err(RecvError));
+        t.join().unwrap();
+        drop(tx1);
+    }
+
+    #[test]
+    fn disconnect_wakes_blocked_select_send() {
+        let (tx1, rx1) = sync_channel::<i32>(1);
+        let (tx2, rx2) = sync_channel::<i32>(0);
+        tx1.send(0).unwrap();
+
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(rx1);
+        });
+
+        let mut sel = Select::new();
+        let oper1 = sel.send(&tx1);
+        sel.send(&tx2);
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper1);
+        assert_eq!(oper.send(&tx1, 1), Err(SendError(1)));
+        t.join().unwrap();
+        drop(rx2);
+    }
+}
@@ -65,7 +65,7 @@ use sys_common::rwlock as sys;
 /// ```
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct RwLock<T: ?Sized> {
-    inner: Box<sys::RWLock>,
+    inner: sys::MovableRWLock,
     poison: poison::Flag,
     data: UnsafeCell<T>,
 }
@@ -125,7 +125,7 @@ impl<T> RwLock<T> {
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new(t: T) -> RwLock<T> {
         RwLock {
-            inner: box sys::RWLock::new(),
+            inner: sys::MovableRWLock::new(),
             poison: poison::Flag::new(),
             data: UnsafeCell::new(t),
         }
@@ -440,8 +440,9 @@ mod tests {
     use rand::{self, Rng};
     use sync::mpsc::channel;
     use thread;
-    use sync::{Arc, RwLock, TryLockError};
-    use sync::atomic::{AtomicUsize, Ordering};
+    use sync::{Arc, Barrier, RwLock, TryLockError};
+    use sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use time::Duration;
 
     #[derive(Eq, PartialEq, Debug)]
     struct NonCopy(i32);
@@ -482,6 +483,115 @@ mod tests {
         let _ = rx.recv();
     }
 
+    #[test]
+    fn contended_readers_and_writers() {
+        const WRITERS: usize = 4;
+        const READERS: usize = 4;
+        const M: usize = 1000;
+
+        // Writers always update both halves together, so a reader must never
+        // observe them differing.
+        let r = Arc::new(RwLock::new((0, 0)));
+        let done = Arc::new(AtomicBool::new(false));
+
+        let writers = (0..WRITERS).map(|_| {
+            let r = r.clone();
+            thread::spawn(move || {
+                for _ in 0..M {
+                    let mut g = r.write().unwrap();
+                    g.0 += 1;
+                    thread::yield_now();
+                    g.1 += 1;
+                }
+            })
+        }).collect::<Vec<_>>();
+        let readers = (0..READERS).map(|_| {
+            let r = r.clone();
+            let done = done.clone();
+            thread::spawn(move || {
+                while !done.load(Ordering::SeqCst) {
+                    let g = r.read().unwrap();
+                    assert_eq!(g.0, g.1);
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        for t in writers {
+            t.join().unwrap();
+        }
+        done.store(true, Ordering::SeqCst);
+        for t in readers {
+            t.join().unwrap();
+        }
+        assert_eq!(*r.read().unwrap(), (WRITERS * M, WRITERS * M));
+    }
+
+    #[test]
+    fn readers_woken_together_after_writer() {
+        const N: usize = 8;
+
+        let r = Arc::new(RwLock::new(()));
+        // Every reader has to be inside the read lock at the same time to get
+        // past the barrier, so a wakeup that only lets some of them in (or
+        // lets them in one at a time) deadlocks the test.
+        let barrier = Arc::new(Barrier::new(N));
+        let g = r.write().unwrap();
+
+        let readers = (0..N).map(|_| {
+            let r = r.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let _g = r.read().unwrap();
+                barrier.wait();
+            })
+        }).collect::<Vec<_>>();
+
+        thread::sleep(Duration::from_millis(50));
+        drop(g);
+        for t in readers {
+            t.join().unwrap();
+        }
+    }
+
+    // Writers are preferred over readers on Linux; elsewhere the system lock
+    // (e.g. glibc's default pthread rwlock) may legitimately prefer readers.
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn writer_not_starved_by_readers() {
+        const READERS: usize = 4;
+
+        let r = Arc::new(RwLock::new(0));
+        let done = Arc::new(AtomicBool::new(false));
+        let reading = Arc::new(Barrier::new(READERS + 1));
+
+        // The readers keep overlapping read locks, so the lock never becomes
+        // fully unlocked unless new readers queue up behind a waiting writer.
+        let readers = (0..READERS).map(|_| {
+            let r = r.clone();
+            let done = done.clone();
+            let reading = reading.clone();
+            thread::spawn(move || {
+                let mut first = true;
+                while !done.load(Ordering::SeqCst) {
+                    let _g = r.read().unwrap();
+                    if first {
+                        reading.wait();
+                        first = false;
+                    }
+                    thread::sleep(Duration::from_millis(1));
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        reading.wait();
+        *r.write().unwrap() += 1;
+        done.store(true, Ordering::SeqCst);
+        for t in readers {
+            t.join().unwrap();
+        }
+        assert_eq!(*r.read().unwrap(), 1);
+    }
+
     #[test]
     fn test_rw_arc_poison_wr() {
         let arc = Arc::new(RwLock::new(1));
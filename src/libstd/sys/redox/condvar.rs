@@ -99,6 +99,9 @@ impl Condvar {
     }
 }
 
+pub type MovableCondvar = Box<Condvar>;
+pub const REQUIRES_SAME_MUTEX: bool = true;
+
 unsafe impl Send for Condvar {}
 
 unsafe impl Sync for Condvar {}
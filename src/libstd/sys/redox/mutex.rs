@@ -96,6 +96,8 @@ impl Mutex {
     }
 }
 
+pub type MovableMutex = Box<Mutex>;
+
 unsafe impl Send for Mutex {}
 
 unsafe impl Sync for Mutex {}
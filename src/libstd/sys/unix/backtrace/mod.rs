@@ -86,6 +86,8 @@
 pub use self::tracing::unwind_backtrace;
 pub use self::printing::{foreach_symbol_fileline, resolve_symname};
 
+use io;
+
 // tracing impls:
 mod tracing;
 // symbol resolvers:
@@ -117,3 +119,9 @@ pub mod gnu {
 }
 
 pub struct BacktraceContext;
+
+/// Prepares the context needed to resolve frames captured earlier by
+/// `unwind_backtrace`.
+pub fn symbolication_context() -> io::Result<BacktraceContext> {
+    Ok(BacktraceContext)
+}
@@ -15,6 +15,11 @@ use time::Duration;
 
 pub struct Condvar { inner: UnsafeCell<libc::pthread_cond_t> }
 
+// A pthread condition variable must not be moved once it has been used, and
+// waiting on it with two different mutexes is undefined behavior.
+pub type MovableCondvar = Box<Condvar>;
+pub const REQUIRES_SAME_MUTEX: bool = true;
+
 unsafe impl Send for Condvar {}
 unsafe impl Sync for Condvar {}
 
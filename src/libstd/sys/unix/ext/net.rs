@@ -28,7 +28,7 @@ mod libc {
 use ascii;
 use ffi::OsStr;
 use fmt;
-use io::{self, Initializer};
+use io::{self, Initializer, IoSlice, IoSliceMut};
 use mem;
 use net::{self, Shutdown};
 use os::unix::ffi::OsStrExt;
@@ -527,6 +527,10 @@ impl io::Read for UnixStream {
         io::Read::read(&mut &*self, buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        io::Read::read_vectored(&mut &*self, bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -539,6 +543,10 @@ impl<'a> io::Read for &'a UnixStream {
         self.0.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -551,6 +559,10 @@ impl io::Write for UnixStream {
         io::Write::write(&mut &*self, buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::Write::write_vectored(&mut &*self, bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         io::Write::flush(&mut &*self)
     }
@@ -562,6 +574,10 @@ impl<'a> io::Write for &'a UnixStream {
         self.0.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -1477,6 +1493,27 @@ mod test {
         thread.join().unwrap();
     }
 
+    #[test]
+    fn vectored_more_than_iov_max() {
+        // More buffers than `IOV_MAX` (1024 on Linux) must result in a short
+        // read or write rather than an error.
+        const N: usize = 2000;
+
+        let (mut s1, mut s2) = or_panic!(UnixStream::pair());
+        let data = (0..N).map(|i| i as u8).collect::<Vec<u8>>();
+        let slices = data.chunks(1).map(IoSlice::new).collect::<Vec<_>>();
+        let written = or_panic!(s1.write_vectored(&slices));
+        assert!(written > 0 && written <= N);
+
+        let mut buf = vec![0; N];
+        let read = {
+            let mut slices = buf.chunks_mut(1).map(IoSliceMut::new).collect::<Vec<_>>();
+            or_panic!(s2.read_vectored(&mut slices))
+        };
+        assert!(read > 0 && read <= written);
+        assert_eq!(&buf[..read], &data[..read]);
+    }
+
     #[test]
     fn try_clone() {
         let dir = tmpdir();
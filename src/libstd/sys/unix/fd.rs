@@ -11,10 +11,10 @@
 #![unstable(reason = "not public", issue = "0", feature = "fd")]
 
 use cmp;
-use io::{self, Read};
+use io::{self, Read, IoSlice, IoSliceMut};
 use libc::{self, c_int, c_void, ssize_t};
 use mem;
-use sync::atomic::{AtomicBool, Ordering};
+use sync::atomic::{AtomicBool, AtomicUsize, ATOMIC_USIZE_INIT, Ordering};
 use sys::cvt;
 use sys_common::AsInner;
 
@@ -39,6 +39,44 @@ fn max_len() -> usize {
     }
 }
 
+// `readv` and `writev` fail with EINVAL when handed more than `IOV_MAX`
+// buffers, so the buffer count is clamped to it; a short read or write is
+// always allowed. The limit is queried once, falling back to Linux's 1024
+// where that isn't possible.
+#[cfg(any(target_os = "linux",
+          target_os = "android",
+          target_os = "macos",
+          target_os = "ios",
+          target_os = "freebsd",
+          target_os = "dragonfly",
+          target_os = "netbsd",
+          target_os = "openbsd",
+          target_os = "bitrig"))]
+fn max_iov() -> usize {
+    static LIMIT: AtomicUsize = ATOMIC_USIZE_INIT;
+
+    let mut limit = LIMIT.load(Ordering::Relaxed);
+    if limit == 0 {
+        let ret = unsafe { libc::sysconf(libc::_SC_IOV_MAX) };
+        limit = if ret > 0 { ret as usize } else { 1024 };
+        LIMIT.store(limit, Ordering::Relaxed);
+    }
+    limit
+}
+
+#[cfg(not(any(target_os = "linux",
+              target_os = "android",
+              target_os = "macos",
+              target_os = "ios",
+              target_os = "freebsd",
+              target_os = "dragonfly",
+              target_os = "netbsd",
+              target_os = "openbsd",
+              target_os = "bitrig")))]
+fn max_iov() -> usize {
+    1024
+}
+
 impl FileDesc {
     pub fn new(fd: c_int) -> FileDesc {
         FileDesc { fd: fd }
@@ -62,6 +100,15 @@ impl FileDesc {
         Ok(ret as usize)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        let ret = cvt(unsafe {
+            libc::readv(self.fd,
+                        bufs.as_ptr() as *const libc::iovec,
+                        cmp::min(bufs.len(), max_iov()) as c_int)
+        })?;
+        Ok(ret as usize)
+    }
+
     pub fn read_to_end(&self, buf: &mut Vec<u8>) -> io::Result<usize> {
         let mut me = self;
         (&mut me).read_to_end(buf)
@@ -108,6 +155,15 @@ impl FileDesc {
         Ok(ret as usize)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let ret = cvt(unsafe {
+            libc::writev(self.fd,
+                         bufs.as_ptr() as *const libc::iovec,
+                         cmp::min(bufs.len(), max_iov()) as c_int)
+        })?;
+        Ok(ret as usize)
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         #[cfg(target_os = "android")]
         use super::android::cvt_pwrite64;
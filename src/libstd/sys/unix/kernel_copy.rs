@@ -0,0 +1,410 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//! Kernel-accelerated `io::copy` on Linux.
+//!
+//! When both ends of an `io::copy` are known to be backed by file descriptors
+//! the data can be moved inside the kernel instead of bouncing through a
+//! user-space buffer:
+//!
+//! * `copy_file_range` between two regular files, which lets filesystems
+//!   share extents or do server-side copies,
+//! * `sendfile` from a regular file to anything, most notably sockets,
+//! * `splice` when either end is a pipe.
+//!
+//! Which of these applies is decided at runtime with `fstat`, and every one
+//! of them can be refused by the kernel (too old, cross-device, `O_APPEND`,
+//! unsupported filesystem, ...). As long as nothing has been transferred yet
+//! such failures fall through to the next strategy and eventually to the
+//! generic read/write loop. All of these calls use and advance the file
+//! offsets just like `read` and `write` would, so the observable result is
+//! the same either way.
+//!
+//! The fd-backed types are selected through specialization on the `CopyRead`
+//! and `CopyWrite` marker traits below; everything else takes the generic
+//! path without any runtime cost.
+
+use fs::File;
+use io::{self, Read, Write};
+use libc::{self, c_uint, size_t, ssize_t};
+use mem;
+use net::TcpStream;
+use process::{ChildStderr, ChildStdin, ChildStdout};
+use ptr;
+use sync::atomic::{AtomicBool, ATOMIC_BOOL_INIT, Ordering};
+use sys::cvt_r;
+use sys::ext::io::{AsRawFd, RawFd};
+use sys::ext::net::UnixStream;
+
+/// Upper bound on the number of bytes requested from the kernel in a single
+/// call, which keeps every request well within `ssize_t`.
+const MAX_CHUNK: usize = 0x4000_0000;
+
+pub fn copy_spec<R: ?Sized, W: ?Sized>(read: &mut R, write: &mut W) -> io::Result<u64>
+    where R: Read, W: Write
+{
+    Copier { read, write }.copy()
+}
+
+struct Copier<'a, 'b, R: Read + ?Sized + 'a, W: Write + ?Sized + 'b> {
+    read: &'a mut R,
+    write: &'b mut W,
+}
+
+trait SpecCopy {
+    fn copy(self) -> io::Result<u64>;
+}
+
+impl<'a, 'b, R: Read + ?Sized, W: Write + ?Sized> SpecCopy for Copier<'a, 'b, R, W> {
+    default fn copy(self) -> io::Result<u64> {
+        io::generic_copy(self.read, self.write)
+    }
+}
+
+impl<'a, 'b, R: CopyRead + ?Sized, W: CopyWrite + ?Sized> SpecCopy for Copier<'a, 'b, R, W> {
+    fn copy(self) -> io::Result<u64> {
+        let reader = self.read.raw_fd();
+        let writer = self.write.raw_fd();
+        let reader_meta = FdMeta::of(reader);
+        let writer_meta = FdMeta::of(writer);
+
+        if reader_meta.is_file() && writer_meta.is_file() {
+            if let CopyResult::Ended(result) = copy_regular_files(reader, writer) {
+                return result;
+            }
+        }
+
+        // `sendfile` only needs an input that can be mmap'd, the output can
+        // be any file descriptor since Linux 2.6.33.
+        if reader_meta.is_file() {
+            if let CopyResult::Ended(result) = sendfile_splice(SpliceMode::Sendfile,
+                                                               reader, writer) {
+                return result;
+            }
+        }
+
+        if reader_meta.is_fifo() || writer_meta.is_fifo() {
+            if let CopyResult::Ended(result) = sendfile_splice(SpliceMode::Splice,
+                                                               reader, writer) {
+                return result;
+            }
+        }
+
+        io::generic_copy(self.read, self.write)
+    }
+}
+
+/// A reader whose data can be pulled straight from a file descriptor.
+///
+/// Implementors must not buffer any data in user space, otherwise that data
+/// would be skipped by the kernel copy.
+trait CopyRead: Read {
+    fn raw_fd(&self) -> RawFd;
+}
+
+/// A writer whose data can be pushed straight into a file descriptor.
+///
+/// Implementors must not buffer any data in user space, otherwise the kernel
+/// copy could reorder it.
+trait CopyWrite: Write {
+    fn raw_fd(&self) -> RawFd;
+}
+
+impl<'a, T: CopyRead + ?Sized> CopyRead for &'a mut T {
+    fn raw_fd(&self) -> RawFd { (**self).raw_fd() }
+}
+
+impl<'a, T: CopyWrite + ?Sized> CopyWrite for &'a mut T {
+    fn raw_fd(&self) -> RawFd { (**self).raw_fd() }
+}
+
+impl CopyRead for File {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl<'a> CopyRead for &'a File {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl CopyWrite for File {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl<'a> CopyWrite for &'a File {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl CopyRead for TcpStream {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl<'a> CopyRead for &'a TcpStream {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl CopyWrite for TcpStream {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl<'a> CopyWrite for &'a TcpStream {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl CopyRead for UnixStream {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl<'a> CopyRead for &'a UnixStream {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl CopyWrite for UnixStream {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl<'a> CopyWrite for &'a UnixStream {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl CopyWrite for ChildStdin {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl CopyRead for ChildStdout {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+impl CopyRead for ChildStderr {
+    fn raw_fd(&self) -> RawFd { self.as_raw_fd() }
+}
+
+/// The kind of file behind a descriptor, as far as picking a copy strategy
+/// is concerned.
+#[derive(PartialEq, Eq)]
+enum FdMeta {
+    File,
+    Fifo,
+    Other,
+}
+
+impl FdMeta {
+    fn of(fd: RawFd) -> FdMeta {
+        let mut stat: libc::stat64 = unsafe { mem::zeroed() };
+        if unsafe { libc::fstat64(fd, &mut stat) } == -1 {
+            return FdMeta::Other;
+        }
+        match stat.st_mode & libc::S_IFMT {
+            libc::S_IFREG => FdMeta::File,
+            libc::S_IFIFO => FdMeta::Fifo,
+            _ => FdMeta::Other,
+        }
+    }
+
+    fn is_file(&self) -> bool { *self == FdMeta::File }
+
+    fn is_fifo(&self) -> bool { *self == FdMeta::Fifo }
+}
+
+enum CopyResult {
+    /// The kernel copy ran to completion or hit a real error.
+    Ended(io::Result<u64>),
+    /// Nothing was transferred and the next strategy should be tried.
+    Fallback,
+}
+
+/// Whether an error from a kernel copy before any byte has been transferred
+/// means that the operation isn't supported for this pair of descriptors,
+/// rather than that the copy itself failed.
+fn is_unsupported(err: &io::Error) -> bool {
+    match err.raw_os_error() {
+        Some(libc::ENOSYS) | Some(libc::EXDEV) | Some(libc::EINVAL) |
+        Some(libc::EPERM) | Some(libc::EOPNOTSUPP) | Some(libc::EBADF) => true,
+        _ => false,
+    }
+}
+
+fn copy_regular_files(reader: RawFd, writer: RawFd) -> CopyResult {
+    // The syscall is invoked directly: glibc only gained a wrapper in 2.27 and
+    // static musl builds can't look one up at runtime, but the kernel has
+    // supported it since 4.5. Older kernels answer with ENOSYS, which is
+    // remembered so later copies go straight to the next strategy.
+    static INVALID: AtomicBool = ATOMIC_BOOL_INIT;
+
+    if INVALID.load(Ordering::Relaxed) {
+        return CopyResult::Fallback;
+    }
+
+    let mut written = 0u64;
+    loop {
+        let result = cvt_r(|| unsafe {
+            libc::syscall(libc::SYS_copy_file_range,
+                          reader,
+                          ptr::null_mut::<i64>(),
+                          writer,
+                          ptr::null_mut::<i64>(),
+                          MAX_CHUNK,
+                          0 as c_uint)
+        });
+        match result {
+            Err(ref e) if e.raw_os_error() == Some(libc::ENOSYS) => {
+                INVALID.store(true, Ordering::Relaxed);
+                return CopyResult::Fallback;
+            }
+            // Some virtual filesystems like procfs report a size of zero and
+            // return no data through `copy_file_range` even though reading
+            // them produces output, so let the next strategy find out.
+            Ok(0) if written == 0 => return CopyResult::Fallback,
+            Ok(0) => return CopyResult::Ended(Ok(written)),
+            Ok(n) => written += n as u64,
+            Err(ref e) if written == 0 && is_unsupported(e) => return CopyResult::Fallback,
+            Err(e) => return CopyResult::Ended(Err(e)),
+        }
+    }
+}
+
+enum SpliceMode {
+    Sendfile,
+    Splice,
+}
+
+// Unlike `copy_file_range` above, glibc and musl have wrapped both of these
+// for a long time, so they're linked directly instead of being looked up at
+// runtime, which static musl builds can't do.
+fn sendfile_splice(mode: SpliceMode, reader: RawFd, writer: RawFd) -> CopyResult {
+    match mode {
+        SpliceMode::Sendfile => sendfile_splice_loop(|len| unsafe {
+            libc::sendfile(writer, reader, ptr::null_mut(), len)
+        }),
+        SpliceMode::Splice => sendfile_splice_loop(|len| unsafe {
+            libc::splice(reader, ptr::null_mut(), writer, ptr::null_mut(), len, 0)
+        }),
+    }
+}
+
+fn sendfile_splice_loop<F>(mut f: F) -> CopyResult
+    where F: FnMut(size_t) -> ssize_t
+{
+    let mut written = 0u64;
+    loop {
+        match cvt_r(|| f(MAX_CHUNK)) {
+            Ok(0) if written == 0 => return CopyResult::Fallback,
+            Ok(0) => return CopyResult::Ended(Ok(written)),
+            Ok(n) => written += n as u64,
+            Err(ref e) if written == 0 && is_unsupported(e) => return CopyResult::Fallback,
+            Err(e) => return CopyResult::Ended(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fs::File;
+    use io::{self, SeekFrom};
+    use io::prelude::*;
+    use net::{TcpListener, TcpStream};
+    use path::Path;
+    use process::{Command, Stdio};
+    use sys::ext::net::UnixStream;
+    use sys_common::io::test::tmpdir;
+    use thread;
+
+    const LEN: usize = 100_000;
+    const SKIP: usize = 10;
+
+    fn test_data() -> Vec<u8> {
+        (0..LEN).map(|i| (i % 251) as u8).collect()
+    }
+
+    // Leaves the returned file positioned `SKIP` bytes in, so the kernel copy
+    // has to start from the current offset rather than the beginning.
+    fn partially_read_file(path: &Path, data: &[u8]) -> File {
+        File::create(path).unwrap().write_all(data).unwrap();
+        let mut file = File::open(path).unwrap();
+        let mut skipped = [0; SKIP];
+        file.read_exact(&mut skipped).unwrap();
+        file
+    }
+
+    #[test]
+    fn copy_file_to_tcp_stream() {
+        let tmpdir = tmpdir();
+        let data = test_data();
+        let mut file = partially_read_file(&tmpdir.join("in.bin"), &data);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let receiver = thread::spawn(move || {
+            let mut v = Vec::new();
+            listener.accept().unwrap().0.read_to_end(&mut v).unwrap();
+            v
+        });
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        assert_eq!(io::copy(&mut file, &mut stream).unwrap(), (LEN - SKIP) as u64);
+        assert_eq!(file.seek(SeekFrom::Current(0)).unwrap(), LEN as u64);
+        drop(stream);
+
+        assert_eq!(receiver.join().unwrap(), &data[SKIP..]);
+    }
+
+    #[test]
+    fn copy_file_to_unix_stream() {
+        let tmpdir = tmpdir();
+        let data = test_data();
+        let mut file = partially_read_file(&tmpdir.join("in.bin"), &data);
+
+        let (mut tx, mut rx) = UnixStream::pair().unwrap();
+        let receiver = thread::spawn(move || {
+            let mut v = Vec::new();
+            rx.read_to_end(&mut v).unwrap();
+            v
+        });
+
+        assert_eq!(io::copy(&mut file, &mut tx).unwrap(), (LEN - SKIP) as u64);
+        assert_eq!(file.seek(SeekFrom::Current(0)).unwrap(), LEN as u64);
+        drop(tx);
+
+        assert_eq!(receiver.join().unwrap(), &data[SKIP..]);
+    }
+
+    #[test]
+    fn copy_through_pipes() {
+        let tmpdir = tmpdir();
+        let data = test_data();
+        let mut file = partially_read_file(&tmpdir.join("in.bin"), &data);
+
+        let mut child = Command::new("/bin/cat")
+                                .stdin(Stdio::piped())
+                                .stdout(Stdio::piped())
+                                .spawn().unwrap();
+
+        // File into the write end of a pipe.
+        let mut stdin = child.stdin.take().unwrap();
+        let sender = thread::spawn(move || {
+            let n = io::copy(&mut file, &mut stdin).unwrap();
+            (n, file.seek(SeekFrom::Current(0)).unwrap())
+        });
+
+        // Read end of a pipe into a file which already has some contents.
+        let out_path = tmpdir.join("out.bin");
+        let mut out = File::create(&out_path).unwrap();
+        out.write_all(b"ab").unwrap();
+        let mut stdout = child.stdout.take().unwrap();
+        assert_eq!(io::copy(&mut stdout, &mut out).unwrap(), (LEN - SKIP) as u64);
+        assert_eq!(out.seek(SeekFrom::Current(0)).unwrap(), (LEN - SKIP + 2) as u64);
+
+        assert_eq!(sender.join().unwrap(), ((LEN - SKIP) as u64, LEN as u64));
+        assert!(child.wait().unwrap().success());
+
+        let mut v = Vec::new();
+        File::open(&out_path).unwrap().read_to_end(&mut v).unwrap();
+        assert_eq!(&v[..2], b"ab");
+        assert_eq!(&v[2..], &data[SKIP..]);
+    }
+}
@@ -37,12 +37,23 @@ pub mod args;
 pub mod android;
 #[cfg(feature = "backtrace")]
 pub mod backtrace;
+#[cfg(not(target_os = "linux"))]
+pub mod condvar;
+#[cfg(target_os = "linux")]
+#[path = "futex_condvar.rs"]
 pub mod condvar;
 pub mod env;
 pub mod ext;
 pub mod fast_thread_local;
 pub mod fd;
 pub mod fs;
+#[cfg(target_os = "linux")]
+pub mod futex;
+#[cfg(target_os = "linux")]
+mod futex_mutex;
+pub mod io;
+#[cfg(target_os = "linux")]
+pub mod kernel_copy;
 pub mod memchr;
 pub mod mutex;
 #[cfg(not(target_os = "l4re"))]
@@ -57,6 +68,10 @@ pub mod path;
 pub mod pipe;
 pub mod process;
 pub mod rand;
+#[cfg(not(target_os = "linux"))]
+pub mod rwlock;
+#[cfg(target_os = "linux")]
+#[path = "futex_rwlock.rs"]
 pub mod rwlock;
 pub mod stack_overflow;
 pub mod thread;
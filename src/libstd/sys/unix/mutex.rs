@@ -12,16 +12,31 @@ use cell::UnsafeCell;
 use libc;
 use mem;
 
+// On Linux `Mutex` is built directly on a futex, see `futex_mutex.rs`;
+// only the reentrant mutex below still uses pthreads there.
+#[cfg(target_os = "linux")]
+pub use super::futex_mutex::{Mutex, MovableMutex};
+
+#[cfg(not(target_os = "linux"))]
 pub struct Mutex { inner: UnsafeCell<libc::pthread_mutex_t> }
 
+// A pthread mutex must not be moved once it has been used, so it's boxed to
+// give it a stable address.
+#[cfg(not(target_os = "linux"))]
+pub type MovableMutex = Box<Mutex>;
+
+#[cfg(not(target_os = "linux"))]
 #[inline]
 pub unsafe fn raw(m: &Mutex) -> *mut libc::pthread_mutex_t {
     m.inner.get()
 }
 
+#[cfg(not(target_os = "linux"))]
 unsafe impl Send for Mutex {}
+#[cfg(not(target_os = "linux"))]
 unsafe impl Sync for Mutex {}
 
+#[cfg(not(target_os = "linux"))]
 #[allow(dead_code)] // sys isn't exported yet
 impl Mutex {
     pub const fn new() -> Mutex {
@@ -18,6 +18,9 @@ pub struct RWLock {
     num_readers: AtomicUsize,
 }
 
+// A pthread rwlock must not be moved once it has been used.
+pub type MovableRWLock = Box<RWLock>;
+
 unsafe impl Send for RWLock {}
 unsafe impl Sync for RWLock {}
 
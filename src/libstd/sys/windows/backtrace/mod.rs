@@ -105,6 +105,27 @@ pub fn unwind_backtrace(frames: &mut [Frame])
     Ok((i, backtrace_context))
 }
 
+/// Prepares the context needed to resolve frames captured earlier by
+/// `unwind_backtrace`.
+pub fn symbolication_context() -> io::Result<BacktraceContext> {
+    let dbghelp = DynamicLibrary::open("dbghelp.dll")?;
+
+    let SymInitialize = sym!(dbghelp, "SymInitialize", SymInitializeFn)?;
+    let SymCleanup = sym!(dbghelp, "SymCleanup", SymCleanupFn)?;
+
+    let process = unsafe { c::GetCurrentProcess() };
+    let ret = unsafe { SymInitialize(process, ptr::null_mut(), c::TRUE) };
+    if ret != c::TRUE {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(BacktraceContext {
+        handle: process,
+        SymCleanup,
+        dbghelp,
+    })
+}
+
 type SymInitializeFn =
     unsafe extern "system" fn(c::HANDLE, *mut c_void,
                               c::BOOL) -> c::BOOL;
@@ -16,6 +16,9 @@ use time::Duration;
 
 pub struct Condvar { inner: UnsafeCell<c::CONDITION_VARIABLE> }
 
+pub type MovableCondvar = Box<Condvar>;
+pub const REQUIRES_SAME_MUTEX: bool = true;
+
 unsafe impl Send for Condvar {}
 unsafe impl Sync for Condvar {}
 
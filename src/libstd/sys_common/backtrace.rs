@@ -27,6 +27,7 @@ pub use sys::backtrace::{
     unwind_backtrace,
     resolve_symname,
     foreach_symbol_fileline,
+    symbolication_context,
     BacktraceContext
 };
 
@@ -47,20 +48,37 @@ pub struct Frame {
 }
 
 /// Max number of frames to print.
-const MAX_NB_FRAMES: usize = 100;
+pub const MAX_NB_FRAMES: usize = 100;
 
-/// Prints the current backtrace.
-pub fn print(w: &mut Write, format: PrintFormat) -> io::Result<()> {
-    static LOCK: Mutex = Mutex::new();
+static LOCK: Mutex = Mutex::new();
+
+/// Guard for the global lock serializing the unwinding and symbolication of
+/// backtraces, released on drop.
+pub struct BacktraceLock(());
 
-    // Use a lock to prevent mixed output in multithreading context.
-    // Some platforms also requires it, like `SymFromAddr` on Windows.
+/// Acquires the global backtrace lock.
+///
+/// Use this lock to prevent mixed output in multithreading context. Some
+/// platforms also requires it, like `SymFromAddr` on Windows.
+pub fn lock() -> BacktraceLock {
     unsafe {
         LOCK.lock();
-        let res = _print(w, format);
-        LOCK.unlock();
-        res
     }
+    BacktraceLock(())
+}
+
+impl Drop for BacktraceLock {
+    fn drop(&mut self) {
+        unsafe {
+            LOCK.unlock();
+        }
+    }
+}
+
+/// Prints the current backtrace.
+pub fn print(w: &mut Write, format: PrintFormat) -> io::Result<()> {
+    let _lock = lock();
+    _print(w, format)
 }
 
 fn _print(w: &mut Write, format: PrintFormat) -> io::Result<()> {
@@ -131,7 +149,7 @@ fn filter_frames(frames: &[Frame],
 /// Fixed frame used to clean the backtrace with `RUST_BACKTRACE=1`.
 #[inline(never)]
 pub fn __rust_begin_short_backtrace<F, T>(f: F) -> T
-    where F: FnOnce() -> T, F: Send + 'static, T: Send + 'static
+    where F: FnOnce() -> T, F: Send, T: Send
 {
     f()
 }
@@ -9,30 +9,30 @@
 // except according to those terms.
 
 use time::Duration;
-use sys_common::mutex::{self, Mutex};
+use sys_common::mutex::MovableMutex;
 use sys::condvar as imp;
 
+pub use sys::condvar::REQUIRES_SAME_MUTEX;
+
 /// An OS-based condition variable.
 ///
 /// This structure is the lowest layer possible on top of the OS-provided
 /// condition variables. It is consequently entirely unsafe to use. It is
 /// recommended to use the safer types at the top level of this crate instead of
 /// this type.
-pub struct Condvar(imp::Condvar);
-
-impl Condvar {
-    /// Creates a new condition variable for use.
-    ///
-    /// Behavior is undefined if the condition variable is moved after it is
-    /// first used with any of the functions below.
-    pub const fn new() -> Condvar { Condvar(imp::Condvar::new()) }
+///
+/// It may be moved after it has been initialized. Whether the platform
+/// condition variable has to be boxed to get a stable address is up to the
+/// platform, see `sys::condvar::MovableCondvar`.
+pub struct MovableCondvar(imp::MovableCondvar);
 
-    /// Prepares the condition variable for use.
-    ///
-    /// This should be called once the condition variable is at a stable memory
-    /// address.
-    #[inline]
-    pub unsafe fn init(&mut self) { self.0.init() }
+impl MovableCondvar {
+    /// Creates a new condition variable, ready for use.
+    pub fn new() -> MovableCondvar {
+        let mut condvar = imp::MovableCondvar::from(imp::Condvar::new());
+        unsafe { condvar.init() }
+        MovableCondvar(condvar)
+    }
 
     /// Signals one waiter on this condition variable to wake up.
     #[inline]
@@ -46,19 +46,19 @@ impl Condvar {
     ///
     /// Behavior is undefined if the mutex is not locked by the current thread.
     /// Behavior is also undefined if more than one mutex is used concurrently
-    /// on this condition variable.
+    /// on this condition variable and `REQUIRES_SAME_MUTEX` is set.
     #[inline]
-    pub unsafe fn wait(&self, mutex: &Mutex) { self.0.wait(mutex::raw(mutex)) }
+    pub unsafe fn wait(&self, mutex: &MovableMutex) { self.0.wait(mutex.raw()) }
 
     /// Waits for a signal on the specified mutex with a timeout duration
     /// specified by `dur` (a relative time into the future).
     ///
     /// Behavior is undefined if the mutex is not locked by the current thread.
     /// Behavior is also undefined if more than one mutex is used concurrently
-    /// on this condition variable.
+    /// on this condition variable and `REQUIRES_SAME_MUTEX` is set.
     #[inline]
-    pub unsafe fn wait_timeout(&self, mutex: &Mutex, dur: Duration) -> bool {
-        self.0.wait_timeout(mutex::raw(mutex), dur)
+    pub unsafe fn wait_timeout(&self, mutex: &MovableMutex, dur: Duration) -> bool {
+        self.0.wait_timeout(mutex.raw(), dur)
     }
 
     /// Deallocates all resources associated with this condition variable.
@@ -19,6 +19,14 @@ pub struct Mutex(imp::Mutex);
 
 unsafe impl Sync for Mutex {}
 
+/// A mutex which may be moved after it has been initialized.
+///
+/// Whether the platform mutex has to be boxed to get a stable address is up
+/// to the platform, see `sys::mutex::MovableMutex`.
+pub struct MovableMutex(imp::MovableMutex);
+
+unsafe impl Sync for MovableMutex {}
+
 impl Mutex {
     /// Creates a new mutex for use.
     ///
@@ -62,5 +70,38 @@ impl Mutex {
     pub unsafe fn destroy(&self) { self.0.destroy() }
 }
 
-// not meant to be exported to the outside world, just the containing module
-pub fn raw(mutex: &Mutex) -> &imp::Mutex { &mutex.0 }
+impl MovableMutex {
+    /// Creates a new mutex, ready for use.
+    pub fn new() -> MovableMutex {
+        let mut mutex = imp::MovableMutex::from(imp::Mutex::new());
+        unsafe { mutex.init() }
+        MovableMutex(mutex)
+    }
+
+    /// Returns the platform mutex, which stays at the same address for as
+    /// long as this value is alive.
+    pub fn raw(&self) -> &imp::Mutex { &self.0 }
+
+    /// Locks the mutex blocking the current thread until it is available.
+    #[inline]
+    pub unsafe fn lock(&self) { self.0.lock() }
+
+    /// Attempts to lock the mutex without blocking, returning whether it was
+    /// successfully acquired or not.
+    #[inline]
+    pub unsafe fn try_lock(&self) -> bool { self.0.try_lock() }
+
+    /// Unlocks the mutex.
+    ///
+    /// Behavior is undefined if the current thread does not actually hold the
+    /// mutex.
+    #[inline]
+    pub unsafe fn unlock(&self) { self.0.unlock() }
+
+    /// Deallocates all resources associated with this mutex.
+    ///
+    /// Behavior is undefined if there are current or will be future users of
+    /// this mutex.
+    #[inline]
+    pub unsafe fn destroy(&self) { self.0.destroy() }
+}
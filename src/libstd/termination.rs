@@ -0,0 +1,77 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use fmt::Debug;
+
+#[cfg(target_arch = "wasm32")]
+mod exit {
+    pub const SUCCESS: i32 = 0;
+    pub const FAILURE: i32 = 1;
+}
+#[cfg(not(target_arch = "wasm32"))]
+mod exit {
+    use libc;
+    pub const SUCCESS: i32 = libc::EXIT_SUCCESS;
+    pub const FAILURE: i32 = libc::EXIT_FAILURE;
+}
+
+/// A trait for types that can be returned from `main` and `#[test]`
+/// functions.
+///
+/// The program's entry point can only report an integer exit code to the
+/// operating system, so every type implementing `Termination` is converted to
+/// one. Successful values report `EXIT_SUCCESS`; failures print a message to
+/// standard error and report `EXIT_FAILURE`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(termination_trait)]
+///
+/// use std::num::ParseIntError;
+///
+/// fn main() -> Result<(), ParseIntError> {
+///     let number: i32 = "42".parse()?;
+///     println!("{}", number);
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(not(any(test, stage0)), lang = "termination")]
+#[unstable(feature = "termination_trait_lib", issue = "0")]
+#[rustc_on_unimplemented =
+  "`main` and tests can only return types that implement `Termination`, not `{Self}`"]
+pub trait Termination {
+    /// Is called to get the representation of the value as status code.
+    /// This status code is returned to the operating system.
+    fn report(self) -> i32;
+}
+
+#[unstable(feature = "termination_trait_lib", issue = "0")]
+impl Termination for () {
+    fn report(self) -> i32 { exit::SUCCESS }
+}
+
+#[unstable(feature = "termination_trait_lib", issue = "0")]
+impl Termination for ! {
+    fn report(self) -> i32 { self }
+}
+
+#[unstable(feature = "termination_trait_lib", issue = "0")]
+impl<T: Termination, E: Debug> Termination for Result<T, E> {
+    fn report(self) -> i32 {
+        match self {
+            Ok(val) => val.report(),
+            Err(err) => {
+                eprintln!("Error: {:?}", err);
+                exit::FAILURE
+            }
+        }
+    }
+}
@@ -70,6 +70,24 @@
 //! value produced by the child thread, or [`Err`] of the value given to
 //! a call to [`panic!`] if the child panicked.
 //!
+//! ## Scoped threads
+//!
+//! Threads created with [`spawn`] may outlive their parent, so they can only
+//! use `'static` data. Threads spawned within a [`scope`] are all joined
+//! before the scope returns, and may therefore borrow from the parent's
+//! stack:
+//!
+//! ```rust
+//! #![feature(scoped_threads)]
+//! use std::thread;
+//!
+//! let mut numbers = vec![1, 2, 3];
+//! thread::scope(|s| {
+//!     s.spawn(|| println!("{:?}", numbers));
+//! });
+//! numbers.push(4);
+//! ```
+//!
 //! ## Configuring threads
 //!
 //! A new thread can be configured before it is spawned via the [`Builder`] type,
@@ -141,6 +159,7 @@
 //! [`JoinHandle`]: ../../std/thread/struct.JoinHandle.html
 //! [`JoinHandle::thread`]: ../../std/thread/struct.JoinHandle.html#method.thread
 //! [`join`]: ../../std/thread/struct.JoinHandle.html#method.join
+//! [`scope`]: ../../std/thread/fn.scope.html
 //! [`Result`]: ../../std/result/enum.Result.html
 //! [`Ok`]: ../../std/result/enum.Result.html#variant.Ok
 //! [`Err`]: ../../std/result/enum.Result.html#variant.Err
@@ -167,6 +186,7 @@ use cell::UnsafeCell;
 use ffi::{CStr, CString};
 use fmt;
 use io;
+use marker::PhantomData;
 use panic;
 use panicking;
 use str;
@@ -187,6 +207,15 @@ use time::Duration;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::local::{LocalKey, LocalKeyState, AccessError};
 
+////////////////////////////////////////////////////////////////////////////////
+// Scoped threads
+////////////////////////////////////////////////////////////////////////////////
+
+mod scoped;
+
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub use self::scoped::{scope, Scope, ScopedJoinHandle};
+
 // The types used by the thread_local! macro to access TLS keys. Note that there
 // are two types, the "OS" type and the "fast" type. The OS thread local key
 // type is accessed via platform-specific API calls and is slow, while the fast
@@ -371,6 +400,20 @@ impl Builder {
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn spawn<F, T>(self, f: F) -> io::Result<JoinHandle<T>> where
         F: FnOnce() -> T, F: Send + 'static, T: Send + 'static
+    {
+        unsafe { self.spawn_unchecked_(f, None).map(JoinHandle) }
+    }
+
+    // Spawns a thread that may borrow data living for `'scope`.
+    //
+    // This is unsafe because nothing here stops the thread from outliving
+    // `'scope`: callers must either pick `'static`, or pass the data of a
+    // scope that joins the thread before `'scope` ends.
+    unsafe fn spawn_unchecked_<'scope, F, T>(self,
+                                             f: F,
+                                             scope_data: Option<Arc<scoped::ScopeData>>)
+                                             -> io::Result<JoinInner<'scope, T>> where
+        F: FnOnce() -> T, F: Send + 'scope, T: Send + 'scope
     {
         let Builder { name, stack_size } = self;
 
@@ -379,33 +422,38 @@ impl Builder {
         let my_thread = Thread::new(name);
         let their_thread = my_thread.clone();
 
-        let my_packet : Arc<UnsafeCell<Option<Result<T>>>>
-            = Arc::new(UnsafeCell::new(None));
+        let my_packet: Arc<Packet<'scope, T>> = Arc::new(Packet {
+            scope: scope_data,
+            result: UnsafeCell::new(None),
+            _marker: PhantomData,
+        });
         let their_packet = my_packet.clone();
 
         let main = move || {
             if let Some(name) = their_thread.cname() {
                 imp::Thread::set_name(name);
             }
-            unsafe {
-                thread_info::set(imp::guard::current(), their_thread);
-                #[cfg(feature = "backtrace")]
-                let try_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
-                    ::sys_common::backtrace::__rust_begin_short_backtrace(f)
-                }));
-                #[cfg(not(feature = "backtrace"))]
-                let try_result = panic::catch_unwind(panic::AssertUnwindSafe(f));
-                *their_packet.get() = Some(try_result);
-            }
+            thread_info::set(imp::guard::current(), their_thread);
+            #[cfg(feature = "backtrace")]
+            let try_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                ::sys_common::backtrace::__rust_begin_short_backtrace(f)
+            }));
+            #[cfg(not(feature = "backtrace"))]
+            let try_result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+            *their_packet.result.get() = Some(try_result);
         };
 
-        Ok(JoinHandle(JoinInner {
-            native: unsafe {
-                Some(imp::Thread::new(stack_size, Box::new(main))?)
-            },
+        if let Some(ref scope_data) = my_packet.scope {
+            scope_data.increment_num_running_threads();
+        }
+
+        // If creating the thread fails, dropping both halves of the packet
+        // undoes the increment above.
+        Ok(JoinInner {
+            native: Some(imp::Thread::new(stack_size, Box::new(main))?),
             thread: my_thread,
-            packet: Packet(my_packet),
-        }))
+            packet: my_packet,
+        })
     }
 }
 
@@ -1102,34 +1150,69 @@ impl fmt::Debug for Thread {
 pub type Result<T> = ::result::Result<T, Box<Any + Send + 'static>>;
 
 // This packet is used to communicate the return value between the child thread
-// and the parent thread. Memory is shared through the `Arc` within and there's
-// no need for a mutex here because synchronization happens with `join()` (the
-// parent thread never reads this packet until the child has exited).
+// and the parent thread. Memory is shared through an `Arc` and there's no need
+// for a mutex here because synchronization happens with `join()` (the parent
+// thread never reads this packet until the child has exited).
+//
+// For scoped threads the packet also records the scope, so that the scope
+// knows when the thread has finished: the running thread count is decremented
+// once both the thread and its join handle have let go of the packet, after
+// the result (which may borrow from the scope) has been dropped.
 //
-// This packet itself is then stored into a `JoinInner` which in turns is placed
-// in `JoinHandle` and `JoinGuard`. Due to the usage of `UnsafeCell` we need to
-// manually worry about impls like Send and Sync. The type `T` should
-// already always be Send (otherwise the thread could not have been created) and
-// this type is inherently Sync because no methods take &self. Regardless,
-// however, we add inheriting impls for Send/Sync to this type to ensure it's
-// Send/Sync and that future modifications will still appropriately classify it.
-struct Packet<T>(Arc<UnsafeCell<Option<Result<T>>>>);
-
-unsafe impl<T: Send> Send for Packet<T> {}
-unsafe impl<T: Sync> Sync for Packet<T> {}
-
-/// Inner representation for JoinHandle
-struct JoinInner<T> {
+// Due to the usage of `UnsafeCell` we need to manually worry about impls like
+// Send and Sync. The packet is only ever accessed by one thread at a time, so
+// it is Sync as long as the result is Send.
+struct Packet<'scope, T> {
+    scope: Option<Arc<scoped::ScopeData>>,
+    result: UnsafeCell<Option<Result<T>>>,
+    _marker: PhantomData<Option<&'scope scoped::ScopeData>>,
+}
+
+unsafe impl<'scope, T: Send> Sync for Packet<'scope, T> {}
+
+impl<'scope, T> Drop for Packet<'scope, T> {
+    fn drop(&mut self) {
+        // If this packet was for a thread that ran in a scope, the thread
+        // panicked, and nobody consumed the panic payload, we make sure
+        // the scope function will panic.
+        let unhandled_panic = match unsafe { &*self.result.get() } {
+            &Some(Err(_)) => true,
+            _ => false,
+        };
+        // Drop the result without causing unwinding. This is only relevant
+        // for threads that aren't joined, as `join` takes the result out.
+        // If this panics we're outside the outermost `catch_unwind` of the
+        // thread, so all we can do is abort.
+        let result = &self.result;
+        if panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe {
+            *result.get() = None;
+        })).is_err() {
+            rtabort!("thread result panicked on drop");
+        }
+        // Book-keeping so the scope knows when it's done.
+        if let Some(ref scope) = self.scope {
+            scope.decrement_num_running_threads(unhandled_panic);
+        }
+    }
+}
+
+/// Inner representation for JoinHandle and ScopedJoinHandle
+struct JoinInner<'scope, T> {
     native: Option<imp::Thread>,
     thread: Thread,
-    packet: Packet<T>,
+    packet: Arc<Packet<'scope, T>>,
 }
 
-impl<T> JoinInner<T> {
+// `T` is only ever moved out of the packet by `join`, so the handle may be
+// sent as long as the result may be.
+unsafe impl<'scope, T: Send> Send for JoinInner<'scope, T> {}
+unsafe impl<'scope, T: Sync> Sync for JoinInner<'scope, T> {}
+
+impl<'scope, T> JoinInner<'scope, T> {
     fn join(&mut self) -> Result<T> {
         self.native.take().unwrap().join();
         unsafe {
-            (*self.packet.0.get()).take().unwrap()
+            (*self.packet.result.get()).take().unwrap()
         }
     }
 }
@@ -1198,7 +1281,7 @@ impl<T> JoinInner<T> {
 /// [`thread::spawn`]: fn.spawn.html
 /// [`thread::Builder::spawn`]: struct.Builder.html#method.spawn
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct JoinHandle<T>(JoinInner<T>);
+pub struct JoinHandle<T>(JoinInner<'static, T>);
 
 impl<T> JoinHandle<T> {
     /// Extracts a handle to the underlying thread.
@@ -1271,6 +1354,7 @@ impl<T> fmt::Debug for JoinHandle<T> {
 fn _assert_sync_and_send() {
     fn _assert_both<T: Send + Sync>() {}
     _assert_both::<JoinHandle<()>>();
+    _assert_both::<ScopedJoinHandle<()>>();
     _assert_both::<Thread>();
 }
 
@@ -1284,6 +1368,8 @@ mod tests {
     use sync::mpsc::{channel, Sender};
     use result;
     use super::{Builder};
+    use sync::Mutex;
+    use sync::atomic::{AtomicUsize, Ordering};
     use thread;
     use time::Duration;
     use u32;
@@ -1525,6 +1611,78 @@ mod tests {
         assert!(thread::current().id() != spawned_id);
     }
 
+    #[test]
+    fn test_scope_borrows() {
+        let counter = AtomicUsize::new(0);
+        let mut data = vec![1, 2, 3];
+        {
+            let data = &data;
+            let counter = &counter;
+            thread::scope(|s| {
+                for _ in 0..4 {
+                    s.spawn(move || {
+                        counter.fetch_add(data.iter().sum::<usize>(), Ordering::SeqCst);
+                    });
+                }
+            });
+        }
+        // All threads have been joined, so the borrows are over.
+        data.push(4);
+        assert_eq!(counter.load(Ordering::SeqCst), 24);
+    }
+
+    #[test]
+    fn test_scope_join_handle() {
+        let names = Mutex::new(Vec::new());
+        let sum = thread::scope(|s| {
+            let a = s.spawn(|| {
+                names.lock().unwrap().push("a");
+                1
+            });
+            let b = Builder::new().name("b".to_string()).spawn_scoped(s, || {
+                assert_eq!(thread::current().name(), Some("b"));
+                names.lock().unwrap().push("b");
+                2
+            }).unwrap();
+            assert_eq!(b.thread().name(), Some("b"));
+            a.join().unwrap() + b.join().unwrap()
+        });
+        assert_eq!(sum, 3);
+        assert_eq!(names.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_scope_joined_panic_is_handled() {
+        let result = thread::scope(|s| {
+            s.spawn(|| panic!()).join().is_err()
+        });
+        assert!(result);
+    }
+
+    #[test]
+    fn test_scope_unjoined_panic_propagates() {
+        let result = ::panic::catch_unwind(|| {
+            thread::scope(|s| {
+                s.spawn(|| panic!());
+            });
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_scope_waits_for_nested_threads() {
+        let done = AtomicUsize::new(0);
+        thread::scope(|s| {
+            s.spawn(|| {
+                s.spawn(|| {
+                    thread::sleep(Duration::from_millis(10));
+                    done.fetch_add(1, Ordering::SeqCst);
+                });
+            });
+        });
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+    }
+
     // NOTE: the corresponding test for stderr is in run-pass/thread-stderr, due
     // to the test harness apparently interfering with stderr configuration.
 }
@@ -170,6 +170,13 @@ pub struct AngleBracketedParameterData {
     pub lifetimes: Vec<Lifetime>,
     /// The type parameters for this path segment, if present.
     pub types: Vec<P<Ty>>,
+    /// The const parameters for this path segment, if present.
+    ///
+    /// Only literals and blocks are parsed as const arguments, a lone
+    /// identifier ends up in `types` until it is resolved.
+    ///
+    /// E.g., `Foo<3>` or `Foo<{ N }>`.
+    pub consts: Vec<P<Expr>>,
     /// Bindings (equality constraints) on associated types, if present.
     ///
     /// E.g., `Foo<A=Bar>`.
@@ -297,12 +304,23 @@ pub struct TyParam {
     pub span: Span,
 }
 
-/// Represents lifetimes and type parameters attached to a declaration
+/// A const generic parameter, e.g. `const N: usize`.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub struct ConstParam {
+    pub attrs: ThinVec<Attribute>,
+    pub ident: Ident,
+    pub id: NodeId,
+    pub ty: P<Ty>,
+    pub span: Span,
+}
+
+/// Represents lifetimes, type and const parameters attached to a declaration
 /// of a function, enum, trait, etc.
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub struct Generics {
     pub lifetimes: Vec<LifetimeDef>,
     pub ty_params: Vec<TyParam>,
+    pub const_params: Vec<ConstParam>,
     pub where_clause: WhereClause,
     pub span: Span,
 }
@@ -314,8 +332,12 @@ impl Generics {
     pub fn is_type_parameterized(&self) -> bool {
         !self.ty_params.is_empty()
     }
+    pub fn is_const_parameterized(&self) -> bool {
+        !self.const_params.is_empty()
+    }
     pub fn is_parameterized(&self) -> bool {
-        self.is_lt_parameterized() || self.is_type_parameterized()
+        self.is_lt_parameterized() || self.is_type_parameterized() ||
+            self.is_const_parameterized()
     }
     pub fn span_for_name(&self, name: &str) -> Option<Span> {
         for t in &self.ty_params {
@@ -333,6 +355,7 @@ impl Default for Generics {
         Generics {
             lifetimes: Vec::new(),
             ty_params: Vec::new(),
+            const_params: Vec::new(),
             where_clause: WhereClause {
                 id: DUMMY_NODE_ID,
                 predicates: Vec::new(),
@@ -955,7 +978,7 @@ pub enum ExprKind {
     /// A closure (for example, `move |a, b, c| a + b + c`)
     ///
     /// The final span is the span of the argument block `|...|`
-    Closure(CaptureBy, P<FnDecl>, P<Expr>, Span),
+    Closure(CaptureBy, Movability, P<FnDecl>, P<Expr>, Span),
     /// A block (`{ ... }`)
     Block(P<Block>),
     /// A catch block (`catch { ... }`)
@@ -1049,6 +1072,14 @@ pub enum CaptureBy {
     Ref,
 }
 
+/// Whether a closure which is a generator may be moved after it has been
+/// resumed (`static || { yield; }` generators may not).
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug, Copy)]
+pub enum Movability {
+    Static,
+    Movable,
+}
+
 pub type Mac = Spanned<Mac_>;
 
 /// Represents a macro invocation. The Path indicates which macro
@@ -1196,7 +1227,7 @@ pub struct TraitItem {
 pub enum TraitItemKind {
     Const(P<Ty>, Option<P<Expr>>),
     Method(MethodSig, Option<P<Block>>),
-    Type(TyParamBounds, Option<P<Ty>>),
+    Type(Generics, TyParamBounds, Option<P<Ty>>),
     Macro(Mac),
 }
 
@@ -1217,7 +1248,7 @@ pub struct ImplItem {
 pub enum ImplItemKind {
     Const(P<Ty>, P<Expr>),
     Method(MethodSig, P<Block>),
-    Type(P<Ty>),
+    Type(Generics, P<Ty>),
     Macro(Mac),
 }
 
@@ -1451,6 +1482,42 @@ pub struct InlineAsmOutput {
     pub is_indirect: bool,
 }
 
+/// The register an operand of checked inline assembly is placed in.
+///
+/// E.g. `reg` or `"eax"` as in `asm!("mov {}, 2", out(reg) x, in("eax") y)`
+#[derive(Clone, Copy, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub enum InlineAsmRegOrRegClass {
+    /// An explicit register.
+    Reg(Symbol),
+    /// Any register of the class, picked by the register allocator.
+    RegClass(Symbol),
+}
+
+/// The register of a single operand of checked inline assembly.
+#[derive(Clone, Copy, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub struct InlineAsmOperandReg {
+    pub reg: InlineAsmRegOrRegClass,
+    /// Whether the operand is a `lateout` or `inlateout`, which may share its
+    /// register with an input.
+    pub late: bool,
+    pub span: Span,
+}
+
+/// The registers used by checked inline assembly.
+///
+/// The constraints of the operands of checked inline assembly are left empty
+/// by the parser, they are computed from these registers during translation
+/// once the registers have been validated against the target.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub struct InlineAsmRegs {
+    /// The register of each output, in the same order as `InlineAsm::outputs`.
+    pub outputs: Vec<InlineAsmOperandReg>,
+    /// The register of each input, in the same order as `InlineAsm::inputs`.
+    pub inputs: Vec<InlineAsmOperandReg>,
+    /// The registers clobbered with `out("reg") _`.
+    pub clobbers: Vec<(Symbol, Span)>,
+}
+
 /// Inline assembly.
 ///
 /// E.g. `asm!("NOP");`
@@ -1464,6 +1531,9 @@ pub struct InlineAsm {
     pub volatile: bool,
     pub alignstack: bool,
     pub dialect: AsmDialect,
+    /// The registers of the operands, only present for checked inline
+    /// assembly, i.e. `asm!` using operand classes instead of constraints.
+    pub regs: Option<InlineAsmRegs>,
     pub ctxt: SyntaxContext,
 }
 
@@ -1576,6 +1646,27 @@ pub enum Constness {
     NotConst,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub enum IsAsync {
+    /// An `async fn`. Its body is lowered to a generator returned as an
+    /// `impl Future`, which are given the two ids here.
+    Async {
+        closure_id: NodeId,
+        return_impl_trait_id: NodeId,
+    },
+    NotAsync,
+}
+
+impl IsAsync {
+    pub fn is_async(self) -> bool {
+        if let IsAsync::Async { .. } = self {
+            true
+        } else {
+            false
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub enum Defaultness {
     Default,
@@ -1887,7 +1978,7 @@ pub enum ItemKind {
     /// A function declaration (`fn` or `pub fn`).
     ///
     /// E.g. `fn foo(bar: usize) -> usize { .. }`
-    Fn(P<FnDecl>, Unsafety, Spanned<Constness>, Abi, Generics, P<Block>),
+    Fn(P<FnDecl>, Unsafety, Spanned<Constness>, IsAsync, Abi, Generics, P<Block>),
     /// A module declaration (`mod` or `pub mod`).
     ///
     /// E.g. `mod foo;` or `mod foo { .. }`
@@ -1918,6 +2009,10 @@ pub enum ItemKind {
     ///
     /// E.g. `trait Foo { .. }` or `trait Foo<T> { .. }`
     Trait(Unsafety, Generics, TyParamBounds, Vec<TraitItem>),
+    /// A trait alias (`trait` or `pub trait`).
+    ///
+    /// E.g. `trait Foo = Bar + Quux;`
+    TraitAlias(Generics, TyParamBounds),
     // Default trait implementation.
     ///
     /// E.g. `impl Trait for .. {}` or `impl<T> Trait<T> for .. {}`
@@ -1957,6 +2052,7 @@ impl ItemKind {
             ItemKind::Struct(..) => "struct",
             ItemKind::Union(..) => "union",
             ItemKind::Trait(..) => "trait",
+            ItemKind::TraitAlias(..) => "trait alias",
             ItemKind::Mac(..) |
             ItemKind::MacroDef(..) |
             ItemKind::Impl(..) |
@@ -1983,13 +2079,16 @@ pub enum ForeignItemKind {
     /// A foreign static item (`static ext: u8`), with optional mutability
     /// (the boolean is true when mutable)
     Static(P<Ty>, bool),
+    /// A foreign type
+    Ty,
 }
 
 impl ForeignItemKind {
     pub fn descriptive_variant(&self) -> &str {
         match *self {
             ForeignItemKind::Fn(..) => "foreign function",
-            ForeignItemKind::Static(..) => "foreign static item"
+            ForeignItemKind::Static(..) => "foreign static item",
+            ForeignItemKind::Ty => "foreign type",
         }
     }
 }
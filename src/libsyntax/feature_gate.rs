@@ -383,6 +383,39 @@ declare_features! (
 
     // allow '|' at beginning of match arms (RFC 1925)
     (active, match_beginning_vert, "1.21.0", Some(44101)),
+
+    // `async fn` and `await!`
+    (active, async_await, "1.22.0", None),
+
+    // generic parameters of integer, `bool` and `char` type (RFC 2000)
+    (active, const_generics, "1.22.0", Some(44580)),
+
+    // generic associated types (RFC 1598)
+    (active, generic_associated_types, "1.22.0", Some(44265)),
+
+    // `impl Trait` in argument position (RFC 1951)
+    (active, universal_impl_trait, "1.22.0", Some(34511)),
+
+    // `impl Trait` in the return type of trait methods
+    (active, impl_trait_in_traits, "1.22.0", Some(34511)),
+
+    // `main` and `#[test]` functions returning any type implementing `Termination`
+    (active, termination_trait, "1.22.0", None),
+
+    // Default binding modes for matching non-reference patterns against references (RFC 2005)
+    (active, match_default_bindings, "1.22.0", Some(42640)),
+
+    // `#[non_exhaustive]` on structs and enums (RFC 2008)
+    (active, non_exhaustive, "1.22.0", Some(44109)),
+
+    // Trait aliases, e.g. `trait Foo = Bar + Baz;` (RFC 1733)
+    (active, trait_alias, "1.22.0", Some(41517)),
+
+    // Allows `extern { type Foo; }` (RFC 1861)
+    (active, extern_types, "1.22.0", Some(43467)),
+
+    // Bounds implied by the well-formedness of fn and impl input types (RFC 2089)
+    (active, implied_bounds, "1.22.0", Some(44491)),
 );
 
 declare_features! (
@@ -661,6 +694,12 @@ pub const BUILTIN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeG
                                       is just used for rustc unit tests \
                                       and will never be stable",
                                      cfg_fn!(rustc_attrs))),
+    ("rustc_dump_program_clauses", Normal, Gated(Stability::Unstable,
+                                                 "rustc_attrs",
+                                                 "the `#[rustc_dump_program_clauses]` \
+                                                  attribute is just used for rustc unit \
+                                                  tests and will never be stable",
+                                                 cfg_fn!(rustc_attrs))),
     ("rustc_error", Whitelisted, Gated(Stability::Unstable,
                                        "rustc_attrs",
                                        "the `#[rustc_error]` attribute \
@@ -774,6 +813,11 @@ pub const BUILTIN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeG
                                         is an experimental feature",
                                        cfg_fn!(fundamental))),
 
+    ("non_exhaustive", Whitelisted, Gated(Stability::Unstable,
+                                          "non_exhaustive",
+                                          "non exhaustive is an experimental feature",
+                                          cfg_fn!(non_exhaustive))),
+
     ("proc_macro_derive", Normal, Ungated),
 
     ("rustc_copy_clone_marker", Whitelisted, Gated(Stability::Unstable,
@@ -1153,6 +1197,13 @@ macro_rules! gate_feature_post {
 }
 
 impl<'a> PostExpansionVisitor<'a> {
+    fn check_gat(&self, generics: &ast::Generics, span: Span) {
+        if generics.is_parameterized() || !generics.where_clause.predicates.is_empty() {
+            gate_feature_post!(&self, generic_associated_types, span,
+                               "generic associated types are unstable");
+        }
+    }
+
     fn check_abi(&self, abi: Abi, span: Span) {
         match abi {
             Abi::RustIntrinsic => {
@@ -1274,7 +1325,10 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                 self.check_abi(foreign_module.abi, i.span);
             }
 
-            ast::ItemKind::Fn(..) => {
+            ast::ItemKind::Fn(_, _, _, asyncness, ..) => {
+                if asyncness.is_async() {
+                    gate_feature_post!(&self, async_await, i.span, "async fn is unstable");
+                }
                 if attr::contains_name(&i.attrs[..], "plugin_registrar") {
                     gate_feature_post!(&self, plugin_registrar, i.span,
                                        "compiler plugins are experimental and possibly buggy");
@@ -1359,6 +1413,12 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                 gate_feature_post!(&self, decl_macro, i.span, msg);
             }
 
+            ast::ItemKind::TraitAlias(..) => {
+                gate_feature_post!(&self, trait_alias,
+                                   i.span,
+                                   "trait aliases are experimental");
+            }
+
             _ => {}
         }
 
@@ -1375,6 +1435,11 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                               "linking to LLVM intrinsics is experimental");
         }
 
+        if let ast::ForeignItemKind::Ty = i.node {
+            gate_feature_post!(&self, extern_types, i.span,
+                               "extern types are experimental");
+        }
+
         visit::walk_foreign_item(self, i)
     }
 
@@ -1426,6 +1491,11 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                                   e.span,
                                   "yield syntax is experimental");
             }
+            ast::ExprKind::Closure(_, ast::Movability::Static, ..) => {
+                gate_feature_post!(&self, generators,
+                                  e.span,
+                                  "static generators are experimental");
+            }
             ast::ExprKind::Lit(ref lit) => {
                 if let ast::LitKind::Int(_, ref ty) = lit.node {
                     match *ty {
@@ -1489,7 +1559,7 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                 span: Span,
                 _node_id: NodeId) {
         // check for const fn declarations
-        if let FnKind::ItemFn(_, _, _, Spanned { node: ast::Constness::Const, .. }, _, _, _) =
+        if let FnKind::ItemFn(_, _, _, Spanned { node: ast::Constness::Const, .. }, _, _, _, _) =
             fn_kind {
             gate_feature_post!(&self, const_fn, span, "const fn is unstable");
         }
@@ -1499,7 +1569,7 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
         // point.
 
         match fn_kind {
-            FnKind::ItemFn(_, _, _, _, abi, _, _) |
+            FnKind::ItemFn(_, _, _, _, _, abi, _, _) |
             FnKind::Method(_, &ast::MethodSig { abi, .. }, _, _) => {
                 self.check_abi(abi, span);
             }
@@ -1518,9 +1588,12 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                     gate_feature_post!(&self, const_fn, ti.span, "const fn is unstable");
                 }
             }
-            ast::TraitItemKind::Type(_, Some(_)) => {
-                gate_feature_post!(&self, associated_type_defaults, ti.span,
-                                  "associated type defaults are unstable");
+            ast::TraitItemKind::Type(ref generics, _, ref default) => {
+                if default.is_some() {
+                    gate_feature_post!(&self, associated_type_defaults, ti.span,
+                                      "associated type defaults are unstable");
+                }
+                self.check_gat(generics, ti.span);
             }
             _ => {}
         }
@@ -1540,6 +1613,9 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                     gate_feature_post!(&self, const_fn, ii.span, "const fn is unstable");
                 }
             }
+            ast::ImplItemKind::Type(ref generics, _) => {
+                self.check_gat(generics, ii.span);
+            }
             _ => {}
         }
         visit::walk_impl_item(self, ii);
@@ -1552,9 +1628,21 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
                                    "attributes on type parameter bindings are experimental");
             }
         }
+        for c in &g.const_params {
+            gate_feature_post!(&self, const_generics, c.span, "const generics are unstable");
+        }
         visit::walk_generics(self, g)
     }
 
+    fn visit_path_parameters(&mut self, path_span: Span, params: &'a ast::PathParameters) {
+        if let ast::PathParameters::AngleBracketed(ref data) = *params {
+            for c in &data.consts {
+                gate_feature_post!(&self, const_generics, c.span, "const generics are unstable");
+            }
+        }
+        visit::walk_path_parameters(self, path_span, params)
+    }
+
     fn visit_lifetime_def(&mut self, lifetime_def: &'a ast::LifetimeDef) {
         if !lifetime_def.attrs.is_empty() {
             gate_feature_post!(&self, generic_param_attrs, lifetime_def.attrs[0].span,
@@ -897,6 +897,7 @@ mod tests {
                                         span: sp(0,2),
                                         node: ast::Constness::NotConst,
                                     },
+                                    ast::IsAsync::NotAsync,
                                     Abi::Rust,
                                     ast::Generics{ // no idea on either of these:
                                         lifetimes: Vec::new(),
@@ -15,18 +15,18 @@ use ast::Unsafety;
 use ast::{Mod, Arg, Arm, Attribute, BindingMode, TraitItemKind};
 use ast::Block;
 use ast::{BlockCheckMode, CaptureBy};
-use ast::{Constness, Crate};
+use ast::{Constness, ConstParam, Crate};
 use ast::Defaultness;
 use ast::EnumDef;
 use ast::{Expr, ExprKind, RangeLimits};
 use ast::{Field, FnDecl};
 use ast::{ForeignItem, ForeignItemKind, FunctionRetTy};
-use ast::{Ident, ImplItem, Item, ItemKind};
+use ast::{Ident, ImplItem, IsAsync, Item, ItemKind};
 use ast::{Lifetime, LifetimeDef, Lit, LitKind, UintTy};
 use ast::Local;
 use ast::MacStmtStyle;
 use ast::Mac_;
-use ast::{MutTy, Mutability};
+use ast::{Movability, MutTy, Mutability};
 use ast::{Pat, PatKind, PathSegment};
 use ast::{PolyTraitRef, QSelf};
 use ast::{Stmt, StmtKind};
@@ -1281,9 +1281,7 @@ impl<'a> Parser<'a> {
         let lo = self.span;
 
         let (name, node) = if self.eat_keyword(keywords::Type) {
-            let TyParam {ident, bounds, default, ..} = self.parse_ty_param(vec![])?;
-            self.expect(&token::Semi)?;
-            (ident, TraitItemKind::Type(bounds, default))
+            self.parse_trait_item_assoc_ty()?
         } else if self.is_const_item() {
             self.expect_keyword(keywords::Const)?;
             let ident = self.parse_ident()?;
@@ -1878,10 +1876,10 @@ impl<'a> Parser<'a> {
 
             let parameters = if self.eat_lt() {
                 // `<'a, T, A = U>`
-                let (lifetimes, types, bindings) = self.parse_generic_args()?;
+                let (lifetimes, types, consts, bindings) = self.parse_generic_args()?;
                 self.expect_gt()?;
                 let span = lo.to(self.prev_span);
-                AngleBracketedParameterData { lifetimes, types, bindings, span }.into()
+                AngleBracketedParameterData { lifetimes, types, consts, bindings, span }.into()
             } else {
                 // `(T, U) -> R`
                 self.bump(); // `(`
@@ -2108,7 +2106,7 @@ impl<'a> Parser<'a> {
             }
             token::BinOp(token::Or) | token::OrOr => {
                 let lo = self.span;
-                return self.parse_lambda_expr(lo, CaptureBy::Ref, attrs);
+                return self.parse_lambda_expr(lo, Movability::Movable, CaptureBy::Ref, attrs);
             }
             token::OpenDelim(token::Bracket) => {
                 self.bump();
@@ -2153,9 +2151,19 @@ impl<'a> Parser<'a> {
                     hi = path.span;
                     return Ok(self.mk_expr(lo.to(hi), ExprKind::Path(Some(qself), path), attrs));
                 }
+                if self.is_static_closure() {
+                    let lo = self.span;
+                    self.expect_keyword(keywords::Static)?;
+                    let capture_clause = if self.eat_keyword(keywords::Move) {
+                        CaptureBy::Value
+                    } else {
+                        CaptureBy::Ref
+                    };
+                    return self.parse_lambda_expr(lo, Movability::Static, capture_clause, attrs);
+                }
                 if self.eat_keyword(keywords::Move) {
                     let lo = self.prev_span;
-                    return self.parse_lambda_expr(lo, CaptureBy::Value, attrs);
+                    return self.parse_lambda_expr(lo, Movability::Movable, CaptureBy::Value, attrs);
                 }
                 if self.eat_keyword(keywords::If) {
                     return self.parse_if_expr(attrs);
@@ -3045,9 +3053,10 @@ impl<'a> Parser<'a> {
         Ok(self.mk_expr(lo.to(hi), ExprKind::IfLet(pat, expr, thn, els), attrs))
     }
 
-    // `move |args| expr`
+    // `[static] [move] |args| expr`
     pub fn parse_lambda_expr(&mut self,
                              lo: Span,
+                             movability: Movability,
                              capture_clause: CaptureBy,
                              attrs: ThinVec<Attribute>)
                              -> PResult<'a, P<Expr>>
@@ -3069,7 +3078,7 @@ impl<'a> Parser<'a> {
 
         Ok(self.mk_expr(
             lo.to(body.span),
-            ExprKind::Closure(capture_clause, decl, body, lo.to(decl_hi)),
+            ExprKind::Closure(capture_clause, movability, decl, body, lo.to(decl_hi)),
             attrs))
     }
 
@@ -3792,6 +3801,21 @@ impl<'a> Parser<'a> {
         !self.restrictions.contains(RESTRICTION_NO_STRUCT_LITERAL)
     }
 
+    /// true if we are looking at `static |..|`, `static ||` or `static move`
+    fn is_static_closure(&self) -> bool {
+        self.token.is_keyword(keywords::Static) &&
+        self.look_ahead(1, |t| *t == token::BinOp(token::Or) ||
+                        *t == token::OrOr ||
+                        t.is_keyword(keywords::Move))
+    }
+
+    fn is_async_fn(&self) -> bool {
+        self.token.is_keyword(keywords::Async) &&
+        (self.look_ahead(1, |t| t.is_keyword(keywords::Fn)) ||
+         self.look_ahead(1, |t| t.is_keyword(keywords::Unsafe)) &&
+         self.look_ahead(2, |t| t.is_keyword(keywords::Fn)))
+    }
+
     fn is_union_item(&self) -> bool {
         self.token.is_keyword(keywords::Union) &&
         self.look_ahead(1, |t| t.is_ident() && !t.is_reserved_ident())
@@ -3897,10 +3921,11 @@ impl<'a> Parser<'a> {
                 node: StmtKind::Item(macro_def),
                 span: lo.to(self.prev_span),
             }
-        // Starts like a simple path, but not a union item.
+        // Starts like a simple path, but not a union item or an async fn.
         } else if self.token.is_path_start() &&
                   !self.token.is_qpath_start() &&
-                  !self.is_union_item() {
+                  !self.is_union_item() &&
+                  !self.is_async_fn() {
             let pth = self.parse_path(PathStyle::Expr)?;
 
             if !self.eat(&token::Not) {
@@ -4293,12 +4318,58 @@ impl<'a> Parser<'a> {
         })
     }
 
-    /// Parses (possibly empty) list of lifetime and type parameters, possibly including
+    /// Parses the part of an associated type declaration in a trait following
+    /// the `type` keyword:
+    ///
+    /// assoc_ty = IDENT generics? (`:` bounds)? where_clause? (`=` ty)? `;`
+    fn parse_trait_item_assoc_ty(&mut self) -> PResult<'a, (Ident, TraitItemKind)> {
+        let ident = self.parse_ident()?;
+        let mut generics = self.parse_generics()?;
+
+        // Parse optional colon and param bounds.
+        let bounds = if self.eat(&token::Colon) {
+            self.parse_ty_param_bounds()?
+        } else {
+            Vec::new()
+        };
+        generics.where_clause = self.parse_where_clause()?;
+
+        let default = if self.eat(&token::Eq) {
+            Some(self.parse_ty()?)
+        } else {
+            None
+        };
+        self.expect(&token::Semi)?;
+
+        Ok((ident, TraitItemKind::Type(generics, bounds, default)))
+    }
+
+    /// Matches constparam = `const` IDENT `:` ty
+    fn parse_const_param(&mut self, preceding_attrs: Vec<Attribute>) -> PResult<'a, ConstParam> {
+        let lo = self.span;
+        self.expect_keyword(keywords::Const)?;
+        let ident = self.parse_ident()?;
+        self.expect(&token::Colon)?;
+        let ty = self.parse_ty()?;
+
+        Ok(ConstParam {
+            attrs: preceding_attrs.into(),
+            ident,
+            id: ast::DUMMY_NODE_ID,
+            ty,
+            span: lo.to(self.prev_span),
+        })
+    }
+
+    /// Parses (possibly empty) list of lifetime, type and const parameters, possibly including
     /// trailing comma and erroneous trailing attributes.
-    pub fn parse_generic_params(&mut self) -> PResult<'a, (Vec<LifetimeDef>, Vec<TyParam>)> {
+    pub fn parse_generic_params(&mut self)
+                                -> PResult<'a, (Vec<LifetimeDef>, Vec<TyParam>, Vec<ConstParam>)> {
         let mut lifetime_defs = Vec::new();
         let mut ty_params = Vec::new();
+        let mut const_params = Vec::new();
         let mut seen_ty_param = false;
+        let mut seen_const_param = false;
         loop {
             let attrs = self.parse_outer_attributes()?;
             if self.check_lifetime() {
@@ -4314,18 +4385,32 @@ impl<'a> Parser<'a> {
                     lifetime,
                     bounds,
                 });
-                if seen_ty_param {
+                if seen_ty_param || seen_const_param {
                     self.span_err(self.prev_span,
                         "lifetime parameters must be declared prior to type parameters");
                 }
+            } else if self.check_keyword(keywords::Const) {
+                // Parse const parameter.
+                const_params.push(self.parse_const_param(attrs)?);
+                seen_const_param = true;
             } else if self.check_ident() {
                 // Parse type parameter.
                 ty_params.push(self.parse_ty_param(attrs)?);
+                if seen_const_param {
+                    self.span_err(self.prev_span,
+                        "type parameters must be declared prior to const parameters");
+                }
                 seen_ty_param = true;
             } else {
                 // Check for trailing attributes and stop parsing.
                 if !attrs.is_empty() {
-                    let param_kind = if seen_ty_param { "type" } else { "lifetime" };
+                    let param_kind = if seen_const_param {
+                        "const"
+                    } else if seen_ty_param {
+                        "type"
+                    } else {
+                        "lifetime"
+                    };
                     self.span_err(attrs[0].span,
                         &format!("trailing attribute after {} parameters", param_kind));
                 }
@@ -4336,7 +4421,7 @@ impl<'a> Parser<'a> {
                 break
             }
         }
-        Ok((lifetime_defs, ty_params))
+        Ok((lifetime_defs, ty_params, const_params))
     }
 
     /// Parse a set of optional generic type parameter declarations. Where
@@ -4351,11 +4436,12 @@ impl<'a> Parser<'a> {
 
         let span_lo = self.span;
         if self.eat_lt() {
-            let (lifetime_defs, ty_params) = self.parse_generic_params()?;
+            let (lifetime_defs, ty_params, const_params) = self.parse_generic_params()?;
             self.expect_gt()?;
             Ok(ast::Generics {
                 lifetimes: lifetime_defs,
                 ty_params,
+                const_params,
                 where_clause: WhereClause {
                     id: ast::DUMMY_NODE_ID,
                     predicates: Vec::new(),
@@ -4368,19 +4454,33 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// Parses (possibly empty) list of lifetime and type arguments and associated type bindings,
-    /// possibly including trailing comma.
-    fn parse_generic_args(&mut self) -> PResult<'a, (Vec<Lifetime>, Vec<P<Ty>>, Vec<TypeBinding>)> {
+    /// Checks whether the next generic argument is a const argument, i.e. a
+    /// (possibly negated) literal or a block.
+    fn check_const_arg(&mut self) -> bool {
+        self.token.is_lit() ||
+        self.token.is_keyword(keywords::True) ||
+        self.token.is_keyword(keywords::False) ||
+        self.token == token::OpenDelim(token::Brace) ||
+        self.token == token::BinOp(token::Minus) && self.look_ahead(1, |t| t.is_lit())
+    }
+
+    /// Parses (possibly empty) list of lifetime, type and const arguments and associated type
+    /// bindings, possibly including trailing comma.
+    fn parse_generic_args(&mut self)
+                          -> PResult<'a, (Vec<Lifetime>, Vec<P<Ty>>, Vec<P<Expr>>,
+                                          Vec<TypeBinding>)> {
         let mut lifetimes = Vec::new();
         let mut types = Vec::new();
+        let mut consts = Vec::new();
         let mut bindings = Vec::new();
         let mut seen_type = false;
+        let mut seen_const = false;
         let mut seen_binding = false;
         loop {
             if self.check_lifetime() && self.look_ahead(1, |t| t != &token::BinOp(token::Plus)) {
                 // Parse lifetime argument.
                 lifetimes.push(self.expect_lifetime());
-                if seen_type || seen_binding {
+                if seen_type || seen_const || seen_binding {
                     self.span_err(self.prev_span,
                         "lifetime parameters must be declared prior to type parameters");
                 }
@@ -4397,12 +4497,37 @@ impl<'a> Parser<'a> {
                     span: lo.to(self.prev_span),
                 });
                 seen_binding = true;
+            } else if self.check_const_arg() {
+                // Parse const argument.
+                let expr = if self.token == token::OpenDelim(token::Brace) {
+                    let lo = self.span;
+                    self.parse_block_expr(lo, BlockCheckMode::Default, ThinVec::new())?
+                } else {
+                    self.parse_pat_literal_maybe_minus()?
+                };
+                if seen_binding {
+                    self.span_err(expr.span,
+                        "const parameters must be declared prior to associated type bindings");
+                }
+                consts.push(expr);
+                seen_const = true;
+            } else if seen_const && !seen_binding && self.check_ident() &&
+                      self.look_ahead(1, |t| t == &token::Comma || t == &token::Gt) {
+                // A bare identifier following a const argument can only name a const
+                // parameter, so parse it as a path expression rather than as a type.
+                let lo = self.span;
+                let path = self.parse_path(PathStyle::Expr)?;
+                let span = lo.to(self.prev_span);
+                consts.push(self.mk_expr(span, ExprKind::Path(None, path), ThinVec::new()));
             } else if self.check_type() {
                 // Parse type argument.
                 types.push(self.parse_ty()?);
                 if seen_binding {
                     self.span_err(types[types.len() - 1].span,
                         "type parameters must be declared prior to associated type bindings");
+                } else if seen_const {
+                    self.span_err(types[types.len() - 1].span,
+                        "type parameters must be declared prior to const parameters");
                 }
                 seen_type = true;
             } else {
@@ -4413,7 +4538,7 @@ impl<'a> Parser<'a> {
                 break
             }
         }
-        Ok((lifetimes, types, bindings))
+        Ok((lifetimes, types, consts, bindings))
     }
 
     /// Parses an optional `where` clause and places it in `generics`.
@@ -4768,13 +4893,15 @@ impl<'a> Parser<'a> {
     fn parse_item_fn(&mut self,
                      unsafety: Unsafety,
                      constness: Spanned<Constness>,
+                     asyncness: IsAsync,
                      abi: abi::Abi)
                      -> PResult<'a, ItemInfo> {
         let (ident, mut generics) = self.parse_fn_header()?;
         let decl = self.parse_fn_decl(false)?;
         generics.where_clause = self.parse_where_clause()?;
         let (inner_attrs, body) = self.parse_inner_attrs_and_block()?;
-        Ok((ident, ItemKind::Fn(decl, unsafety, constness, abi, generics, body), Some(inner_attrs)))
+        let kind = ItemKind::Fn(decl, unsafety, constness, asyncness, abi, generics, body);
+        Ok((ident, kind, Some(inner_attrs)))
     }
 
     /// true if we are looking at `const ID`, false for things like `const fn` etc
@@ -4836,10 +4963,12 @@ impl<'a> Parser<'a> {
         let defaultness = self.parse_defaultness()?;
         let (name, node) = if self.eat_keyword(keywords::Type) {
             let name = self.parse_ident()?;
+            let mut generics = self.parse_generics()?;
+            generics.where_clause = self.parse_where_clause()?;
             self.expect(&token::Eq)?;
             let typ = self.parse_ty()?;
             self.expect(&token::Semi)?;
-            (name, ast::ImplItemKind::Type(typ))
+            (name, ast::ImplItemKind::Type(generics, typ))
         } else if self.is_const_item() {
             self.expect_keyword(keywords::Const)?;
             let name = self.parse_ident()?;
@@ -4965,7 +5094,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// Parse trait Foo { ... }
+    /// Parse trait Foo { ... } or trait Foo = Bar;
     fn parse_item_trait(&mut self, unsafety: Unsafety) -> PResult<'a, ItemInfo> {
         let ident = self.parse_ident()?;
         let mut tps = self.parse_generics()?;
@@ -4977,6 +5106,21 @@ impl<'a> Parser<'a> {
             Vec::new()
         };
 
+        if self.eat(&token::Eq) {
+            // it's a trait alias
+            let alias_lo = self.prev_span;
+            let alias_bounds = self.parse_ty_param_bounds()?;
+            tps.where_clause = self.parse_where_clause()?;
+            self.expect(&token::Semi)?;
+            if unsafety != Unsafety::Normal {
+                self.span_err(alias_lo, "trait aliases cannot be unsafe");
+            }
+            if !bounds.is_empty() {
+                self.span_err(alias_lo, "bounds are not allowed on trait aliases");
+            }
+            return Ok((ident, ItemKind::TraitAlias(tps, alias_bounds), None));
+        }
+
         tps.where_clause = self.parse_where_clause()?;
 
         self.expect(&token::OpenDelim(token::Brace))?;
@@ -5093,11 +5237,14 @@ impl<'a> Parser<'a> {
     fn parse_late_bound_lifetime_defs(&mut self) -> PResult<'a, Vec<LifetimeDef>> {
         if self.eat_keyword(keywords::For) {
             self.expect_lt()?;
-            let (lifetime_defs, ty_params) = self.parse_generic_params()?;
+            let (lifetime_defs, ty_params, const_params) = self.parse_generic_params()?;
             self.expect_gt()?;
             if !ty_params.is_empty() {
                 self.span_err(ty_params[0].span,
                               "only lifetime parameters can be used in this context");
+            } else if !const_params.is_empty() {
+                self.span_err(const_params[0].span,
+                              "only lifetime parameters can be used in this context");
             }
             Ok(lifetime_defs)
         } else {
@@ -5598,6 +5745,24 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parse a type from a foreign module
+    fn parse_item_foreign_type(&mut self, vis: ast::Visibility, lo: Span, attrs: Vec<Attribute>)
+                             -> PResult<'a, ForeignItem> {
+        self.expect_keyword(keywords::Type)?;
+
+        let ident = self.parse_ident()?;
+        let hi = self.span;
+        self.expect(&token::Semi)?;
+        Ok(ast::ForeignItem {
+            ident,
+            attrs,
+            node: ForeignItemKind::Ty,
+            id: ast::DUMMY_NODE_ID,
+            span: lo.to(hi),
+            vis,
+        })
+    }
+
     /// Parse extern crate links
     ///
     /// # Examples
@@ -5808,6 +5973,7 @@ impl<'a> Parser<'a> {
                 let (ident, item_, extra_attrs) =
                     self.parse_item_fn(Unsafety::Normal,
                                        respan(fn_span, Constness::NotConst),
+                                       IsAsync::NotAsync,
                                        abi)?;
                 let prev_span = self.prev_span;
                 let item = self.mk_item(lo.to(prev_span),
@@ -5823,7 +5989,7 @@ impl<'a> Parser<'a> {
             self.unexpected()?;
         }
 
-        if self.eat_keyword(keywords::Static) {
+        if !self.is_static_closure() && self.eat_keyword(keywords::Static) {
             // STATIC ITEM
             let m = if self.eat_keyword(keywords::Mut) {
                 Mutability::Mutable
@@ -5854,6 +6020,7 @@ impl<'a> Parser<'a> {
                 let (ident, item_, extra_attrs) =
                     self.parse_item_fn(unsafety,
                                        respan(const_span, Constness::Const),
+                                       IsAsync::NotAsync,
                                        Abi::Rust)?;
                 let prev_span = self.prev_span;
                 let item = self.mk_item(lo.to(prev_span),
@@ -5917,6 +6084,29 @@ impl<'a> Parser<'a> {
                                     maybe_append(attrs, extra_attrs));
             return Ok(Some(item));
         }
+        if self.is_async_fn() {
+            // ASYNC FUNCTION ITEM
+            self.bump();
+            let unsafety = self.parse_unsafety()?;
+            self.expect_keyword(keywords::Fn)?;
+            let fn_span = self.prev_span;
+            let asyncness = IsAsync::Async {
+                closure_id: ast::DUMMY_NODE_ID,
+                return_impl_trait_id: ast::DUMMY_NODE_ID,
+            };
+            let (ident, item_, extra_attrs) =
+                self.parse_item_fn(unsafety,
+                                   respan(fn_span, Constness::NotConst),
+                                   asyncness,
+                                   Abi::Rust)?;
+            let prev_span = self.prev_span;
+            let item = self.mk_item(lo.to(prev_span),
+                                    ident,
+                                    item_,
+                                    visibility,
+                                    maybe_append(attrs, extra_attrs));
+            return Ok(Some(item));
+        }
         if self.check_keyword(keywords::Fn) {
             // FUNCTION ITEM
             self.bump();
@@ -5924,6 +6114,7 @@ impl<'a> Parser<'a> {
             let (ident, item_, extra_attrs) =
                 self.parse_item_fn(Unsafety::Normal,
                                    respan(fn_span, Constness::NotConst),
+                                   IsAsync::NotAsync,
                                    Abi::Rust)?;
             let prev_span = self.prev_span;
             let item = self.mk_item(lo.to(prev_span),
@@ -5947,6 +6138,7 @@ impl<'a> Parser<'a> {
             let (ident, item_, extra_attrs) =
                 self.parse_item_fn(Unsafety::Unsafe,
                                    respan(fn_span, Constness::NotConst),
+                                   IsAsync::NotAsync,
                                    abi)?;
             let prev_span = self.prev_span;
             let item = self.mk_item(lo.to(prev_span),
@@ -6072,6 +6264,10 @@ impl<'a> Parser<'a> {
         if self.check_keyword(keywords::Fn) {
             return Ok(Some(self.parse_item_foreign_fn(visibility, lo, attrs)?));
         }
+        // FOREIGN TYPE
+        if self.check_keyword(keywords::Type) {
+            return Ok(Some(self.parse_item_foreign_type(visibility, lo, attrs)?));
+        }
 
         // FIXME #5668: this will occur for a macro invocation:
         match self.parse_macro_use_or_failure(attrs, true, false, lo, visibility)? {
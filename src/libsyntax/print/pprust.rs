@@ -372,7 +372,7 @@ pub fn fun_to_string(decl: &ast::FnDecl,
                      -> String {
     to_string(|s| {
         s.head("")?;
-        s.print_fn(decl, unsafety, constness, Abi::Rust, Some(name),
+        s.print_fn(decl, unsafety, constness, ast::IsAsync::NotAsync, Abi::Rust, Some(name),
                    generics, &ast::Visibility::Inherited)?;
         s.end()?; // Close the head box
         s.end() // Close the outer box
@@ -1028,6 +1028,7 @@ impl<'a> State<'a> {
                 let generics = ast::Generics {
                     lifetimes: f.lifetimes.clone(),
                     ty_params: Vec::new(),
+                    const_params: Vec::new(),
                     where_clause: ast::WhereClause {
                         id: ast::DUMMY_NODE_ID,
                         predicates: Vec::new(),
@@ -1091,6 +1092,7 @@ impl<'a> State<'a> {
                 self.head("")?;
                 self.print_fn(decl, ast::Unsafety::Normal,
                               ast::Constness::NotConst,
+                              ast::IsAsync::NotAsync,
                               Abi::Rust, Some(item.ident),
                               generics, &item.vis)?;
                 self.end()?; // end head-ibox
@@ -1109,6 +1111,13 @@ impl<'a> State<'a> {
                 self.end()?; // end the head-ibox
                 self.end() // end the outer cbox
             }
+            ast::ForeignItemKind::Ty => {
+                self.head(&visibility_qualified(&item.vis, "type"))?;
+                self.print_ident(item.ident)?;
+                self.s.word(";")?;
+                self.end()?; // end the head-ibox
+                self.end() // end the outer cbox
+            }
         }
     }
 
@@ -1134,14 +1143,17 @@ impl<'a> State<'a> {
 
     fn print_associated_type(&mut self,
                              ident: ast::Ident,
+                             generics: &ast::Generics,
                              bounds: Option<&ast::TyParamBounds>,
                              ty: Option<&ast::Ty>)
                              -> io::Result<()> {
         self.word_space("type")?;
         self.print_ident(ident)?;
+        self.print_generics(generics)?;
         if let Some(bounds) = bounds {
             self.print_bounds(":", bounds)?;
         }
+        self.print_where_clause(&generics.where_clause)?;
         if let Some(ty) = ty {
             self.s.space()?;
             self.word_space("=")?;
@@ -1211,12 +1223,14 @@ impl<'a> State<'a> {
                 self.s.word(";")?;
                 self.end()?; // end the outer cbox
             }
-            ast::ItemKind::Fn(ref decl, unsafety, constness, abi, ref typarams, ref body) => {
+            ast::ItemKind::Fn(ref decl, unsafety, constness, asyncness, abi, ref typarams,
+                              ref body) => {
                 self.head("")?;
                 self.print_fn(
                     decl,
                     unsafety,
                     constness.node,
+                    asyncness,
                     abi,
                     Some(item.ident),
                     typarams,
@@ -1354,6 +1368,18 @@ impl<'a> State<'a> {
                 }
                 self.bclose(item.span)?;
             }
+            ast::ItemKind::TraitAlias(ref generics, ref bounds) => {
+                self.head("")?;
+                self.print_visibility(&item.vis)?;
+                self.word_nbsp("trait")?;
+                self.print_ident(item.ident)?;
+                self.print_generics(generics)?;
+                self.print_bounds(" =", bounds)?;
+                self.print_where_clause(&generics.where_clause)?;
+                self.s.word(";")?;
+                self.end()?; // end inner head-block
+                self.end()?; // end outer head-block
+            }
             ast::ItemKind::Mac(codemap::Spanned { ref node, .. }) => {
                 self.print_path(&node.path, false, 0, false)?;
                 self.s.word("! ")?;
@@ -1528,6 +1554,7 @@ impl<'a> State<'a> {
         self.print_fn(&m.decl,
                       m.unsafety,
                       m.constness.node,
+                      ast::IsAsync::NotAsync,
                       m.abi,
                       Some(ident),
                       &m.generics,
@@ -1558,8 +1585,8 @@ impl<'a> State<'a> {
                     self.s.word(";")?;
                 }
             }
-            ast::TraitItemKind::Type(ref bounds, ref default) => {
-                self.print_associated_type(ti.ident, Some(bounds),
+            ast::TraitItemKind::Type(ref generics, ref bounds, ref default) => {
+                self.print_associated_type(ti.ident, generics, Some(bounds),
                                            default.as_ref().map(|ty| &**ty))?;
             }
             ast::TraitItemKind::Macro(codemap::Spanned { ref node, .. }) => {
@@ -1593,8 +1620,8 @@ impl<'a> State<'a> {
                 self.nbsp()?;
                 self.print_block_with_attrs(body, &ii.attrs)?;
             }
-            ast::ImplItemKind::Type(ref ty) => {
-                self.print_associated_type(ii.ident, None, Some(ty))?;
+            ast::ImplItemKind::Type(ref generics, ref ty) => {
+                self.print_associated_type(ii.ident, generics, None, Some(ty))?;
             }
             ast::ImplItemKind::Macro(codemap::Spanned { ref node, .. }) => {
                 // code copied from ItemKind::Mac:
@@ -2126,7 +2153,8 @@ impl<'a> State<'a> {
                 }
                 self.bclose_(expr.span, INDENT_UNIT)?;
             }
-            ast::ExprKind::Closure(capture_clause, ref decl, ref body, _) => {
+            ast::ExprKind::Closure(capture_clause, movability, ref decl, ref body, _) => {
+                self.print_movability(movability)?;
                 self.print_capture_clause(capture_clause)?;
 
                 self.print_fn_block_args(decl)?;
@@ -2448,6 +2476,17 @@ impl<'a> State<'a> {
                         comma = true;
                 }
 
+                if !data.consts.is_empty() {
+                    if comma {
+                        self.word_space(",")?
+                    }
+                    self.commasep(
+                        Inconsistent,
+                        &data.consts,
+                        |s, e| s.print_expr(e))?;
+                        comma = true;
+                }
+
                 for binding in data.bindings.iter() {
                     if comma {
                         self.word_space(",")?
@@ -2688,11 +2727,12 @@ impl<'a> State<'a> {
                     decl: &ast::FnDecl,
                     unsafety: ast::Unsafety,
                     constness: ast::Constness,
+                    asyncness: ast::IsAsync,
                     abi: abi::Abi,
                     name: Option<ast::Ident>,
                     generics: &ast::Generics,
                     vis: &ast::Visibility) -> io::Result<()> {
-        self.print_fn_header_info(unsafety, constness, abi, vis)?;
+        self.print_fn_header_info(unsafety, constness, asyncness, abi, vis)?;
 
         if let Some(name) = name {
             self.nbsp()?;
@@ -2738,6 +2778,14 @@ impl<'a> State<'a> {
         }
     }
 
+    pub fn print_movability(&mut self, movability: ast::Movability)
+                                -> io::Result<()> {
+        match movability {
+            ast::Movability::Static => self.word_space("static"),
+            ast::Movability::Movable => Ok(()),
+        }
+    }
+
     pub fn print_capture_clause(&mut self, capture_clause: ast::CaptureBy)
                                 -> io::Result<()> {
         match capture_clause {
@@ -2809,7 +2857,8 @@ impl<'a> State<'a> {
                           generics: &ast::Generics)
                           -> io::Result<()>
     {
-        let total = generics.lifetimes.len() + generics.ty_params.len();
+        let total = generics.lifetimes.len() + generics.ty_params.len() +
+                    generics.const_params.len();
         if total == 0 {
             return Ok(());
         }
@@ -2826,10 +2875,14 @@ impl<'a> State<'a> {
                 let lifetime_def = &generics.lifetimes[idx];
                 s.print_outer_attributes_inline(&lifetime_def.attrs)?;
                 s.print_lifetime_bounds(&lifetime_def.lifetime, &lifetime_def.bounds)
-            } else {
+            } else if idx < generics.lifetimes.len() + generics.ty_params.len() {
                 let idx = idx - generics.lifetimes.len();
                 let param = &generics.ty_params[idx];
                 s.print_ty_param(param)
+            } else {
+                let idx = idx - generics.lifetimes.len() - generics.ty_params.len();
+                let param = &generics.const_params[idx];
+                s.print_const_param(param)
             }
         })?;
 
@@ -2851,6 +2904,14 @@ impl<'a> State<'a> {
         }
     }
 
+    pub fn print_const_param(&mut self, param: &ast::ConstParam) -> io::Result<()> {
+        self.print_outer_attributes_inline(&param.attrs)?;
+        self.word_space("const")?;
+        self.print_ident(param.ident)?;
+        self.word_space(":")?;
+        self.print_type(&param.ty)
+    }
+
     pub fn print_where_clause(&mut self, where_clause: &ast::WhereClause)
                               -> io::Result<()> {
         if where_clause.predicates.is_empty() {
@@ -3008,6 +3069,7 @@ impl<'a> State<'a> {
         let generics = ast::Generics {
             lifetimes: Vec::new(),
             ty_params: Vec::new(),
+            const_params: Vec::new(),
             where_clause: ast::WhereClause {
                 id: ast::DUMMY_NODE_ID,
                 predicates: Vec::new(),
@@ -3018,6 +3080,7 @@ impl<'a> State<'a> {
         self.print_fn(decl,
                       unsafety,
                       ast::Constness::NotConst,
+                      ast::IsAsync::NotAsync,
                       abi,
                       name,
                       &generics,
@@ -3083,6 +3146,7 @@ impl<'a> State<'a> {
     pub fn print_fn_header_info(&mut self,
                                 unsafety: ast::Unsafety,
                                 constness: ast::Constness,
+                                asyncness: ast::IsAsync,
                                 abi: Abi,
                                 vis: &ast::Visibility) -> io::Result<()> {
         self.s.word(&visibility_qualified(vis, ""))?;
@@ -3092,6 +3156,10 @@ impl<'a> State<'a> {
             ast::Constness::Const => self.word_nbsp("const")?
         }
 
+        if asyncness.is_async() {
+            self.word_nbsp("async")?;
+        }
+
         self.print_unsafety(unsafety)?;
 
         if abi != Abi::Rust {
@@ -31,6 +31,7 @@ use ext::base::{ExtCtxt, Resolver};
 use ext::build::AstBuilder;
 use ext::expand::ExpansionConfig;
 use ext::hygiene::{Mark, SyntaxContext};
+use feature_gate::Features;
 use fold::Folder;
 use util::move_map::MoveMap;
 use fold;
@@ -74,7 +75,8 @@ pub fn modify_for_testing(sess: &ParseSess,
                           resolver: &mut Resolver,
                           should_test: bool,
                           krate: ast::Crate,
-                          span_diagnostic: &errors::Handler) -> ast::Crate {
+                          span_diagnostic: &errors::Handler,
+                          features: &Features) -> ast::Crate {
     // Check for #[reexport_test_harness_main = "some_name"] which
     // creates a `use some_name = __test::main;`. This needs to be
     // unconditional, so that the attribute is still marked as used in
@@ -84,7 +86,8 @@ pub fn modify_for_testing(sess: &ParseSess,
                                            "reexport_test_harness_main");
 
     if should_test {
-        generate_test_harness(sess, resolver, reexport_test_harness_main, krate, span_diagnostic)
+        generate_test_harness(sess, resolver, reexport_test_harness_main,
+                              krate, span_diagnostic, features)
     } else {
         krate
     }
@@ -121,10 +124,14 @@ impl<'a> fold::Folder for TestHarnessGenerator<'a> {
 
         if is_test_fn(&self.cx, &i) || is_bench_fn(&self.cx, &i) {
             match i.node {
-                ast::ItemKind::Fn(_, ast::Unsafety::Unsafe, _, _, _, _) => {
+                ast::ItemKind::Fn(_, ast::Unsafety::Unsafe, _, _, _, _, _) => {
                     let diag = self.cx.span_diagnostic;
                     panic!(diag.span_fatal(i.span, "unsafe functions cannot be used for tests"));
                 }
+                ast::ItemKind::Fn(_, _, _, ast::IsAsync::Async { .. }, _, _, _) => {
+                    let diag = self.cx.span_diagnostic;
+                    panic!(diag.span_fatal(i.span, "async functions cannot be used for tests"));
+                }
                 _ => {
                     debug!("this is a test function");
                     let test = Test {
@@ -265,16 +272,22 @@ fn generate_test_harness(sess: &ParseSess,
                          resolver: &mut Resolver,
                          reexport_test_harness_main: Option<Symbol>,
                          krate: ast::Crate,
-                         sd: &errors::Handler) -> ast::Crate {
+                         sd: &errors::Handler,
+                         features: &Features) -> ast::Crate {
     // Remove the entry points
     let mut cleaner = EntryPointCleaner { depth: 0 };
     let krate = cleaner.fold_crate(krate);
 
     let mark = Mark::fresh(Mark::root());
 
+    let econfig = ExpansionConfig {
+        features: Some(features),
+        ..ExpansionConfig::default("test".to_string())
+    };
+
     let mut cx: TestCtxt = TestCtxt {
         span_diagnostic: sd,
-        ext_cx: ExtCtxt::new(sess, ExpansionConfig::default("test".to_string()), resolver),
+        ext_cx: ExtCtxt::new(sess, econfig, resolver),
         path: Vec::new(),
         testfns: Vec::new(),
         reexport_test_harness_main,
@@ -319,16 +332,21 @@ enum HasTestSignature {
 fn is_test_fn(cx: &TestCtxt, i: &ast::Item) -> bool {
     let has_test_attr = attr::contains_name(&i.attrs, "test");
 
-    fn has_test_signature(i: &ast::Item) -> HasTestSignature {
+    fn has_test_signature(cx: &TestCtxt, i: &ast::Item) -> HasTestSignature {
         match i.node {
-          ast::ItemKind::Fn(ref decl, _, _, _, ref generics, _) => {
+          ast::ItemKind::Fn(ref decl, _, _, _, _, ref generics, _) => {
             let no_output = match decl.output {
                 ast::FunctionRetTy::Default(..) => true,
                 ast::FunctionRetTy::Ty(ref t) if t.node == ast::TyKind::Tup(vec![]) => true,
                 _ => false
             };
+            // With `termination_trait`, a test may also return any type
+            // implementing `Termination`. That bound can't be checked before
+            // resolve, so the harness passes the returned value to
+            // `test::assert_test_result` and leaves the check to typeck.
+            let valid_output = no_output || cx.ext_cx.ecfg.enable_termination_trait();
             if decl.inputs.is_empty()
-                   && no_output
+                   && valid_output
                    && !generics.is_parameterized() {
                 Yes
             } else {
@@ -341,15 +359,19 @@ fn is_test_fn(cx: &TestCtxt, i: &ast::Item) -> bool {
 
     if has_test_attr {
         let diag = cx.span_diagnostic;
-        match has_test_signature(i) {
+        match has_test_signature(cx, i) {
             Yes => {},
+            No if cx.ext_cx.ecfg.enable_termination_trait() => {
+                diag.span_err(i.span, "functions used as tests must have signature fn() -> T \
+                                       where T: Termination")
+            }
             No => diag.span_err(i.span, "functions used as tests must have signature fn() -> ()"),
             NotEvenAFunction => diag.span_err(i.span,
                                               "only functions may be used as tests"),
         }
     }
 
-    has_test_attr && has_test_signature(i) == Yes
+    has_test_attr && has_test_signature(cx, i) == Yes
 }
 
 fn is_bench_fn(cx: &TestCtxt, i: &ast::Item) -> bool {
@@ -357,7 +379,7 @@ fn is_bench_fn(cx: &TestCtxt, i: &ast::Item) -> bool {
 
     fn has_test_signature(i: &ast::Item) -> bool {
         match i.node {
-            ast::ItemKind::Fn(ref decl, _, _, _, ref generics, _) => {
+            ast::ItemKind::Fn(ref decl, _, _, _, _, ref generics, _) => {
                 let input_cnt = decl.inputs.len();
                 let no_output = match decl.output {
                     ast::FunctionRetTy::Default(..) => true,
@@ -503,6 +525,7 @@ fn mk_main(cx: &mut TestCtxt) -> P<ast::Item> {
     let main = ast::ItemKind::Fn(ecx.fn_decl(vec![], main_ret_ty),
                            ast::Unsafety::Normal,
                            dummy_spanned(ast::Constness::NotConst),
+                           ast::IsAsync::NotAsync,
                            ::abi::Abi::Rust, ast::Generics::default(), main_body);
     P(ast::Item {
         ident: Ident::from_str("main"),
@@ -694,7 +717,17 @@ fn mk_test_desc_and_fn_rec(cx: &TestCtxt, test: &Test) -> P<ast::Expr> {
     };
     visible_path.extend(path);
 
-    let fn_expr = ecx.expr_path(ecx.path_global(span, visible_path));
+    let mut fn_expr = ecx.expr_path(ecx.path_global(span, visible_path));
+
+    // With `termination_trait`, tests may return any `Termination` type, so
+    // the test is run as `|| self::test::assert_test_result($fn_expr())`.
+    if !test.bench && ecx.ecfg.enable_termination_trait() {
+        let call = ecx.expr_call(span, fn_expr, vec![]);
+        let assert_result = ecx.expr_call(span,
+                                          ecx.expr_path(test_path("assert_test_result")),
+                                          vec![call]);
+        fn_expr = ecx.lambda0(span, assert_result);
+    }
 
     let variant_name = if test.bench { "StaticBenchFn" } else { "StaticTestFn" };
     // self::test::$variant_name($fn_expr)
@@ -12,13 +12,15 @@
 //
 use self::State::*;
 
+use std::collections::HashMap;
+
 use syntax::ast;
 use syntax::ext::base;
 use syntax::ext::base::*;
 use syntax::feature_gate;
 use syntax::parse::{self, token};
 use syntax::ptr::P;
-use syntax::symbol::Symbol;
+use syntax::symbol::{keywords, Symbol};
 use syntax::ast::AsmDialect;
 use syntax_pos::Span;
 use syntax::tokenstream;
@@ -60,6 +62,13 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt,
         return DummyResult::expr(sp);
     }
 
+    // `asm!("template", operands...)` is the checked form, anything else
+    // (`asm!("template" : outputs : inputs ...)`) passes constraints straight
+    // through to LLVM.
+    if let Some(&tokenstream::TokenTree::Token(_, token::Comma)) = tts.get(1) {
+        return expand_checked_asm(cx, sp, tts);
+    }
+
     // Split the tts before the first colon, to avoid `asm!("x": y)`  being
     // parsed as `asm!(z)` with `z = "x": y` which is type ascription.
     let first_colon = tts.iter()
@@ -250,9 +259,347 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt,
             volatile,
             alignstack,
             dialect,
+            regs: None,
+            ctxt: cx.backtrace(),
+        })),
+        span: sp,
+        attrs: ast::ThinVec::new(),
+    }))
+}
+
+/// An operand of checked inline assembly, before it is split into outputs
+/// and inputs.
+struct Operand {
+    kind: OperandKind,
+    reg: ast::InlineAsmRegOrRegClass,
+    expr: Option<P<ast::Expr>>,
+    span: Span,
+}
+
+#[derive(PartialEq)]
+enum OperandKind {
+    In,
+    Out { late: bool },
+    InOut { late: bool },
+}
+
+/// Where an operand ends up in the LLVM operand list.
+#[derive(Clone, Copy)]
+enum Slot {
+    Output(usize),
+    Input(usize),
+    /// A discarded output, i.e. a clobber.
+    Discarded,
+}
+
+const CHECKED_OPTIONS: &'static [&'static str] =
+    &["pure", "nomem", "nostack", "att_syntax", "intel_syntax"];
+
+/// Expands inline assembly in its checked form:
+///
+/// ```text
+/// asm!("template", [name =] in|out|lateout|inout|inlateout(reg) expr, ...,
+///      options(...))
+/// ```
+///
+/// Operands are referred to as `{}`, `{0}` or `{name}` in the template and are
+/// placed in either an explicit register (`"eax"`) or any register of a
+/// class (`reg`). The registers are validated during type checking.
+fn expand_checked_asm<'cx>(cx: &'cx mut ExtCtxt,
+                           sp: Span,
+                           tts: &[tokenstream::TokenTree])
+                           -> Box<base::MacResult + 'cx> {
+    let mut p = cx.new_parser_from_tts(tts);
+
+    let template_expr = panictry!(p.parse_expr());
+    let template_sp = template_expr.span;
+    let (template, asm_str_style) = match expr_to_string(cx, template_expr,
+                                                         "inline assembly must be a \
+                                                          string literal") {
+        Some((s, st)) => (s, st),
+        // let compilation continue
+        None => return DummyResult::expr(sp),
+    };
+
+    let mut operands: Vec<Operand> = Vec::new();
+    let mut names = HashMap::new();
+    let mut volatile = true;
+    let mut alignstack = true;
+    let mut nomem = false;
+    let mut dialect = AsmDialect::Att;
+
+    while p.eat(&token::Comma) {
+        if p.token == token::Eof {
+            break;
+        }
+
+        if p.token.is_ident() && p.look_ahead(1, |t| *t == token::OpenDelim(token::Paren)) &&
+           p.token.ident().map_or(false, |ident| ident.name == "options") {
+            p.bump();
+            panictry!(p.expect(&token::OpenDelim(token::Paren)));
+            while p.token != token::CloseDelim(token::Paren) {
+                let ident = panictry!(p.parse_ident());
+                match &*ident.name.as_str() {
+                    "pure" => volatile = false,
+                    "nomem" => nomem = true,
+                    "nostack" => alignstack = false,
+                    "att_syntax" => dialect = AsmDialect::Att,
+                    "intel_syntax" => dialect = AsmDialect::Intel,
+                    _ => {
+                        cx.span_err(p.prev_span,
+                                    &format!("expected one of {}, found `{}`",
+                                             CHECKED_OPTIONS.iter()
+                                                            .map(|o| format!("`{}`", o))
+                                                            .collect::<Vec<_>>()
+                                                            .join(", "),
+                                             ident));
+                    }
+                }
+                if !p.eat(&token::Comma) {
+                    break;
+                }
+            }
+            panictry!(p.expect(&token::CloseDelim(token::Paren)));
+            continue;
+        }
+
+        let lo = p.span;
+        let name = if p.token.is_ident() && p.look_ahead(1, |t| *t == token::Eq) {
+            let ident = panictry!(p.parse_ident());
+            p.bump();
+            Some(ident.name)
+        } else {
+            None
+        };
+
+        let kind = if p.eat_keyword(keywords::In) {
+            OperandKind::In
+        } else {
+            let ident = panictry!(p.parse_ident());
+            match &*ident.name.as_str() {
+                "out" => OperandKind::Out { late: false },
+                "lateout" => OperandKind::Out { late: true },
+                "inout" => OperandKind::InOut { late: false },
+                "inlateout" => OperandKind::InOut { late: true },
+                _ => {
+                    cx.span_err(p.prev_span,
+                                &format!("expected one of `in`, `out`, `lateout`, `inout`, \
+                                          `inlateout` or `options`, found `{}`", ident));
+                    return DummyResult::expr(sp);
+                }
+            }
+        };
+
+        panictry!(p.expect(&token::OpenDelim(token::Paren)));
+        let reg = match p.parse_optional_str() {
+            Some((reg, ..)) => ast::InlineAsmRegOrRegClass::Reg(reg),
+            None => ast::InlineAsmRegOrRegClass::RegClass(panictry!(p.parse_ident()).name),
+        };
+        panictry!(p.expect(&token::CloseDelim(token::Paren)));
+
+        let expr = match kind {
+            OperandKind::Out { .. } if p.eat(&token::Underscore) => None,
+            _ => Some(panictry!(p.parse_expr())),
+        };
+        let span = lo.to(p.prev_span);
+
+        if let Some(name) = name {
+            if names.insert(name, operands.len()).is_some() {
+                cx.span_err(span, &format!("duplicate operand name `{}`", name));
+            }
+        } else if !names.is_empty() {
+            cx.span_err(span, "positional operands cannot follow named operands");
+        }
+
+        operands.push(Operand { kind, reg, expr, span });
+    }
+
+    if p.token != token::Eof {
+        panictry!(p.expect(&token::Comma));
+    }
+
+    if !volatile && !operands.iter().any(|op| op.kind != OperandKind::In && op.expr.is_some()) {
+        cx.span_err(sp, "asm with the `pure` option must have at least one output");
+    }
+
+    // Split the operands into LLVM outputs and inputs, outputs come first in
+    // the operand list.
+    let mut outputs = Vec::new();
+    let mut output_regs = Vec::new();
+    let mut inputs = Vec::new();
+    let mut input_regs = Vec::new();
+    let mut clobber_regs = Vec::new();
+    let mut slots = Vec::new();
+    for op in operands {
+        let reg = ast::InlineAsmOperandReg {
+            reg: op.reg,
+            late: match op.kind {
+                OperandKind::In => false,
+                OperandKind::Out { late } | OperandKind::InOut { late } => late,
+            },
+            span: op.span,
+        };
+        match (op.kind, op.expr) {
+            (OperandKind::In, Some(expr)) => {
+                slots.push(Slot::Input(inputs.len()));
+                inputs.push((Symbol::intern(""), expr));
+                input_regs.push(reg);
+            }
+            (kind, Some(expr)) => {
+                slots.push(Slot::Output(outputs.len()));
+                outputs.push(ast::InlineAsmOutput {
+                    constraint: Symbol::intern(""),
+                    expr,
+                    is_rw: match kind {
+                        OperandKind::InOut { .. } => true,
+                        _ => false,
+                    },
+                    is_indirect: false,
+                });
+                output_regs.push(reg);
+            }
+            (_, None) => {
+                slots.push(Slot::Discarded);
+                match op.reg {
+                    ast::InlineAsmRegOrRegClass::Reg(r) => clobber_regs.push((r, op.span)),
+                    ast::InlineAsmRegOrRegClass::RegClass(_) => {
+                        cx.span_err(op.span, "discarded outputs must name an explicit register");
+                    }
+                }
+            }
+        }
+    }
+    let num_outputs = outputs.len();
+    let llvm_indices = slots.iter().map(|slot| match *slot {
+        Slot::Output(i) => Some(i),
+        Slot::Input(i) => Some(num_outputs + i),
+        Slot::Discarded => None,
+    }).collect::<Vec<_>>();
+
+    let asm = match expand_template(cx, &template.as_str(), template_sp, &llvm_indices, &names) {
+        Some(asm) => asm,
+        None => return DummyResult::expr(sp),
+    };
+
+    MacEager::expr(P(ast::Expr {
+        id: ast::DUMMY_NODE_ID,
+        node: ast::ExprKind::InlineAsm(P(ast::InlineAsm {
+            asm: Symbol::intern(&asm),
+            asm_str_style,
+            outputs,
+            inputs,
+            clobbers: if nomem { vec![] } else { vec![Symbol::intern("memory")] },
+            volatile,
+            alignstack,
+            dialect,
+            regs: Some(ast::InlineAsmRegs {
+                outputs: output_regs,
+                inputs: input_regs,
+                clobbers: clobber_regs,
+            }),
             ctxt: cx.backtrace(),
         })),
         span: sp,
         attrs: ast::ThinVec::new(),
     }))
 }
+
+/// Rewrites the `{}`, `{0}` and `{name}` placeholders of a checked template
+/// to LLVM's `${0}` syntax and escapes everything else. `llvm_indices` maps
+/// the operands in source order to their index in the LLVM operand list.
+fn expand_template(cx: &mut ExtCtxt,
+                   template: &str,
+                   sp: Span,
+                   llvm_indices: &[Option<usize>],
+                   names: &HashMap<Symbol, usize>)
+                   -> Option<String> {
+    let mut asm = String::new();
+    let mut used = vec![false; llvm_indices.len()];
+    let mut next_implicit = 0;
+    let mut chars = template.chars().peekable();
+    let mut ok = true;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '$' => asm.push_str("$$"),
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                asm.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                asm.push('}');
+            }
+            '}' => {
+                cx.span_err(sp, "invalid asm template string: unmatched `}` found");
+                return None;
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => {
+                            cx.span_err(sp, "invalid asm template string: expected `}`");
+                            return None;
+                        }
+                    }
+                }
+
+                let (arg, modifier) = match placeholder.find(':') {
+                    Some(i) => (&placeholder[..i], Some(&placeholder[i + 1..])),
+                    None => (&placeholder[..], None),
+                };
+
+                let operand = if arg.is_empty() {
+                    next_implicit += 1;
+                    Some(next_implicit - 1)
+                } else if let Ok(i) = arg.parse::<usize>() {
+                    Some(i)
+                } else {
+                    let index = names.get(&Symbol::intern(arg)).cloned();
+                    if index.is_none() {
+                        cx.span_err(sp, &format!("there is no operand named `{}`", arg));
+                        ok = false;
+                    }
+                    index
+                };
+
+                if let Some(operand) = operand {
+                    match llvm_indices.get(operand) {
+                        Some(&Some(llvm_index)) => {
+                            used[operand] = true;
+                            match modifier {
+                                Some(modifier) => {
+                                    asm.push_str(&format!("${{{}:{}}}", llvm_index, modifier))
+                                }
+                                None => asm.push_str(&format!("${{{}}}", llvm_index)),
+                            }
+                        }
+                        Some(&None) => {
+                            cx.span_err(sp, &format!("operand {} is a discarded output and \
+                                                      cannot be referenced", operand));
+                            ok = false;
+                        }
+                        None => {
+                            cx.span_err(sp, &format!("invalid reference to operand at index {}",
+                                                     operand));
+                            ok = false;
+                        }
+                    }
+                }
+            }
+            c => asm.push(c),
+        }
+    }
+
+    for (i, used) in used.into_iter().enumerate() {
+        if !used && llvm_indices[i].is_some() {
+            cx.span_err(sp, &format!("operand {} is never used in the template", i));
+            ok = false;
+        }
+    }
+
+    if ok { Some(asm) } else { None }
+}
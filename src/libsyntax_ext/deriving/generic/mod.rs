@@ -503,13 +503,16 @@ impl<'a> TraitDef<'a> {
                 vis: ast::Visibility::Inherited,
                 defaultness: ast::Defaultness::Final,
                 attrs: Vec::new(),
-                node: ast::ImplItemKind::Type(type_def.to_ty(cx, self.span, type_ident, generics)),
+                node: ast::ImplItemKind::Type(
+                    Generics::default(),
+                    type_def.to_ty(cx, self.span, type_ident, generics),
+                ),
                 tokens: None,
             }
         });
 
-        let Generics { mut lifetimes, mut ty_params, mut where_clause, span } = self.generics
-            .to_generics(cx, self.span, type_ident, generics);
+        let Generics { mut lifetimes, mut ty_params, mut const_params, mut where_clause, span } =
+            self.generics.to_generics(cx, self.span, type_ident, generics);
 
         // Copy the lifetimes
         lifetimes.extend(generics.lifetimes.iter().cloned());
@@ -605,9 +608,13 @@ impl<'a> TraitDef<'a> {
             }
         }
 
+        // Copy the const parameters
+        const_params.extend(generics.const_params.iter().cloned());
+
         let trait_generics = Generics {
             lifetimes,
             ty_params,
+            const_params,
             where_clause,
             span,
         };
@@ -615,10 +622,13 @@ impl<'a> TraitDef<'a> {
         // Create the reference to the trait.
         let trait_ref = cx.trait_ref(trait_path);
 
-        // Create the type parameters on the `self` path.
+        // Create the type parameters on the `self` path. Const parameters are
+        // passed as plain identifiers, which are resolved to consts later.
         let self_ty_params = generics.ty_params
             .iter()
-            .map(|ty_param| cx.ty_ident(self.span, ty_param.ident))
+            .map(|ty_param| ty_param.ident)
+            .chain(generics.const_params.iter().map(|const_param| const_param.ident))
+            .map(|ident| cx.ty_ident(self.span, ident))
             .collect();
 
         let self_lifetimes: Vec<ast::Lifetime> = generics.lifetimes
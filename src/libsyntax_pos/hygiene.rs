@@ -346,6 +346,7 @@ pub enum CompilerDesugaringKind {
     BackArrow,
     DotFill,
     QuestionMark,
+    Async,
 }
 
 impl CompilerDesugaringKind {
@@ -355,6 +356,7 @@ impl CompilerDesugaringKind {
             BackArrow => "<-",
             DotFill => "...",
             QuestionMark => "?",
+            Async => "async",
         };
         Symbol::intern(s)
     }
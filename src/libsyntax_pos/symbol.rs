@@ -313,6 +313,7 @@ declare_keywords! {
     (56, StaticLifetime, "'static")
     (57, Union,          "union")
     (58, Catch,          "catch")
+    (59, Async,          "async")
 }
 
 // If an interner exists in TLS, return it. Otherwise, prepare a fresh one.
@@ -39,6 +39,7 @@
 #![feature(set_stdio)]
 #![feature(panic_unwind)]
 #![feature(staged_api)]
+#![feature(termination_trait_lib)]
 
 extern crate getopts;
 extern crate term;
@@ -69,6 +70,7 @@ use std::sync::mpsc::{channel, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Instant, Duration};
+use std::Termination;
 
 const TEST_WARN_TIMEOUT_S: u64 = 60;
 
@@ -326,6 +328,16 @@ pub fn test_main_static(tests: &[TestDescAndFn]) {
     test_main(&args, owned_tests, Options::new())
 }
 
+/// Invoked by the test harness with the value returned by a `#[test]`
+/// function. Panics, failing the test, if the value reports a nonzero exit
+/// code.
+pub fn assert_test_result<T: Termination>(result: T) {
+    let code = result.report();
+    assert_eq!(code, 0,
+               "the test returned a termination value with a non-zero status code ({}) \
+                which indicates a failure", code);
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum ColorConfig {
     AutoColor,
@@ -0,0 +1,134 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#include "rustllvm.h"
+
+#if LLVM_VERSION_GE(5, 0)
+#include "llvm/ADT/Triple.h"
+#include "llvm/ProfileData/Coverage/CoverageMapping.h"
+#include "llvm/ProfileData/Coverage/CoverageMappingWriter.h"
+#include "llvm/ProfileData/InstrProf.h"
+#endif
+
+using namespace llvm;
+
+// A source region counted by a single counter, mirrors
+// `coverageinfo::CounterMappingRegion` on the Rust side.
+struct LLVMRustCounterMappingRegion {
+  uint32_t Counter;
+  uint32_t FileID;
+  uint32_t LineStart;
+  uint32_t ColumnStart;
+  uint32_t LineEnd;
+  uint32_t ColumnEnd;
+};
+
+#if LLVM_VERSION_GE(5, 0)
+
+extern "C" bool LLVMRustCoverageAvailable() {
+  return true;
+}
+
+extern "C" void LLVMRustCoverageWriteFilenamesSectionToBuffer(
+    const char *const Filenames[], size_t FilenamesLen,
+    RustStringRef BufferOut) {
+  SmallVector<StringRef, 32> FilenameRefs;
+  for (size_t i = 0; i < FilenamesLen; i++) {
+    FilenameRefs.push_back(StringRef(Filenames[i]));
+  }
+  RawRustStringOstream OS(BufferOut);
+  coverage::CoverageFilenamesSectionWriter(FilenameRefs).write(OS);
+}
+
+extern "C" void LLVMRustCoverageWriteMappingToBuffer(
+    const unsigned *VirtualFileMappingIDs, unsigned NumVirtualFileMappingIDs,
+    const LLVMRustCounterMappingRegion *RustMappingRegions,
+    unsigned NumMappingRegions, RustStringRef BufferOut) {
+  SmallVector<coverage::CounterMappingRegion, 32> MappingRegions;
+  for (unsigned i = 0; i < NumMappingRegions; i++) {
+    const LLVMRustCounterMappingRegion &Region = RustMappingRegions[i];
+    MappingRegions.push_back(coverage::CounterMappingRegion::makeRegion(
+        coverage::Counter::getCounter(Region.Counter), Region.FileID,
+        Region.LineStart, Region.ColumnStart, Region.LineEnd,
+        Region.ColumnEnd));
+  }
+  // We only ever count regions directly, so there are no counter
+  // expressions to write.
+  ArrayRef<coverage::CounterExpression> Expressions;
+  auto VirtualFileMapping =
+      makeArrayRef(VirtualFileMappingIDs, NumVirtualFileMappingIDs);
+  RawRustStringOstream OS(BufferOut);
+  coverage::CoverageMappingWriter(VirtualFileMapping, Expressions,
+                                  MappingRegions)
+      .write(OS);
+}
+
+extern "C" LLVMValueRef LLVMRustCoverageCreatePGOFuncNameVar(LLVMValueRef F,
+                                                             const char *FuncName) {
+  StringRef FuncNameRef(FuncName);
+  return wrap(createPGOFuncNameVar(*cast<Function>(unwrap(F)), FuncNameRef));
+}
+
+extern "C" uint64_t LLVMRustCoverageComputeHash(const char *Name) {
+  StringRef NameRef(Name);
+  return IndexedInstrProf::ComputeHash(NameRef);
+}
+
+extern "C" void LLVMRustCoverageWriteSectionNameToString(LLVMModuleRef M,
+                                                         RustStringRef Str) {
+  Triple TargetTriple(unwrap(M)->getTargetTriple());
+  auto Name = getInstrProfSectionName(IPSK_covmap,
+                                      TargetTriple.getObjectFormat());
+  RawRustStringOstream OS(Str);
+  OS << Name;
+}
+
+extern "C" uint32_t LLVMRustCoverageMappingVersion() {
+  return coverage::CovMapVersion::CurrentVersion;
+}
+
+#else
+
+extern "C" bool LLVMRustCoverageAvailable() {
+  return false;
+}
+
+extern "C" void LLVMRustCoverageWriteFilenamesSectionToBuffer(
+    const char *const Filenames[], size_t FilenamesLen,
+    RustStringRef BufferOut) {
+  report_fatal_error("coverage instrumentation requires LLVM 5.0 or later");
+}
+
+extern "C" void LLVMRustCoverageWriteMappingToBuffer(
+    const unsigned *VirtualFileMappingIDs, unsigned NumVirtualFileMappingIDs,
+    const LLVMRustCounterMappingRegion *RustMappingRegions,
+    unsigned NumMappingRegions, RustStringRef BufferOut) {
+  report_fatal_error("coverage instrumentation requires LLVM 5.0 or later");
+}
+
+extern "C" LLVMValueRef LLVMRustCoverageCreatePGOFuncNameVar(LLVMValueRef F,
+                                                             const char *FuncName) {
+  report_fatal_error("coverage instrumentation requires LLVM 5.0 or later");
+}
+
+extern "C" uint64_t LLVMRustCoverageComputeHash(const char *Name) {
+  report_fatal_error("coverage instrumentation requires LLVM 5.0 or later");
+}
+
+extern "C" void LLVMRustCoverageWriteSectionNameToString(LLVMModuleRef M,
+                                                         RustStringRef Str) {
+  report_fatal_error("coverage instrumentation requires LLVM 5.0 or later");
+}
+
+extern "C" uint32_t LLVMRustCoverageMappingVersion() {
+  report_fatal_error("coverage instrumentation requires LLVM 5.0 or later");
+}
+
+#endif
@@ -0,0 +1,45 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-arm
+// ignore-aarch64
+// ignore-s390x
+// ignore-emscripten
+// ignore-powerpc
+// ignore-sparc
+// ignore-mips
+// ignore-mips64
+// ignore-x86
+
+#![feature(asm)]
+
+fn main() {
+    let x: u64;
+    let y: u64 = 1;
+    let s: &str = "";
+    unsafe {
+        asm!("{}", out("foo") x);
+        //~^ ERROR invalid register `foo`
+        asm!("{}", in(foo_reg) y);
+        //~^ ERROR invalid register class `foo_reg`
+        asm!("{}", in("rsp") y);
+        //~^ ERROR invalid register `rsp`: the stack pointer cannot be used
+        asm!("", out("rbp") _);
+        //~^ ERROR invalid register `rbp`: the frame pointer cannot be used
+        asm!("{} {}", in("eax") y, in("rax") y);
+        //~^ ERROR register `rax` is used more than once
+        asm!("{} {}", in("rcx") y, out("rcx") x);
+        //~^ ERROR register `rcx` conflicts with an input
+        asm!("{}", in(reg) s);
+        //~^ ERROR cannot use value of type `&str` for inline assembly
+        asm!("{}", in(xmm_reg) [0u64; 4]);
+        //~^ ERROR cannot use value of type `[u64; 4]` for inline assembly
+    }
+}
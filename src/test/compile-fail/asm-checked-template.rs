@@ -0,0 +1,41 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-arm
+// ignore-aarch64
+// ignore-s390x
+// ignore-emscripten
+// ignore-powerpc
+// ignore-sparc
+// ignore-mips
+// ignore-mips64
+
+#![feature(asm)]
+
+fn main() {
+    let x: u32;
+    let y: u32 = 1;
+    unsafe {
+        asm!("{}", in(reg) y, in(reg) y);
+        //~^ ERROR operand 1 is never used in the template
+        asm!("{2}", in(reg) y);
+        //~^ ERROR invalid reference to operand at index 2
+        asm!("{name}", in(reg) y);
+        //~^ ERROR there is no operand named `name`
+        asm!("{}", a = in(reg) y, in(reg) y);
+        //~^ ERROR positional operands cannot follow named operands
+        asm!("{}", out(reg) _);
+        //~^ ERROR discarded outputs must name an explicit register
+        asm!("nop", options(pure));
+        //~^ ERROR asm with the `pure` option must have at least one output
+        asm!("{}", out(reg) x, options(volatile));
+        //~^ ERROR expected one of `pure`, `nomem`, `nostack`, `att_syntax`, `intel_syntax`
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z chalk
+
+// An impl that requires itself cannot be used to prove anything. The
+// chalk-style solver answers the recursive goal from its table instead
+// of overflowing.
+
+trait Foo { }
+
+struct Bar<T>(T);
+
+impl<T> Foo for Bar<T> where Bar<T>: Foo { }
+
+fn is_foo<T: Foo>() { }
+
+fn main() {
+    is_foo::<Bar<i32>>();
+    //~^ ERROR the trait bound `Bar<i32>: Foo` is not satisfied
+}
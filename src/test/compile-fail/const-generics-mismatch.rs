@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(const_generics)]
+
+struct Buf<const N: usize>;
+
+struct Float<const F: f64>; //~ ERROR E0632
+
+fn takes_three(_: Buf<3>) {}
+
+fn first<const N: usize>(a: [u8; N]) -> u8 { a[0] }
+
+fn main() {
+    takes_three(Buf::<4>);
+    //~^ ERROR mismatched types
+    //~| expected `3`, found `4`
+
+    let _: Buf<1, 2> = Buf; //~ ERROR wrong number of const arguments
+
+    let _: u8 = first::<3>([1, 2]);
+    //~^ ERROR mismatched types
+}
@@ -0,0 +1,21 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Without `#![feature(implied_bounds)]`, the where-clauses of argument
+// types have to be repeated.
+
+struct Set<T: Eq>(Vec<T>);
+
+fn first<T>(set: &Set<T>) -> Option<&T> {
+    //~^ ERROR the trait bound `T: std::cmp::Eq` is not satisfied
+    set.0.first()
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(generic_associated_types)]
+
+use std::ops::Deref;
+
+trait LendingIterator {
+    type Item<'a> where Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+trait PointerFamily {
+    type Pointer<T>: Deref<Target = T>;
+}
+
+struct Bytes;
+
+impl LendingIterator for Bytes {
+    type Item = u8;
+    //~^ ERROR lifetime parameters on associated type `Item` do not match the trait declaration
+
+    fn next<'a>(&'a mut self) -> Option<u8> { None }
+}
+
+struct Family;
+
+impl PointerFamily for Family {
+    type Pointer<T, U> = Box<T>;
+    //~^ ERROR associated type `Pointer` has 2 type parameters but its trait declaration has 1
+}
+
+fn first<I: LendingIterator<Item = u8>>(_: I) {}
+//~^ ERROR cannot constrain the generic associated type `Item` in a binding
+
+fn erase(_: &PointerFamily) {}
+//~^ ERROR the trait `PointerFamily` cannot be made into an object
+
+fn main() {}
@@ -10,9 +10,9 @@
 
 #![feature(conservative_impl_trait)]
 
-fn arguments(_: impl Fn(),
+fn arguments(_: fn(impl Fn()),
 //~^ ERROR `impl Trait` not allowed outside of function and inherent method return types
-             _: Vec<impl Clone>) {}
+             _: Box<Fn(impl Clone)>) {}
 //~^ ERROR `impl Trait` not allowed outside of function and inherent method return types
 
 type Factory<R> = impl Fn() -> R;
@@ -22,14 +22,14 @@ type GlobalFactory<R> = fn() -> impl FnOnce() -> R;
 //~^ ERROR `impl Trait` not allowed outside of function and inherent method return types
 
 trait LazyToString {
-    fn lazy_to_string<'a>(&'a self) -> impl Fn() -> String;
+    fn lazy_to_string<'a>(&'a self) -> Box<impl Fn() -> String>;
     //~^ ERROR `impl Trait` not allowed outside of function and inherent method return types
 }
 
 impl LazyToString for String {
-    fn lazy_to_string<'a>(&'a self) -> impl Fn() -> String {
+    fn lazy_to_string<'a>(&'a self) -> Box<impl Fn() -> String> {
     //~^ ERROR `impl Trait` not allowed outside of function and inherent method return types
-        || self.clone()
+        Box::new(|| self.clone())
     }
 }
 
@@ -0,0 +1,20 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(conservative_impl_trait, impl_trait_in_traits)]
+
+trait Numbers {
+    fn numbers(&self) -> impl Iterator<Item = u32> {
+    //~^ ERROR trait methods returning `impl Trait` cannot have a default body
+        None.into_iter()
+    }
+}
+
+fn main() {}
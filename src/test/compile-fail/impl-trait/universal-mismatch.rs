@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(conservative_impl_trait, universal_impl_trait)]
+
+use std::fmt::Debug;
+
+trait Show {
+    fn show<T: Debug>(&self, t: T);
+}
+
+impl Show for () {
+    fn show(&self, t: impl Debug) {}
+    //~^ ERROR method `show` has an incompatible signature for trait
+}
+
+trait ShowImpl {
+    fn show(&self, t: impl Debug);
+}
+
+impl ShowImpl for () {
+    fn show<T: Debug>(&self, t: T) {}
+    //~^ ERROR method `show` has an incompatible signature for trait
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Implied bounds are predicates of the fn, so callers still have to
+// prove them.
+
+#![feature(implied_bounds)]
+
+struct Set<T: Eq>(Vec<T>);
+
+fn is_empty<T>(set: Option<Set<T>>) -> bool {
+    set.map_or(true, |set| set.0.is_empty())
+}
+
+struct NotEq;
+
+fn main() {
+    is_empty::<NotEq>(None);
+    //~^ ERROR the trait bound `NotEq: std::cmp::Eq` is not satisfied
+}
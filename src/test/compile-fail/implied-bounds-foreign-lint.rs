@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:implied_bounds_aux.rs
+
+#![feature(implied_bounds)]
+#![deny(foreign_implied_bounds)]
+
+extern crate implied_bounds_aux;
+
+use implied_bounds_aux::Set;
+
+fn contains<T>(set: &Set<T>, x: &T) -> bool {
+    //~^ ERROR `T: std::cmp::Eq` is implied by the where-clauses of `implied_bounds_aux::Set`
+    set.0.iter().any(|y| y == x)
+}
+
+fn contains_explicit<T: Eq>(set: &Set<T>, x: &T) -> bool {
+    set.0.iter().any(|y| y == x)
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(match_default_bindings)]
+
+fn main() {
+    // Matching through `&` makes `n` a `ref` binding, which cannot be
+    // used to mutate, even behind an outer `&mut`.
+    match &Some(3) {
+        Some(n) => {
+            *n += 1; //~ ERROR cannot assign
+        },
+        _ => panic!(),
+    }
+
+    match &mut &Some(3) {
+        Some(n) => {
+            *n += 1; //~ ERROR cannot assign
+        },
+        _ => panic!(),
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:non_exhaustive.rs
+extern crate non_exhaustive;
+
+use non_exhaustive::{NormalStruct, UnitStruct, TupleStruct};
+
+fn main() {
+    let fr = NormalStruct {
+    //~^ ERROR cannot create non-exhaustive struct using struct expression [E0639]
+        first_field: 640,
+        second_field: 480
+    };
+
+    let NormalStruct { first_field, second_field } = fr;
+    //~^ ERROR `..` required with struct marked as non-exhaustive [E0640]
+
+    let ts = TupleStruct(640, 480);
+    //~^ ERROR expected function, found struct `TupleStruct` [E0423]
+
+    let us = UnitStruct;
+    //~^ ERROR expected value, found struct `UnitStruct` [E0423]
+}
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn classify(x: u32) -> &'static str {
+    match x {
+        0 => "zero",
+        1 | 2 => "small",
+        _ => "large",
+    }
+}
+
+fn main() {
+    let f = |x: u32| x * 2;
+    for i in 0..4 {
+        println!("{}", classify(f(i)));
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-arm
+// ignore-aarch64
+// ignore-s390x
+// ignore-emscripten
+// ignore-powerpc
+// ignore-sparc
+// ignore-mips
+// ignore-mips64
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn main() {
+    let x: u32;
+    unsafe {
+        asm!("mov {}, 5", out(reg) x, options(intel_syntax, pure, nomem, nostack));
+    }
+    assert_eq!(x, 5);
+
+    // Named operands, explicit registers and a tied operand.
+    let mut acc: u32 = 1;
+    unsafe {
+        asm!("add {acc}, {n}",
+             acc = inout(reg) acc,
+             n = in("ecx") 41_u32,
+             options(intel_syntax));
+    }
+    assert_eq!(acc, 42);
+
+    // Outputs written after all inputs are read may reuse an input register.
+    let y: u32;
+    unsafe {
+        asm!("lea {0}, [{1} + {1}]", lateout(reg) y, in(reg) 21_u32,
+             options(intel_syntax, pure, nomem, nostack));
+    }
+    assert_eq!(y, 42);
+
+    // Braces and dollars are escaped, clobbers are discarded outputs.
+    let z: u32;
+    unsafe {
+        asm!("movl $$7, %eax; movl %eax, {}", out(reg) z, out("eax") _);
+    }
+    assert_eq!(z, 7);
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn main() {}
@@ -0,0 +1,75 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(async_await, futures_api, pin)]
+
+use std::future::Future;
+use std::mem::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, Wake, Waker};
+
+struct Counter {
+    wakes: AtomicUsize,
+}
+
+impl Wake for Counter {
+    fn wake(this: &Arc<Self>) {
+        this.wakes.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// A future which is pending once before completing.
+struct WakeOnce {
+    polled: bool,
+}
+
+impl Future for WakeOnce {
+    type Output = u32;
+
+    fn poll(mut this: Pin<Self>, cx: &mut Context) -> Poll<u32> {
+        if this.polled {
+            Poll::Ready(1)
+        } else {
+            this.polled = true;
+            cx.waker().wake();
+            Poll::Pending
+        }
+    }
+}
+
+async fn add(x: u32) -> u32 {
+    let one = await!(WakeOnce { polled: false });
+    x + one
+}
+
+async fn borrow_across_await<'a>(x: &'a u32) -> u32 {
+    let y = &x;
+    await!(add(**y)) + **y
+}
+
+fn block_on<F: Future>(mut f: F) -> (F::Output, usize) {
+    let counter = Arc::new(Counter { wakes: AtomicUsize::new(0) });
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::new(&waker);
+    // The future is never moved after this point.
+    let mut f = unsafe { Pin::new_unchecked(&mut f) };
+    loop {
+        if let Poll::Ready(x) = F::poll(Pin::borrow(&mut f), &mut cx) {
+            return (x, counter.wakes.load(Ordering::SeqCst));
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(block_on(add(1)), (2, 1));
+    let x = 5;
+    assert_eq!(block_on(borrow_across_await(&x)), (11, 1));
+}
@@ -0,0 +1,78 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z chalk
+
+// Check that impls and where-clauses are selected the same way with the
+// chalk-style solver, including through associated types.
+
+use std::fmt::Debug;
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for i32 {
+    fn describe(&self) -> String {
+        format!("i32 {}", self)
+    }
+}
+
+impl<T: Describe> Describe for Option<T> {
+    fn describe(&self) -> String {
+        match *self {
+            Some(ref x) => format!("Some({})", x.describe()),
+            None => format!("None"),
+        }
+    }
+}
+
+impl<T> Describe for Vec<T> where T: Describe {
+    fn describe(&self) -> String {
+        let items: Vec<_> = self.iter().map(|x| x.describe()).collect();
+        format!("[{}]", items.join(", "))
+    }
+}
+
+fn describe_twice<T: Describe>(x: &T) -> String {
+    format!("{} {}", x.describe(), x.describe())
+}
+
+trait Widen {
+    type Output: Debug;
+
+    fn widen(self) -> Self::Output;
+}
+
+impl Widen for u8 {
+    type Output = u16;
+
+    fn widen(self) -> u16 {
+        self as u16 * 256
+    }
+}
+
+impl<T: Widen> Widen for (T,) {
+    type Output = T::Output;
+
+    fn widen(self) -> T::Output {
+        self.0.widen()
+    }
+}
+
+fn widen_debug<T>(x: T) -> String where T: Widen {
+    format!("{:?}", x.widen())
+}
+
+fn main() {
+    assert_eq!(vec![Some(1), None].describe(), "[Some(i32 1), None]");
+    assert_eq!(describe_twice(&Some(2)), "Some(i32 2) Some(i32 2)");
+    assert_eq!(widen_debug((1u8,)), "256");
+}
@@ -0,0 +1,65 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(const_generics)]
+
+struct Buf<const N: usize> {
+    data: [u8; N],
+}
+
+impl<const N: usize> Buf<N> {
+    fn new() -> Buf<N> {
+        Buf { data: [0; N] }
+    }
+
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+trait Sum {
+    fn sum(&self) -> u32;
+}
+
+impl<const N: usize> Sum for [u32; N] {
+    fn sum(&self) -> u32 {
+        self.iter().fold(0, |acc, &x| acc + x)
+    }
+}
+
+fn len<T, const N: usize>(_: &[T; N]) -> usize {
+    N
+}
+
+struct Flag<const B: bool>;
+
+impl<const B: bool> Flag<B> {
+    fn get(&self) -> bool {
+        B
+    }
+}
+
+fn main() {
+    let buf: Buf<4> = Buf::new();
+    assert_eq!(buf.capacity(), 4);
+    assert_eq!(buf.data.len(), 4);
+    assert_eq!(Buf::<{ 2 + 3 }>::new().capacity(), 5);
+
+    assert_eq!([1, 2, 3].sum(), 6);
+    assert_eq!([1; 40].sum(), 40);
+
+    // `N` is inferred from the array argument.
+    assert_eq!(len(&[1u8, 2, 3]), 3);
+    assert_eq!(len(&["a"; 33]), 33);
+    assert_eq!(len::<char, 2>(&['x', 'y']), 2);
+
+    assert!(Flag::<true>.get());
+    assert!(!Flag::<false>.get());
+}
@@ -17,5 +17,5 @@ extern crate xcrate_reachable as foo;
 use std::ops::Generator;
 
 fn main() {
-    foo::foo().resume();
+    unsafe { foo::foo().resume() };
 }
@@ -0,0 +1,103 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(generic_associated_types)]
+
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+
+trait LendingIterator {
+    type Item<'a> where Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+struct WindowsMut {
+    data: Vec<u8>,
+    start: usize,
+    size: usize,
+}
+
+impl LendingIterator for WindowsMut {
+    type Item<'a> = &'a mut [u8];
+
+    fn next<'a>(&'a mut self) -> Option<&'a mut [u8]> {
+        let start = self.start;
+        if start + self.size > self.data.len() {
+            return None;
+        }
+        self.start += 1;
+        Some(&mut self.data[start..start + self.size])
+    }
+}
+
+fn count<I: LendingIterator>(mut iter: I) -> usize {
+    let mut n = 0;
+    while let Some(_) = iter.next() {
+        n += 1;
+    }
+    n
+}
+
+trait PointerFamily {
+    type Pointer<T>: Deref<Target = T> + Clone;
+
+    fn new<T>(value: T) -> Self::Pointer<T>;
+}
+
+struct RcFamily;
+
+impl PointerFamily for RcFamily {
+    type Pointer<T> = Rc<T>;
+
+    fn new<T>(value: T) -> Rc<T> {
+        Rc::new(value)
+    }
+}
+
+struct ArcFamily;
+
+impl PointerFamily for ArcFamily {
+    type Pointer<T> = Arc<T>;
+
+    fn new<T>(value: T) -> Arc<T> {
+        Arc::new(value)
+    }
+}
+
+struct Shared<P: PointerFamily> {
+    name: P::Pointer<String>,
+}
+
+impl<P: PointerFamily> Shared<P> {
+    fn new(name: &str) -> Shared<P> {
+        Shared { name: P::new(name.to_string()) }
+    }
+
+    fn len(&self) -> usize {
+        let name = self.name.clone();
+        name.len()
+    }
+}
+
+fn main() {
+    let mut windows = WindowsMut { data: vec![1, 2, 3, 4, 5], start: 0, size: 2 };
+    while let Some(window) = windows.next() {
+        window[1] += window[0];
+    }
+    assert_eq!(windows.data, [1, 3, 6, 10, 15]);
+
+    let windows = WindowsMut { data: vec![0; 5], start: 0, size: 3 };
+    assert_eq!(count(windows), 3);
+
+    assert_eq!(Shared::<RcFamily>::new("rc").len(), 2);
+    assert_eq!(Shared::<ArcFamily>::new("arc").len(), 3);
+}
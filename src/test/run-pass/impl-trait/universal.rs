@@ -0,0 +1,36 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(conservative_impl_trait, universal_impl_trait)]
+
+use std::fmt::Debug;
+
+fn sum(it: impl Iterator<Item = u32>) -> u32 {
+    it.fold(0, |a, b| a + b)
+}
+
+fn show_all(items: &[impl Debug]) -> String {
+    items.iter().map(|i| format!("{:?}", i)).collect::<Vec<_>>().join(",")
+}
+
+struct Printer;
+
+impl Printer {
+    fn show<'a>(&self, t: &'a impl Debug) -> String {
+        format!("{:?}", t)
+    }
+}
+
+fn main() {
+    assert_eq!(sum(vec![1, 2, 3].into_iter()), 6);
+    assert_eq!(sum((1..5).map(|x| x * 2)), 20);
+    assert_eq!(show_all(&[1, 2]), "1,2");
+    assert_eq!(Printer.show(&"hi"), "\"hi\"");
+}
@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+// The implicit `Sized` bounds on the type parameters of a foreign type are
+// not where-clauses anybody wrote, so they must not trigger
+// `foreign_implied_bounds`.
+
+#![feature(implied_bounds)]
+#![deny(foreign_implied_bounds)]
+
+fn first<T: Clone>(v: Vec<Option<T>>) -> Option<T> {
+    v.into_iter().next().and_then(|x| x)
+}
+
+struct Wrapper<T>(Vec<Option<T>>);
+
+impl<T> Wrapper<T> {
+    fn len(&self, _other: &Vec<Vec<T>>) -> usize {
+        self.0.len()
+    }
+}
+
+fn main() {
+    assert_eq!(first(vec![Some(1), None]), Some(1));
+    assert_eq!(Wrapper(vec![None::<u8>]).len(&vec![]), 1);
+}
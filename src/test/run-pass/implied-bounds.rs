@@ -0,0 +1,52 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(implied_bounds)]
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+struct Counter<K> where K: Hash + Eq {
+    counts: HashMap<K, usize>,
+}
+
+impl<K> Counter<K> {
+    fn new() -> Counter<K> {
+        Counter { counts: HashMap::new() }
+    }
+
+    fn add(&mut self, key: K) {
+        *self.counts.entry(key).or_insert(0) += 1;
+    }
+}
+
+fn count_of<K>(counter: &Counter<K>, key: &K) -> usize {
+    counter.counts.get(key).cloned().unwrap_or(0)
+}
+
+trait Total {
+    fn total(&self) -> usize;
+}
+
+impl<K> Total for Counter<K> {
+    fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+fn main() {
+    let mut counter = Counter::new();
+    counter.add("a");
+    counter.add("b");
+    counter.add("a");
+    assert_eq!(count_of(&counter, &"a"), 2);
+    assert_eq!(count_of(&counter, &"c"), 0);
+    assert_eq!(counter.total(), 3);
+}
@@ -0,0 +1,65 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(match_default_bindings)]
+
+struct Foo {
+    name: String,
+    pair: (u32, u32),
+}
+
+fn first_name(names: &[Option<String>]) -> Option<&str> {
+    for name in names {
+        if let Some(s) = name {
+            return Some(s);
+        }
+    }
+    None
+}
+
+fn main() {
+    let names = vec![None, Some("a".to_string()), Some("b".to_string())];
+    assert_eq!(first_name(&names), Some("a"));
+
+    // `n` binds by reference through nested references.
+    match &&Some(5) {
+        Some(n) => {
+            let n: &i32 = n;
+            assert_eq!(*n, 5);
+        }
+        None => panic!(),
+    }
+
+    // Matching through `&mut` gives `ref mut` bindings.
+    let mut value = Some(1);
+    match &mut value {
+        Some(n) => *n += 1,
+        None => panic!(),
+    }
+    assert_eq!(value, Some(2));
+
+    // Struct and tuple patterns, mixed with explicit `ref`.
+    let foo = Foo { name: "foo".to_string(), pair: (1, 2) };
+    let Foo { name, pair: (a, ref b) } = &foo;
+    let _: &String = name;
+    let _: &u32 = a;
+    let _: &u32 = b;
+    assert_eq!((name.as_str(), *a, *b), ("foo", 1, 2));
+
+    // Bindings annotated with `mut` still bind by value.
+    match &(Some(1), 2) {
+        (Some(x), mut y) => {
+            let _: &i32 = x;
+            y += 1;
+            assert_eq!((*x, y), (1, 3));
+        }
+        _ => panic!(),
+    }
+}
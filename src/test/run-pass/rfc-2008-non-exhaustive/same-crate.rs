@@ -0,0 +1,46 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(non_exhaustive)]
+
+#[non_exhaustive]
+pub enum NonExhaustiveEnum {
+    Unit,
+    Tuple(u32),
+    Struct { field: u32 }
+}
+
+#[non_exhaustive]
+pub struct NormalStruct {
+    pub first_field: u16,
+    pub second_field: u16,
+}
+
+#[non_exhaustive]
+pub struct TupleStruct(pub u16, pub u16);
+
+fn main() {
+    // Within the defining crate the attribute has no effect.
+    let enum_unit = NonExhaustiveEnum::Unit;
+
+    let x = match enum_unit {
+        NonExhaustiveEnum::Unit => 1,
+        NonExhaustiveEnum::Tuple(_) => 2,
+        NonExhaustiveEnum::Struct { .. } => 3,
+    };
+    assert_eq!(x, 1);
+
+    let NormalStruct { first_field, second_field } =
+        NormalStruct { first_field: 640, second_field: 480 };
+    assert_eq!(first_field + second_field, 1120);
+
+    let TupleStruct(a, b) = TupleStruct(1, 2);
+    assert_eq!(a + b, 3);
+}
@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(termination_trait)]
+
+use std::io::{Error, ErrorKind};
+
+fn check(n: i32) -> Result<i32, Error> {
+    if n >= 0 { Ok(n) } else { Err(Error::new(ErrorKind::Other, "negative")) }
+}
+
+fn main() -> Result<(), Error> {
+    assert_eq!(check(3)?, 3);
+    assert!(check(-1).is_err());
+    Ok(())
+}
@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C lto=thin -O
+// aux-build:thin-lto-inlines-aux.rs
+// min-llvm-version 5.0
+// no-prefer-dynamic
+
+// Check that ThinLTO over the whole crate graph pulls in the code of
+// upstream crates.
+
+extern crate thin_lto_inlines_aux as bar;
+
+fn main() {
+    assert_eq!(bar::bar(), 3);
+}
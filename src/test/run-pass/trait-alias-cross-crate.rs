@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:trait_alias.rs
+
+#![feature(trait_alias)]
+
+extern crate trait_alias;
+
+use trait_alias::{Describe, DescribeDefault, SendSyncClone};
+
+fn share<T: SendSyncClone>(t: &T) -> T {
+    t.clone()
+}
+
+fn describe_default<T: DescribeDefault>() -> String {
+    T::default().describe()
+}
+
+fn main() {
+    assert_eq!(share(&5u8), 5);
+    assert_eq!(describe_default::<u32>(), "u32 0");
+}
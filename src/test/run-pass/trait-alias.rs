@@ -0,0 +1,47 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(trait_alias)]
+
+use std::fmt::Debug;
+
+trait SimpleAlias = Default;
+trait GenericAlias<T> = Iterator<Item=T>;
+trait Partial<T> = IntoIterator<Item=T>;
+trait SendSyncFn = Fn() -> u32 + Send + Sync;
+trait WhereAlias<T> = Clone where T: Debug;
+
+fn make<T: SimpleAlias>() -> T {
+    T::default()
+}
+
+fn sum<I: GenericAlias<u32>>(iter: I) -> u32 {
+    iter.fold(0, |a, b| a + b)
+}
+
+fn count<C: Partial<u8>>(c: C) -> usize {
+    c.into_iter().count()
+}
+
+fn dup<T: WhereAlias<u8>>(t: &T) -> T {
+    t.clone()
+}
+
+fn main() {
+    let x: u32 = make();
+    assert_eq!(x, 0);
+
+    assert_eq!(sum(vec![1, 2, 3].into_iter()), 6);
+    assert_eq!(count(vec![1u8, 2]), 2);
+    assert_eq!(dup(&"hi".to_string()), "hi");
+
+    let f: Box<SendSyncFn> = Box::new(|| 42);
+    assert_eq!(f(), 42);
+}
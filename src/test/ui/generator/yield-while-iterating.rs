@@ -52,7 +52,7 @@ fn yield_during_iter_borrowed_slice_3() {
             yield p;
         }
     };
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn yield_during_iter_borrowed_slice_4() {
@@ -65,7 +65,7 @@ fn yield_during_iter_borrowed_slice_4() {
         }
     };
     println!("{}", x[0]); //~ ERROR
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn yield_during_range_iter() {
@@ -78,7 +78,7 @@ fn yield_during_range_iter() {
             yield x;
         }
     };
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn main() { }
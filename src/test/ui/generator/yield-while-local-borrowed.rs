@@ -23,7 +23,7 @@ fn borrow_local_inline() {
         yield();
         println!("{}", a);
     };
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn borrow_local_inline_done() {
@@ -34,7 +34,7 @@ fn borrow_local_inline_done() {
         }
         yield();
     };
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn borrow_local() {
@@ -50,7 +50,7 @@ fn borrow_local() {
             println!("{}", b);
         }
     };
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn main() { }
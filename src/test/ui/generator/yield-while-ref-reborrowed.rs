@@ -21,7 +21,7 @@ fn reborrow_shared_ref(x: &i32) {
         yield();
         println!("{}", a);
     };
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn reborrow_mutable_ref(x: &mut i32) {
@@ -32,7 +32,7 @@ fn reborrow_mutable_ref(x: &mut i32) {
         yield();
         println!("{}", a);
     };
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn reborrow_mutable_ref_2(x: &mut i32) {
@@ -43,7 +43,7 @@ fn reborrow_mutable_ref_2(x: &mut i32) {
         println!("{}", a);
     };
     println!("{}", x); //~ ERROR
-    b.resume();
+    unsafe { b.resume() };
 }
 
 fn main() { }